@@ -3,13 +3,26 @@
 //! Provides the main application state and event loop functionality.
 //! Manages tabs, event handling, and the core application lifecycle.
 
+use crate::cli::StartupOptions;
+use crate::components::background_work_running;
 use crate::components::tab::Tab;
-use crate::event_managment::event::TabEvent;
+use crate::diagnostics;
+use crate::event_managment::event::{ComponentAction, ComponentType};
+use crate::event_managment::event::{PopupAction, TabAction, TabEvent, WidgetAction};
 use crate::event_managment::event::{AppEvent, Event, EventHandler};
+use crate::widgets::WidgetExt;
+use crate::widgets::popup::{PopupContent, PopupWidget};
 use ratatui::{
     DefaultTerminal,
     crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
 };
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// Longest an unchanged screen goes without a redraw, so relative times such as
+/// "Updated 12s ago" in the status line keep counting while idle
+const IDLE_REDRAW_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Main application state container
 ///
@@ -25,22 +38,36 @@ pub struct App {
     pub active_tab: usize,
     /// Collection of all tabs in the application
     pub tabs: Vec<Tab>,
+    /// Popup shown over the active tab: the tab overview, the diagnostics log or the
+    /// quit confirmation
+    pub overlay: Option<PopupWidget>,
+    /// Whether the overlay asks to quit while background work is running
+    confirming_quit: bool,
+    /// Whether state changed since the last frame, so the next loop iteration redraws
+    dirty: bool,
+    /// When the last frame was drawn
+    last_draw: Instant,
 }
 
 impl Default for App {
     /// Creates a default application state with initial tabs
     fn default() -> Self {
-        let events = EventHandler::new();
+        let mut events = EventHandler::new();
+        let tabs = vec![
+            Self::new_tab(&mut events, "Tab 1", "This is Tab 1."),
+            Self::new_tab(&mut events, "Tab 2", "This is Tab 2."),
+            Self::new_tab(&mut events, "Tab 3", "This is Tab 3."),
+        ];
         Self {
             running: true,
             counter: 0,
-            tabs: vec![
-                Tab::new("Tab 1", "This is Tab 1.", events.sender.clone()),
-                Tab::new("Tab 2", "This is Tab 2.", events.sender.clone()),
-                Tab::new("Tab 3", "This is Tab 3.", events.sender.clone()),
-            ],
+            tabs,
             events,
             active_tab: 0,
+            overlay: None,
+            confirming_quit: false,
+            dirty: true,
+            last_draw: Instant::now(),
         }
     }
 }
@@ -51,13 +78,60 @@ impl App {
         Self::default()
     }
 
+    /// Creates a tab with an id of its own, which the events raised inside it are routed by
+    fn new_tab(events: &mut EventHandler, name: &str, content: &str) -> Tab {
+        let (tab_id, event_sender) = events.tab_sender();
+        Tab::new(tab_id, name, content, event_sender)
+    }
+
+    /// Constructs an [`App`] for the command-line options
+    ///
+    /// With a profile, there is a single tab already on that profile instead of the
+    /// profile popup, opening the given region and service if any.
+    pub fn with_options(options: StartupOptions) -> Self {
+        let mut app = Self::default();
+        if let Some(profile) = options.profile {
+            let mut tab = Self::new_tab(&mut app.events, &profile, "");
+            tab.set_profile(profile.clone());
+            app.tabs = vec![tab];
+            app.events.send(Event::Tab(TabEvent::TabAction(TabAction::OpenProfile(
+                profile,
+                options.region,
+                options.service,
+            ))));
+            // CloudWatch holds on to the search until it has listed the log groups
+            if let Some(view) = options.open {
+                app.events.send(Event::Tab(TabEvent::ComponentActions(
+                    ComponentAction::OpenLogView(view),
+                    ComponentType::CloudWatch,
+                )));
+            }
+        }
+        app
+    }
+
     /// Run the application's main event loop
     ///
-    /// Processes events and updates the terminal UI until the application exits
+    /// Processes events and updates the terminal UI until the application exits. Frames
+    /// are only drawn after an event that may have changed the state (keys, resizes, app
+    /// and tab events) or once [`IDLE_REDRAW_INTERVAL`] has passed, not on every tick.
+    ///
+    /// Events are handled one at a time on this loop: AWS requests run in background
+    /// tasks that report back as events for the tab that started them, but a tab event
+    /// that still awaits one inline holds up drawing and every later event. ⌘+Q is the
+    /// exception; the event task also raises it on a separate signal, so quitting
+    /// abandons such an event.
     pub async fn run(mut self, mut terminal: DefaultTerminal) -> color_eyre::Result<()> {
         while self.running {
-            terminal.draw(|frame| frame.render_widget(&self, frame.area()))?;
-            match self.events.next().await? {
+            if self.dirty {
+                terminal.draw(|frame| frame.render_widget(&self, frame.area()))?;
+                self.dirty = false;
+                self.last_draw = Instant::now();
+            }
+            let event = self.events.next().await?;
+            // Any event but a tick may change what is shown, e.g. a resize or a result
+            self.dirty |= !matches!(event, Event::Tick);
+            match event {
                 Event::Tick => self.tick(),
                 Event::Crossterm(event) => match event {
                     crossterm::event::Event::Key(key_event) => self.handle_key_events(key_event)?,
@@ -67,9 +141,28 @@ impl App {
                     self.apply_app_state(app_event);
                 }
                 Event::Tab(tab_event) => {
-                    self.apply_tab_state(tab_event).await;
+                    let quit = self.events.quit_signal();
+                    if unless_quit(&quit, self.apply_tab_state(tab_event)).await {
+                        self.quit();
+                    }
+                }
+                Event::FromTab(tab_id, tab_event) => {
+                    let quit = self.events.quit_signal();
+                    if unless_quit(&quit, self.apply_tab_state_from(tab_id, tab_event)).await {
+                        self.quit();
+                    }
+                }
+            }
+            // A tab on an automatically selected profile opens it once it is shown
+            if self.running
+                && let Some(tab) = self.tabs.get_mut(self.active_tab)
+            {
+                let quit = self.events.quit_signal();
+                if unless_quit(&quit, tab.open_pending_profile()).await {
+                    self.quit();
                 }
             }
+            self.events.set_animating(self.is_animating());
         }
         Ok(())
     }
@@ -78,6 +171,12 @@ impl App {
     ///
     /// Handles global shortcuts and routes other keypresses to the active tab
     pub fn handle_key_events(&mut self, key_event: KeyEvent) -> color_eyre::Result<()> {
+        // An open overlay takes every key
+        if self.overlay.is_some() {
+            self.handle_overlay_input(key_event);
+            return Ok(());
+        }
+
         match key_event.code {
             // Mac-style shortcuts (Command/⌘ is mapped to CONTROL in terminal apps)
             KeyCode::Char('w') if key_event.modifiers == KeyModifiers::CONTROL => {
@@ -95,6 +194,28 @@ impl App {
             KeyCode::Char('q') if key_event.modifiers == KeyModifiers::CONTROL => {
                 self.events.send(Event::App(AppEvent::Quit)) // ⌘+Q to quit
             }
+            KeyCode::Char('o') if key_event.modifiers == KeyModifiers::CONTROL => {
+                self.events.send(Event::App(AppEvent::ShowTabOverview)) // ⌘+O to list all tabs
+            }
+            KeyCode::Char('g') if key_event.modifiers == KeyModifiers::CONTROL => {
+                self.events.send(Event::App(AppEvent::ShowDiagnostics)) // ⌘+G to show the diagnostics log
+            }
+            // Ctrl+Shift+←/→ to move the current tab; plain Ctrl+←/→ resize panes
+            KeyCode::Left
+                if key_event.modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT =>
+            {
+                self.events.send(Event::App(AppEvent::MoveTabLeft))
+            }
+            KeyCode::Right
+                if key_event.modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT =>
+            {
+                self.events.send(Event::App(AppEvent::MoveTabRight))
+            }
+            KeyCode::Char(digit @ '1'..='9') if key_event.modifiers == KeyModifiers::CONTROL => {
+                // ⌘+1..9 to jump straight to a tab
+                let index = digit as usize - '1' as usize;
+                self.events.send(Event::App(AppEvent::JumpToTab(index)))
+            }
             _ => {
                 if let Some(tab) = self.tabs.get_mut(self.active_tab) {
                     tab.handle_input(key_event);
@@ -112,11 +233,8 @@ impl App {
             AppEvent::NextTab => self.next_tab(),
             AppEvent::PreviousTab => self.previous_tab(),
             AppEvent::CreateTab => {
-                self.tabs.push(Tab::new(
-                    "New Tab",
-                    "This is a new tab.",
-                    self.events.sender.clone(),
-                ));
+                let tab = Self::new_tab(&mut self.events, "New Tab", "This is a new tab.");
+                self.tabs.push(tab);
             }
             AppEvent::CloseTab => {
                 if self.tabs.len() > 1 {
@@ -124,7 +242,98 @@ impl App {
                     self.active_tab = self.active_tab.saturating_sub(1);
                 }
             }
-            AppEvent::Quit => self.quit(),
+            AppEvent::JumpToTab(index) => self.jump_to_tab(index),
+            AppEvent::MoveTabLeft => self.move_active_tab(false),
+            AppEvent::MoveTabRight => self.move_active_tab(true),
+            AppEvent::ShowTabOverview => {
+                let labels = self.tab_overview_labels();
+                self.overlay = Some(PopupWidget::new(
+                    PopupContent::Choices(labels),
+                    "Tabs",
+                    true,
+                    true,
+                ));
+            }
+            AppEvent::ShowDiagnostics => {
+                let entries = diagnostics::recent_entries();
+                let content = if entries.is_empty() {
+                    String::from("Nothing logged yet")
+                } else {
+                    entries.join("\n")
+                };
+                self.overlay = Some(PopupWidget::new(
+                    PopupContent::Details(content),
+                    "Diagnostics (newest first)",
+                    true,
+                    true,
+                ));
+            }
+            AppEvent::Quit => self.request_quit(),
+        }
+    }
+
+    /// Quits right away unless a background fetch is still running, which asks first
+    fn request_quit(&mut self) {
+        if !background_work_running() {
+            self.quit();
+            return;
+        }
+        self.overlay = Some(PopupWidget::new(
+            PopupContent::Confirm(String::from(
+                "Operations in progress — quit anyway?\n\nRunning fetches are cut off.",
+            )),
+            "Quit",
+            true,
+            true,
+        ));
+        self.confirming_quit = true;
+    }
+
+    /// Numbered overview line for every tab
+    fn tab_overview_labels(&self) -> Vec<String> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .map(|(i, tab)| format!("{}. {}", i + 1, tab.overview_label()))
+            .collect()
+    }
+
+    /// Navigates the open overlay; choosing a tab in the tab overview switches to it
+    fn handle_overlay_input(&mut self, key_event: KeyEvent) {
+        let Some(overlay) = self.overlay.as_mut() else {
+            return;
+        };
+        // ⌘+Q again answers the quit confirmation too
+        if self.confirming_quit
+            && key_event.code == KeyCode::Char('q')
+            && key_event.modifiers == KeyModifiers::CONTROL
+        {
+            self.quit();
+            return;
+        }
+        let selected = overlay
+            .handle_input(key_event)
+            .and_then(|action| overlay.process_event(action));
+        let closed = !overlay.is_visible();
+
+        if self.confirming_quit {
+            if matches!(
+                selected,
+                Some(WidgetAction::PopupAction(PopupAction::Confirmed))
+            ) {
+                self.quit();
+            }
+            if closed {
+                self.overlay = None;
+                self.confirming_quit = false;
+            }
+        } else if let Some(WidgetAction::PopupAction(PopupAction::ItemSelected(label))) = selected {
+            if let Some(index) = self.tab_overview_labels().iter().position(|l| *l == label) {
+                self.jump_to_tab(index);
+            }
+            self.overlay = None;
+        } else if closed {
+            self.overlay = None;
         }
     }
 
@@ -141,16 +350,73 @@ impl App {
         }
     }
 
+    /// Routes tab events raised inside a tab back to that tab, whether it is active or not
+    ///
+    /// Results of requests a tab started before the user switched away still land in it,
+    /// and those for a tab closed in the meantime are dropped.
+    pub async fn apply_tab_state_from(&mut self, tab_id: usize, tab_event: TabEvent) {
+        if let Some(tab) = self.tabs.iter_mut().find(|tab| tab.id() == tab_id) {
+            tab.process_event(tab_event).await;
+        }
+    }
+
     /// Handles the tick event of the terminal
     ///
-    /// Called at a fixed frame rate to update animations or poll external systems
-    pub fn tick(&self) {}
+    /// Called at the tick rate to update animations or poll external systems; an idle
+    /// screen is redrawn once [`IDLE_REDRAW_INTERVAL`] has passed since the last frame, and
+    /// on every tick while a tab is busy so its spinner turns.
+    /// Only the active tab is ticked, so background tabs do not poll.
+    pub fn tick(&mut self) {
+        if let Some(tab) = self.tabs.get_mut(self.active_tab) {
+            tab.tick();
+        }
+        // Busy tabs turn their spinner on every tick
+        if self.last_draw.elapsed() >= IDLE_REDRAW_INTERVAL || self.tabs.iter().any(Tab::is_busy) {
+            self.dirty = true;
+        }
+    }
+
+    /// Whether the active tab animates or any tab shows its busy spinner, so ticks come
+    /// at the active rate
+    pub fn is_animating(&self) -> bool {
+        self.tabs.iter().any(Tab::is_busy)
+            || self
+                .tabs
+                .get(self.active_tab)
+                .is_some_and(|tab| tab.is_animating())
+    }
 
     /// Terminates the application by setting running to false
     pub fn quit(&mut self) {
         self.running = false;
     }
 
+    /// Switches to the tab at the given index, ignoring indexes past the last tab
+    pub fn jump_to_tab(&mut self, index: usize) {
+        if index < self.tabs.len() {
+            self.active_tab = index;
+        }
+    }
+
+    /// Swaps the active tab with its left or right neighbour, staying on the moved tab
+    ///
+    /// The moved tab stays active, and results of requests still in flight find it by its
+    /// id. The first and last tabs do not wrap.
+    pub fn move_active_tab(&mut self, right: bool) {
+        let target = if right {
+            self.active_tab + 1
+        } else {
+            match self.active_tab.checked_sub(1) {
+                Some(target) => target,
+                None => return,
+            }
+        };
+        if target < self.tabs.len() {
+            self.tabs.swap(self.active_tab, target);
+            self.active_tab = target;
+        }
+    }
+
     /// Cycles to the next tab in the tab collection
     pub fn next_tab(&mut self) {
         // self.tabs[self.active_tab].show_popup = false;
@@ -165,3 +431,148 @@ impl App {
         }
     }
 }
+
+/// Awaits `work` unless the user quits twice first, returning whether they did
+///
+/// Tab events are awaited on the main loop, so a component awaiting AWS inline would
+/// otherwise keep ⌘+Q from being seen until it finished. A first ⌘+Q lets the work, e.g.
+/// a download, finish and is handled as a quit request afterwards; a second one drops it.
+async fn unless_quit(quit: &Notify, work: impl Future<Output = ()>) -> bool {
+    tokio::pin!(work);
+    tokio::select! {
+        () = &mut work => return false,
+        () = quit.notified() => {}
+    }
+    tokio::select! {
+        () = work => false,
+        () = quit.notified() => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::spawn_in_generation;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn quitting_twice_interrupts_tab_work_that_never_finishes() {
+        let quit = Arc::new(Notify::new());
+        let presses = Arc::clone(&quit);
+        let presser = tokio::spawn(async move {
+            loop {
+                presses.notify_waiters();
+                tokio::task::yield_now().await;
+            }
+        });
+        assert!(unless_quit(&quit, std::future::pending()).await);
+        presser.abort();
+
+        // Finished work is not mistaken for a quit
+        assert!(!unless_quit(&quit, async {}).await);
+    }
+
+    #[tokio::test]
+    async fn a_single_quit_lets_tab_work_finish() {
+        let quit = Notify::new();
+        let work = async {
+            quit.notify_waiters();
+            for _ in 0..3 {
+                tokio::task::yield_now().await;
+            }
+        };
+        assert!(!unless_quit(&quit, work).await);
+    }
+
+    #[tokio::test]
+    async fn quitting_asks_first_while_a_fetch_is_running() {
+        let mut app = App::new();
+        let fetch = spawn_in_generation(&app.events.sender, ComponentType::S3, 0, async {
+            std::future::pending::<()>().await;
+            vec![]
+        });
+
+        app.apply_app_state(AppEvent::Quit);
+        assert!(app.running);
+        assert!(app.overlay.is_some());
+
+        // n cancels, y quits
+        app.handle_key_events(KeyEvent::from(KeyCode::Char('n')))
+            .unwrap();
+        assert!(app.running);
+        assert!(app.overlay.is_none());
+
+        app.apply_app_state(AppEvent::Quit);
+        app.handle_key_events(KeyEvent::from(KeyCode::Char('y')))
+            .unwrap();
+        assert!(!app.running);
+        fetch.abort();
+    }
+
+    #[tokio::test]
+    async fn results_reach_the_tab_that_asked_after_switching_tabs() {
+        let mut app = App::new();
+        app.tabs[0].set_profile("prod".to_string());
+        app.tabs[1].set_profile("dev".to_string());
+
+        // The first tab asks to switch regions, then the user moves on before it is handled
+        app.tabs[0].handle_input(KeyEvent::from(KeyCode::F(3)));
+        app.tabs[0].handle_input(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        for c in "us-east-1".chars() {
+            app.tabs[0].handle_input(KeyEvent::from(KeyCode::Char(c)));
+        }
+        app.tabs[0].handle_input(KeyEvent::from(KeyCode::Enter));
+        app.apply_app_state(AppEvent::NextTab);
+
+        let (tab_id, tab_event) = loop {
+            match app.events.next().await.unwrap() {
+                Event::FromTab(tab_id, tab_event) => break (tab_id, tab_event),
+                _ => continue,
+            }
+        };
+        assert_eq!(tab_id, app.tabs[0].id());
+        app.apply_tab_state_from(tab_id, tab_event).await;
+
+        assert!(app.tabs[0].overview_label().contains("region: us-east-1"));
+        assert!(!app.tabs[1].overview_label().contains("region: us-east-1"));
+    }
+
+    fn tab_names(app: &App) -> Vec<String> {
+        app.tabs.iter().map(|tab| tab.name.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn idle_ticks_only_redraw_once_the_interval_passed() {
+        let mut app = App::new();
+        app.dirty = false;
+        app.last_draw = Instant::now();
+        app.tick();
+        assert!(!app.dirty);
+
+        app.last_draw = Instant::now() - IDLE_REDRAW_INTERVAL;
+        app.tick();
+        assert!(app.dirty);
+    }
+
+    #[tokio::test]
+    async fn moving_a_tab_keeps_it_active() {
+        let mut app = App::new();
+        app.jump_to_tab(1);
+
+        app.apply_app_state(AppEvent::MoveTabRight);
+        assert_eq!(tab_names(&app), ["Tab 1", "Tab 3", "Tab 2"]);
+        assert_eq!(app.active_tab, 2);
+
+        // The last tab stays last instead of wrapping around
+        app.apply_app_state(AppEvent::MoveTabRight);
+        assert_eq!(app.active_tab, 2);
+
+        app.apply_app_state(AppEvent::MoveTabLeft);
+        app.apply_app_state(AppEvent::MoveTabLeft);
+        assert_eq!(tab_names(&app), ["Tab 2", "Tab 1", "Tab 3"]);
+        assert_eq!(app.active_tab, 0);
+
+        app.apply_app_state(AppEvent::MoveTabLeft);
+        assert_eq!(tab_names(&app), ["Tab 2", "Tab 1", "Tab 3"]);
+    }
+}
@@ -0,0 +1,245 @@
+//! Command-line arguments module
+//!
+//! Parses the options that start the TUI on a profile, region and service instead of
+//! the profile popup, so it can be launched from scripts and shell aliases. Invalid
+//! values are reported before the terminal is switched to raw mode.
+
+use crate::event_managment::event::WidgetEventType;
+use crate::services::aws::log_link::LogView;
+use crate::services::read_config;
+use thiserror::Error;
+
+/// Usage text printed for `--help` and after invalid arguments
+pub const USAGE: &str =
+    "Usage: aws-tui [--profile NAME [--region REGION] [--service SERVICE | --open LINK]] [--yes]
+
+Options:
+  --profile NAME     Open a single tab on this AWS profile, skipping the profile popup
+  --region REGION    Region to use instead of the profile's default, e.g. eu-west-1
+  --service SERVICE  Service to open: S3, DynamoDB, CloudWatch, SSM or ECS
+  --open LINK        Open a CloudWatch log search copied with `l`, e.g. cw:///app?filter=ERROR
+  --yes              Skip delete confirmations (power-user mode); read-only mode wins
+  -h, --help         Print this help";
+
+/// Errors in the command-line arguments
+#[derive(Error, Debug, PartialEq)]
+pub enum CliError {
+    #[error("unknown option '{0}'")]
+    UnknownOption(String),
+
+    #[error("{0} needs a value")]
+    MissingValue(&'static str),
+
+    #[error("'{0}' is not a region name like eu-west-1")]
+    InvalidRegion(String),
+
+    #[error("unknown service '{0}', expected S3, DynamoDB, CloudWatch, SSM or ECS")]
+    UnknownService(String),
+
+    /// A region, service or link only applies to a profile opened from the command line
+    #[error("--region, --service and --open need --profile")]
+    ProfileRequired,
+
+    #[error("invalid --open link: {0}")]
+    InvalidLink(String),
+
+    /// Links describe CloudWatch searches, so no other service can be opened with one
+    #[error("--open opens CloudWatch and cannot be combined with --service {0}")]
+    LinkServiceConflict(String),
+
+    #[error("profile '{0}' not found in the AWS config or credentials files")]
+    UnknownProfile(String),
+
+    #[error("cannot read AWS profiles: {0}")]
+    Profiles(String),
+}
+
+/// Profile, region and service to start on, each `None` when not given
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StartupOptions {
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    pub service: Option<WidgetEventType>,
+    /// CloudWatch log search to open, from a `cw://` link
+    pub open: Option<LogView>,
+    /// Skip the confirmations before changes for this run
+    pub yes: bool,
+    /// Only print the usage
+    pub help: bool,
+}
+
+/// Parses the arguments after the program name, accepting `--option value` and `--option=value`
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<StartupOptions, CliError> {
+    let mut options = StartupOptions::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => {
+                (name.to_string(), Some(value.to_string()))
+            }
+            _ => (arg, None),
+        };
+        let mut value = |option: &'static str| {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .filter(|value| !value.is_empty())
+                .ok_or(CliError::MissingValue(option))
+        };
+
+        match name.as_str() {
+            "-h" | "--help" => options.help = true,
+            "--yes" => options.yes = true,
+            "--profile" => options.profile = Some(value("--profile")?),
+            "--region" => {
+                let region = value("--region")?;
+                if !is_region_name(&region) {
+                    return Err(CliError::InvalidRegion(region));
+                }
+                options.region = Some(region);
+            }
+            "--service" => {
+                let service = value("--service")?;
+                options.service = Some(
+                    WidgetEventType::from_service_name(&service)
+                        .ok_or(CliError::UnknownService(service))?,
+                );
+            }
+            "--open" => {
+                let link = value("--open")?;
+                options.open = Some(
+                    LogView::parse(&link).map_err(|err| CliError::InvalidLink(err.to_string()))?,
+                );
+            }
+            _ => return Err(CliError::UnknownOption(name)),
+        }
+    }
+
+    if options.profile.is_none()
+        && (options.region.is_some() || options.service.is_some() || options.open.is_some())
+    {
+        return Err(CliError::ProfileRequired);
+    }
+    if options.open.is_some() {
+        match &options.service {
+            None | Some(WidgetEventType::CloudWatch) => {
+                options.service = Some(WidgetEventType::CloudWatch)
+            }
+            Some(service) => return Err(CliError::LinkServiceConflict(format!("{:?}", service))),
+        }
+    }
+    Ok(options)
+}
+
+/// Checks that the profile to start on exists in the AWS config or credentials files
+pub fn check_profile(options: StartupOptions) -> Result<StartupOptions, CliError> {
+    let Some(profile) = &options.profile else {
+        return Ok(options);
+    };
+    let profiles =
+        read_config::get_aws_profiles().map_err(|err| CliError::Profiles(err.to_string()))?;
+    if !profiles.contains(profile) {
+        return Err(CliError::UnknownProfile(profile.clone()));
+    }
+    Ok(options)
+}
+
+/// Whether the name looks like an AWS region, e.g. `eu-west-1` or `us-gov-west-1`
+pub(crate) fn is_region_name(region: &str) -> bool {
+    let parts: Vec<&str> = region.split('-').collect();
+    let Some((number, words)) = parts.split_last() else {
+        return false;
+    };
+    parts.len() >= 3
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+        && words
+            .iter()
+            .all(|word| !word.is_empty() && word.chars().all(|c| c.is_ascii_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<StartupOptions, CliError> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_profile_region_and_service() {
+        assert_eq!(parse(&[]), Ok(StartupOptions::default()));
+        assert_eq!(
+            parse(&["--profile", "prod", "--region=us-east-1", "--service", "s3"]),
+            Ok(StartupOptions {
+                profile: Some("prod".to_string()),
+                region: Some("us-east-1".to_string()),
+                service: Some(WidgetEventType::S3),
+                open: None,
+                yes: false,
+                help: false,
+            })
+        );
+        assert!(parse(&["-h"]).unwrap().help);
+        // Power-user mode applies to every tab, so it needs no profile
+        assert!(parse(&["--yes"]).unwrap().yes);
+    }
+
+    #[test]
+    fn open_links_start_on_cloudwatch() {
+        let options = parse(&[
+            "--profile",
+            "prod",
+            "--open",
+            "cw:///aws/lambda/api?range=1h",
+        ]);
+        let options = options.unwrap();
+        assert_eq!(options.service, Some(WidgetEventType::CloudWatch));
+        assert_eq!(options.open.unwrap().log_group, "/aws/lambda/api");
+
+        assert!(matches!(
+            parse(&["--profile", "prod", "--open", "api"]),
+            Err(CliError::InvalidLink(_))
+        ));
+        assert!(matches!(
+            parse(&["--profile", "prod", "--service", "s3", "--open", "cw://api"]),
+            Err(CliError::LinkServiceConflict(_))
+        ));
+        assert_eq!(
+            parse(&["--open", "cw://api"]),
+            Err(CliError::ProfileRequired)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert_eq!(
+            parse(&["--verbose"]),
+            Err(CliError::UnknownOption("--verbose".to_string()))
+        );
+        assert_eq!(
+            parse(&["--profile"]),
+            Err(CliError::MissingValue("--profile"))
+        );
+        assert_eq!(
+            parse(&["--profile", "prod", "--region", "Europe"]),
+            Err(CliError::InvalidRegion("Europe".to_string()))
+        );
+        assert_eq!(
+            parse(&["--profile", "prod", "--service", "ec2"]),
+            Err(CliError::UnknownService("ec2".to_string()))
+        );
+        assert_eq!(parse(&["--service", "s3"]), Err(CliError::ProfileRequired));
+    }
+
+    #[test]
+    fn recognizes_region_names() {
+        for region in ["eu-west-1", "us-gov-west-1", "ap-southeast-2"] {
+            assert!(is_region_name(region), "{region}");
+        }
+        for region in ["", "eu-west", "eu--1", "EU-WEST-1", "west-1"] {
+            assert!(!is_region_name(region), "{region}");
+        }
+    }
+}
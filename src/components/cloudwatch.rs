@@ -1,22 +1,120 @@
-use crate::components::AWSComponent;
+use crate::components::{
+    AWSComponent, ComponentFocus, SplitRatio, abort_fetch, copy_cli_command, copy_console_link,
+    fetch_running, focus_jump_help, navigator_action, next_generation, render_error_banner,
+    spawn_fetch, spawn_in_generation,
+};
+use crate::diagnostics;
 use crate::event_managment::event::{
-    ComponentAction, ComponentType, Event, InputBoxEvent, InputBoxType, ServiceNavigatorEvent,
-    TabEvent, WidgetAction, WidgetEventType, WidgetType,
+    ComponentAction, ComponentType, Event, InputBoxEvent, InputBoxType, PopupAction,
+    SendOrLog, ServiceNavigatorEvent, TabEvent, WidgetAction, WidgetEventType, WidgetType,
 };
 use crate::services::aws::TabClients;
-use crate::services::aws::cloudwatch_client::CloudWatchClient;
+use crate::services::aws::cli_command::CliCommand;
+use crate::services::aws::console_link::ConsoleResource;
+use crate::services::aws::log_link::{self, LogView};
+use crate::services::aws::cloudwatch_client::{
+    self, AlarmSummary, CloudWatchClient, CloudWatchClientError, MetricId,
+};
+use crate::services::read_config::{self, Timeouts};
+use crate::theme::theme;
 use crate::widgets::WidgetExt;
+use crate::widgets::clipboard;
 use crate::widgets::input_box::InputBoxWidget;
+use crate::widgets::metric_chart::MetricChart;
 use crate::widgets::popup::{PopupContent, PopupWidget};
+use crate::widgets::record_sort::SortKey;
 use crate::widgets::service_navigator::{NavigatorContent, ServiceNavigator};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
 };
 use std::any::Any;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
+use tokio::task::AbortHandle;
+
+/// Relative time ranges offered by the presets popup
+const TIME_RANGE_PRESETS: [&str; 6] = ["5m", "15m", "1h", "6h", "24h", "7d"];
+/// Label prefix for entries in the presets popup
+const PRESET_PREFIX: &str = "Last ";
+/// Time range searched for log events when neither the input nor `log_time_range` sets one
+const DEFAULT_LOG_TIME_RANGE: &str = "5m";
+/// Time range charted when none is set; metrics are a minute or more apart, so longer than for logs
+const METRICS_TIME_RANGE: &str = "3h";
+/// Title of the query input while it filters metrics by namespace
+const NAMESPACE_INPUT_TITLE: &str = "Namespace (e.g. AWS/Lambda)";
+/// Title of the query input while it filters alarms by name
+const ALARM_PREFIX_INPUT_TITLE: &str = "Alarm name prefix";
+/// How often a live tail polls for new log events
+const TAIL_INTERVAL: Duration = Duration::from_secs(3);
+/// How long a live tail waits for a poll before polling again, e.g. when its result was
+/// lost because another tab was active when it arrived
+const TAIL_STALLED: Duration = Duration::from_secs(60);
+
+/// Runs a request within the given limit, if any, reporting which config key sets it on timeout
+async fn within_limit<T>(
+    limit: Option<Duration>,
+    key: &'static str,
+    request: impl Future<Output = Result<T, CloudWatchClientError>>,
+) -> Result<T, CloudWatchClientError> {
+    let Some(limit) = limit else {
+        return request.await;
+    };
+    tokio::time::timeout(limit, request)
+        .await
+        .unwrap_or(Err(CloudWatchClientError::Elapsed(limit.as_secs(), key)))
+}
+
+/// Log search waiting for the user to confirm its wide time range
+#[derive(Debug)]
+struct PendingSearch {
+    log_group: String,
+    filter_pattern: String,
+    time_range: String,
+    title_prefix: String,
+    focus_results: bool,
+}
+
+/// Returns whether a time window spans more than `limit` up to `now`
+fn exceeds_range(window: &cloudwatch_client::TimeWindow, limit: Duration, now: i64) -> bool {
+    let span = window.end_time.unwrap_or(now) - window.start_time;
+    span > limit.as_millis() as i64
+}
+
+/// Polling state of a live tail of the selected log group
+#[derive(Debug)]
+struct LiveTail {
+    /// Only events after this time (epoch millis) are fetched by the next poll
+    since: i64,
+    /// When the last poll started, or its result arrived
+    polled: Instant,
+    /// Whether a poll is waiting for its result
+    pending: bool,
+}
+
+impl LiveTail {
+    /// Starts tailing events from now on, polling on the next tick
+    fn new(now: i64) -> Self {
+        Self {
+            since: now,
+            polled: Instant::now() - TAIL_INTERVAL,
+            pending: false,
+        }
+    }
+
+    /// Whether the next poll is due
+    fn is_due(&self) -> bool {
+        if self.pending {
+            self.polled.elapsed() >= TAIL_STALLED
+        } else {
+            self.polled.elapsed() >= TAIL_INTERVAL
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CloudWatchFocus {
@@ -32,7 +130,28 @@ pub enum CloudWatchFocus {
     None,
 }
 
-/// Component for interacting with AWS CloudWatch logs
+/// What the CloudWatch component browses
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CloudWatchMode {
+    /// Log groups, searching their events
+    Logs,
+    /// Metrics, charting their datapoints
+    Metrics,
+    /// Alarms, showing their state and configuration
+    Alarms,
+}
+
+/// Colors alarms firing in the error color and those that are OK in the success color
+fn alarm_state_style(label: &str) -> Option<Style> {
+    let color = match label.split_whitespace().next()? {
+        "ALARM" => theme().error_fg,
+        "OK" => theme().success_fg,
+        _ => return None,
+    };
+    Some(Style::default().fg(color))
+}
+
+/// Component for interacting with AWS CloudWatch logs and metrics
 pub struct CloudWatch {
     /// Component type identifier
     component_type: ComponentType,
@@ -44,6 +163,12 @@ pub struct CloudWatch {
     time_range_input: InputBoxWidget,
     /// Current time range value
     time_range: Option<String>,
+    /// Time range searched while none is set, from the config or `DEFAULT_LOG_TIME_RANGE`
+    default_time_range: String,
+    /// Log search to open once the log groups are listed, e.g. one given with `--open`
+    pending_view: Option<LogView>,
+    /// Whether a search typed into the inputs moves focus to its results once it succeeds
+    focus_results_after_search: bool,
     /// AWS service client
     aws_clients: Option<TabClients>,
     
@@ -63,6 +188,36 @@ pub struct CloudWatch {
     event_sender: tokio::sync::mpsc::UnboundedSender<Event>,
     /// Current focus state within the component
     current_focus: CloudWatchFocus,
+    /// Error shown in a banner above the component's widgets
+    error_message: Option<String>,
+    /// Width of the left navigator relative to the right panel
+    split: SplitRatio,
+    /// Generation of the latest background request; older results are ignored
+    generation: u64,
+    /// Time limits for log searches and loading log groups
+    timeouts: Timeouts,
+    /// Whether log groups or metrics are browsed
+    mode: CloudWatchMode,
+    /// Metrics listed in the navigator, in the same order
+    metrics: Vec<MetricId>,
+    /// Metric charted in the results area
+    selected_metric: Option<MetricId>,
+    /// Chart shown instead of the results navigator in metrics mode
+    metric_chart: MetricChart,
+    /// Alarms listed in the navigator, in the same order
+    alarms: Vec<AlarmSummary>,
+    /// When log groups, logs or metrics were last fetched successfully
+    last_updated: Option<Instant>,
+    /// Live tail of the selected log group, if new events are being followed
+    live_tail: Option<LiveTail>,
+    /// Task of the latest fetch, aborted by Esc or when another fetch starts
+    fetch_task: Option<AbortHandle>,
+    /// Panel the latest fetch fills: the navigator, or the results (chart in metrics mode)
+    fetch_target: WidgetType,
+    /// Log searches over a longer time range ask for confirmation first
+    confirm_log_range: Option<Duration>,
+    /// Log search awaiting confirmation of its time range
+    pending_search: Option<PendingSearch>,
 }
 
 impl CloudWatch {
@@ -76,10 +231,13 @@ impl CloudWatch {
             selected_log_group: None,
             time_range_input: InputBoxWidget::new(
                 InputBoxType::TimeRange,
-                "Time Range (e.g. 1h, 1d, 7d)",
+                "Time Range (1h, 7d or start..end)",
                 false,
             ),
             time_range: None,
+            default_time_range: default_log_time_range(read_config::get_log_time_range()),
+            pending_view: None,
+            focus_results_after_search: read_config::get_focus_results_after_search(),
             aws_clients: None,
             
             // Fields moved from AWSComponentBase
@@ -88,24 +246,49 @@ impl CloudWatch {
                 false,
                 NavigatorContent::Records(vec![]),
             ),
-            input: InputBoxWidget::new(InputBoxType::Text, "Query Input", false),
-            results_navigator: ServiceNavigator::new(
-                WidgetType::QueryResultsNavigator,
-                false,
-                NavigatorContent::Records(vec![]),
-            ),
+            input: {
+                let mut input = InputBoxWidget::new(InputBoxType::Text, "Query Input", false);
+                // Filter patterns are recalled with Up/Down, per profile
+                input.enable_history("cloudwatch.filter");
+                input
+            },
+            results_navigator: {
+                let mut navigator = ServiceNavigator::new(
+                    WidgetType::QueryResultsNavigator,
+                    false,
+                    NavigatorContent::Records(vec![]),
+                );
+                navigator.set_severity_colors(read_config::get_log_colors());
+                // JSON logs carry their time in a field; other lines mostly start with it
+                navigator.enable_sorting(Some(vec![SortKey::new(
+                    "time",
+                    &["timestamp", "@timestamp", "time"],
+                )]));
+                navigator
+            },
             details_popup: PopupWidget::new(popup_content, "Details", false, false),
             active: false,
             visible: true,
             event_sender,
             current_focus: CloudWatchFocus::Navigation,
+            error_message: None,
+            split: SplitRatio::load("cloudwatch", 30),
+            generation: 0,
+            timeouts: read_config::get_timeouts(),
+            mode: CloudWatchMode::Logs,
+            metrics: Vec::new(),
+            selected_metric: None,
+            metric_chart: MetricChart::new("Metric"),
+            alarms: Vec::new(),
+            last_updated: None,
+            live_tail: None,
+            fetch_task: None,
+            fetch_target: WidgetType::AWSServiceNavigator,
+            confirm_log_range: read_config::get_large_fetch_confirmations().log_range,
+            pending_search: None,
         }
     }
 
-    fn get_current_focus(&self) -> CloudWatchFocus {
-        self.current_focus
-    }
-
     /// Updates active states of all widgets based on current focus
     fn update_widget_states(&mut self) {
         self.navigator
@@ -114,6 +297,8 @@ impl CloudWatch {
             .set_active(self.active & (self.current_focus == CloudWatchFocus::Input));
         self.results_navigator
             .set_active(self.active & (self.current_focus == CloudWatchFocus::Results));
+        self.metric_chart
+            .set_active(self.active & (self.current_focus == CloudWatchFocus::Results));
     }
 
     /// Shifts focus to the previous widget in the cyclic order
@@ -152,28 +337,129 @@ impl CloudWatch {
         self.navigator
             .set_title(format!("Log Group: {}", log_group));
 
-        let time_range = self.time_range.clone().unwrap_or_else(|| "5m".to_string());
+        let time_range = self.log_time_range();
         let filter_pattern = self.input.get_content().unwrap_or_default();
 
         // Fetch logs with current filter and time range
-        self.fetch_logs(&log_group, &filter_pattern, &time_range, "Log Events")
+        self.fetch_logs(&log_group, &filter_pattern, &time_range, "Log Events", false)
             .await;
     }
 
+    /// Time range log searches use: the one set in the input, else the default
+    fn log_time_range(&self) -> String {
+        self.time_range
+            .clone()
+            .unwrap_or_else(|| self.default_time_range.clone())
+    }
+
+    /// Searches the log group of a link with its filter pattern and time range
+    async fn open_log_view(&mut self, view: LogView) {
+        if let Some(range) = &view.time_range
+            && let Err(err) = cloudwatch_client::parse_time_range(range, chrono::Utc::now())
+        {
+            self.error_message = Some(err.to_string());
+            return;
+        }
+        self.error_message = None;
+        self.stop_live_tail();
+        if let Some(range) = &view.time_range {
+            self.time_range_input.set_content(range);
+        }
+        self.time_range = view.time_range;
+        self.input.set_content(&view.filter_pattern);
+        self.handle_log_group_selection(view.log_group).await;
+    }
+
+    /// Copies the current log search as a `cw://` link and reports it in the status line
+    fn copy_log_link(&self) {
+        let action = match &self.selected_log_group {
+            Some(log_group) => {
+                let link = LogView {
+                    log_group: log_group.clone(),
+                    filter_pattern: self.input.get_content().unwrap_or_default(),
+                    time_range: Some(self.log_time_range()),
+                }
+                .to_link();
+                match clipboard::copy_text(&link) {
+                    Ok(()) => WidgetAction::ServiceNavigatorEvent(
+                        ServiceNavigatorEvent::Copied(link),
+                        WidgetType::AWSServiceNavigator,
+                    ),
+                    Err(err) => WidgetAction::Notify(err.to_string()),
+                }
+            }
+            None => WidgetAction::Notify("Select a log group to copy a link to".to_string()),
+        };
+        self.event_sender
+            .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                ComponentAction::WidgetAction(action),
+                self.component_type.clone(),
+            )));
+    }
+
+    /// Fetches logs with the specified parameters, first asking for confirmation when the
+    /// time range is wider than configured
+    async fn fetch_logs(
+        &mut self,
+        log_group: &str,
+        filter_pattern: &str,
+        time_range: &str,
+        title_prefix: &str,
+        focus_results: bool,
+    ) {
+        let now = chrono::Utc::now();
+        if let Some(limit) = self.confirm_log_range
+            && let Ok(window) = cloudwatch_client::parse_time_range(time_range, now)
+            && exceeds_range(&window, limit, now.timestamp_millis())
+        {
+            self.details_popup.set_title("Confirm".to_string());
+            self.details_popup.set_content(PopupContent::Confirm(format!(
+                "Search '{}' over {}?\n\nThis may return a lot of data and take a while. Continue?",
+                log_group,
+                time_range.trim()
+            )));
+            self.details_popup.set_visible(true);
+            self.details_popup.set_active(true);
+            self.pending_search = Some(PendingSearch {
+                log_group: log_group.to_string(),
+                filter_pattern: filter_pattern.to_string(),
+                time_range: time_range.to_string(),
+                title_prefix: title_prefix.to_string(),
+                focus_results,
+            });
+            return;
+        }
+        self.start_log_search(log_group, filter_pattern, time_range, title_prefix, focus_results);
+    }
+
     /// Fetches logs with the specified parameters and updates the UI
     ///
-    /// Consolidates the previous separate log fetching methods into one
-    /// Fetches logs with the specified parameters and updates the UI
-    ///
-    /// Uses background task to prevent UI blocking
-    async fn fetch_logs(
+    /// Uses background task to prevent UI blocking. With `focus_results`, a successful
+    /// search moves focus to the results, as configured.
+    fn start_log_search(
         &mut self,
         log_group: &str,
         filter_pattern: &str,
         time_range: &str,
         title_prefix: &str,
+        focus_results: bool,
     ) {
+        // A new search replaces the tailed events, and the details viewed among them
+        self.live_tail = None;
+        self.details_popup.clear_history();
         if let Some(client_ref) = &self.cloudwatch_client {
+            // Resolve the range now so relative ranges end at the time of the request
+            let window = match cloudwatch_client::parse_time_range(time_range, chrono::Utc::now()) {
+                Ok(window) => window,
+                Err(err) => {
+                    self.error_message = Some(err.to_string());
+                    return;
+                }
+            };
+
+            // The titles say which range the events come from
+            let title_prefix = format!("{} [{}]", title_prefix, time_range.trim());
+
             // Show loading state immediately
             let title = if filter_pattern.is_empty() {
                 format!("{} (Loading...)", title_prefix)
@@ -182,106 +468,484 @@ impl CloudWatch {
             };
 
             self.event_sender
-                .send(Event::Tab(TabEvent::ComponentActions(
+                .send_or_log(Event::Tab(TabEvent::ComponentActions(
                     ComponentAction::WidgetAction(WidgetAction::ServiceNavigatorEvent(
                         ServiceNavigatorEvent::UpdateTitle(title),
                         WidgetType::QueryResultsNavigator,
                     )),
                     self.component_type.clone(),
-                )))
-                .unwrap_or_default();
+                )));
             self.event_sender
-                .send(Event::Tab(TabEvent::ComponentActions(
+                .send_or_log(Event::Tab(TabEvent::ComponentActions(
                     ComponentAction::WidgetAction(WidgetAction::ServiceNavigatorEvent(
-                        ServiceNavigatorEvent::UpdateContent(vec![
+                        ServiceNavigatorEvent::ShowPlaceholder(
                             "Fetching logs, please wait...".to_string(),
-                        ], false),
+                        ),
                         WidgetType::QueryResultsNavigator,
                     )),
                     self.component_type.clone(),
-                )))
-                .unwrap_or_default();
+                )));
 
             // Clone what we need for the background task
             let client_clone = Arc::clone(client_ref);
             let log_group = log_group.to_string();
             let filter_pattern = filter_pattern.to_string();
-            let time_range = time_range.to_string();
-            let event_sender = self.event_sender.clone();
-            let title = title_prefix.to_string();
-            let component_type = self.component_type.clone();
-            // Spawn background task to fetch logs without blocking UI
-            let _ = tokio::spawn(async move {
-                // Fetch logs in background
-
-                let logs_result = match tokio::time::timeout(
-                    std::time::Duration::from_secs(330), // 30-second timeout
-                    client_clone.lock().await.list_log_events(
-                        &log_group,
-                        &filter_pattern,
-                        Some(&time_range),
-                    ),
-                )
-                .await
-                {
-                    Ok(result) => result,
-                    Err(_) => Ok(vec!["Request timed out after 30 seconds".to_string()]),
-                };
-                // Send event with results back to the component
-                match logs_result {
-                    Ok(logs) => {
-                        // Send event with logs
-                        event_sender
-                            .send(Event::Tab(TabEvent::ComponentActions(
-                                ComponentAction::WidgetAction(WidgetAction::ServiceNavigatorEvent(
-                                    ServiceNavigatorEvent::UpdateContent(logs, false),
-                                    WidgetType::QueryResultsNavigator,
-                                )),
-                                component_type.clone(),
-                            )))
-                            .unwrap_or_default();
-                        event_sender
-                            .send(Event::Tab(TabEvent::ComponentActions(
-                                ComponentAction::WidgetAction(WidgetAction::ServiceNavigatorEvent(
-                                    ServiceNavigatorEvent::UpdateTitle(title),
-                                    WidgetType::QueryResultsNavigator,
-                                )),
-                                component_type.clone(),
-                            )))
-                            .unwrap_or_default();
-                    }
-                    Err(err) => {
-                        // Send event with error message
-                        event_sender
-                            .send(Event::Tab(TabEvent::ComponentActions(
-                                ComponentAction::WidgetAction(WidgetAction::ServiceNavigatorEvent(
-                                    ServiceNavigatorEvent::UpdateContent(vec![err.to_string()], false),
-                                    WidgetType::QueryResultsNavigator,
-                                )),
-                                component_type.clone(),
-                            )))
-                            .unwrap_or_default();
+            let title = title_prefix;
+            let limit = self.timeouts.cloudwatch_search;
+            let focus_results = focus_results && self.focus_results_after_search;
+            // Fetch logs in the background without blocking the UI, superseding any fetch
+            // still in flight
+            let task = spawn_fetch(
+                &self.event_sender,
+                self.component_type.clone(),
+                &mut self.generation,
+                async move {
+                    let logs_result = within_limit(limit, "cloudwatch_search", async {
+                        client_clone
+                            .lock()
+                            .await
+                            .list_log_events(
+                                &log_group,
+                                &filter_pattern,
+                                window.start_time,
+                                window.end_time,
+                            )
+                            .await
+                    })
+                    .await;
+                    let logs = match logs_result {
+                        Ok(logs) => logs,
+                        Err(err) => {
+                            return vec![navigator_action(
+                                ServiceNavigatorEvent::ShowPlaceholder(err.to_string()),
+                                WidgetType::QueryResultsNavigator,
+                            )];
+                        }
+                    };
+                    // Say in the title when the search stopped at max_results
+                    let title = format!("{}{}", title, logs.truncation_note());
+                    let total = logs.total;
+                    let logs = logs.records;
+                    // An empty result is a message in place of the logs, not a log line
+                    let content = if logs.is_empty() && filter_pattern.is_empty() {
+                        ServiceNavigatorEvent::ShowPlaceholder(
+                            "No logs found in the specified time range".to_string(),
+                        )
+                    } else if logs.is_empty() {
+                        ServiceNavigatorEvent::ShowPlaceholder(format!(
+                            "No logs matching filter '{}' found in the time range",
+                            filter_pattern
+                        ))
+                    } else {
+                        ServiceNavigatorEvent::UpdateContent(logs, false, total)
+                    };
+                    let mut actions = vec![
+                        navigator_action(content, WidgetType::QueryResultsNavigator),
+                        navigator_action(
+                            ServiceNavigatorEvent::UpdateTitle(title),
+                            WidgetType::QueryResultsNavigator,
+                        ),
+                        ComponentAction::MarkUpdated,
+                    ];
+                    if focus_results {
+                        actions.push(ComponentAction::FocusResults);
                     }
+                    actions
+                },
+            );
+            self.track_fetch(task, WidgetType::QueryResultsNavigator);
+        }
+    }
+
+    /// Keeps the task of a fetch that was just started, aborting the one it supersedes
+    fn track_fetch(&mut self, task: AbortHandle, target: WidgetType) {
+        abort_fetch(&mut self.fetch_task);
+        self.fetch_task = Some(task);
+        self.fetch_target = target;
+    }
+
+    /// Aborts the running fetch, if any, and says so in the panel it was loading
+    fn cancel_fetch(&mut self) {
+        if !abort_fetch(&mut self.fetch_task) {
+            return;
+        }
+        self.generation = next_generation();
+        match (self.fetch_target, self.mode) {
+            (WidgetType::AWSServiceNavigator, CloudWatchMode::Logs) => {
+                self.navigator.set_title(String::from("Log Groups (Cancelled)"));
+                self.navigator.show_placeholder("Loading log groups was cancelled");
+            }
+            (WidgetType::AWSServiceNavigator, CloudWatchMode::Metrics) => {
+                self.navigator.set_title(String::from("Metrics (Cancelled)"));
+                self.navigator.show_placeholder("Loading metrics was cancelled");
+            }
+            (_, CloudWatchMode::Alarms) => {
+                self.navigator.set_title(String::from("Alarms (Cancelled)"));
+                self.navigator.show_placeholder("Loading alarms was cancelled");
+            }
+            (_, CloudWatchMode::Logs) => {
+                self.results_navigator
+                    .set_title(String::from("Log Events (Cancelled)"));
+                self.results_navigator.show_placeholder("Search cancelled");
+            }
+            (_, CloudWatchMode::Metrics) => {
+                self.metric_chart.show_message(
+                    "Metric".to_string(),
+                    "Loading datapoints was cancelled".to_string(),
+                );
+            }
+        }
+        self.event_sender
+            .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                ComponentAction::WidgetAction(WidgetAction::Notify(String::from(
+                    "Cancelled the running CloudWatch request",
+                ))),
+                self.component_type.clone(),
+            )));
+    }
+
+    /// Starts following new events of the selected log group, or stops following them
+    fn toggle_live_tail(&mut self) {
+        if self.live_tail.is_some() {
+            self.stop_live_tail();
+            return;
+        }
+        let Some(log_group) = &self.selected_log_group else {
+            return;
+        };
+        if self.cloudwatch_client.is_none() {
+            return;
+        }
+        // Results of a search still in flight must not replace the tailed events
+        self.generation = next_generation();
+        self.live_tail = Some(LiveTail::new(chrono::Utc::now().timestamp_millis()));
+        self.results_navigator
+            .set_title(format!("Live tail: {} (following)", log_group));
+    }
+
+    /// Stops following new events, keeping those already shown
+    fn stop_live_tail(&mut self) {
+        if self.live_tail.take().is_none() {
+            return;
+        }
+        // Drop the result of a poll still in flight
+        self.generation = next_generation();
+        if let Some(log_group) = &self.selected_log_group {
+            self.results_navigator
+                .set_title(format!("Live tail: {} (stopped)", log_group));
+        }
+    }
+
+    /// Fetches the events logged since the last poll in the background
+    fn poll_live_tail(&mut self) {
+        let (Some(tail), Some(client), Some(log_group)) = (
+            self.live_tail.as_mut(),
+            &self.cloudwatch_client,
+            &self.selected_log_group,
+        ) else {
+            return;
+        };
+        tail.pending = true;
+        tail.polled = Instant::now();
+
+        let since = tail.since;
+        let client = Arc::clone(client);
+        let log_group = log_group.clone();
+        let filter_pattern = self.input.get_content().unwrap_or_default();
+        let limit = self.timeouts.cloudwatch_search;
+        // Polls keep the generation of the results they append to
+        spawn_in_generation(
+            &self.event_sender,
+            self.component_type.clone(),
+            self.generation,
+            async move {
+                let result = within_limit(limit, "cloudwatch_search", async {
+                    client
+                        .lock()
+                        .await
+                        .list_log_events_since(&log_group, &filter_pattern, since)
+                        .await
+                })
+                .await
+                .map_err(|err| err.to_string());
+                vec![ComponentAction::LogsTailed(result)]
+            },
+        );
+    }
+
+    /// Appends tailed events to the results, keeping at most `max_results` of them
+    fn apply_tailed_logs(&mut self, result: Result<(Vec<String>, Option<i64>), String>) {
+        let Some(tail) = self.live_tail.as_mut() else {
+            return;
+        };
+        tail.pending = false;
+        tail.polled = Instant::now();
+        match result {
+            Ok((logs, newest)) => {
+                tail.since = newest.map_or(tail.since, |newest| newest.max(tail.since));
+                self.last_updated = Some(Instant::now());
+                if !logs.is_empty() {
+                    self.results_navigator
+                        .append_and_follow(logs, read_config::get_max_results());
                 }
-            });
+            }
+            Err(err) => {
+                self.live_tail = None;
+                self.results_navigator
+                    .set_title(format!("Live tail stopped: {}", err));
+            }
         }
     }
 
     /// Sets the time range and refreshes the current view
     async fn set_time_range(&mut self, time_range: String) {
+        // Reject malformed ranges up front rather than silently querying a default window
+        if let Err(err) = cloudwatch_client::parse_time_range(&time_range, chrono::Utc::now()) {
+            self.error_message = Some(err.to_string());
+            return;
+        }
+        if self.cloudwatch_client.is_some() {
+            self.error_message = None;
+        }
         self.time_range = Some(time_range.clone());
+        self.time_range_input.set_content(&time_range);
+
+        // Redraw the charted metric over the new time range
+        if let Some(metric) = self.selected_metric.clone() {
+            self.fetch_metric_data(metric);
+            return;
+        }
 
         // If a log group is selected, refresh the logs with the new time range
         if let Some(log_group) = &self.selected_log_group {
             let log_group = log_group.clone();
             let filter = self.input.get_content().unwrap_or_default();
-            self.fetch_logs(&log_group, &filter, &time_range, "Search Results")
+            self.fetch_logs(&log_group, &filter, &time_range, "Search Results", true)
                 .await;
         }
     }
 
+    /// AWS CLI command repeating the search of the selected log group
+    ///
+    /// Relative time ranges are resolved now, like a search started now would be.
+    fn log_search_command(&self) -> Option<CliCommand> {
+        let log_group = self.selected_log_group.clone()?;
+        let time_range = self.log_time_range();
+        let window = cloudwatch_client::parse_time_range(&time_range, chrono::Utc::now()).ok()?;
+        Some(CliCommand::FilterLogEvents {
+            log_group,
+            filter_pattern: self.input.get_content().unwrap_or_default(),
+            start_time: window.start_time,
+            end_time: window.end_time,
+        })
+    }
+
+    /// Switches to browsing metrics or alarms, or back to log groups when they already are
+    async fn toggle_mode(&mut self, mode: CloudWatchMode) {
+        self.mode = if self.mode == mode {
+            CloudWatchMode::Logs
+        } else {
+            mode
+        };
+        self.selected_log_group = None;
+        self.selected_metric = None;
+        self.metrics.clear();
+        self.alarms.clear();
+        self.details_popup.clear_history();
+        self.input.set_content("");
+        // Alarms are colored by their state
+        self.navigator.set_record_colors(
+            (self.mode == CloudWatchMode::Alarms).then_some(alarm_state_style as _),
+        );
+        // Namespaces and alarm names are recalled separately from filter patterns
+        match self.mode {
+            CloudWatchMode::Logs => {
+                self.input.set_title("Query Input".to_string());
+                self.input.enable_history("cloudwatch.filter");
+            }
+            CloudWatchMode::Metrics => {
+                self.input.set_title(NAMESPACE_INPUT_TITLE.to_string());
+                self.input.enable_history("cloudwatch.namespace");
+            }
+            CloudWatchMode::Alarms => {
+                self.input.set_title(ALARM_PREFIX_INPUT_TITLE.to_string());
+                self.input.enable_history("cloudwatch.alarm");
+            }
+        }
+        if let Some(clients) = &self.aws_clients {
+            self.input.load_history(clients.profile());
+        }
+
+        match self.mode {
+            CloudWatchMode::Logs => {
+                self.update().await.ok();
+            }
+            CloudWatchMode::Metrics => {
+                self.metric_chart
+                    .show_message("Metric".to_string(), "Select a metric".to_string());
+                self.load_metrics(String::new());
+            }
+            CloudWatchMode::Alarms => {
+                self.results_navigator.set_title(String::from("Alarm"));
+                self.results_navigator
+                    .show_placeholder("Select an alarm to see its configuration and state");
+                self.load_alarms(String::new());
+            }
+        }
+    }
+
+    /// Lists the alarms whose names start with the prefix, or all alarms when it is empty
+    fn load_alarms(&mut self, prefix: String) {
+        let Some(client) = &self.cloudwatch_client else {
+            return;
+        };
+        let title = if prefix.is_empty() {
+            "Alarms".to_string()
+        } else {
+            format!("Alarms: {}*", prefix)
+        };
+        self.navigator.set_title(format!("{} (Loading...)", title));
+        self.navigator
+            .show_placeholder("Fetching alarms, please wait...".to_string());
+
+        let client = Arc::clone(client);
+        let limit = self.timeouts.cloudwatch;
+        let task = spawn_fetch(
+            &self.event_sender,
+            self.component_type.clone(),
+            &mut self.generation,
+            async move {
+                let prefix = (!prefix.is_empty()).then_some(prefix.as_str());
+                let result = within_limit(limit, "cloudwatch", async {
+                    client.lock().await.describe_alarms(prefix).await
+                })
+                .await
+                .map_err(|err| err.to_string());
+                vec![
+                    ComponentAction::SetTitle(title),
+                    ComponentAction::AlarmsListed(result),
+                ]
+            },
+        );
+        self.track_fetch(task, WidgetType::AWSServiceNavigator);
+    }
+
+    /// Shows the configuration and latest state change of an alarm in the results and
+    /// the details popup
+    fn show_alarm(&mut self, label: &str) {
+        let Some(alarm) = self.alarms.iter().find(|alarm| alarm.label() == label) else {
+            return;
+        };
+        let title = format!("Alarm: {} ({})", alarm.name, alarm.state);
+        self.results_navigator.set_title(title.clone());
+        self.results_navigator
+            .set_content(NavigatorContent::Records(vec![alarm.details.clone()]));
+        self.details_popup.set_title(title);
+        self.details_popup
+            .set_content(PopupContent::Details(alarm.details.clone()));
+        self.details_popup.set_visible(true);
+        self.details_popup.set_active(true);
+    }
+
+    /// Lists the metrics of a namespace, or of all namespaces when it is empty
+    fn load_metrics(&mut self, namespace: String) {
+        let Some(client) = &self.cloudwatch_client else {
+            return;
+        };
+        let title = if namespace.is_empty() {
+            "Metrics".to_string()
+        } else {
+            format!("Metrics: {}", namespace)
+        };
+        self.navigator.set_title(format!("{} (Loading...)", title));
+        self.navigator
+            .show_placeholder("Fetching metrics, please wait...".to_string());
+        self.selected_metric = None;
+
+        let client = Arc::clone(client);
+        let limit = self.timeouts.cloudwatch;
+        let task = spawn_fetch(
+            &self.event_sender,
+            self.component_type.clone(),
+            &mut self.generation,
+            async move {
+                let namespace = (!namespace.is_empty()).then_some(namespace.as_str());
+                let result = within_limit(limit, "cloudwatch", async {
+                    client.lock().await.list_metrics(namespace).await
+                })
+                .await
+                .map_err(|err| err.to_string());
+                vec![
+                    ComponentAction::SetTitle(title),
+                    ComponentAction::MetricsListed(result),
+                ]
+            },
+        );
+        self.track_fetch(task, WidgetType::AWSServiceNavigator);
+    }
+
+    /// Fetches the metric's datapoints over the current time range and charts them
+    fn fetch_metric_data(&mut self, metric: MetricId) {
+        let Some(client) = &self.cloudwatch_client else {
+            return;
+        };
+        let time_range = self
+            .time_range
+            .clone()
+            .unwrap_or_else(|| METRICS_TIME_RANGE.to_string());
+        let now = chrono::Utc::now();
+        let window = match cloudwatch_client::parse_time_range(&time_range, now) {
+            Ok(window) => window,
+            Err(err) => {
+                self.error_message = Some(err.to_string());
+                return;
+            }
+        };
+        let period = cloudwatch_client::metric_period(window, now);
+        let label = metric.label();
+        self.metric_chart.show_message(
+            label.clone(),
+            "Fetching datapoints, please wait...".to_string(),
+        );
+        self.selected_metric = Some(metric.clone());
+
+        let client = Arc::clone(client);
+        let limit = self.timeouts.cloudwatch;
+        let task = spawn_fetch(
+            &self.event_sender,
+            self.component_type.clone(),
+            &mut self.generation,
+            async move {
+                let result = within_limit(limit, "cloudwatch", async {
+                    client
+                        .lock()
+                        .await
+                        .get_metric_statistics(&metric, window, period)
+                        .await
+                })
+                .await
+                .map_err(|err| err.to_string());
+                vec![ComponentAction::MetricDataLoaded(label, result)]
+            },
+        );
+        self.track_fetch(task, WidgetType::QueryResultsNavigator);
+    }
+
+    /// Opens the popup listing relative time range presets
+    fn show_time_range_presets(&mut self) {
+        let presets = TIME_RANGE_PRESETS
+            .iter()
+            .map(|preset| format!("{}{}", PRESET_PREFIX, preset))
+            .collect();
+        self.details_popup.set_title("Time Range".to_string());
+        self.details_popup.set_content(PopupContent::Choices(presets));
+        self.details_popup.set_visible(true);
+        self.details_popup.set_active(true);
+    }
+
     /// Shows detailed view of a log entry in a popup
     async fn view_log_details(&mut self, log_content: &str) {
+        self.details_popup.set_title("Details".to_string());
         self.details_popup
             .set_content(PopupContent::Details(log_content.to_string()));
         self.details_popup.set_visible(true);
@@ -306,32 +970,91 @@ impl CloudWatch {
 
         // Check if the popup is visible
         if self.details_popup.is_visible() {
-            items.push(("Esc".to_string(), "Close details".to_string()));
-            items.push(("PgUp/PgDn".to_string(), "Scroll content".to_string()));
-            return items;
+            return self.details_popup.get_help_items();
         }
 
         // Different help items based on current focus
         match self.current_focus {
+            CloudWatchFocus::Navigation if self.mode == CloudWatchMode::Metrics => {
+                items.push(("Enter".to_string(), "Chart metric".to_string()));
+                items.push(("m".to_string(), "Show log groups".to_string()));
+                items.push(("!".to_string(), "Show alarms".to_string()));
+                items.push(("Alt+R".to_string(), "Time range presets".to_string()));
+            }
+            CloudWatchFocus::Navigation if self.mode == CloudWatchMode::Alarms => {
+                items.push(("Enter".to_string(), "Alarm details".to_string()));
+                items.push(("!".to_string(), "Show log groups".to_string()));
+                items.push(("m".to_string(), "Show metrics".to_string()));
+            }
             CloudWatchFocus::Navigation => {
                 items.push(("Enter".to_string(), "Select log group".to_string()));
-                items.push(("Alt+2".to_string(), "Focus results".to_string()));
-                items.push(("Alt+3".to_string(), "Focus input".to_string()));
+                items.push(("i".to_string(), "Log group info".to_string()));
+                items.push(("o".to_string(), "Copy console link".to_string()));
+                items.push(("a".to_string(), "Copy as AWS CLI command".to_string()));
+                if self.selected_log_group.is_some() {
+                    items.push(("l".to_string(), "Copy search link".to_string()));
+                }
+                items.push(("m".to_string(), "Show metrics".to_string()));
+                items.push(("!".to_string(), "Show alarms".to_string()));
+                items.push(("Alt+R".to_string(), "Time range presets".to_string()));
+            }
+            CloudWatchFocus::Results if self.mode == CloudWatchMode::Alarms => {
+                items.push(("Enter".to_string(), "Alarm details".to_string()));
             }
             CloudWatchFocus::Results => {
                 items.push(("Enter".to_string(), "View log details".to_string()));
-                items.push(("Alt+1".to_string(), "Focus log groups".to_string()));
-                items.push(("Alt+3".to_string(), "Focus input".to_string()));
+                if self.live_tail.is_some() {
+                    items.push(("f/Esc".to_string(), "Stop live tail".to_string()));
+                } else if self.selected_log_group.is_some() {
+                    items.push(("f".to_string(), "Live tail".to_string()));
+                }
+                if self.selected_log_group.is_some() {
+                    items.push(("l".to_string(), "Copy search link".to_string()));
+                }
+                items.push(("c".to_string(), "Toggle severity colors".to_string()));
+            }
+            CloudWatchFocus::Input if self.mode == CloudWatchMode::Metrics => {
+                items.push(("Enter".to_string(), "List namespace metrics".to_string()));
+            }
+            CloudWatchFocus::Input if self.mode == CloudWatchMode::Alarms => {
+                items.push(("Enter".to_string(), "List alarms by name prefix".to_string()));
             }
             CloudWatchFocus::Input => {
                 items.push(("Enter".to_string(), "Search logs".to_string()));
-                items.push(("Alt+1".to_string(), "Focus log groups".to_string()));
-                items.push(("Alt+2".to_string(), "Focus results".to_string()));
+            }
+            CloudWatchFocus::TimeRange => {
+                items.push(("Enter".to_string(), "Apply time range".to_string()));
+                items.push(("Alt+R".to_string(), "Time range presets".to_string()));
             }
             _ => {}
         }
+        items.extend(self.focus_jump_help());
         items
     }
+
+    /// Help items for the Alt+number jumps, leaving out the focused area
+    fn focus_jump_help(&self) -> Vec<(String, String)> {
+        let focused = match self.current_focus {
+            CloudWatchFocus::Navigation => 1,
+            CloudWatchFocus::Input => 2,
+            CloudWatchFocus::TimeRange => 3,
+            _ => 4,
+        };
+        let (list, input) = match self.mode {
+            CloudWatchMode::Metrics => ("Focus metrics", "Filter by namespace"),
+            CloudWatchMode::Alarms => ("Focus alarms", "Filter by name prefix"),
+            CloudWatchMode::Logs => ("Focus log groups", "Focus filter"),
+        };
+        focus_jump_help(
+            [
+                Some(list),
+                Some(input),
+                Some("Focus time range"),
+                Some("Focus results"),
+            ],
+            focused,
+        )
+    }
 }
 
 #[async_trait::async_trait]
@@ -341,13 +1064,16 @@ impl AWSComponent for CloudWatch {
             return;
         }
 
+        // Reserve space for the error banner if a client failed to initialize
+        let area = match &self.error_message {
+            Some(message) => render_error_banner(message, area, buf),
+            None => area,
+        };
+
         // Create a horizontal split for left panel (log groups) and right panel (log events)
         let horizontal_split = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(30), // Left panel - log groups list
-                Constraint::Percentage(70), // Right panel - log events and search
-            ])
+            .constraints(self.split.constraints())
             .split(area);
 
         // Create a vertical split for the right panel to separate inputs from results
@@ -368,8 +1094,13 @@ impl AWSComponent for CloudWatch {
             ])
             .split(right_vertical_split[0]);
 
-        // Render components
-        self.navigator.render(horizontal_split[0], buf);
+        // Render components, guiding newcomers until there is something to list
+        let group_prompt = self
+            .cloudwatch_client
+            .is_none()
+            .then_some("Select a profile to list its log groups");
+        self.navigator
+            .render_with_prompt(horizontal_split[0], buf, group_prompt);
 
         // Render the search input box
         self.input.render(input_row[0], buf);
@@ -377,8 +1108,19 @@ impl AWSComponent for CloudWatch {
         // Render the time range input box
         self.time_range_input.render(input_row[1], buf);
 
-        // Render the results navigator
-        self.results_navigator.render(right_vertical_split[1], buf);
+        // Render the results navigator, or the chart of the selected metric
+        match self.mode {
+            CloudWatchMode::Logs => {
+                let events_prompt = self
+                    .selected_log_group
+                    .is_none()
+                    .then_some("Pick a log group, then type a filter pattern to search its events");
+                self.results_navigator
+                    .render_with_prompt(right_vertical_split[1], buf, events_prompt)
+            }
+            CloudWatchMode::Metrics => self.metric_chart.render(right_vertical_split[1], buf),
+            CloudWatchMode::Alarms => self.results_navigator.render(right_vertical_split[1], buf),
+        }
 
         // Render popup if visible
         if self.details_popup.is_visible() {
@@ -392,11 +1134,10 @@ impl AWSComponent for CloudWatch {
         if self.details_popup.is_visible() {
             if let Some(signal) = self.details_popup.handle_input(key_event) {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::WidgetAction(signal),
                         self.component_type.clone(),
-                    )))
-                    .unwrap();
+                    )));
                 return;
             }
         }
@@ -404,19 +1145,17 @@ impl AWSComponent for CloudWatch {
         match key_event.code {
             KeyCode::Tab => {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::NextFocus,
                         self.component_type.clone(),
-                    )))
-                    .unwrap();
+                    )));
             }
             KeyCode::BackTab => {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::PreviousFocus,
                         self.component_type.clone(),
-                    )))
-                    .unwrap();
+                    )));
             }
             // Alt+number shortcuts to switch focus between areas
             KeyCode::Char('1') if key_event.modifiers == KeyModifiers::ALT => {
@@ -434,7 +1173,132 @@ impl AWSComponent for CloudWatch {
                 self.current_focus = CloudWatchFocus::Results;
                 self.update_widget_states();
             }
+            KeyCode::Char('r') if key_event.modifiers == KeyModifiers::ALT => {
+                self.show_time_range_presets();
+            }
+            // Switch between log groups and metrics
+            KeyCode::Char('m')
+                if key_event.modifiers.is_empty()
+                    && self.current_focus == CloudWatchFocus::Navigation
+                    && !self.navigator.is_capturing_input() =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::ToggleMetrics,
+                        self.component_type.clone(),
+                    )));
+            }
+            // Switch between log groups and alarms
+            KeyCode::Char('!')
+                if !key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                    && self.current_focus == CloudWatchFocus::Navigation
+                    && !self.navigator.is_capturing_input() =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::ToggleAlarms,
+                        self.component_type.clone(),
+                    )));
+            }
+            // Show metadata for the highlighted log group
+            KeyCode::Char('i')
+                if key_event.modifiers.is_empty()
+                    && self.mode == CloudWatchMode::Logs
+                    && self.current_focus == CloudWatchFocus::Navigation
+                    && !self.navigator.is_capturing_input() =>
+            {
+                if let Some(log_group) = self.navigator.selected_label() {
+                    self.event_sender
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                            ComponentAction::DescribeLogGroup(log_group),
+                            self.component_type.clone(),
+                        )));
+                }
+            }
+            // Toggle coloring log lines by severity
+            KeyCode::Char('c')
+                if key_event.modifiers.is_empty()
+                    && self.current_focus == CloudWatchFocus::Results
+                    && !self.results_navigator.is_capturing_input() =>
+            {
+                let enabled = self.results_navigator.severity_colors();
+                self.results_navigator.set_severity_colors(!enabled);
+            }
+            // Follow new events of the selected log group, like tail -f
+            KeyCode::Char('f')
+                if key_event.modifiers.is_empty()
+                    && self.mode == CloudWatchMode::Logs
+                    && self.current_focus == CloudWatchFocus::Results
+                    && !self.results_navigator.is_capturing_input() =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::ToggleLiveTail,
+                        self.component_type.clone(),
+                    )));
+            }
+            // Copy the console link of the highlighted log group
+            KeyCode::Char('o')
+                if key_event.modifiers.is_empty()
+                    && self.mode == CloudWatchMode::Logs
+                    && self.current_focus == CloudWatchFocus::Navigation
+                    && !self.navigator.is_capturing_input() =>
+            {
+                if let Some(name) = self.navigator.selected_label() {
+                    copy_console_link(
+                        ConsoleResource::LogGroup(name),
+                        self.aws_clients.as_ref(),
+                        &self.event_sender,
+                        self.component_type.clone(),
+                    );
+                }
+            }
+            // Copy the AWS CLI command repeating the current listing or query
+            KeyCode::Char('a')
+                if key_event.modifiers.is_empty()
+                    && match self.current_focus {
+                        CloudWatchFocus::Navigation => !self.navigator.is_capturing_input(),
+                        CloudWatchFocus::Results => !self.results_navigator.is_capturing_input(),
+                        _ => false,
+                    } =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::CopyCliCommand,
+                        self.component_type.clone(),
+                    )));
+            }
+            // Copy the log search as a link teammates can open
+            KeyCode::Char('l')
+                if key_event.modifiers.is_empty()
+                    && self.mode == CloudWatchMode::Logs
+                    && match self.current_focus {
+                        CloudWatchFocus::Navigation => !self.navigator.is_capturing_input(),
+                        CloudWatchFocus::Results => !self.results_navigator.is_capturing_input(),
+                        _ => false,
+                    } =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::CopyLogLink,
+                        self.component_type.clone(),
+                    )));
+            }
+            // Ctrl+Left/Right resize the panes; input boxes keep them for word movement
+            KeyCode::Left | KeyCode::Right
+                if key_event.modifiers == KeyModifiers::CONTROL
+                    && matches!(
+                        self.current_focus,
+                        CloudWatchFocus::Navigation | CloudWatchFocus::Results
+                    ) =>
+            {
+                self.split.nudge(key_event.code == KeyCode::Right);
+            }
             KeyCode::Esc => {
+                self.stop_live_tail();
+                self.cancel_fetch();
                 if self.current_focus != CloudWatchFocus::Navigation {
                     self.current_focus = CloudWatchFocus::Navigation;
                     self.update_widget_states();
@@ -450,49 +1314,127 @@ impl AWSComponent for CloudWatch {
                     CloudWatchFocus::None => None,
                 } {
                     self.event_sender
-                        .send(Event::Tab(TabEvent::ComponentActions(
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
                             ComponentAction::WidgetAction(signal),
                             self.component_type.clone(),
-                        )))
-                        .unwrap();
+                        )));
                 }
             }
         }
     }
 
+    /// Polls a live tail once it is due
+    fn tick(&mut self) {
+        if self.live_tail.as_ref().is_some_and(LiveTail::is_due) {
+            self.poll_live_tail();
+        }
+    }
+
+    /// A live tail keeps polling, so it ticks at the active rate
+    fn is_animating(&self) -> bool {
+        self.live_tail.is_some()
+    }
+
     /// Processes CloudWatch-specific component actions
     async fn process_event(&mut self, event: ComponentAction) {
         match event {
             cw_event => match cw_event {
-                ComponentAction::Active(aws_profile) => {
+                ComponentAction::Active(aws_profile, region) => {
+                    // Results for the previous profile must not land in the new view
+                    self.generation = next_generation();
+                    self.live_tail = None;
+                    self.details_popup.clear_history();
+                    abort_fetch(&mut self.fetch_task);
+                    self.input.load_history(&aws_profile);
                     self.aws_clients =
-                        Some(TabClients::new(aws_profile, String::from("eu-west-1")));
+                        Some(TabClients::new(aws_profile, region));
+                    self.last_updated = None;
 
                     // Unwrap the Result and handle errors properly
                     if let Some(clients) = &mut self.aws_clients {
                         match clients.get_cloudwatch_client().await {
                             Ok(client) => {
                                 self.cloudwatch_client = Some(client);
-                                self.update().await.ok();
+                                self.error_message = None;
+                                match self.mode {
+                                    CloudWatchMode::Logs => {
+                                        self.update().await.ok();
+                                    }
+                                    CloudWatchMode::Metrics => {
+                                        self.load_metrics(
+                                            self.input.get_content().unwrap_or_default(),
+                                        );
+                                    }
+                                    CloudWatchMode::Alarms => {
+                                        self.load_alarms(
+                                            self.input.get_content().unwrap_or_default(),
+                                        );
+                                    }
+                                }
                             }
                             Err(err) => {
-                                // Handle the error (show error in UI)
-                                self.results_navigator
-                                    .set_title(String::from("Error connecting to CloudWatch"));
-                                self.results_navigator.set_content(NavigatorContent::Records(vec![format!(
-                                    "Failed to initialize CloudWatch client: {}",
+                                let message = format!(
+                                    "Failed to initialize CloudWatch client (profile: {}, region: {}): {}",
+                                    clients.profile(),
+                                    clients.region(),
                                     err
-                                )]));
+                                );
+                                // The error banner shows it instead of clobbering the navigators
+                                self.event_sender
+                                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                        ComponentAction::ShowError(message),
+                                        self.component_type.clone(),
+                                    )));
                             }
                         }
                     }
                 }
+                ComponentAction::ShowError(message) => {
+                    diagnostics::error(format!("CloudWatch: {}", message));
+                    self.error_message = Some(message);
+                }
+                ComponentAction::Reset => {
+                    // Results of requests still in flight must not refill the cleared view
+                    self.generation = next_generation();
+                    self.details_popup.clear_history();
+                    self.pending_view = None;
+                    self.pending_search = None;
+                    self.live_tail = None;
+                    abort_fetch(&mut self.fetch_task);
+                    self.navigator.reset_view();
+                    self.results_navigator.reset_view();
+                    self.results_navigator
+                        .set_content(NavigatorContent::Records(vec![]));
+                    self.results_navigator
+                        .set_title(String::from("Select a log group"));
+                    self.input.set_content("");
+                    self.time_range_input.set_content("");
+                    self.time_range = None;
+                    self.selected_log_group = None;
+                    self.selected_metric = None;
+                    self.metric_chart
+                        .show_message("Metric".to_string(), "Select a metric".to_string());
+                    self.error_message = None;
+                    self.reset_focus();
+                }
+                // Apply background results only if no newer request has started since;
+                // stale ones fall through and are dropped
+                ComponentAction::BackgroundResult(generation, action)
+                    if generation == self.generation =>
+                {
+                    self.process_event(*action).await;
+                }
+                ComponentAction::MarkUpdated => {
+                    self.last_updated = Some(Instant::now());
+                }
                 ComponentAction::Focused => {
-                    // Set the component as inactive
+                    // Set the component as active
                     self.set_active(true);
                 }
                 ComponentAction::Unfocused => {
-                    if self.get_current_focus() == CloudWatchFocus::None {
+                    // Tailing only runs while the user is looking at the component
+                    self.stop_live_tail();
+                    if self.current_focus == CloudWatchFocus::None {
                         self.reset_focus();
                     }
                     // Set the component as inactive
@@ -507,12 +1449,24 @@ impl AWSComponent for CloudWatch {
                     self.handle_log_group_selection(log_group).await;
                 }
                 // Handle search/filter request for logs
+                // A pasted cw:// link opens its search instead
+                ComponentAction::SearchLogs(filter) if log_link::is_log_link(&filter) => {
+                    match LogView::parse(&filter) {
+                        Ok(view) => {
+                            self.event_sender
+                                .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                    ComponentAction::OpenLogView(view),
+                                    self.component_type.clone(),
+                                )));
+                        }
+                        Err(err) => self.error_message = Some(format!("Invalid link: {}", err)),
+                    }
+                }
                 ComponentAction::SearchLogs(filter) => {
                     if let Some(log_group) = &self.selected_log_group {
                         let log_group = log_group.clone();
-                        let time_range =
-                            self.time_range.clone().unwrap_or_else(|| "5m".to_string());
-                        self.fetch_logs(&log_group, &filter, &time_range, "Search Results")
+                        let time_range = self.log_time_range();
+                        self.fetch_logs(&log_group, &filter, &time_range, "Search Results", true)
                             .await;
                     }
                 }
@@ -520,6 +1474,126 @@ impl AWSComponent for CloudWatch {
                 ComponentAction::SetTimeRange(time_range) => {
                     self.set_time_range(time_range).await;
                 }
+                // Results of a search started from the inputs are read next, unless the
+                // user has moved on to another area in the meantime
+                ComponentAction::FocusResults
+                    if matches!(
+                        self.current_focus,
+                        CloudWatchFocus::Input | CloudWatchFocus::TimeRange
+                    ) =>
+                {
+                    self.update_time_range_focus(false);
+                    self.current_focus = CloudWatchFocus::Results;
+                    self.update_widget_states();
+                }
+                // Show log group metadata in the details popup
+                ComponentAction::DescribeLogGroup(log_group) => {
+                    if let Some(client) = &self.cloudwatch_client {
+                        let summary = client
+                            .lock()
+                            .await
+                            .describe_log_group(&log_group)
+                            .await
+                            .unwrap_or_else(|err| {
+                                format!("Failed to describe log group {}: {}", log_group, err)
+                            });
+                        self.view_log_details(&summary).await;
+                    }
+                }
+                ComponentAction::CopyCliCommand => {
+                    copy_cli_command(
+                        self.log_search_command(),
+                        self.aws_clients.as_ref(),
+                        &self.event_sender,
+                        self.component_type.clone(),
+                    );
+                }
+                ComponentAction::CopyLogLink => self.copy_log_link(),
+                // Until log groups are listed in this region, opening one would stop the listing
+                ComponentAction::OpenLogView(view)
+                    if self.cloudwatch_client.is_none() || self.mode != CloudWatchMode::Logs =>
+                {
+                    self.pending_view = Some(view);
+                    if self.cloudwatch_client.is_some() {
+                        self.stop_live_tail();
+                        self.toggle_mode(self.mode).await;
+                    }
+                }
+                ComponentAction::OpenLogView(view) => self.open_log_view(view).await,
+                ComponentAction::ToggleMetrics => {
+                    self.stop_live_tail();
+                    self.toggle_mode(CloudWatchMode::Metrics).await;
+                }
+                ComponentAction::ToggleAlarms => {
+                    self.stop_live_tail();
+                    self.toggle_mode(CloudWatchMode::Alarms).await;
+                }
+                ComponentAction::ListAlarms(prefix) => {
+                    self.load_alarms(prefix);
+                }
+                ComponentAction::AlarmsListed(Ok(alarms)) => {
+                    self.last_updated = Some(Instant::now());
+                    if alarms.is_empty() {
+                        self.navigator.show_placeholder("No alarms found".to_string());
+                    } else {
+                        let labels = alarms.iter().map(AlarmSummary::label).collect();
+                        self.navigator.set_content(NavigatorContent::Records(labels));
+                    }
+                    self.alarms = alarms;
+                }
+                ComponentAction::AlarmsListed(Err(err)) => {
+                    self.alarms.clear();
+                    self.navigator
+                        .show_placeholder(format!("Error listing alarms: {}", err));
+                }
+                ComponentAction::SelectAlarm(label) => self.show_alarm(&label),
+                ComponentAction::ToggleLiveTail => {
+                    self.toggle_live_tail();
+                }
+                ComponentAction::LogsTailed(result) => {
+                    self.apply_tailed_logs(result);
+                }
+                ComponentAction::ListMetrics(namespace) => {
+                    self.load_metrics(namespace);
+                }
+                ComponentAction::SetTitle(title) => {
+                    self.navigator.set_title(title);
+                }
+                ComponentAction::MetricsListed(Ok(metrics)) => {
+                    self.last_updated = Some(Instant::now());
+                    if metrics.is_empty() {
+                        self.navigator
+                            .show_placeholder("No metrics found in this namespace".to_string());
+                    } else {
+                        let labels = metrics.iter().map(MetricId::label).collect();
+                        self.navigator.set_content(NavigatorContent::Records(labels));
+                    }
+                    self.metrics = metrics;
+                }
+                ComponentAction::MetricsListed(Err(err)) => {
+                    self.metrics.clear();
+                    self.navigator
+                        .show_placeholder(format!("Error listing metrics: {}", err));
+                }
+                ComponentAction::SelectMetric(label) => {
+                    if let Some(metric) = self.metrics.iter().find(|metric| metric.label() == label)
+                    {
+                        self.fetch_metric_data(metric.clone());
+                    }
+                }
+                ComponentAction::MetricDataLoaded(label, result) => {
+                    let title = match &self.time_range {
+                        Some(range) => format!("{} ({})", label, range),
+                        None => format!("{} ({})", label, METRICS_TIME_RANGE),
+                    };
+                    match result {
+                        Ok(points) => {
+                            self.last_updated = Some(Instant::now());
+                            self.metric_chart.set_points(title, points);
+                        }
+                        Err(err) => self.metric_chart.show_message(title, err),
+                    }
+                }
                 // Display detailed view of a log entry
                 ComponentAction::ViewLogDetails(log_content) => {
                     self.view_log_details(&log_content).await;
@@ -568,6 +1642,7 @@ impl AWSComponent for CloudWatch {
                 }
                 // Show details in popup window
                 ComponentAction::PopupDetails(details) => {
+                    self.details_popup.set_title("Details".to_string());
                     self.details_popup
                         .set_content(PopupContent::Details(details.clone()));
                     self.details_popup.set_visible(true);
@@ -588,12 +1663,22 @@ impl AWSComponent for CloudWatch {
                                         ),
                                         WidgetType::AWSServiceNavigator,
                                     ) => {
+                                        let action = match self.mode {
+                                            CloudWatchMode::Logs => {
+                                                ComponentAction::SelectLogGroup(log_group)
+                                            }
+                                            CloudWatchMode::Metrics => {
+                                                ComponentAction::SelectMetric(log_group)
+                                            }
+                                            CloudWatchMode::Alarms => {
+                                                ComponentAction::SelectAlarm(log_group)
+                                            }
+                                        };
                                         self.event_sender
-                                            .send(Event::Tab(TabEvent::ComponentActions(
-                                                ComponentAction::SelectLogGroup(log_group),
+                                            .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                                action,
                                                 self.component_type.clone(),
-                                            )))
-                                            .unwrap();
+                                            )));
                                     }
                                     _ => {}
                                 }
@@ -613,11 +1698,10 @@ impl AWSComponent for CloudWatch {
                                     ) => {
                                         // Show log details in popup
                                         self.event_sender
-                                            .send(Event::Tab(TabEvent::ComponentActions(
+                                            .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                                 ComponentAction::PopupDetails(log_content),
                                                 self.component_type.clone(),
-                                            )))
-                                            .unwrap();
+                                            )));
                                     }
                                     _ => {}
                                 }
@@ -635,14 +1719,28 @@ impl AWSComponent for CloudWatch {
                                         _,
                                     ) = signal
                                     {
-                                        // Use input content to filter logs
-                                        if self.selected_log_group.is_some() {
+                                        // In metrics mode the input names the namespace to list
+                                        if self.mode == CloudWatchMode::Metrics {
                                             self.event_sender
-                                                .send(Event::Tab(TabEvent::ComponentActions(
+                                                .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                                    ComponentAction::ListMetrics(content),
+                                                    self.component_type.clone(),
+                                                )));
+                                        } else if self.mode == CloudWatchMode::Alarms {
+                                            self.event_sender
+                                                .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                                    ComponentAction::ListAlarms(content),
+                                                    self.component_type.clone(),
+                                                )));
+                                        } else if self.selected_log_group.is_some()
+                                            || log_link::is_log_link(&content)
+                                        {
+                                            // Use input content to filter logs, or open a link
+                                            self.event_sender
+                                                .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                                     ComponentAction::SearchLogs(content),
                                                     self.component_type.clone(),
-                                                )))
-                                                .unwrap();
+                                                )));
                                         }
                                     }
                                 }
@@ -659,21 +1757,48 @@ impl AWSComponent for CloudWatch {
                                     {
                                         // Set the time range and refresh logs
                                         self.event_sender
-                                            .send(Event::Tab(TabEvent::ComponentActions(
+                                            .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                                 ComponentAction::SetTimeRange(content),
                                                 self.component_type.clone(),
-                                            )))
-                                            .unwrap();
+                                            )));
                                     }
                                 }
                             }
                             _ => {}
                         }
                     }
-                    // Close popup when exit event received
+                    // Let the popup handle navigation, closing it once it hides itself
                     WidgetAction::PopupAction(_) => {
-                        self.details_popup.set_visible(false);
-                        self.details_popup.set_active(false);
+                        // The popup closes itself on Esc, or Enter when nothing is selectable
+                        let signal = self.details_popup.process_event(widget_action);
+                        // Run a log search once its wide time range is confirmed
+                        if !self.details_popup.is_visible()
+                            && let Some(search) = self.pending_search.take()
+                            && matches!(
+                                signal,
+                                Some(WidgetAction::PopupAction(PopupAction::Confirmed))
+                            )
+                        {
+                            self.start_log_search(
+                                &search.log_group,
+                                &search.filter_pattern,
+                                &search.time_range,
+                                &search.title_prefix,
+                                search.focus_results,
+                            );
+                        }
+                        if let Some(WidgetAction::PopupAction(PopupAction::ItemSelected(choice))) =
+                            signal
+                        {
+                            // A time range preset was picked
+                            self.details_popup.close();
+                            let range = choice.trim_start_matches(PRESET_PREFIX).to_string();
+                            self.event_sender
+                                .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                    ComponentAction::SetTimeRange(range),
+                                    self.component_type.clone(),
+                                )));
+                        }
                     }
                     _ => {}
                 },
@@ -714,90 +1839,73 @@ impl AWSComponent for CloudWatch {
             self.results_navigator.set_content(NavigatorContent::Records(vec![]));
             self.results_navigator.set_title(String::from("Select a log group"));
             
-            // Clone what we need for the background task
+            // Fetch log groups in the background without blocking the UI, superseding any
+            // fetch still in flight
             let client_clone = Arc::clone(client);
-            let event_sender = self.event_sender.clone();
-            let component_type = self.component_type.clone();
-            
-            // Spawn background task to fetch log groups without blocking UI
-            let _ = tokio::spawn(async move {
-                // Fetch log groups in background
-                let log_groups_result = match tokio::time::timeout(
-                    std::time::Duration::from_secs(30), // 30-second timeout
-                    client_clone.lock().await.list_log_groups(),
-                ).await {
-                    Ok(result) => result,
-                    Err(_) => Ok(vec!["Request timed out after 30 seconds".to_string()]),
-                };
-                
-                // Send event with results back to the component
-                match log_groups_result {
-                    Ok(log_groups) => {
-                        // Send event to update navigator with log groups
-                        event_sender
-                            .send(Event::Tab(TabEvent::ComponentActions(
-                                ComponentAction::WidgetAction(WidgetAction::ServiceNavigatorEvent(
-                                    ServiceNavigatorEvent::UpdateContent(log_groups, false),
-                                    WidgetType::AWSServiceNavigator,
-                                )),
-                                component_type.clone(),
-                            )))
-                            .unwrap_or_default();
-                        
-                        // Update navigator title
-                        event_sender
-                            .send(Event::Tab(TabEvent::ComponentActions(
-                                ComponentAction::WidgetAction(WidgetAction::ServiceNavigatorEvent(
+            let limit = self.timeouts.cloudwatch;
+            // A search waiting for the log groups opens right after them
+            let pending_view = self.pending_view.take();
+            let task = spawn_fetch(
+                &self.event_sender,
+                self.component_type.clone(),
+                &mut self.generation,
+                async move {
+                    let log_groups_result = within_limit(limit, "cloudwatch", async {
+                        client_clone.lock().await.list_log_groups().await
+                    })
+                    .await;
+                    match log_groups_result {
+                        Ok(log_groups) => {
+                            let content = if log_groups.is_empty() {
+                                ServiceNavigatorEvent::ShowPlaceholder(
+                                    "No log groups found in this region".to_string(),
+                                )
+                            } else {
+                                ServiceNavigatorEvent::UpdateContent(log_groups, false, None)
+                            };
+                            let mut actions = vec![
+                                navigator_action(content, WidgetType::AWSServiceNavigator),
+                                navigator_action(
                                     ServiceNavigatorEvent::UpdateTitle(String::from("Log Groups")),
                                     WidgetType::AWSServiceNavigator,
+                                ),
+                                ComponentAction::MarkUpdated,
+                            ];
+                            actions.extend(pending_view.map(ComponentAction::OpenLogView));
+                            actions
+                        }
+                        // Say so in the placeholder and the title
+                        Err(err) => vec![
+                            navigator_action(
+                                ServiceNavigatorEvent::ShowPlaceholder(format!(
+                                    "Error fetching log groups: {}",
+                                    err
                                 )),
-                                component_type.clone(),
-                            )))
-                            .unwrap_or_default();
-                    },
-                    Err(err) => {
-                        // Send event with error message
-                        event_sender
-                            .send(Event::Tab(TabEvent::ComponentActions(
-                                ComponentAction::WidgetAction(WidgetAction::ServiceNavigatorEvent(
-                                    ServiceNavigatorEvent::UpdateContent(vec![format!(
-                                        "Error fetching log groups: {}", err
-                                    )], false),
-                                    WidgetType::AWSServiceNavigator,
-                                )),
-                                component_type.clone(),
-                            )))
-                            .unwrap_or_default();
-                        
-                        // Update navigator title to reflect error
-                        event_sender
-                            .send(Event::Tab(TabEvent::ComponentActions(
-                                ComponentAction::WidgetAction(WidgetAction::ServiceNavigatorEvent(
-                                    ServiceNavigatorEvent::UpdateTitle(String::from("Log Groups (Error)")),
-                                    WidgetType::AWSServiceNavigator,
+                                WidgetType::AWSServiceNavigator,
+                            ),
+                            navigator_action(
+                                ServiceNavigatorEvent::UpdateTitle(String::from(
+                                    "Log Groups (Error)",
                                 )),
-                                component_type,
-                            )))
-                            .unwrap_or_default();
-                    },
-                }
-            });
+                                WidgetType::AWSServiceNavigator,
+                            ),
+                        ],
+                    }
+                },
+            );
+            self.track_fetch(task, WidgetType::AWSServiceNavigator);
         }
         Ok(())
     }
 
-    fn allows_focus_continuation(&self) -> bool {
-        if self.current_focus == CloudWatchFocus::None {
-            return  true;
-        }
-        false
-    }
-
-    fn allows_focus_continuation_backward(&self) -> bool {
-        if self.current_focus != CloudWatchFocus::Navigation {
-            return  true;
+    fn current_focus(&self) -> ComponentFocus {
+        match self.current_focus {
+            CloudWatchFocus::Navigation => ComponentFocus::Navigation,
+            CloudWatchFocus::Input => ComponentFocus::Input,
+            CloudWatchFocus::TimeRange => ComponentFocus::TimeRange,
+            CloudWatchFocus::Results => ComponentFocus::Results,
+            CloudWatchFocus::None => ComponentFocus::None,
         }
-        false
     }
 
     /// Resets focus to the navigation pane
@@ -831,14 +1939,279 @@ impl AWSComponent for CloudWatch {
             help_items.push(("Enter".to_string(), "Apply time range".to_string()));
             help_items.push(("Time formats".to_string(), "15m, 1h, 1d, 7d".to_string()));
             help_items.push(("Esc".to_string(), "Return to navigation".to_string()));
+            help_items.extend(self.focus_jump_help());
         } else {
             // Return default help items based on the base component's state
             help_items = self.get_base_help_items();
-
-            // Add time range navigation helper
-            help_items.push(("Alt+3".to_string(), "Time range".to_string()));
         }
 
         help_items
     }
+
+    fn last_updated(&self) -> Option<Instant> {
+        self.last_updated
+    }
+
+    fn is_busy(&self) -> bool {
+        fetch_running(&self.fetch_task)
+    }
+}
+
+/// Default log time range from the configured one, falling back to `DEFAULT_LOG_TIME_RANGE`
+/// when it is unset or does not parse
+fn default_log_time_range(configured: Option<String>) -> String {
+    configured
+        .filter(|range| cloudwatch_client::parse_time_range(range, chrono::Utc::now()).is_ok())
+        .unwrap_or_else(|| DEFAULT_LOG_TIME_RANGE.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results_update(generation: u64, line: &str) -> ComponentAction {
+        ComponentAction::BackgroundResult(
+            generation,
+            Box::new(ComponentAction::WidgetAction(
+                WidgetAction::ServiceNavigatorEvent(
+                    ServiceNavigatorEvent::UpdateContent(vec![line.to_string()], false, None),
+                    WidgetType::QueryResultsNavigator,
+                ),
+            )),
+        )
+    }
+
+    #[test]
+    fn configured_log_time_range_falls_back_when_invalid() {
+        assert_eq!(default_log_time_range(Some("1h".to_string())), "1h");
+        assert_eq!(default_log_time_range(Some("soon".to_string())), DEFAULT_LOG_TIME_RANGE);
+        assert_eq!(default_log_time_range(None), DEFAULT_LOG_TIME_RANGE);
+    }
+
+    #[tokio::test]
+    async fn stale_background_results_are_dropped() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = CloudWatch::new(sender);
+
+        // A newer request supersedes the one that produced the stale result
+        let stale = next_generation();
+        component.generation = next_generation();
+
+        component.process_event(results_update(stale, "stale")).await;
+        assert_eq!(component.results_navigator.selected_label(), None);
+
+        let current = component.generation;
+        component.process_event(results_update(current, "fresh")).await;
+        assert_eq!(
+            component.results_navigator.selected_label(),
+            Some("fresh".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn successful_searches_focus_results_unless_focus_moved_on() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = CloudWatch::new(sender);
+        component.set_active(true);
+        component.update_time_range_focus(true);
+
+        component.process_event(ComponentAction::FocusResults).await;
+        assert_eq!(component.current_focus, CloudWatchFocus::Results);
+        assert!(component.results_navigator.is_active());
+        assert!(!component.time_range_input.is_active());
+
+        // A search that finishes after the user went back to the list leaves focus there
+        component.current_focus = CloudWatchFocus::Navigation;
+        component.update_widget_states();
+        component.process_event(ComponentAction::FocusResults).await;
+        assert_eq!(component.current_focus, CloudWatchFocus::Navigation);
+    }
+
+    #[tokio::test]
+    async fn links_opened_before_the_client_wait_for_the_log_groups() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = CloudWatch::new(sender);
+        let view = LogView::parse("cw://app?filter=ERROR&range=1h").unwrap();
+
+        component.process_event(ComponentAction::OpenLogView(view.clone())).await;
+        assert_eq!(component.pending_view, Some(view));
+        assert_eq!(component.selected_log_group, None);
+    }
+
+    #[tokio::test]
+    async fn reset_clears_results_and_ignores_in_flight_requests() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = CloudWatch::new(sender);
+        component.generation = next_generation();
+        let in_flight = component.generation;
+        component.process_event(results_update(in_flight, "old")).await;
+        component.selected_log_group = Some("/aws/lambda/app".to_string());
+
+        component.process_event(ComponentAction::Reset).await;
+        assert_eq!(component.results_navigator.selected_label(), None);
+        assert_eq!(component.selected_log_group, None);
+
+        component.process_event(results_update(in_flight, "late")).await;
+        assert_eq!(component.results_navigator.selected_label(), None);
+    }
+
+    #[tokio::test]
+    async fn only_current_fetches_mark_the_data_updated() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = CloudWatch::new(sender);
+        let stale = next_generation();
+        component.generation = next_generation();
+
+        let updated = |generation| {
+            ComponentAction::BackgroundResult(generation, Box::new(ComponentAction::MarkUpdated))
+        };
+        component.process_event(updated(stale)).await;
+        assert!(component.last_updated().is_none());
+
+        component.process_event(updated(component.generation)).await;
+        assert!(component.last_updated().is_some());
+    }
+
+    #[tokio::test]
+    async fn live_tail_appends_new_events_until_stopped() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = CloudWatch::new(sender);
+        component.selected_log_group = Some("/aws/lambda/app".to_string());
+        component.live_tail = Some(LiveTail::new(1_000));
+        let tailed = |generation, logs: &[&str], newest| {
+            ComponentAction::BackgroundResult(
+                generation,
+                Box::new(ComponentAction::LogsTailed(Ok((
+                    logs.iter().map(|log| log.to_string()).collect(),
+                    newest,
+                )))),
+            )
+        };
+
+        let generation = component.generation;
+        component.process_event(tailed(generation, &["first"], Some(2_000))).await;
+        component.process_event(tailed(generation, &["second"], Some(3_000))).await;
+        // The newest event is selected so the view follows the tail
+        assert_eq!(
+            component.results_navigator.selected_label(),
+            Some("second".to_string())
+        );
+        assert_eq!(component.live_tail.as_ref().map(|tail| tail.since), Some(3_000));
+        assert!(!component.live_tail.as_ref().unwrap().is_due());
+
+        // A poll still in flight when the tail stops is dropped
+        component.process_event(ComponentAction::ToggleLiveTail).await;
+        assert!(component.live_tail.is_none());
+        component.process_event(tailed(generation, &["late"], Some(4_000))).await;
+        assert_eq!(
+            component.results_navigator.selected_label(),
+            Some("second".to_string())
+        );
+    }
+
+    #[test]
+    fn live_tail_polls_again_once_a_poll_is_lost() {
+        let mut tail = LiveTail::new(0);
+        assert!(tail.is_due());
+        tail.pending = true;
+        tail.polled = Instant::now();
+        assert!(!tail.is_due());
+        tail.polled = Instant::now() - TAIL_STALLED;
+        assert!(tail.is_due());
+    }
+
+    fn metric(name: &str) -> MetricId {
+        MetricId {
+            namespace: "AWS/Lambda".to_string(),
+            name: name.to_string(),
+            dimensions: vec![("FunctionName".to_string(), "api".to_string())],
+        }
+    }
+
+    #[tokio::test]
+    async fn metrics_mode_lists_metrics_by_label() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = CloudWatch::new(sender);
+        component.process_event(ComponentAction::ToggleMetrics).await;
+        assert_eq!(component.mode, CloudWatchMode::Metrics);
+
+        // An empty namespace is a message, not a metric to select
+        component
+            .process_event(ComponentAction::MetricsListed(Ok(vec![])))
+            .await;
+        assert_eq!(component.navigator.selected_label(), None);
+
+        component
+            .process_event(ComponentAction::MetricsListed(Ok(vec![metric("Errors")])))
+            .await;
+        assert_eq!(
+            component.navigator.selected_label(),
+            Some("AWS/Lambda Errors FunctionName=api".to_string())
+        );
+
+        component.process_event(ComponentAction::ToggleMetrics).await;
+        assert_eq!(component.mode, CloudWatchMode::Logs);
+        assert!(component.metrics.is_empty());
+    }
+
+    fn alarm(name: &str, state: &str) -> AlarmSummary {
+        AlarmSummary {
+            name: name.to_string(),
+            state: state.to_string(),
+            watches: "AWS/Lambda Errors".to_string(),
+            details: format!("{{\"AlarmName\":\"{}\"}}", name),
+        }
+    }
+
+    #[tokio::test]
+    async fn alarms_mode_lists_alarms_by_state() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = CloudWatch::new(sender);
+        component.process_event(ComponentAction::ToggleAlarms).await;
+        assert_eq!(component.mode, CloudWatchMode::Alarms);
+
+        component
+            .process_event(ComponentAction::AlarmsListed(Ok(vec![alarm("api-errors", "ALARM")])))
+            .await;
+        let label = "ALARM api-errors (AWS/Lambda Errors)".to_string();
+        assert_eq!(component.navigator.selected_label(), Some(label.clone()));
+        assert!(alarm_state_style(&label).is_some());
+        assert!(alarm_state_style("INSUFFICIENT_DATA db (composite: x)").is_none());
+
+        component.process_event(ComponentAction::SelectAlarm(label)).await;
+        assert!(component.details_popup.is_visible());
+
+        // A failed listing does not leave the previous alarms selectable
+        component
+            .process_event(ComponentAction::AlarmsListed(Err("denied".to_string())))
+            .await;
+        assert!(component.alarms.is_empty());
+
+        component.process_event(ComponentAction::ToggleAlarms).await;
+        assert_eq!(component.mode, CloudWatchMode::Logs);
+    }
+
+    #[tokio::test]
+    async fn log_searches_over_wide_ranges_wait_for_confirmation() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = CloudWatch::new(sender);
+        component.confirm_log_range = Some(Duration::from_secs(24 * 3600));
+
+        component.fetch_logs("app", "", "1h", "Log Events", false).await;
+        assert!(component.pending_search.is_none());
+
+        component.fetch_logs("app", "error", "7d", "Log Events", false).await;
+        assert!(component.details_popup.is_visible());
+        assert!(matches!(
+            &component.pending_search,
+            Some(search) if search.time_range == "7d" && search.filter_pattern == "error"
+        ));
+
+        let window = cloudwatch_client::TimeWindow {
+            start_time: 0,
+            end_time: Some(3_600_000),
+        };
+        assert!(!exceeds_range(&window, Duration::from_secs(3600), 0));
+        assert!(exceeds_range(&window, Duration::from_secs(60), 0));
+    }
 }
@@ -1,6 +1,11 @@
-use crate::components::{AWSComponent};
+use crate::components::{
+    AWSComponent, ComponentFocus, SplitRatio, abort_fetch, copy_cli_command, copy_console_link,
+    fetch_running, focus_jump_help, next_generation, render_error_banner, spawn_fetch,
+    spawn_in_generation,
+};
+use crate::diagnostics;
 use crate::event_managment::event::{
-    ComponentAction, ComponentType, Event, InputBoxEvent, ServiceNavigatorEvent, TabEvent,
+    ComponentAction, ComponentType, Event, InputBoxEvent, PopupAction, SendOrLog, ServiceNavigatorEvent, TabEvent,
     WidgetAction, WidgetEventType, WidgetType, InputBoxType,
 };
 use ratatui::{
@@ -8,15 +13,24 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
 };
 use crate::services::aws::TabClients;
-use crate::services::aws::dynamo_client::DynamoDBClient;
+use crate::services::aws::cli_command::CliCommand;
+use crate::services::aws::console_link::ConsoleResource;
+use crate::services::aws::dynamo_client::{self, DynamoDBClient, DynamoDBClientError, QueryFailure};
+use crate::services::read_config;
 use crate::widgets::WidgetExt;
 use crate::widgets::popup::{PopupContent, PopupWidget};
 use crate::widgets::service_navigator::{NavigatorContent, ServiceNavigator};
 use crate::widgets::input_box::InputBoxWidget;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::any::Any;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::Mutex;
+use tokio::task::AbortHandle;
+
+/// Title of the sort key input before the table's key schema is known
+const SORT_KEY_TITLE: &str = "Sort Key (if applicable)";
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DynamodbFocus {
@@ -42,6 +56,12 @@ pub struct DynamoDB {
     aws_clients: Option<TabClients>,
     /// Input box for sort key
     sort_key_input: InputBoxWidget,
+    /// Input box for the comma-separated projection, sharing the sort key's slot
+    projection_input: InputBoxWidget,
+    /// Whether the sort key slot currently shows the projection input
+    projection_mode: bool,
+    /// Projection last filled in from the config, replaced for the next table unless edited
+    configured_projection: Option<String>,
     /// Current focus within this component
     current_sub_focus: DynamodbFocus,
     
@@ -65,6 +85,28 @@ pub struct DynamoDB {
     selected_item: Option<String>,
     /// Current query string being executed
     selected_query: Option<String>,
+    /// Error shown in a banner above the component's widgets
+    error_message: Option<String>,
+    /// Width of the left navigator relative to the right panel
+    split: SplitRatio,
+    /// Generation of the latest background query; older results are ignored
+    generation: u64,
+    /// Whether a query is running; new queries are ignored until it finishes
+    is_loading: bool,
+    /// Task running the current query, aborted when the query is cancelled
+    query_task: Option<AbortHandle>,
+    /// Results title of the running query, restored once it finishes
+    query_title: String,
+    /// Sort key attribute of each table whose key schema was looked up, `None` if it has none
+    sort_keys: HashMap<String, Option<String>>,
+    /// When tables or query results were last fetched successfully
+    last_updated: Option<Instant>,
+    /// Items with every attribute in DynamoDB JSON, keyed by their simplified records
+    raw_items: HashMap<String, String>,
+    /// Whether scanning a whole table asks for confirmation first
+    confirm_scans: bool,
+    /// Table whose scan awaits confirmation
+    pending_scan: Option<String>,
 }
 
 impl DynamoDB {
@@ -78,23 +120,45 @@ impl DynamoDB {
             aws_clients: None,
             sort_key_input: InputBoxWidget::new(
                 InputBoxType::TimeRange,
-                "Sort Key (if applicable)",
+                SORT_KEY_TITLE,
+                false,
+            ),
+            projection_input: InputBoxWidget::new(
+                InputBoxType::Projection,
+                "Projection (attr1, attr2)",
                 false,
             ),
+            projection_mode: false,
+            configured_projection: None,
             current_sub_focus: DynamodbFocus::Input,
-            
+
             // Fields moved from AWSComponentBase
             navigator: ServiceNavigator::new(
                 WidgetType::AWSServiceNavigator,
                 false,
                 NavigatorContent::Records(vec![]),
             ),
-            input: InputBoxWidget::new(InputBoxType::Text, "Query Input", false),
-            results_navigator: ServiceNavigator::new(
-                WidgetType::QueryResultsNavigator,
-                false,
-                NavigatorContent::Records(vec![]),
-            ),
+            input: {
+                let mut input = InputBoxWidget::new(InputBoxType::Text, "Query Input", false);
+                // Partition keys are recalled with Up/Down, per profile
+                input.enable_history("dynamodb.query");
+                // A pasted column of keys becomes a batch get
+                input.join_pasted_lines_with(Some(", "));
+                input
+            },
+            results_navigator: {
+                let mut navigator = ServiceNavigator::new(
+                    WidgetType::QueryResultsNavigator,
+                    false,
+                    NavigatorContent::Records(vec![]),
+                );
+                // Items have different attributes, so `t` derives columns from their keys
+                navigator.enable_table_view(None);
+                navigator.enable_pretty_json();
+                // Likewise `s` sorts by any attribute the fetched items have
+                navigator.enable_sorting(None);
+                navigator
+            },
             details_popup: PopupWidget::new(popup_content, "Details", false, false),
             active: false,
             visible: true,
@@ -102,6 +166,17 @@ impl DynamoDB {
             current_focus: DynamodbFocus::Navigation,
             selected_item: None,
             selected_query: None,
+            error_message: None,
+            split: SplitRatio::load("dynamodb", 30),
+            generation: 0,
+            is_loading: false,
+            query_task: None,
+            query_title: String::new(),
+            sort_keys: HashMap::new(),
+            last_updated: None,
+            raw_items: HashMap::new(),
+            confirm_scans: read_config::get_large_fetch_confirmations().scans,
+            pending_scan: None,
         }
     }
 
@@ -139,9 +214,233 @@ impl DynamoDB {
         self.current_focus
     }
     
+    /// Returns the input currently shown in the sort key slot
+    fn slot_input_mut(&mut self) -> &mut InputBoxWidget {
+        if self.projection_mode {
+            &mut self.projection_input
+        } else {
+            &mut self.sort_key_input
+        }
+    }
+
+    /// Fills in the table's projection from the config, unless the one shown was typed
+    fn apply_configured_projection(&mut self, table: &str) {
+        let current = self.projection_input.get_content();
+        if current.is_some() && current != self.configured_projection {
+            return;
+        }
+        self.configured_projection = read_config::get_dynamodb_projection(table);
+        self.projection_input
+            .set_content(self.configured_projection.as_deref().unwrap_or_default());
+    }
+
+    /// Attributes to fetch for each item; empty means the whole item
+    fn projection(&self) -> Vec<String> {
+        self.projection_input
+            .get_content()
+            .map(|content| dynamo_client::parse_projection(&content))
+            .unwrap_or_default()
+    }
+
+    /// AWS CLI command repeating the last query of the selected table, or a scan of the
+    /// table before it has been queried
+    ///
+    /// The key attribute names are looked up, as the query only holds their values.
+    /// Takes `&mut self` so the future stays `Send` while the client is awaited.
+    async fn cli_command(&mut self) -> Result<Option<CliCommand>, DynamoDBClientError> {
+        let (Some(client), Some(table)) = (&self.dynamodb_client, &self.selected_item) else {
+            return Ok(None);
+        };
+        let table = table.clone();
+        let projection = self.projection();
+        let Some(partition_value) = self.selected_query.clone() else {
+            return Ok(Some(CliCommand::DynamoDBScan { table, projection }));
+        };
+        if let Some(keys) = dynamo_client::parse_batch_keys(&partition_value) {
+            let client = client.lock().await;
+            return Ok(Some(CliCommand::DynamoDBBatchGet {
+                partition_key: client.get_table_primary_key(&table).await?,
+                table,
+                keys,
+                projection,
+            }));
+        }
+
+        // Like the query itself, a sort key value only counts if the table has a sort key
+        let sort_value = self
+            .sort_key_input
+            .get_content()
+            .filter(|value| !value.is_empty());
+        let client = client.lock().await;
+        let partition_key = client.get_table_primary_key(&table).await?;
+        let sort_key = match sort_value {
+            Some(value) => client
+                .get_table_sort_key(&table)
+                .await?
+                .map(|name| (name, value)),
+            None => None,
+        };
+        Ok(Some(CliCommand::DynamoDBQuery {
+            table,
+            partition_key: (partition_key, partition_value),
+            sort_key,
+            projection,
+        }))
+    }
+
+    /// Titles the sort key input after the selected table's sort key, disabling it when the
+    /// table has none
+    ///
+    /// The key schema is looked up once per table, in the background.
+    fn show_sort_key(&mut self, table: &str) {
+        if let Some(sort_key) = self.sort_keys.get(table).cloned() {
+            self.apply_sort_key(sort_key);
+            return;
+        }
+        let Some(client) = &self.dynamodb_client else {
+            return;
+        };
+        self.sort_key_input.set_title(String::from("Sort Key (loading...)"));
+        self.sort_key_input.set_disabled(false);
+
+        let client = Arc::clone(client);
+        let table = table.to_string();
+        let event_sender = self.event_sender.clone();
+        let component_type = self.component_type.clone();
+        tokio::spawn(async move {
+            let result = client
+                .lock()
+                .await
+                .get_table_sort_key(&table)
+                .await
+                .map_err(|err| err.to_string());
+            event_sender.send_or_log(Event::Tab(TabEvent::ComponentActions(
+                ComponentAction::SortKeyLoaded(table, result),
+                component_type,
+            )));
+        });
+    }
+
+    /// Updates the sort key input for a table with the given sort key, or without one
+    fn apply_sort_key(&mut self, sort_key: Option<String>) {
+        match sort_key {
+            Some(name) => {
+                self.sort_key_input.set_title(format!("Sort Key: {}", name));
+                self.sort_key_input.set_disabled(false);
+            }
+            None => {
+                self.sort_key_input.set_title(String::from("Sort Key (no sort key)"));
+                self.sort_key_input.set_disabled(true);
+            }
+        }
+    }
+
+    /// Runs the query again with the current partition key, if one was entered
+    fn rerun_query(&self) {
+        if let Some(partition_key) = self.input.get_content() {
+            self.event_sender
+                .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                    ComponentAction::SetQuery(partition_key),
+                    self.component_type.clone(),
+                )));
+        }
+    }
+
+    /// Queries the selected table by partition key in the background, moving focus to the
+    /// results; an empty partition key scans the table and a comma- or newline-separated
+    /// list of them reads those items with a batch get
+    fn start_query(&mut self, partition_key: String) {
+        // Details viewed among the previous results are not worth going back to
+        self.details_popup.clear_history();
+        let scan = partition_key.trim().is_empty();
+        let batch_keys = dynamo_client::parse_batch_keys(&partition_key);
+        let projection = self.projection();
+        let label = match &batch_keys {
+            _ if scan => String::from("Scan"),
+            Some(keys) => format!("Batch get ({} keys)", keys.len()),
+            None => partition_key.clone(),
+        };
+        // Make it obvious that results only show some attributes
+        self.query_title = if projection.is_empty() {
+            label
+        } else {
+            format!("{} [projection: {}]", label, projection.join(", "))
+        };
+        self.results_navigator.set_title(self.query_title.clone());
+        // A scan is repeated by `aws dynamodb scan`, which the CLI command falls back to
+        self.selected_query = (!scan).then(|| partition_key.clone());
+
+        if let (Some(client), Some(selected_table)) =
+            (&self.dynamodb_client, &self.selected_item)
+        {
+            // Show loading state immediately
+            self.results_navigator
+                .set_title(format!("{} (Loading...)", self.query_title));
+            self.is_loading = true;
+
+            // Clone what we need for the background task
+            let client = Arc::clone(client);
+            let table = selected_table.clone();
+            let sort_key = self.sort_key_input.get_content();
+
+            // Query in the background so the UI keeps responding; Esc aborts it
+            let task = spawn_fetch(
+                &self.event_sender,
+                self.component_type.clone(),
+                &mut self.generation,
+                async move {
+                    let client = client.lock().await;
+                    let result = if scan {
+                        client.scan_table(table, &projection).await
+                    } else if let Some(keys) = batch_keys {
+                        return match client.batch_get(&table, keys, &projection).await {
+                            Ok(batch) => vec![
+                                ComponentAction::QueryCompleted(Ok(batch.records)),
+                                ComponentAction::KeysNotFound(batch.missing),
+                            ],
+                            Err(err) => vec![ComponentAction::QueryCompleted(Err(err.into()))],
+                        };
+                    } else {
+                        client
+                            .query_table_composite(table, partition_key, sort_key, &projection)
+                            .await
+                    };
+                    vec![ComponentAction::QueryCompleted(result.map_err(QueryFailure::from))]
+                },
+            );
+            self.query_task = Some(task);
+        }
+        // Move focus to the results after query
+        self.current_focus = DynamodbFocus::Results;
+        self.update_sort_key_focus(false);
+        self.update_widget_states();
+    }
+
+    /// Aborts the running query, if any, so another can be started right away
+    fn cancel_query(&mut self) {
+        if !self.is_loading || !abort_fetch(&mut self.query_task) {
+            return;
+        }
+        self.generation = next_generation();
+        self.is_loading = false;
+        self.results_navigator
+            .set_title(format!("{} (Cancelled)", self.query_title));
+        self.results_navigator.show_placeholder("Query cancelled");
+        self.event_sender
+            .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                ComponentAction::WidgetAction(WidgetAction::Notify(format!(
+                    "Cancelled the query for '{}'",
+                    self.query_title
+                ))),
+                self.component_type.clone(),
+            )));
+    }
+
     /// Updates focus for sort key input and other components
     fn update_sort_key_focus(&mut self, activate: bool) {
-        self.sort_key_input.set_active(activate);
+        self.sort_key_input.set_active(false);
+        self.projection_input.set_active(false);
+        self.slot_input_mut().set_active(activate);
         self.input.set_active(!activate);
         self.navigator.set_active(!activate);
         self.results_navigator.set_active(!activate);
@@ -157,30 +456,45 @@ impl DynamoDB {
 
         // Check if the popup is visible
         if self.details_popup.is_visible() {
-            items.push(("Esc".to_string(), "Close details".to_string()));
-            items.push(("PgUp/PgDn".to_string(), "Scroll content".to_string()));
-            return items;
+            return self.details_popup.get_help_items();
         }
 
         // Different help items based on current focus
         match self.current_focus {
             DynamodbFocus::Navigation => {
                 items.push(("Enter".to_string(), "Select table".to_string()));
-                items.push(("Alt+2".to_string(), "Focus query input".to_string()));
-                items.push(("Alt+4".to_string(), "Focus results".to_string()));
+                items.push(("i".to_string(), "Table info".to_string()));
+                items.push(("o".to_string(), "Copy console link".to_string()));
             }
             DynamodbFocus::Results => {
                 items.push(("Enter".to_string(), "View item details".to_string()));
-                items.push(("Alt+1".to_string(), "Focus tables".to_string()));
-                items.push(("Alt+2".to_string(), "Focus query input".to_string()));
+                items.push(("Ctrl+Y".to_string(), "Query on item's key".to_string()));
             }
             DynamodbFocus::Input => {
-                items.push(("Enter".to_string(), "Execute query".to_string()));
-                items.push(("Alt+1".to_string(), "Focus tables".to_string()));
-                items.push(("Alt+4".to_string(), "Focus results".to_string()));
+                items.push(("Enter".to_string(), "Execute query (empty scans, a,b batch gets)".to_string()));
             }
             _ => {}
         }
+        let focused = match self.current_focus {
+            DynamodbFocus::Navigation => 1,
+            DynamodbFocus::Input if self.current_sub_focus == DynamodbFocus::TimeRange => 3,
+            DynamodbFocus::Input => 2,
+            _ => 4,
+        };
+        let second_input = if self.projection_mode {
+            "Focus projection"
+        } else {
+            "Focus sort key"
+        };
+        items.extend(focus_jump_help(
+            [
+                Some("Focus tables"),
+                Some("Focus partition key"),
+                Some(second_input),
+                Some("Focus results"),
+            ],
+            focused,
+        ));
         items
     }
 }
@@ -192,13 +506,16 @@ impl AWSComponent for DynamoDB {
             return;
         }
 
+        // Reserve space for the error banner if a client failed to initialize
+        let area = match &self.error_message {
+            Some(message) => render_error_banner(message, area, buf),
+            None => area,
+        };
+
         // Create a horizontal split for left panel (tables) and right panel (query and results)
         let horizontal_split = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(30), // Left panel - tables list
-                Constraint::Percentage(70), // Right panel - queries and results
-            ])
+            .constraints(self.split.constraints())
             .split(area);
 
         // Create a vertical split for the right panel to separate inputs from results
@@ -219,17 +536,32 @@ impl AWSComponent for DynamoDB {
             ])
             .split(right_vertical_split[0]);
 
-        // Render components
-        self.navigator.render(horizontal_split[0], buf);
+        // Render components, guiding newcomers until there is something to list
+        let table_prompt = self
+            .dynamodb_client
+            .is_none()
+            .then_some("Select a profile to list its tables");
+        self.navigator
+            .render_with_prompt(horizontal_split[0], buf, table_prompt);
 
         // Render the partition key input box
         self.input.render(input_row[0], buf);
 
-        // Render the sort key input box
-        self.sort_key_input.render(input_row[1], buf);
+        // Render the sort key or projection input box
+        if self.projection_mode {
+            self.projection_input.render(input_row[1], buf);
+        } else {
+            self.sort_key_input.render(input_row[1], buf);
+        }
 
         // Render the results navigator
-        self.results_navigator.render(right_vertical_split[1], buf);
+        let item_prompt = match (&self.selected_item, &self.selected_query) {
+            (None, _) => Some("Choose a table, then type a partition key to query it"),
+            (Some(_), None) => Some("Type a partition key and press Enter to query the table"),
+            (Some(_), Some(_)) => None,
+        };
+        self.results_navigator
+            .render_with_prompt(right_vertical_split[1], buf, item_prompt);
 
         // Render popup if visible
         if self.details_popup.is_visible() {
@@ -251,18 +583,14 @@ impl AWSComponent for DynamoDB {
     }
 
 
-    fn allows_focus_continuation(&self) -> bool {
-        if self.current_focus == DynamodbFocus::None {
-            return  true;
-        }
-        false
-    }
-
-    fn allows_focus_continuation_backward(&self) -> bool {
-        if self.current_focus != DynamodbFocus::Navigation {
-            return  true;
+    fn current_focus(&self) -> ComponentFocus {
+        match self.current_focus {
+            DynamodbFocus::Navigation => ComponentFocus::Navigation,
+            DynamodbFocus::Input => ComponentFocus::Input,
+            DynamodbFocus::TimeRange => ComponentFocus::TimeRange,
+            DynamodbFocus::Results => ComponentFocus::Results,
+            DynamodbFocus::None => ComponentFocus::None,
         }
-        false
     }
 
     /// Handles keyboard input events
@@ -271,11 +599,10 @@ impl AWSComponent for DynamoDB {
         if self.details_popup.is_visible() {
             if let Some(signal) = self.details_popup.handle_input(key_event) {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::WidgetAction(signal),
                         self.component_type.clone(),
-                    )))
-                    .unwrap();
+                    )));
                 return;
             }
         }
@@ -283,19 +610,17 @@ impl AWSComponent for DynamoDB {
         match key_event.code {
             KeyCode::Tab => {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::NextFocus,
                         self.component_type.clone(),
-                    )))
-                    .unwrap();
+                    )));
             }
             KeyCode::BackTab => {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::PreviousFocus,
                         self.component_type.clone(),
-                    )))
-                    .unwrap();
+                    )));
             }
             // Alt+number shortcuts to switch focus between areas
             KeyCode::Char('1') if key_event.modifiers == KeyModifiers::ALT => {
@@ -317,7 +642,82 @@ impl AWSComponent for DynamoDB {
                 self.update_sort_key_focus(false);
                 self.update_widget_states();
             }
+            // Alt+J switches the second input between sort key and projection
+            KeyCode::Char('j') if key_event.modifiers == KeyModifiers::ALT => {
+                self.projection_mode = !self.projection_mode;
+                self.current_focus = DynamodbFocus::Input;
+                self.update_sort_key_focus(true);
+            }
+            // Show metadata for the highlighted table
+            KeyCode::Char('i')
+                if key_event.modifiers.is_empty()
+                    && self.current_focus == DynamodbFocus::Navigation
+                    && !self.navigator.is_capturing_input() =>
+            {
+                if let Some(table) = self.navigator.selected_label() {
+                    self.event_sender
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                            ComponentAction::DescribeTable(table),
+                            self.component_type.clone(),
+                        )));
+                }
+            }
+            // Copy the console link of the highlighted table
+            KeyCode::Char('o')
+                if key_event.modifiers.is_empty()
+                    && self.current_focus == DynamodbFocus::Navigation
+                    && !self.navigator.is_capturing_input() =>
+            {
+                if let Some(name) = self.navigator.selected_label() {
+                    copy_console_link(
+                        ConsoleResource::DynamoDBTable(name),
+                        self.aws_clients.as_ref(),
+                        &self.event_sender,
+                        self.component_type.clone(),
+                    );
+                }
+            }
+            // Copy the AWS CLI command repeating the current listing or query
+            KeyCode::Char('a')
+                if key_event.modifiers.is_empty()
+                    && match self.current_focus {
+                        DynamodbFocus::Navigation => !self.navigator.is_capturing_input(),
+                        DynamodbFocus::Results => !self.results_navigator.is_capturing_input(),
+                        _ => false,
+                    } =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::CopyCliCommand,
+                        self.component_type.clone(),
+                    )));
+            }
+            // Ctrl+Y queries on the selected item's partition key, after editing it
+            KeyCode::Char('y')
+                if key_event.modifiers == KeyModifiers::CONTROL
+                    && self.current_focus == DynamodbFocus::Results
+                    && !self.results_navigator.is_capturing_input() =>
+            {
+                if let Some(item) = self.results_navigator.selected_label() {
+                    self.event_sender
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                            ComponentAction::RefineQuery(item),
+                            self.component_type.clone(),
+                        )));
+                }
+            }
+            // Ctrl+Left/Right resize the panes; input boxes keep them for word movement
+            KeyCode::Left | KeyCode::Right
+                if key_event.modifiers == KeyModifiers::CONTROL
+                    && matches!(
+                        self.current_focus,
+                        DynamodbFocus::Navigation | DynamodbFocus::Results
+                    ) =>
+            {
+                self.split.nudge(key_event.code == KeyCode::Right);
+            }
             KeyCode::Esc => {
+                self.cancel_query();
                 if self.current_focus != DynamodbFocus::Navigation {
                     self.current_focus = DynamodbFocus::Navigation;
                     self.update_sort_key_focus(false);
@@ -330,7 +730,7 @@ impl AWSComponent for DynamoDB {
                     DynamodbFocus::Navigation => self.navigator.handle_input(key_event),
                     DynamodbFocus::Input => {
                         if self.current_sub_focus == DynamodbFocus::TimeRange {
-                            self.sort_key_input.handle_input(key_event)
+                            self.slot_input_mut().handle_input(key_event)
                         } else {
                             self.input.handle_input(key_event)
                         }
@@ -340,11 +740,10 @@ impl AWSComponent for DynamoDB {
                     _ => None,
                 } {
                     self.event_sender
-                        .send(Event::Tab(TabEvent::ComponentActions(
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
                             ComponentAction::WidgetAction(signal),
                             self.component_type.clone(),
-                        )))
-                        .unwrap();
+                        )));
                 }
             }
         }
@@ -352,29 +751,78 @@ impl AWSComponent for DynamoDB {
     /// Processes component-specific actions
     async fn process_event(&mut self, event: ComponentAction) {
         match event {
-            ComponentAction::Active(aws_profile) => {
-                self.aws_clients = Some(TabClients::new(aws_profile, String::from("eu-west-1")));
+            ComponentAction::Active(aws_profile, region) => {
+                // Results for the previous profile or region must not land in the new view
+                abort_fetch(&mut self.query_task);
+                self.generation = next_generation();
+                self.is_loading = false;
+                self.details_popup.clear_history();
+                self.input.load_history(&aws_profile);
+                // Tables of another profile or region may share names but not key schemas
+                self.sort_keys.clear();
+                self.last_updated = None;
+                self.aws_clients = Some(TabClients::new(aws_profile, region));
 
                 // Unwrap the Result and handle errors properly
                 if let Some(clients) = &mut self.aws_clients {
                     match clients.get_dynamodb_client().await {
                         Ok(client) => {
                             self.dynamodb_client = Some(client);
+                            self.error_message = None;
                             self.update().await.ok();
                         }
                         Err(err) => {
-                            // Handle the error (show error in UI)
-                            self.results_navigator
-                                .set_title(String::from("Error connecting to DynamoDB"));
-                            self.results_navigator
-                                .set_content(NavigatorContent::Records(vec![format!(
-                                    "Failed to initialize DynamoDB client: {}",
-                                    err
-                                )]));
+                            let message = format!(
+                                "Failed to initialize DynamoDB client (profile: {}, region: {}): {}",
+                                clients.profile(),
+                                clients.region(),
+                                err
+                            );
+                            // The error banner shows it instead of clobbering the navigators
+                            self.event_sender
+                                .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                    ComponentAction::ShowError(message),
+                                    self.component_type.clone(),
+                                )));
                         }
                     }
                 }
             }
+            ComponentAction::ShowError(message) => {
+                diagnostics::error(format!("DynamoDB: {}", message));
+                self.error_message = Some(message);
+            }
+            ComponentAction::Reset => {
+                self.navigator.reset_view();
+                self.results_navigator.reset_view();
+                self.details_popup.clear_history();
+                self.results_navigator
+                    .set_content(NavigatorContent::Records(vec![]));
+                // Titles name the selected table and key until one is chosen again
+                self.navigator.set_title(String::from("Services"));
+                self.results_navigator.set_title(String::from("Services"));
+                self.input.set_content("");
+                self.sort_key_input.set_content("");
+                self.sort_key_input.set_title(SORT_KEY_TITLE.to_string());
+                self.sort_key_input.set_disabled(false);
+                self.selected_item = None;
+                self.selected_query = None;
+                self.error_message = None;
+                self.raw_items.clear();
+                self.pending_scan = None;
+                // Drop the result of a query that is still running
+                abort_fetch(&mut self.query_task);
+                self.generation = next_generation();
+                self.is_loading = false;
+                self.reset_focus();
+            }
+            // Apply background results only if no newer request has started since;
+            // stale ones fall through and are dropped
+            ComponentAction::BackgroundResult(generation, action)
+                if generation == self.generation =>
+            {
+                self.process_event(*action).await;
+            }
             ComponentAction::Focused => {
                 self.set_active(true);
             }
@@ -387,14 +835,105 @@ impl AWSComponent for DynamoDB {
             // Handle selection of a table
             ComponentAction::SetTitle(title) => {
                 self.navigator.set_title(title.clone());
+                self.show_sort_key(&title);
+                self.apply_configured_projection(&title);
                 self.selected_item = Some(title);
                 self.focus_next();
                 self.update_widget_states();
             }
+            // Copy the AWS CLI command repeating the query, or scanning the table
+            ComponentAction::CopyCliCommand => match self.cli_command().await {
+                Ok(command) => copy_cli_command(
+                    command,
+                    self.aws_clients.as_ref(),
+                    &self.event_sender,
+                    self.component_type.clone(),
+                ),
+                Err(err) => self.event_sender.send_or_log(Event::Tab(
+                    TabEvent::ComponentActions(
+                        ComponentAction::WidgetAction(WidgetAction::Notify(format!(
+                            "Cannot build the AWS CLI command: {}",
+                            err
+                        ))),
+                        self.component_type.clone(),
+                    ),
+                )),
+            },
+            ComponentAction::SortKeyLoaded(table, result) => match result {
+                Ok(sort_key) => {
+                    self.sort_keys.insert(table.clone(), sort_key.clone());
+                    if self.selected_item.as_ref() == Some(&table) {
+                        self.apply_sort_key(sort_key);
+                    }
+                }
+                // Leave the input usable; the query still ignores a sort key the table lacks
+                Err(err) => {
+                    diagnostics::warn(format!("DynamoDB: key schema of {}: {}", table, err));
+                    if self.selected_item.as_ref() == Some(&table) {
+                        self.sort_key_input.set_title(SORT_KEY_TITLE.to_string());
+                    }
+                }
+            },
+            // Describe the table in the background; the popup opens once it arrives
+            ComponentAction::DescribeTable(table) => {
+                if let Some(client) = &self.dynamodb_client {
+                    let client = Arc::clone(client);
+                    spawn_in_generation(
+                        &self.event_sender,
+                        self.component_type.clone(),
+                        self.generation,
+                        async move {
+                            let summary = client
+                                .lock()
+                                .await
+                                .describe_table_summary(&table)
+                                .await
+                                .map_err(|err| err.to_string());
+                            vec![ComponentAction::TableDescribed(table, summary)]
+                        },
+                    );
+                }
+            }
+            // Show table metadata in the details popup
+            ComponentAction::TableDescribed(table, summary) => {
+                let summary = summary
+                    .unwrap_or_else(|err| format!("Failed to describe table {}: {}", table, err));
+                self.details_popup.set_content(PopupContent::Details(summary));
+                self.details_popup.set_visible(true);
+                self.details_popup.set_active(true);
+            }
+            // Put the item's partition key value into the query input for the next query
+            ComponentAction::RefineQuery(item) => {
+                let (Some(client), Some(table)) = (&self.dynamodb_client, &self.selected_item)
+                else {
+                    return;
+                };
+                let table = table.clone();
+                let partition_key = client.lock().await.get_table_primary_key(&table).await;
+                match partition_key {
+                    Ok(partition_key) => {
+                        // Rows that are not items, e.g. a message, are left alone
+                        if let Some(value) = dynamo_client::item_key_value(&item, &partition_key)
+                        {
+                            self.input.set_content(&value);
+                            self.current_focus = DynamodbFocus::Input;
+                            self.update_sort_key_focus(false);
+                            self.update_widget_states();
+                        }
+                    }
+                    Err(err) => {
+                        diagnostics::warn(format!("DynamoDB: key schema of {}: {}", table, err))
+                    }
+                }
+            }
             // Show item details in a popup
             ComponentAction::PopupDetails(title) => {
                 self.details_popup
                     .set_content(PopupContent::Details(title.clone()));
+                // The item JSON leaves unsupported types out; offer all of its attributes
+                if let Some(raw) = self.raw_items.get(&title) {
+                    self.details_popup.set_complete_details(raw.clone());
+                }
                 self.details_popup.set_visible(true);
                 self.details_popup.set_active(true);
             }
@@ -439,35 +978,80 @@ impl AWSComponent for DynamoDB {
                     }
                 }
             }
-            ComponentAction::SetQuery(partition_key) => {
-                self.results_navigator.set_title(partition_key.clone());
-                self.selected_query = Some(partition_key.clone());
-
-                if let Some(client) = &self.dynamodb_client {
-                    if let Some(selected_table) = &self.selected_item {
-                        // Get the sort key value if available
-                        let sort_key = self.sort_key_input.get_content();
-                        
-                        // Query the selected table with the partition key and sort key
-                        let content = client
-                            .lock()
-                            .await
-                            .query_table_composite(
-                                selected_table.clone(), 
-                                partition_key.clone(),
-                                sort_key
-                            )
-                            .await
-                            .unwrap_or_else(|_| vec!["Query error".to_string()]);
-
+            // Repeated Enter presses must not start a second query before the first returns
+            ComponentAction::SetQuery(partition_key) if self.is_loading => {
+                diagnostics::info(format!(
+                    "DynamoDB: ignored query for '{}' while another is running",
+                    partition_key
+                ));
+            }
+            // A scan of the whole table may be huge, so it asks first unless configured not to
+            ComponentAction::SetQuery(partition_key)
+                if partition_key.trim().is_empty() && self.confirm_scans =>
+            {
+                if let Some(table) = self.selected_item.clone() {
+                    self.details_popup.set_content(PopupContent::Confirm(format!(
+                        "Scan the whole table '{}'?\n\nThis may return a lot of data and take a while. Continue?",
+                        table
+                    )));
+                    self.details_popup.set_visible(true);
+                    self.details_popup.set_active(true);
+                    self.pending_scan = Some(table);
+                }
+            }
+            ComponentAction::SetQuery(partition_key) => self.start_query(partition_key),
+            ComponentAction::QueryCompleted(result) => {
+                self.is_loading = false;
+                self.results_navigator.set_title(self.query_title.clone());
+                if result.is_ok() {
+                    self.last_updated = Some(Instant::now());
+                }
+                match result {
+                    Ok(items) if items.records.is_empty() => {
+                        self.error_message = None;
                         self.results_navigator
-                            .set_content(NavigatorContent::Records(content));
+                            .show_placeholder("No items match the query");
+                    }
+                    Ok(mut items) => {
+                        self.error_message = None;
+                        let raw = std::mem::take(&mut items.raw);
+                        self.raw_items = items.records.iter().cloned().zip(raw).collect();
+                        self.results_navigator.set_title(format!(
+                            "{}{}",
+                            self.query_title,
+                            items.truncation_note()
+                        ));
+                        self.results_navigator
+                            .set_content(NavigatorContent::Records(items.records));
+                        self.results_navigator.set_total_available(items.total);
+                    }
+                    // The banner explains common failures in a line; the results keep the
+                    // message AWS returned
+                    Err(failure) => {
+                        diagnostics::error(format!("DynamoDB: Query failed: {}", failure.message));
+                        self.error_message = Some(format!(
+                            "Query failed: {}",
+                            failure.hint.unwrap_or(&failure.message)
+                        ));
+                        self.results_navigator.show_placeholder(failure.message);
                     }
                 }
-                // Move focus to the results after query
-                self.current_focus = DynamodbFocus::Results;
-                self.update_sort_key_focus(false);
-                self.update_widget_states();
+            }
+            // Say which keys of a batch get no item was found for
+            ComponentAction::KeysNotFound(missing) if !missing.is_empty() => {
+                self.results_navigator.set_title(format!(
+                    "{} ({} not found)",
+                    self.query_title,
+                    missing.len()
+                ));
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::WidgetAction(WidgetAction::Notify(format!(
+                            "No items for: {}",
+                            missing.join(", ")
+                        ))),
+                        self.component_type.clone(),
+                    )));
             }
             // Handle widget-specific actions
             ComponentAction::WidgetAction(widget_action) => match widget_action {
@@ -486,11 +1070,10 @@ impl AWSComponent for DynamoDB {
                                     WidgetType::AWSServiceNavigator,
                                 ) => {
                                     self.event_sender
-                                        .send(Event::Tab(TabEvent::ComponentActions(
+                                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                             ComponentAction::SetTitle(title.clone()),
                                             self.component_type.clone(),
-                                        )))
-                                        .unwrap();
+                                        )));
                                 }
                                 _ => {}
                             }
@@ -510,11 +1093,10 @@ impl AWSComponent for DynamoDB {
                                     WidgetType::QueryResultsNavigator,
                                 ) => {
                                     self.event_sender
-                                        .send(Event::Tab(TabEvent::ComponentActions(
+                                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                             ComponentAction::PopupDetails(title.clone()),
                                             self.component_type.clone(),
-                                        )))
-                                        .unwrap();
+                                        )));
                                 }
                                 _ => {}
                             }
@@ -530,11 +1112,10 @@ impl AWSComponent for DynamoDB {
                                     WidgetAction::InputBoxEvent(InputBoxEvent::Written(content), _) => {
                                         // If the Enter key was pressed in the partition key input
                                         self.event_sender
-                                            .send(Event::Tab(TabEvent::ComponentActions(
+                                            .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                                 ComponentAction::SetQuery(content),
                                                 self.component_type.clone(),
-                                            )))
-                                            .unwrap();
+                                            )));
                                     }
                                     _ => {}
                                 }
@@ -546,26 +1127,38 @@ impl AWSComponent for DynamoDB {
                                     WidgetAction::InputBoxEvent(InputBoxEvent::Written(_), _) => {
                                         // When Enter is pressed in the sort key input, 
                                         // execute the query using the partition key
-                                        if let Some(partition_key) = self.input.get_content() {
-                                            self.event_sender
-                                                .send(Event::Tab(TabEvent::ComponentActions(
-                                                    ComponentAction::SetQuery(partition_key),
-                                                    self.component_type.clone(),
-                                                )))
-                                                .unwrap();
-                                        }
+                                        self.rerun_query();
                                     }
                                     _ => {}
                                 }
                             }
                         }
+                        InputBoxType::Projection => {
+                            if let Some(WidgetAction::InputBoxEvent(InputBoxEvent::Written(_), _)) =
+                                self.projection_input.process_event(widget_action.clone())
+                            {
+                                // Apply the new projection to the current query right away
+                                self.rerun_query();
+                            }
+                        }
                         _ => {}
                     }
                 }
-                // Handle popup close events
+                // Handle popup navigation and close events
                 WidgetAction::PopupAction(_) => {
-                    self.details_popup.set_visible(false);
-                    self.details_popup.set_active(false);
+                    // The popup closes itself on Esc, or Enter when nothing is selectable
+                    let confirmed = matches!(
+                        self.details_popup.process_event(widget_action),
+                        Some(WidgetAction::PopupAction(PopupAction::Confirmed))
+                    );
+                    // Run a confirmed scan, unless another table was picked meanwhile
+                    if !self.details_popup.is_visible()
+                        && let Some(table) = self.pending_scan.take()
+                        && confirmed
+                        && self.selected_item.as_ref() == Some(&table)
+                    {
+                        self.start_query(String::new());
+                    }
                 }
                 _ => {}
             },
@@ -595,9 +1188,24 @@ impl AWSComponent for DynamoDB {
     async fn update(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(client) = &self.dynamodb_client {
             let client = client.lock().await;
-            let tables = client.list_tables().await?;
-            self.navigator
-                .set_content(NavigatorContent::Records(tables));
+            let tables = client.list_tables().await;
+            if tables.is_ok() {
+                self.last_updated = Some(Instant::now());
+            }
+            match tables {
+                Ok(tables) if tables.is_empty() => {
+                    self.navigator.show_placeholder("No tables found in this region");
+                }
+                Ok(tables) => {
+                    self.navigator
+                        .set_content(NavigatorContent::Records(tables));
+                }
+                Err(err) => {
+                    self.navigator
+                        .show_placeholder(format!("Error listing tables: {}", err));
+                    return Err(err.into());
+                }
+            }
         }
         Ok(())
     }
@@ -619,13 +1227,213 @@ impl AWSComponent for DynamoDB {
         
         // Add sort key specific help
         if self.current_focus == DynamodbFocus::Input {
-            if self.current_sub_focus == DynamodbFocus::TimeRange {
-                help_items.push(("Alt+2".to_string(), "Partition Key".to_string()));
-            } else {
-                help_items.push(("Alt+3".to_string(), "Sort Key".to_string()));
-            }
+            let other = if self.projection_mode { "Sort Key" } else { "Projection" };
+            help_items.push(("Alt+J".to_string(), other.to_string()));
         }
         
         help_items
     }
+
+    fn last_updated(&self) -> Option<Instant> {
+        self.last_updated
+    }
+
+    fn is_busy(&self) -> bool {
+        fetch_running(&self.query_task)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::aws::CappedRecords;
+
+    #[tokio::test]
+    async fn table_summaries_open_once_they_arrive_for_the_current_profile() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = DynamoDB::new(sender);
+
+        // A summary requested before the profile or region changed is dropped
+        let stale = ComponentAction::BackgroundResult(
+            component.generation.wrapping_sub(1),
+            Box::new(ComponentAction::TableDescribed(
+                "Orders".to_string(),
+                Ok("{}".to_string()),
+            )),
+        );
+        component.process_event(stale).await;
+        assert!(!component.details_popup.is_visible());
+
+        component
+            .process_event(ComponentAction::TableDescribed(
+                "Orders".to_string(),
+                Err("DescribeTable error: ResourceNotFoundException".to_string()),
+            ))
+            .await;
+        assert!(component.details_popup.is_visible());
+    }
+
+    #[tokio::test]
+    async fn queries_are_ignored_while_one_is_running() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = DynamoDB::new(sender);
+        component.is_loading = true;
+
+        component
+            .process_event(ComponentAction::SetQuery("user#1".to_string()))
+            .await;
+        assert_eq!(component.selected_query, None);
+    }
+
+    #[tokio::test]
+    async fn esc_cancels_the_running_query() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = DynamoDB::new(sender);
+        let task = tokio::spawn(std::future::pending::<()>());
+        component.query_task = Some(task.abort_handle());
+        component.is_loading = true;
+        let generation = next_generation();
+        component.generation = generation;
+
+        component.handle_input(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!component.is_loading);
+        assert!(task.await.unwrap_err().is_cancelled());
+
+        // A result sent just before the abort is dropped
+        let late = ComponentAction::BackgroundResult(
+            generation,
+            Box::new(ComponentAction::QueryCompleted(Ok(CappedRecords {
+                records: vec![r#"{"id":"late"}"#.to_string()],
+                truncated_at: None,
+                total: None,
+                raw: Vec::new(),
+            }))),
+        );
+        component.process_event(late).await;
+        assert_eq!(component.results_navigator.selected_label(), None);
+    }
+
+    #[tokio::test]
+    async fn finished_queries_show_results_unless_superseded() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = DynamoDB::new(sender);
+        let stale = next_generation();
+        component.generation = next_generation();
+        component.is_loading = true;
+
+        let completed = |generation, item: &str| {
+            ComponentAction::BackgroundResult(
+                generation,
+                Box::new(ComponentAction::QueryCompleted(Ok(CappedRecords {
+                    records: vec![item.to_string()],
+                    truncated_at: None,
+                    total: None,
+                    raw: Vec::new(),
+                }))),
+            )
+        };
+        component.process_event(completed(stale, "stale")).await;
+        assert!(component.is_loading);
+
+        component
+            .process_event(completed(component.generation, "fresh"))
+            .await;
+        assert!(!component.is_loading);
+        assert_eq!(
+            component.results_navigator.selected_label(),
+            Some("fresh".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_gets_report_keys_without_items() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = DynamoDB::new(sender);
+        component.selected_item = Some("Users".to_string());
+        component
+            .process_event(ComponentAction::SetQuery("u1, u2\nu3".to_string()))
+            .await;
+        assert_eq!(component.query_title, "Batch get (3 keys)");
+
+        component
+            .process_event(ComponentAction::KeysNotFound(vec![
+                "u2".to_string(),
+                "u3".to_string(),
+            ]))
+            .await;
+        let mut notified = None;
+        while let Ok(event) = receiver.try_recv() {
+            if let Event::Tab(TabEvent::ComponentActions(
+                ComponentAction::WidgetAction(WidgetAction::Notify(message)),
+                _,
+            )) = event
+            {
+                notified = Some(message);
+            }
+        }
+        assert_eq!(notified.as_deref(), Some("No items for: u2, u3"));
+    }
+
+    #[tokio::test]
+    async fn sort_key_input_follows_the_selected_table() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = DynamoDB::new(sender);
+        let typed = |component: &mut DynamoDB| {
+            component.sort_key_input.set_content("");
+            if let Some(typed) = component
+                .sort_key_input
+                .handle_input(KeyEvent::from(KeyCode::Char('x')))
+            {
+                component.sort_key_input.process_event(typed);
+            }
+            component.sort_key_input.get_content()
+        };
+        component.selected_item = Some("Users".to_string());
+        component.sort_key_input.set_content("2024");
+
+        // A table without a sort key clears and disables the input
+        component
+            .process_event(ComponentAction::SortKeyLoaded("Users".to_string(), Ok(None)))
+            .await;
+        assert_eq!(component.sort_key_input.get_content(), None);
+        assert_eq!(typed(&mut component), None);
+
+        // Lookups for a table that is no longer selected are only cached
+        component
+            .process_event(ComponentAction::SortKeyLoaded(
+                "Orders".to_string(),
+                Ok(Some("created_at".to_string())),
+            ))
+            .await;
+        assert_eq!(typed(&mut component), None);
+
+        component
+            .process_event(ComponentAction::SetTitle("Orders".to_string()))
+            .await;
+        assert_eq!(typed(&mut component), Some("x".to_string()));
+    }
+
+    #[tokio::test]
+    async fn scans_wait_for_confirmation_unless_disabled() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = DynamoDB::new(sender);
+        component.selected_item = Some("orders".to_string());
+        component.confirm_scans = true;
+
+        component
+            .process_event(ComponentAction::SetQuery(String::new()))
+            .await;
+        assert_eq!(component.pending_scan.as_deref(), Some("orders"));
+        assert!(component.details_popup.is_visible());
+        assert_eq!(component.current_focus, DynamodbFocus::Navigation);
+
+        component.pending_scan = None;
+        component.confirm_scans = false;
+        component
+            .process_event(ComponentAction::SetQuery(String::new()))
+            .await;
+        assert_eq!(component.pending_scan, None);
+        assert_eq!(component.query_title, "Scan");
+        assert_eq!(component.selected_query, None);
+    }
 }
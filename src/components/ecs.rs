@@ -0,0 +1,794 @@
+use crate::components::{
+    AWSComponent, ComponentFocus, SplitRatio, abort_fetch, fetch_running, focus_jump_help,
+    next_generation, render_error_banner, spawn_fetch,
+};
+use crate::diagnostics;
+use crate::event_managment::event::{
+    ComponentAction, ComponentType, Event, InputBoxEvent, InputBoxType, SendOrLog,
+    ServiceNavigatorEvent, TabEvent, WidgetAction, WidgetEventType, WidgetType,
+};
+use crate::services::aws::TabClients;
+use crate::services::aws::ecs_client::{EcsClient, ServiceDetails};
+use crate::widgets::WidgetExt;
+use crate::widgets::input_box::InputBoxWidget;
+use crate::widgets::popup::{PopupContent, PopupWidget};
+use crate::widgets::service_navigator::{NavigatorContent, ServiceNavigator};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+};
+use std::any::Any;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
+use tokio::task::AbortHandle;
+
+/// Component for browsing ECS clusters and their services
+///
+/// Selecting a cluster lists its services, Backspace goes back to the clusters, and
+/// selecting a service shows its task counts and task definition in the popup.
+pub struct Ecs {
+    /// Component type identifier
+    component_type: ComponentType,
+    /// Client for ECS API interactions
+    ecs_client: Option<Arc<Mutex<EcsClient>>>,
+    /// AWS service client
+    aws_clients: Option<TabClients>,
+    /// Left navigator listing clusters
+    navigator: ServiceNavigator,
+    /// Input for opening a cluster by name or ARN
+    input: InputBoxWidget,
+    /// Services of the selected cluster
+    results_navigator: ServiceNavigator,
+    /// Popup for displaying a service as JSON
+    details_popup: PopupWidget,
+    /// Whether the component is currently active
+    active: bool,
+    /// Whether the component is currently visible
+    visible: bool,
+    /// Channel for sending events to the application
+    event_sender: tokio::sync::mpsc::UnboundedSender<Event>,
+    /// Current focus state within the component
+    current_focus: ComponentFocus,
+    /// Error shown in a banner above the component's widgets
+    error_message: Option<String>,
+    /// Width of the left navigator relative to the right panel
+    split: SplitRatio,
+    /// Generation of the latest cluster listing; older listings are ignored
+    generation: u64,
+    /// Generation of the latest service listing; older listings are ignored
+    services_generation: u64,
+    /// Generation of the latest service description; older ones are ignored
+    service_generation: u64,
+    /// When clusters were last listed successfully
+    last_updated: Option<Instant>,
+    /// Cluster whose services are listed
+    selected_cluster: Option<String>,
+    /// Latest background cluster listing, while it may still be running
+    list_task: Option<AbortHandle>,
+    /// Latest background service listing, while it may still be running
+    services_task: Option<AbortHandle>,
+    /// Latest background service description, while it may still be running
+    service_task: Option<AbortHandle>,
+}
+
+impl Ecs {
+    /// Creates a new ECS component with the provided event sender
+    pub fn new(event_sender: tokio::sync::mpsc::UnboundedSender<Event>) -> Self {
+        let popup_content = PopupContent::Profiles(vec!["No content".to_string()]);
+
+        Self {
+            component_type: ComponentType::ECS,
+            ecs_client: None,
+            aws_clients: None,
+            navigator: ServiceNavigator::new(
+                WidgetType::AWSServiceNavigator,
+                false,
+                NavigatorContent::Records(vec![]),
+            ),
+            input: {
+                let mut input =
+                    InputBoxWidget::new(InputBoxType::Text, "Cluster (name or ARN)", false);
+                // Clusters are recalled with Up/Down, per profile
+                input.enable_history("ecs.cluster");
+                input
+            },
+            results_navigator: ServiceNavigator::new(
+                WidgetType::QueryResultsNavigator,
+                false,
+                NavigatorContent::Records(vec![]),
+            ),
+            details_popup: PopupWidget::new(popup_content, "Details", false, false),
+            active: false,
+            visible: true,
+            event_sender,
+            current_focus: ComponentFocus::Navigation,
+            error_message: None,
+            split: SplitRatio::load("ecs", 40),
+            generation: 0,
+            services_generation: 0,
+            service_generation: 0,
+            last_updated: None,
+            selected_cluster: None,
+            list_task: None,
+            services_task: None,
+            service_task: None,
+        }
+    }
+
+    /// Updates active states of all widgets based on current focus
+    fn update_widget_states(&mut self) {
+        self.navigator
+            .set_active(self.active && self.current_focus == ComponentFocus::Navigation);
+        self.input
+            .set_active(self.active && self.current_focus == ComponentFocus::Input);
+        self.results_navigator
+            .set_active(self.active && self.current_focus == ComponentFocus::Results);
+    }
+
+    /// Shifts focus to the next widget in the cyclic order
+    fn focus_next(&mut self) {
+        self.current_focus = match self.current_focus {
+            ComponentFocus::Navigation => ComponentFocus::Input,
+            ComponentFocus::Input | ComponentFocus::TimeRange => ComponentFocus::Results,
+            ComponentFocus::Results => ComponentFocus::None,
+            ComponentFocus::None => ComponentFocus::Navigation,
+        };
+        self.update_widget_states();
+    }
+
+    /// Shifts focus to the previous widget in the cyclic order
+    fn focus_previous(&mut self) {
+        self.current_focus = match self.current_focus {
+            ComponentFocus::Navigation => ComponentFocus::None,
+            ComponentFocus::Input | ComponentFocus::TimeRange => ComponentFocus::Navigation,
+            ComponentFocus::Results => ComponentFocus::Input,
+            ComponentFocus::None => ComponentFocus::Results,
+        };
+        self.update_widget_states();
+    }
+
+    /// Moves focus to a widget, e.g. for the Alt+number shortcuts
+    fn focus(&mut self, focus: ComponentFocus) {
+        self.current_focus = focus;
+        self.update_widget_states();
+    }
+
+    /// Sends an action to this component through the event loop
+    fn send(&self, action: ComponentAction) {
+        self.event_sender.send_or_log(Event::Tab(TabEvent::ComponentActions(
+            action,
+            self.component_type.clone(),
+        )));
+    }
+
+    /// Lists the clusters of the region, in the background
+    fn list_clusters(&mut self) {
+        let Some(client) = &self.ecs_client else {
+            return;
+        };
+        let client = Arc::clone(client);
+        self.navigator
+            .set_title(String::from("Clusters (Loading...)"));
+        let task = spawn_fetch(
+            &self.event_sender,
+            self.component_type.clone(),
+            &mut self.generation,
+            async move {
+                let result = client
+                    .lock()
+                    .await
+                    .list_clusters()
+                    .await
+                    .map_err(|err| err.to_string());
+                vec![ComponentAction::ClustersListed(result)]
+            },
+        );
+        self.list_task = Some(task);
+    }
+
+    /// Opens a cluster and lists its services in the background
+    fn select_cluster(&mut self, cluster: String) {
+        // Details viewed in the previous cluster are not worth going back to
+        self.details_popup.clear_history();
+        self.selected_cluster = Some(cluster.clone());
+        self.results_navigator
+            .set_content(NavigatorContent::Records(vec![]));
+        let Some(client) = &self.ecs_client else {
+            return;
+        };
+        let client = Arc::clone(client);
+        self.results_navigator
+            .set_title(format!("Services: {} (Loading...)", cluster));
+        let task = spawn_fetch(
+            &self.event_sender,
+            self.component_type.clone(),
+            &mut self.services_generation,
+            async move {
+                let result = client
+                    .lock()
+                    .await
+                    .list_services(&cluster)
+                    .await
+                    .map_err(|err| err.to_string());
+                vec![ComponentAction::ServicesListed(cluster, result)]
+            },
+        );
+        self.services_task = Some(task);
+        self.focus(ComponentFocus::Results);
+    }
+
+    /// Shows the services listed for a cluster, unless another cluster was opened since
+    fn apply_services(&mut self, cluster: String, result: Result<Vec<String>, String>) {
+        if self.selected_cluster.as_deref() != Some(cluster.as_str()) {
+            return;
+        }
+        match result {
+            Ok(names) if names.is_empty() => {
+                self.error_message = None;
+                self.results_navigator
+                    .set_title(format!("Services: {}", cluster));
+                self.results_navigator
+                    .show_placeholder("No services in this cluster");
+            }
+            Ok(names) => {
+                self.error_message = None;
+                self.results_navigator
+                    .set_title(format!("Services: {} ({})", cluster, names.len()));
+                self.results_navigator
+                    .set_content(NavigatorContent::Records(names));
+            }
+            Err(err) => {
+                let message = format!("Listing the services of {} failed: {}", cluster, err);
+                diagnostics::error(format!("ECS: {}", message));
+                self.error_message = Some(message);
+                self.results_navigator
+                    .set_title(format!("Services: {}", cluster));
+                self.results_navigator
+                    .show_placeholder("Services could not be listed");
+            }
+        }
+    }
+
+    /// Describes a service of the open cluster in the background
+    fn select_service(&mut self, service: String) {
+        let (Some(client), Some(cluster)) = (&self.ecs_client, &self.selected_cluster) else {
+            return;
+        };
+        let client = Arc::clone(client);
+        let cluster = cluster.clone();
+        self.results_navigator
+            .set_title(format!("Services: {} (Describing {}...)", cluster, service));
+        let task = spawn_fetch(
+            &self.event_sender,
+            self.component_type.clone(),
+            &mut self.service_generation,
+            async move {
+                let result = client
+                    .lock()
+                    .await
+                    .describe_service(&cluster, &service)
+                    .await
+                    .map_err(|err| err.to_string());
+                vec![ComponentAction::ServiceDescribed(result)]
+            },
+        );
+        self.service_task = Some(task);
+    }
+
+    /// Shows a described service in the popup
+    fn apply_service(&mut self, result: Result<ServiceDetails, String>) {
+        if let Some(cluster) = &self.selected_cluster {
+            self.results_navigator
+                .set_title(format!("Services: {}", cluster));
+        }
+        match result {
+            Ok(service) => {
+                self.error_message = None;
+                self.details_popup
+                    .set_content(PopupContent::Details(service.to_json()));
+                self.details_popup.set_visible(true);
+                self.details_popup.set_active(true);
+            }
+            Err(err) => {
+                let message = format!("Describing the service failed: {}", err);
+                diagnostics::error(format!("ECS: {}", message));
+                self.error_message = Some(message);
+            }
+        }
+    }
+
+    /// Goes back from a cluster's services to the clusters
+    fn navigate_up(&mut self) {
+        if self.selected_cluster.take().is_none() {
+            return;
+        }
+        // Services still being listed or described belong to the cluster left behind
+        abort_fetch(&mut self.services_task);
+        abort_fetch(&mut self.service_task);
+        self.services_generation = next_generation();
+        self.service_generation = next_generation();
+        self.results_navigator
+            .set_content(NavigatorContent::Records(vec![]));
+        self.results_navigator.set_title(String::from("Services"));
+        self.focus(ComponentFocus::Navigation);
+    }
+
+    /// Returns contextual help items based on current component state
+    fn get_base_help_items(&self) -> Vec<(String, String)> {
+        if self.details_popup.is_visible() {
+            return self.details_popup.get_help_items();
+        }
+
+        let mut items = vec![];
+        match self.current_focus {
+            ComponentFocus::Navigation => {
+                items.push(("Enter".to_string(), "List services".to_string()));
+            }
+            ComponentFocus::Input => {
+                items.push((
+                    "Enter".to_string(),
+                    "List services of cluster".to_string(),
+                ));
+            }
+            ComponentFocus::Results => {
+                items.push(("Enter".to_string(), "Describe service".to_string()));
+                items.push(("Backspace".to_string(), "Back to clusters".to_string()));
+            }
+            _ => {}
+        }
+        let focused = match self.current_focus {
+            ComponentFocus::Navigation => 1,
+            ComponentFocus::Input => 2,
+            _ => 4,
+        };
+        items.extend(focus_jump_help(
+            [
+                Some("Focus clusters"),
+                Some("Focus cluster input"),
+                None,
+                Some("Focus results"),
+            ],
+            focused,
+        ));
+        items
+    }
+}
+
+#[async_trait::async_trait]
+impl AWSComponent for Ecs {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if !self.visible {
+            return;
+        }
+
+        // Reserve space for the error banner if a request failed
+        let area = match &self.error_message {
+            Some(message) => render_error_banner(message, area, buf),
+            None => area,
+        };
+
+        // Clusters on the left, the cluster input and the open cluster's services on the right
+        let horizontal_split = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(self.split.constraints())
+            .split(area);
+        let right_vertical_split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Cluster input
+                Constraint::Min(1),    // Services
+            ])
+            .split(horizontal_split[1]);
+
+        let list_prompt = self
+            .ecs_client
+            .is_none()
+            .then_some("Select a profile to list its clusters");
+        self.navigator
+            .render_with_prompt(horizontal_split[0], buf, list_prompt);
+        self.input.render(right_vertical_split[0], buf);
+        let services_prompt = self
+            .selected_cluster
+            .is_none()
+            .then_some("Pick a cluster to list its services");
+        self.results_navigator
+            .render_with_prompt(right_vertical_split[1], buf, services_prompt);
+
+        if self.details_popup.is_visible() {
+            self.details_popup.render(area, buf);
+        }
+    }
+
+    /// Sets focus to the last active widget in the component
+    fn set_focus_to_last(&mut self) {
+        self.focus(ComponentFocus::Results);
+    }
+
+    fn current_focus(&self) -> ComponentFocus {
+        self.current_focus
+    }
+
+    /// Handles keyboard input events
+    fn handle_input(&mut self, key_event: KeyEvent) {
+        if self.details_popup.is_visible() {
+            if let Some(signal) = self.details_popup.handle_input(key_event) {
+                self.send(ComponentAction::WidgetAction(signal));
+            }
+            return;
+        }
+
+        match key_event.code {
+            KeyCode::Tab => self.send(ComponentAction::NextFocus),
+            KeyCode::BackTab => self.send(ComponentAction::PreviousFocus),
+            // Alt+number shortcuts to switch focus between areas
+            KeyCode::Char('1') if key_event.modifiers == KeyModifiers::ALT => {
+                self.focus(ComponentFocus::Navigation);
+            }
+            KeyCode::Char('2') if key_event.modifiers == KeyModifiers::ALT => {
+                self.focus(ComponentFocus::Input);
+            }
+            KeyCode::Char('4') if key_event.modifiers == KeyModifiers::ALT => {
+                self.focus(ComponentFocus::Results);
+            }
+            // Back from a cluster's services to the clusters
+            KeyCode::Backspace
+                if self.current_focus == ComponentFocus::Results
+                    && !self.results_navigator.is_capturing_input() =>
+            {
+                self.send(ComponentAction::NavigateUp);
+            }
+            // Ctrl+Left/Right resize the panes; the input keeps them for word movement
+            KeyCode::Left | KeyCode::Right
+                if key_event.modifiers == KeyModifiers::CONTROL
+                    && matches!(
+                        self.current_focus,
+                        ComponentFocus::Navigation | ComponentFocus::Results
+                    ) =>
+            {
+                self.split.nudge(key_event.code == KeyCode::Right);
+            }
+            KeyCode::Esc if self.current_focus != ComponentFocus::Navigation => {
+                self.focus(ComponentFocus::Navigation);
+            }
+            _ => {
+                if let Some(signal) = match self.current_focus {
+                    ComponentFocus::Navigation => self.navigator.handle_input(key_event),
+                    ComponentFocus::Input => self.input.handle_input(key_event),
+                    ComponentFocus::Results => self.results_navigator.handle_input(key_event),
+                    _ => None,
+                } {
+                    self.send(ComponentAction::WidgetAction(signal));
+                }
+            }
+        }
+    }
+
+    /// Processes component-specific actions
+    async fn process_event(&mut self, event: ComponentAction) {
+        match event {
+            ComponentAction::Active(aws_profile, region) => {
+                // Listings for the previous profile or region must not land in the new view
+                abort_fetch(&mut self.list_task);
+                abort_fetch(&mut self.services_task);
+                abort_fetch(&mut self.service_task);
+                self.generation = next_generation();
+                self.services_generation = next_generation();
+                self.service_generation = next_generation();
+                self.selected_cluster = None;
+                self.details_popup.clear_history();
+                self.results_navigator
+                    .set_content(NavigatorContent::Records(vec![]));
+                self.input.load_history(&aws_profile);
+                self.last_updated = None;
+                self.aws_clients = Some(TabClients::new(aws_profile, region));
+
+                if let Some(clients) = &mut self.aws_clients {
+                    match clients.get_ecs_client().await {
+                        Ok(client) => {
+                            self.ecs_client = Some(client);
+                            self.error_message = None;
+                            self.update().await.ok();
+                        }
+                        Err(err) => {
+                            let message = format!(
+                                "Failed to initialize ECS client (profile: {}, region: {}): {}",
+                                clients.profile(),
+                                clients.region(),
+                                err
+                            );
+                            // The error banner shows it instead of clobbering the navigators
+                            self.send(ComponentAction::ShowError(message));
+                        }
+                    }
+                }
+            }
+            ComponentAction::ShowError(message) => {
+                diagnostics::error(format!("ECS: {}", message));
+                self.error_message = Some(message);
+            }
+            ComponentAction::Reset => {
+                self.navigator.reset_view();
+                self.results_navigator.reset_view();
+                self.details_popup.clear_history();
+                self.results_navigator
+                    .set_content(NavigatorContent::Records(vec![]));
+                self.results_navigator.set_title(String::from("Services"));
+                self.input.set_content("");
+                self.selected_cluster = None;
+                self.error_message = None;
+                // Stop listings and descriptions that are still running, dropping any
+                // result already sent
+                abort_fetch(&mut self.list_task);
+                abort_fetch(&mut self.services_task);
+                abort_fetch(&mut self.service_task);
+                self.services_generation = next_generation();
+                self.service_generation = next_generation();
+                self.list_clusters();
+                self.reset_focus();
+            }
+            // Apply background results only if no newer request has started since
+            ComponentAction::BackgroundResult(generation, action)
+                if generation == self.generation
+                    || generation == self.services_generation
+                    || generation == self.service_generation =>
+            {
+                self.process_event(*action).await;
+            }
+            ComponentAction::Focused => {
+                self.set_active(true);
+            }
+            ComponentAction::Unfocused => {
+                if self.current_focus == ComponentFocus::None {
+                    self.reset_focus();
+                }
+                self.set_active(false);
+            }
+            ComponentAction::NextFocus => self.focus_next(),
+            ComponentAction::PreviousFocus => self.focus_previous(),
+            ComponentAction::ClustersListed(result) => {
+                self.navigator.set_title(String::from("Clusters"));
+                match result {
+                    Ok(names) if names.is_empty() => {
+                        self.last_updated = Some(Instant::now());
+                        self.error_message = None;
+                        self.navigator.show_placeholder("No clusters in this region");
+                    }
+                    Ok(names) => {
+                        self.last_updated = Some(Instant::now());
+                        self.error_message = None;
+                        self.navigator.set_content(NavigatorContent::Records(names));
+                    }
+                    Err(err) => {
+                        let message = format!("Listing clusters failed: {}", err);
+                        diagnostics::error(format!("ECS: {}", message));
+                        self.error_message = Some(message);
+                        self.navigator
+                            .show_placeholder("Clusters could not be listed");
+                    }
+                }
+            }
+            ComponentAction::SelectCluster(cluster) => self.select_cluster(cluster),
+            ComponentAction::ServicesListed(cluster, result) => {
+                self.apply_services(cluster, result);
+            }
+            ComponentAction::SelectService(service) => self.select_service(service),
+            ComponentAction::ServiceDescribed(result) => self.apply_service(result),
+            // Navigate up from the services to the clusters
+            ComponentAction::NavigateUp => self.navigate_up(),
+            ComponentAction::WidgetAction(widget_action) => match widget_action {
+                WidgetAction::ServiceNavigatorEvent(_, WidgetType::AWSServiceNavigator) => {
+                    if let Some(WidgetAction::ServiceNavigatorEvent(
+                        ServiceNavigatorEvent::ItemSelected(WidgetEventType::RecordSelected(name)),
+                        _,
+                    )) = self.navigator.process_event(widget_action)
+                    {
+                        self.send(ComponentAction::SelectCluster(name));
+                    }
+                }
+                WidgetAction::ServiceNavigatorEvent(_, WidgetType::QueryResultsNavigator) => {
+                    if let Some(WidgetAction::ServiceNavigatorEvent(
+                        ServiceNavigatorEvent::ItemSelected(WidgetEventType::RecordSelected(name)),
+                        _,
+                    )) = self.results_navigator.process_event(widget_action)
+                    {
+                        self.send(ComponentAction::SelectService(name));
+                    }
+                }
+                WidgetAction::InputBoxEvent(_, InputBoxType::Text) => {
+                    if let Some(WidgetAction::InputBoxEvent(InputBoxEvent::Written(cluster), _)) =
+                        self.input.process_event(widget_action)
+                    {
+                        let cluster = cluster.trim().to_string();
+                        if !cluster.is_empty() {
+                            self.send(ComponentAction::SelectCluster(cluster));
+                        }
+                    }
+                }
+                // Let the popup handle navigation, closing it once it hides itself
+                WidgetAction::PopupAction(_) => {
+                    self.details_popup.process_event(widget_action);
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Sets the active state of this component
+    fn set_active(&mut self, active: bool) {
+        self.active = active;
+        self.update_widget_states();
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Lists the clusters again, and the services of the open cluster
+    async fn update(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.list_clusters();
+        if let Some(cluster) = self.selected_cluster.clone() {
+            self.select_cluster(cluster);
+        }
+        Ok(())
+    }
+
+    /// Resets focus to the navigation pane
+    fn reset_focus(&mut self) {
+        self.focus(ComponentFocus::Navigation);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_help_items(&self) -> Vec<(String, String)> {
+        self.get_base_help_items()
+    }
+
+    fn last_updated(&self) -> Option<Instant> {
+        self.last_updated
+    }
+
+    fn is_busy(&self) -> bool {
+        fetch_running(&self.list_task)
+            || fetch_running(&self.services_task)
+            || fetch_running(&self.service_task)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(status: &str) -> ServiceDetails {
+        ServiceDetails {
+            name: "web".to_string(),
+            arn: None,
+            status: status.to_string(),
+            desired_count: 2,
+            running_count: 1,
+            pending_count: 0,
+            task_definition: "web:7".to_string(),
+            launch_type: Some("FARGATE".to_string()),
+            created_at: None,
+            deployments: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn services_of_the_open_cluster_are_listed_until_going_back_up() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = Ecs::new(sender);
+
+        component
+            .process_event(ComponentAction::SelectCluster("prod".to_string()))
+            .await;
+        assert_eq!(component.selected_cluster.as_deref(), Some("prod"));
+
+        // Services of a cluster left behind are not shown
+        let listed = |cluster: &str, names: &[&str]| {
+            ComponentAction::ServicesListed(
+                cluster.to_string(),
+                Ok(names.iter().map(|name| name.to_string()).collect()),
+            )
+        };
+        component.process_event(listed("staging", &["worker"])).await;
+        assert_eq!(component.results_navigator.selected_label(), None);
+        component.process_event(listed("prod", &["api", "web"])).await;
+        assert_eq!(
+            component.results_navigator.selected_label(),
+            Some("api".to_string())
+        );
+
+        component.process_event(ComponentAction::NavigateUp).await;
+        assert_eq!(component.selected_cluster, None);
+        assert_eq!(component.current_focus, ComponentFocus::Navigation);
+        assert_eq!(component.results_navigator.selected_label(), None);
+    }
+
+    #[tokio::test]
+    async fn reset_stops_services_still_being_listed() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = Ecs::new(sender);
+        component.services_generation = next_generation();
+        let in_flight = component.services_generation;
+        component
+            .process_event(ComponentAction::SelectCluster("prod".to_string()))
+            .await;
+        let listing = tokio::spawn(std::future::pending::<()>());
+        component.services_task = Some(listing.abort_handle());
+
+        component.process_event(ComponentAction::Reset).await;
+        assert!(component.services_task.is_none());
+        assert!(listing.await.unwrap_err().is_cancelled());
+        assert_eq!(component.selected_cluster, None);
+        assert_eq!(component.current_focus, ComponentFocus::Navigation);
+
+        // Reopening the cluster must not pick up the listing started before the reset
+        component
+            .process_event(ComponentAction::SelectCluster("prod".to_string()))
+            .await;
+        let late = ComponentAction::ServicesListed("prod".to_string(), Ok(vec!["api".to_string()]));
+        component
+            .process_event(ComponentAction::BackgroundResult(in_flight, Box::new(late)))
+            .await;
+        assert_eq!(component.results_navigator.selected_label(), None);
+    }
+
+    #[tokio::test]
+    async fn empty_clusters_and_failed_listings_are_told_apart() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = Ecs::new(sender);
+        component
+            .process_event(ComponentAction::SelectCluster("prod".to_string()))
+            .await;
+
+        component
+            .process_event(ComponentAction::ServicesListed("prod".to_string(), Ok(vec![])))
+            .await;
+        assert_eq!(component.error_message, None);
+
+        component
+            .process_event(ComponentAction::ServicesListed(
+                "prod".to_string(),
+                Err("Cluster not found: prod".to_string()),
+            ))
+            .await;
+        assert_eq!(
+            component.error_message.as_deref(),
+            Some("Listing the services of prod failed: Cluster not found: prod")
+        );
+    }
+
+    #[tokio::test]
+    async fn described_services_open_in_the_popup_even_while_draining() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = Ecs::new(sender);
+
+        component
+            .process_event(ComponentAction::ServiceDescribed(Ok(service("DRAINING"))))
+            .await;
+        assert!(component.details_popup.is_visible());
+        assert_eq!(component.error_message, None);
+
+        component.details_popup.set_visible(false);
+        component
+            .process_event(ComponentAction::ServiceDescribed(Err(
+                "Service not found: web".to_string(),
+            )))
+            .await;
+        assert!(!component.details_popup.is_visible());
+        assert!(component.error_message.is_some());
+    }
+}
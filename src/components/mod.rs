@@ -1,11 +1,180 @@
 pub(crate) mod cloudwatch;
 pub(crate) mod dynamodb;
+pub(crate) mod ecs;
+pub(crate) mod parameter_store;
 pub(crate) mod s3;
 pub(crate) mod tab;
-use crate::event_managment::event::ComponentAction;
+use crate::event_managment::event::{
+    ComponentAction, ComponentType, Event, SendOrLog, ServiceNavigatorEvent, TabEvent,
+    WidgetAction, WidgetType,
+};
+use crate::services::aws::TabClients;
+use crate::services::aws::cli_command::CliCommand;
+use crate::services::aws::console_link::{self, ConsoleResource};
+use crate::services::read_config;
+use crate::widgets::clipboard;
+use crate::theme::theme;
 use crossterm::event::KeyEvent;
-use ratatui::{buffer::Buffer, layout::Rect};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, BorderType, Borders, Paragraph, Widget, Wrap},
+};
 use std::any::Any;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::AbortHandle;
+
+/// Height of the error banner rendered at the top of a component
+const ERROR_BANNER_HEIGHT: u16 = 3;
+
+/// Percentage the split boundary moves per key press
+const SPLIT_STEP: u16 = 5;
+/// Narrowest the left pane of a split may become, in percent
+const MIN_SPLIT: u16 = 10;
+/// Widest the left pane of a split may become, in percent
+const MAX_SPLIT: u16 = 70;
+
+/// Source of background request generations, shared by every component in every tab
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// Allocates a new request generation
+///
+/// Generations are unique across component instances, so a result that outlives its
+/// component (e.g. after the tab is closed) can never match another component's generation
+pub fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Background fetches that have not finished yet, across every tab
+static RUNNING_FETCHES: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts a background fetch as running until it is dropped, whether it finished or was
+/// aborted
+struct RunningFetch;
+
+impl RunningFetch {
+    fn start() -> Self {
+        RUNNING_FETCHES.fetch_add(1, Ordering::Relaxed);
+        Self
+    }
+}
+
+impl Drop for RunningFetch {
+    fn drop(&mut self) {
+        RUNNING_FETCHES.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Whether any component in any tab still has a background fetch running, so quitting
+/// would cut it off
+pub fn background_work_running() -> bool {
+    RUNNING_FETCHES.load(Ordering::Relaxed) > 0
+}
+
+/// Runs a fetch in the background, superseding the component's earlier fetches
+///
+/// Moves `generation` to a new one and sends the actions the fetch resolves to back to
+/// the component tagged with it, so they are dropped if yet another fetch starts in the
+/// meantime. Returns the task's abort handle for cancelling the fetch.
+pub fn spawn_fetch<F>(
+    event_sender: &UnboundedSender<Event>,
+    component_type: ComponentType,
+    generation: &mut u64,
+    fetch: F,
+) -> AbortHandle
+where
+    F: Future<Output = Vec<ComponentAction>> + Send + 'static,
+{
+    *generation = next_generation();
+    spawn_in_generation(event_sender, component_type, *generation, fetch)
+}
+
+/// Runs background work whose actions apply only while `generation` is still current,
+/// without superseding the fetch in flight (e.g. a poll alongside the shown results)
+pub fn spawn_in_generation<F>(
+    event_sender: &UnboundedSender<Event>,
+    component_type: ComponentType,
+    generation: u64,
+    fetch: F,
+) -> AbortHandle
+where
+    F: Future<Output = Vec<ComponentAction>> + Send + 'static,
+{
+    let event_sender = event_sender.clone();
+    let running = RunningFetch::start();
+    tokio::spawn(async move {
+        let _running = running;
+        for action in fetch.await {
+            event_sender.send_or_log(Event::Tab(TabEvent::ComponentActions(
+                ComponentAction::BackgroundResult(generation, Box::new(action)),
+                component_type.clone(),
+            )));
+        }
+    })
+    .abort_handle()
+}
+
+/// Runs background work whose actions always reach the component, however many fetches
+/// start in the meantime, e.g. a delete that must be reported once it is done
+pub fn spawn_task<F>(event_sender: &UnboundedSender<Event>, component_type: ComponentType, work: F)
+where
+    F: Future<Output = Vec<ComponentAction>> + Send + 'static,
+{
+    let event_sender = event_sender.clone();
+    let running = RunningFetch::start();
+    tokio::spawn(async move {
+        let _running = running;
+        for action in work.await {
+            event_sender.send_or_log(Event::Tab(TabEvent::ComponentActions(
+                action,
+                component_type.clone(),
+            )));
+        }
+    });
+}
+
+/// Action updating one of a component's navigators
+pub fn navigator_action(event: ServiceNavigatorEvent, navigator: WidgetType) -> ComponentAction {
+    ComponentAction::WidgetAction(WidgetAction::ServiceNavigatorEvent(event, navigator))
+}
+
+/// Aborts a background fetch that is still running, returning whether there was one
+///
+/// Callers also move to a new generation, so a result sent just before the abort is dropped.
+pub fn abort_fetch(task: &mut Option<AbortHandle>) -> bool {
+    match task.take() {
+        Some(task) if !task.is_finished() => {
+            task.abort();
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Whether a background fetch was started and has not finished yet
+pub fn fetch_running(task: &Option<AbortHandle>) -> bool {
+    task.as_ref().is_some_and(|task| !task.is_finished())
+}
+
+/// Help items for the Alt+number focus jumps every service shares: Alt+1 the list,
+/// Alt+2 the main input, Alt+3 the second input (if the service has one) and Alt+4
+/// the results
+///
+/// `targets` holds the label of each jump and `focused` the number of the focused area,
+/// which is left out.
+pub fn focus_jump_help(targets: [Option<&str>; 4], focused: usize) -> Vec<(String, String)> {
+    targets
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| index + 1 != focused)
+        .filter_map(|(index, label)| {
+            label.map(|label| (format!("Alt+{}", index + 1), label.to_string()))
+        })
+        .collect()
+}
 
 /// Common trait for all AWS service components
 #[async_trait::async_trait]
@@ -16,6 +185,19 @@ pub trait AWSComponent: Send {
     /// Handle keyboard input
     fn handle_input(&mut self, key_event: KeyEvent);
 
+    /// Called on every tick of the event loop while the component is shown, e.g. to poll
+    fn tick(&mut self) {}
+
+    /// Whether the component animates, e.g. follows a live tail, and needs the faster tick rate
+    fn is_animating(&self) -> bool {
+        false
+    }
+
+    /// Whether a background fetch of the component is still running
+    fn is_busy(&self) -> bool {
+        false
+    }
+
     /// Process component actions
     async fn process_event(&mut self, event: ComponentAction);
 
@@ -46,11 +228,21 @@ pub trait AWSComponent: Send {
     /// Get contextual help information for the component
     fn get_help_items(&self) -> Vec<(String, String)>;
 
-    /// Is the component navigable
-    fn allows_focus_continuation(&self) -> bool;
+    /// Area of the component with focus, `None` once Tab has moved past the last one
+    fn current_focus(&self) -> ComponentFocus;
+
+    /// Whether Tab leaves the component, its focus having moved past the last area
+    fn allows_focus_continuation(&self) -> bool {
+        self.current_focus() == ComponentFocus::None
+    }
+
+    /// Whether Shift+Tab stays in the component, its first area not having focus
+    fn allows_focus_continuation_backward(&self) -> bool {
+        self.current_focus() != ComponentFocus::Navigation
+    }
 
-    /// Is the component navigable
-    fn allows_focus_continuation_backward(&self) -> bool;
+    /// When the component last fetched its data successfully, if it has yet
+    fn last_updated(&self) -> Option<Instant>;
 }
 
 /// Represents the current input focus within a component
@@ -67,3 +259,328 @@ pub enum ComponentFocus {
     /// No focus set
     None,
 }
+
+/// Resizable left/right split, saved under a name so it survives restarts
+#[derive(Debug, Clone)]
+pub struct SplitRatio {
+    /// Key under which the split is saved
+    name: &'static str,
+    /// Width of the left pane in percent
+    left: u16,
+}
+
+impl SplitRatio {
+    /// Loads the saved split for `name`, falling back to `default` (left pane percent)
+    pub fn load(name: &'static str, default: u16) -> Self {
+        let left = read_config::get_split_ratios()
+            .get(name)
+            .copied()
+            .unwrap_or(default)
+            .clamp(MIN_SPLIT, MAX_SPLIT);
+        Self { name, left }
+    }
+
+    /// Constraints for the left and right panes
+    pub fn constraints(&self) -> [Constraint; 2] {
+        [
+            Constraint::Percentage(self.left),
+            Constraint::Percentage(100 - self.left),
+        ]
+    }
+
+    /// Moves the boundary one step right (`wider`) or left and saves the result
+    pub fn nudge(&mut self, wider: bool) {
+        let left = step_split(self.left, wider);
+        if left != self.left {
+            self.left = left;
+            // Losing the saved layout is not worth interrupting the user for
+            let _ = read_config::save_split_ratio(self.name, left);
+        }
+    }
+}
+
+/// Widens or narrows a left pane by one step, staying within the allowed range
+fn step_split(left: u16, wider: bool) -> u16 {
+    let left = if wider {
+        left.saturating_add(SPLIT_STEP)
+    } else {
+        left.saturating_sub(SPLIT_STEP)
+    };
+    left.clamp(MIN_SPLIT, MAX_SPLIT)
+}
+
+/// Copies the console link of a resource and reports it in the tab's status line
+///
+/// Falls back to the resource identifier when there is no region yet. With `open_console`
+/// configured the link is opened in the browser too.
+pub fn copy_console_link(
+    resource: ConsoleResource,
+    aws_clients: Option<&TabClients>,
+    event_sender: &tokio::sync::mpsc::UnboundedSender<Event>,
+    component_type: ComponentType,
+) {
+    let url = aws_clients.map(|clients| resource.url(clients.region()));
+    if let Some(url) = &url
+        && read_config::get_open_console_links()
+    {
+        // Copying still works when no browser can be launched
+        let _ = console_link::open_in_browser(url);
+    }
+
+    let text = url.unwrap_or_else(|| resource.identifier().to_string());
+    let action = match clipboard::copy_text(&text) {
+        Ok(()) => WidgetAction::ServiceNavigatorEvent(
+            ServiceNavigatorEvent::Copied(text),
+            WidgetType::AWSServiceNavigator,
+        ),
+        Err(err) => WidgetAction::Notify(err.to_string()),
+    };
+    event_sender.send_or_log(Event::Tab(TabEvent::ComponentActions(
+        ComponentAction::WidgetAction(action),
+        component_type,
+    )));
+}
+
+/// Copies the AWS CLI command repeating a component's request and reports it in the
+/// tab's status line
+///
+/// Says so instead when there is no request to repeat yet or no profile is active.
+pub fn copy_cli_command(
+    command: Option<CliCommand>,
+    aws_clients: Option<&TabClients>,
+    event_sender: &tokio::sync::mpsc::UnboundedSender<Event>,
+    component_type: ComponentType,
+) {
+    let action = match (command, aws_clients) {
+        (Some(command), Some(clients)) => {
+            let text = command.command_line(clients.profile(), clients.region());
+            match clipboard::copy_text(&text) {
+                Ok(()) => WidgetAction::ServiceNavigatorEvent(
+                    ServiceNavigatorEvent::Copied(text),
+                    WidgetType::AWSServiceNavigator,
+                ),
+                Err(err) => WidgetAction::Notify(err.to_string()),
+            }
+        }
+        _ => WidgetAction::Notify("Nothing to copy as an AWS CLI command yet".to_string()),
+    };
+    event_sender.send_or_log(Event::Tab(TabEvent::ComponentActions(
+        ComponentAction::WidgetAction(action),
+        component_type,
+    )));
+}
+
+/// How long ago data was fetched, in the largest whole unit, e.g. `12s ago` or `3m ago`
+pub fn format_age(age: Duration) -> String {
+    match age.as_secs() {
+        0..5 => String::from("just now"),
+        secs @ 5..60 => format!("{}s ago", secs),
+        secs @ 60..3600 => format!("{}m ago", secs / 60),
+        secs => format!("{}h ago", secs / 3600),
+    }
+}
+
+/// Renders a red error banner at the top of a component's area
+///
+/// Returns the remaining area below the banner for the component's own widgets
+pub fn render_error_banner(message: &str, area: Rect, buf: &mut Buffer) -> Rect {
+    let split = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(ERROR_BANNER_HEIGHT), Constraint::Min(1)])
+        .split(area);
+
+    let block = Block::default()
+        .title("Error")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme().error_fg));
+
+    Paragraph::new(message)
+        .block(block)
+        .style(Style::default().fg(theme().error_fg))
+        .wrap(Wrap { trim: true })
+        .render(split[0], buf);
+
+    split[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{
+        cloudwatch::CloudWatch, dynamodb::DynamoDB, ecs::Ecs, parameter_store::ParameterStore,
+        s3::S3Component,
+    };
+
+    #[tokio::test]
+    async fn focused_activates_and_unfocused_deactivates_every_component() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut components: Vec<Box<dyn AWSComponent>> = vec![
+            Box::new(S3Component::new(sender.clone())),
+            Box::new(DynamoDB::new(sender.clone())),
+            Box::new(CloudWatch::new(sender.clone())),
+            Box::new(ParameterStore::new(sender.clone())),
+            Box::new(Ecs::new(sender)),
+        ];
+
+        for component in components.iter_mut() {
+            component.process_event(ComponentAction::Focused).await;
+            assert!(component.is_active());
+            component.process_event(ComponentAction::Unfocused).await;
+            assert!(!component.is_active());
+        }
+    }
+
+    #[tokio::test]
+    async fn tab_cycles_through_every_component_and_shift_tab_retraces_it() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        // Whether the component has a second input, the sort key or time range
+        let components: Vec<(Box<dyn AWSComponent>, bool)> = vec![
+            (Box::new(S3Component::new(sender.clone())), false),
+            (Box::new(DynamoDB::new(sender.clone())), true),
+            (Box::new(CloudWatch::new(sender.clone())), true),
+            (Box::new(ParameterStore::new(sender.clone())), false),
+            (Box::new(Ecs::new(sender)), false),
+        ];
+
+        for (mut component, second_input) in components {
+            let mut expected = vec![ComponentFocus::Navigation, ComponentFocus::Input];
+            if second_input {
+                expected.push(ComponentFocus::TimeRange);
+            }
+            expected.push(ComponentFocus::Results);
+
+            // Tab visits every area once, then hands focus back to the tab
+            component.process_event(ComponentAction::Focused).await;
+            let mut visited = vec![component.current_focus()];
+            while !component.allows_focus_continuation() {
+                assert!(visited.len() <= expected.len(), "{:?}", visited);
+                component.process_event(ComponentAction::NextFocus).await;
+                visited.push(component.current_focus());
+            }
+            assert_eq!(visited.pop(), Some(ComponentFocus::None));
+            assert_eq!(visited, expected);
+
+            // Shift+Tab enters at the last area and walks back to the first
+            component.process_event(ComponentAction::Unfocused).await;
+            component.set_focus_to_last();
+            let mut visited = vec![component.current_focus()];
+            while component.allows_focus_continuation_backward() {
+                component
+                    .process_event(ComponentAction::PreviousFocus)
+                    .await;
+                visited.push(component.current_focus());
+            }
+            visited.reverse();
+            assert_eq!(visited, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn every_component_lists_the_same_focus_jumps() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut components: Vec<Box<dyn AWSComponent>> = vec![
+            Box::new(S3Component::new(sender.clone())),
+            Box::new(DynamoDB::new(sender.clone())),
+            Box::new(CloudWatch::new(sender.clone())),
+            Box::new(ParameterStore::new(sender.clone())),
+            Box::new(Ecs::new(sender)),
+        ];
+
+        for component in components.iter_mut() {
+            component.process_event(ComponentAction::Focused).await;
+            let help = component.get_help_items();
+            let jump = |key: &str| help.iter().find(|(k, _)| k == key).map(|(_, d)| d.as_str());
+            // The list is focused, so only the other areas are offered
+            assert_eq!(jump("Alt+1"), None);
+            assert!(jump("Alt+2").is_some());
+            assert_eq!(jump("Alt+4"), Some("Focus results"));
+        }
+    }
+
+    #[tokio::test]
+    async fn every_component_shows_errors_in_its_banner() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut components: Vec<Box<dyn AWSComponent>> = vec![
+            Box::new(S3Component::new(sender.clone())),
+            Box::new(DynamoDB::new(sender.clone())),
+            Box::new(CloudWatch::new(sender.clone())),
+            Box::new(ParameterStore::new(sender.clone())),
+            Box::new(Ecs::new(sender)),
+        ];
+
+        let area = Rect::new(0, 0, 100, 30);
+        for component in components.iter_mut() {
+            component.set_visible(true);
+            component
+                .process_event(ComponentAction::ShowError("no credentials".to_string()))
+                .await;
+            let mut buf = Buffer::empty(area);
+            component.render(area, &mut buf);
+            let banner: String = (0..area.width)
+                .map(|x| buf[(x, 1)].symbol().to_string())
+                .collect();
+            assert!(banner.contains("no credentials"), "{:?}", banner);
+        }
+    }
+
+    #[tokio::test]
+    async fn fetches_count_as_running_until_they_finish() {
+        let mut task = None;
+        assert!(!fetch_running(&task));
+
+        let pending = tokio::spawn(std::future::pending::<()>());
+        task = Some(pending.abort_handle());
+        assert!(fetch_running(&task));
+
+        pending.abort();
+        let _ = pending.await;
+        assert!(!fetch_running(&task));
+    }
+
+    #[tokio::test]
+    async fn fetches_supersede_the_generation_and_tag_their_results() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut generation = 0;
+        spawn_fetch(&sender, ComponentType::S3, &mut generation, async {
+            vec![ComponentAction::MarkUpdated]
+        });
+        assert_ne!(generation, 0);
+        assert!(matches!(
+            receiver.recv().await,
+            Some(Event::Tab(TabEvent::ComponentActions(
+                ComponentAction::BackgroundResult(tagged, action),
+                ComponentType::S3,
+            ))) if tagged == generation && matches!(*action, ComponentAction::MarkUpdated)
+        ));
+    }
+
+    #[test]
+    fn focus_jumps_skip_the_focused_area_and_missing_inputs() {
+        let help = focus_jump_help([Some("List"), Some("Input"), None, Some("Results")], 2);
+        assert_eq!(
+            help,
+            vec![
+                ("Alt+1".to_string(), "List".to_string()),
+                ("Alt+4".to_string(), "Results".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn ages_use_the_largest_whole_unit() {
+        assert_eq!(format_age(Duration::from_millis(1500)), "just now");
+        assert_eq!(format_age(Duration::from_secs(12)), "12s ago");
+        assert_eq!(format_age(Duration::from_secs(179)), "2m ago");
+        assert_eq!(format_age(Duration::from_secs(7300)), "2h ago");
+    }
+
+    #[test]
+    fn split_steps_are_clamped() {
+        assert_eq!(step_split(30, true), 35);
+        assert_eq!(step_split(30, false), 25);
+        assert_eq!(step_split(MAX_SPLIT, true), MAX_SPLIT);
+        assert_eq!(step_split(MIN_SPLIT, false), MIN_SPLIT);
+        assert_eq!(step_split(12, false), MIN_SPLIT);
+    }
+}
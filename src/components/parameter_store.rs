@@ -0,0 +1,801 @@
+use crate::components::{
+    AWSComponent, ComponentFocus, SplitRatio, abort_fetch, focus_jump_help, next_generation,
+    fetch_running, render_error_banner, spawn_fetch,
+};
+use crate::diagnostics;
+use crate::event_managment::event::{
+    ComponentAction, ComponentType, Event, InputBoxEvent, InputBoxType, SendOrLog,
+    ServiceNavigatorEvent, TabEvent, WidgetAction, WidgetEventType, WidgetType,
+};
+use crate::services::aws::TabClients;
+use crate::services::aws::ssm_client::{Parameter, SsmClient, SsmClientError};
+use crate::widgets::WidgetExt;
+use crate::widgets::input_box::InputBoxWidget;
+use crate::widgets::popup::{PopupContent, PopupWidget};
+use crate::widgets::service_navigator::{NavigatorContent, ServiceNavigator};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+};
+use serde_json::json;
+use std::any::Any;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
+use tokio::task::AbortHandle;
+
+/// Shown in place of a value until it is revealed
+const MASK: &str = "••••";
+
+/// Lines describing a parameter in the results pane, its value masked unless `revealed`
+fn parameter_lines(parameter: &Parameter, revealed: bool, decryption_denied: bool) -> Vec<String> {
+    let value = if revealed {
+        parameter.value.clone()
+    } else if decryption_denied {
+        format!("{} (decryption denied: needs kms:Decrypt)", MASK)
+    } else {
+        MASK.to_string()
+    };
+    let mut lines = vec![
+        format!("Name: {}", parameter.name),
+        format!("Type: {}", parameter.parameter_type),
+        format!("Value: {}", value),
+    ];
+    if let Some(version) = parameter.version {
+        lines.push(format!("Version: {}", version));
+    }
+    if let Some(last_modified) = &parameter.last_modified {
+        lines.push(format!("Last modified: {}", last_modified));
+    }
+    if let Some(arn) = &parameter.arn {
+        lines.push(format!("ARN: {}", arn));
+    }
+    lines
+}
+
+/// Parameter as JSON for the details popup, its value masked unless `revealed`
+fn parameter_details(parameter: &Parameter, revealed: bool) -> String {
+    let value = if revealed {
+        parameter.value.as_str()
+    } else {
+        MASK
+    };
+    json!({
+        "Name": parameter.name,
+        "Type": parameter.parameter_type,
+        "Value": value,
+        "Version": parameter.version,
+        "LastModifiedDate": parameter.last_modified,
+        "ARN": parameter.arn,
+    })
+    .to_string()
+}
+
+/// Component for browsing SSM Parameter Store
+///
+/// Values stay masked until revealed with `v`; each reveal is recorded in the diagnostics log.
+pub struct ParameterStore {
+    /// Component type identifier
+    component_type: ComponentType,
+    /// Client for SSM API interactions
+    ssm_client: Option<Arc<Mutex<SsmClient>>>,
+    /// AWS service client
+    aws_clients: Option<TabClients>,
+    /// Left navigator listing parameter names
+    navigator: ServiceNavigator,
+    /// Input for the path the listing is limited to
+    input: InputBoxWidget,
+    /// Details of the selected parameter
+    results_navigator: ServiceNavigator,
+    /// Popup for displaying the parameter as JSON
+    details_popup: PopupWidget,
+    /// Whether the component is currently active
+    active: bool,
+    /// Whether the component is currently visible
+    visible: bool,
+    /// Channel for sending events to the application
+    event_sender: tokio::sync::mpsc::UnboundedSender<Event>,
+    /// Current focus state within the component
+    current_focus: ComponentFocus,
+    /// Error shown in a banner above the component's widgets
+    error_message: Option<String>,
+    /// Width of the left navigator relative to the right panel
+    split: SplitRatio,
+    /// Generation of the latest listing; older listings are ignored
+    generation: u64,
+    /// Generation of the latest parameter read; older reads are ignored
+    parameter_generation: u64,
+    /// When parameters were last listed successfully
+    last_updated: Option<Instant>,
+    /// Parameter shown in the results pane
+    shown: Option<Parameter>,
+    /// Whether the shown parameter's value is revealed
+    revealed: bool,
+    /// Whether decrypting the shown parameter was refused by KMS
+    decryption_denied: bool,
+    /// Latest background listing, while it may still be running
+    list_task: Option<AbortHandle>,
+    /// Latest background parameter read, while it may still be running
+    parameter_task: Option<AbortHandle>,
+}
+
+impl ParameterStore {
+    /// Creates a new Parameter Store component with the provided event sender
+    pub fn new(event_sender: tokio::sync::mpsc::UnboundedSender<Event>) -> Self {
+        let popup_content = PopupContent::Profiles(vec!["No content".to_string()]);
+
+        Self {
+            component_type: ComponentType::ParameterStore,
+            ssm_client: None,
+            aws_clients: None,
+            navigator: ServiceNavigator::new(
+                WidgetType::AWSServiceNavigator,
+                false,
+                NavigatorContent::Records(vec![]),
+            ),
+            input: {
+                let mut input =
+                    InputBoxWidget::new(InputBoxType::Text, "Path (e.g. /prod/api)", false);
+                input.enable_history("ssm.path");
+                input
+            },
+            results_navigator: ServiceNavigator::new(
+                WidgetType::QueryResultsNavigator,
+                false,
+                NavigatorContent::Records(vec![]),
+            ),
+            details_popup: PopupWidget::new(popup_content, "Details", false, false),
+            active: false,
+            visible: true,
+            event_sender,
+            current_focus: ComponentFocus::Navigation,
+            error_message: None,
+            split: SplitRatio::load("parameter_store", 40),
+            generation: 0,
+            parameter_generation: 0,
+            last_updated: None,
+            shown: None,
+            revealed: false,
+            decryption_denied: false,
+            list_task: None,
+            parameter_task: None,
+        }
+    }
+
+    /// Updates active states of all widgets based on current focus
+    fn update_widget_states(&mut self) {
+        self.navigator
+            .set_active(self.active && self.current_focus == ComponentFocus::Navigation);
+        self.input
+            .set_active(self.active && self.current_focus == ComponentFocus::Input);
+        self.results_navigator
+            .set_active(self.active && self.current_focus == ComponentFocus::Results);
+    }
+
+    /// Shifts focus to the next widget in the cyclic order
+    fn focus_next(&mut self) {
+        self.current_focus = match self.current_focus {
+            ComponentFocus::Navigation => ComponentFocus::Input,
+            ComponentFocus::Input | ComponentFocus::TimeRange => ComponentFocus::Results,
+            ComponentFocus::Results => ComponentFocus::None,
+            ComponentFocus::None => ComponentFocus::Navigation,
+        };
+        self.update_widget_states();
+    }
+
+    /// Shifts focus to the previous widget in the cyclic order
+    fn focus_previous(&mut self) {
+        self.current_focus = match self.current_focus {
+            ComponentFocus::Navigation => ComponentFocus::None,
+            ComponentFocus::Input | ComponentFocus::TimeRange => ComponentFocus::Navigation,
+            ComponentFocus::Results => ComponentFocus::Input,
+            ComponentFocus::None => ComponentFocus::Results,
+        };
+        self.update_widget_states();
+    }
+
+    /// Moves focus to a widget, e.g. for the Alt+number shortcuts
+    fn focus(&mut self, focus: ComponentFocus) {
+        self.current_focus = focus;
+        self.update_widget_states();
+    }
+
+    /// Sends an action to this component through the event loop
+    fn send(&self, action: ComponentAction) {
+        self.event_sender
+            .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                action,
+                self.component_type.clone(),
+            )));
+    }
+
+    /// Shows the parameter in the results pane, masked unless revealed
+    fn show_parameter(&mut self) {
+        if let Some(parameter) = &self.shown {
+            let lines = parameter_lines(parameter, self.revealed, self.decryption_denied);
+            self.results_navigator.set_title(parameter.name.clone());
+            self.results_navigator
+                .set_content(NavigatorContent::Records(lines));
+        }
+    }
+
+    /// Masks the shown value again, e.g. when focus leaves the component
+    fn mask(&mut self) {
+        if self.revealed {
+            self.revealed = false;
+            self.show_parameter();
+        }
+    }
+
+    /// Lists the parameters under the path, in the background
+    fn list_parameters(&mut self, path: String) {
+        let Some(client) = &self.ssm_client else {
+            return;
+        };
+        // Details viewed in the previous listing are not worth going back to
+        self.details_popup.clear_history();
+        let client = Arc::clone(client);
+        self.navigator
+            .set_title(String::from("Parameters (Loading...)"));
+        let task = spawn_fetch(
+            &self.event_sender,
+            self.component_type.clone(),
+            &mut self.generation,
+            async move {
+                let result = client
+                    .lock()
+                    .await
+                    .list_parameters(&path)
+                    .await
+                    .map_err(|err| err.to_string());
+                vec![ComponentAction::ParametersListed(result)]
+            },
+        );
+        self.list_task = Some(task);
+    }
+
+    /// Reads the shown parameter again with its value decrypted, to reveal it
+    fn decrypt_shown(&mut self) {
+        let (Some(client), Some(parameter)) = (&self.ssm_client, &self.shown) else {
+            return;
+        };
+        let client = Arc::clone(client);
+        let name = parameter.name.clone();
+        self.results_navigator
+            .set_title(format!("{} (Decrypting...)", name));
+        let task = spawn_fetch(
+            &self.event_sender,
+            self.component_type.clone(),
+            &mut self.parameter_generation,
+            async move {
+                match client.lock().await.get_parameter(&name, true).await {
+                    Ok(parameter) => vec![ComponentAction::ParameterRevealed(Ok(parameter))],
+                    // KMS refusing to decrypt is told apart from other failures
+                    Err(SsmClientError::DecryptionDenied(name)) => {
+                        vec![ComponentAction::DecryptionDenied(name)]
+                    }
+                    Err(err) => vec![ComponentAction::ParameterRevealed(Err(err.to_string()))],
+                }
+            },
+        );
+        self.parameter_task = Some(task);
+    }
+
+    /// Reveals or masks the shown value
+    ///
+    /// SecureString values read without decryption are read again decrypted first.
+    fn toggle_reveal(&mut self) {
+        let Some(parameter) = &self.shown else {
+            return;
+        };
+        if self.revealed {
+            self.mask();
+        } else if parameter.is_secure() && !parameter.decrypted {
+            self.decrypt_shown();
+        } else {
+            diagnostics::info(format!(
+                "Parameter Store: revealed the value of {}",
+                parameter.name
+            ));
+            self.revealed = true;
+            self.show_parameter();
+        }
+    }
+
+    /// Returns contextual help items based on current component state
+    fn get_base_help_items(&self) -> Vec<(String, String)> {
+        if self.details_popup.is_visible() {
+            return self.details_popup.get_help_items();
+        }
+
+        let mut items = vec![];
+        let reveal = if self.revealed {
+            "Mask value"
+        } else {
+            "Reveal value"
+        };
+        match self.current_focus {
+            ComponentFocus::Navigation => {
+                items.push(("Enter".to_string(), "Show parameter".to_string()));
+                items.push(("v".to_string(), reveal.to_string()));
+            }
+            ComponentFocus::Input => {
+                items.push((
+                    "Enter".to_string(),
+                    "List parameters under path".to_string(),
+                ));
+            }
+            ComponentFocus::Results => {
+                items.push(("Enter".to_string(), "View details".to_string()));
+                items.push(("v".to_string(), reveal.to_string()));
+            }
+            _ => {}
+        }
+        let focused = match self.current_focus {
+            ComponentFocus::Navigation => 1,
+            ComponentFocus::Input => 2,
+            _ => 4,
+        };
+        items.extend(focus_jump_help(
+            [
+                Some("Focus parameters"),
+                Some("Focus path"),
+                None,
+                Some("Focus results"),
+            ],
+            focused,
+        ));
+        items
+    }
+}
+
+#[async_trait::async_trait]
+impl AWSComponent for ParameterStore {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if !self.visible {
+            return;
+        }
+
+        // Reserve space for the error banner if a request failed
+        let area = match &self.error_message {
+            Some(message) => render_error_banner(message, area, buf),
+            None => area,
+        };
+
+        // Parameter names on the left, the path input and the selected parameter on the right
+        let horizontal_split = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(self.split.constraints())
+            .split(area);
+        let right_vertical_split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Path input
+                Constraint::Min(1),    // Parameter details
+            ])
+            .split(horizontal_split[1]);
+
+        let list_prompt = self
+            .ssm_client
+            .is_none()
+            .then_some("Select a profile to list its parameters");
+        self.navigator
+            .render_with_prompt(horizontal_split[0], buf, list_prompt);
+        self.input.render(right_vertical_split[0], buf);
+        let parameter_prompt = self
+            .shown
+            .is_none()
+            .then_some("Pick a parameter to see it; press v to reveal its value");
+        self.results_navigator
+            .render_with_prompt(right_vertical_split[1], buf, parameter_prompt);
+
+        if self.details_popup.is_visible() {
+            self.details_popup.render(area, buf);
+        }
+    }
+
+    /// Sets focus to the last active widget in the component
+    fn set_focus_to_last(&mut self) {
+        self.focus(ComponentFocus::Results);
+    }
+
+    fn current_focus(&self) -> ComponentFocus {
+        self.current_focus
+    }
+
+    /// Handles keyboard input events
+    fn handle_input(&mut self, key_event: KeyEvent) {
+        if self.details_popup.is_visible() {
+            if let Some(signal) = self.details_popup.handle_input(key_event) {
+                self.send(ComponentAction::WidgetAction(signal));
+            }
+            return;
+        }
+
+        match key_event.code {
+            KeyCode::Tab => self.send(ComponentAction::NextFocus),
+            KeyCode::BackTab => self.send(ComponentAction::PreviousFocus),
+            // Alt+number shortcuts to switch focus between areas
+            KeyCode::Char('1') if key_event.modifiers == KeyModifiers::ALT => {
+                self.focus(ComponentFocus::Navigation);
+            }
+            KeyCode::Char('2') if key_event.modifiers == KeyModifiers::ALT => {
+                self.focus(ComponentFocus::Input);
+            }
+            KeyCode::Char('4') if key_event.modifiers == KeyModifiers::ALT => {
+                self.focus(ComponentFocus::Results);
+            }
+            // Reveal the shown value, or mask it again
+            KeyCode::Char('v')
+                if key_event.modifiers.is_empty()
+                    && match self.current_focus {
+                        ComponentFocus::Navigation => !self.navigator.is_capturing_input(),
+                        ComponentFocus::Results => !self.results_navigator.is_capturing_input(),
+                        _ => false,
+                    } =>
+            {
+                self.send(ComponentAction::ToggleReveal);
+            }
+            // Ctrl+Left/Right resize the panes; the input keeps them for word movement
+            KeyCode::Left | KeyCode::Right
+                if key_event.modifiers == KeyModifiers::CONTROL
+                    && matches!(
+                        self.current_focus,
+                        ComponentFocus::Navigation | ComponentFocus::Results
+                    ) =>
+            {
+                self.split.nudge(key_event.code == KeyCode::Right);
+            }
+            KeyCode::Esc if self.current_focus != ComponentFocus::Navigation => {
+                self.focus(ComponentFocus::Navigation);
+            }
+            _ => {
+                if let Some(signal) = match self.current_focus {
+                    ComponentFocus::Navigation => self.navigator.handle_input(key_event),
+                    ComponentFocus::Input => self.input.handle_input(key_event),
+                    ComponentFocus::Results => self.results_navigator.handle_input(key_event),
+                    _ => None,
+                } {
+                    self.send(ComponentAction::WidgetAction(signal));
+                }
+            }
+        }
+    }
+
+    /// Processes component-specific actions
+    async fn process_event(&mut self, event: ComponentAction) {
+        match event {
+            ComponentAction::Active(aws_profile, region) => {
+                // Reads for the previous profile or region must not land in the new view
+                abort_fetch(&mut self.list_task);
+                abort_fetch(&mut self.parameter_task);
+                self.generation = next_generation();
+                self.parameter_generation = next_generation();
+                self.input.load_history(&aws_profile);
+                self.last_updated = None;
+                self.shown = None;
+                self.revealed = false;
+                self.results_navigator
+                    .set_content(NavigatorContent::Records(vec![]));
+                self.aws_clients = Some(TabClients::new(aws_profile, region));
+
+                if let Some(clients) = &mut self.aws_clients {
+                    match clients.get_ssm_client().await {
+                        Ok(client) => {
+                            self.ssm_client = Some(client);
+                            self.error_message = None;
+                            self.update().await.ok();
+                        }
+                        Err(err) => {
+                            let message = format!(
+                                "Failed to initialize SSM client (profile: {}, region: {}): {}",
+                                clients.profile(),
+                                clients.region(),
+                                err
+                            );
+                            // The error banner shows it instead of clobbering the navigators
+                            self.send(ComponentAction::ShowError(message));
+                        }
+                    }
+                }
+            }
+            ComponentAction::ShowError(message) => {
+                diagnostics::error(format!("Parameter Store: {}", message));
+                self.error_message = Some(message);
+            }
+            ComponentAction::Reset => {
+                self.navigator.reset_view();
+                self.results_navigator.reset_view();
+                self.results_navigator
+                    .set_content(NavigatorContent::Records(vec![]));
+                self.results_navigator.set_title(String::from("Services"));
+                self.input.set_content("");
+                self.shown = None;
+                self.revealed = false;
+                self.decryption_denied = false;
+                self.error_message = None;
+                // Drop reads that are still running
+                self.parameter_generation = next_generation();
+                self.list_parameters(String::new());
+                self.reset_focus();
+            }
+            // Apply background results only if no newer request has started since
+            ComponentAction::BackgroundResult(generation, action)
+                if generation == self.generation || generation == self.parameter_generation =>
+            {
+                self.process_event(*action).await;
+            }
+            ComponentAction::Focused => {
+                self.set_active(true);
+            }
+            ComponentAction::Unfocused => {
+                // Nothing stays revealed once the user looks elsewhere
+                self.mask();
+                self.reset_focus();
+                self.set_active(false);
+            }
+            ComponentAction::NextFocus => self.focus_next(),
+            ComponentAction::PreviousFocus => self.focus_previous(),
+            ComponentAction::ListParameters(path) => self.list_parameters(path),
+            ComponentAction::ParametersListed(result) => {
+                self.navigator.set_title(String::from("Parameters"));
+                match result {
+                    Ok(names) if names.is_empty() => {
+                        self.last_updated = Some(Instant::now());
+                        self.error_message = None;
+                        self.navigator.show_placeholder("No parameters found");
+                    }
+                    Ok(names) => {
+                        self.last_updated = Some(Instant::now());
+                        self.error_message = None;
+                        self.navigator.set_content(NavigatorContent::Records(names));
+                    }
+                    Err(err) => {
+                        let message = format!("Listing parameters failed: {}", err);
+                        diagnostics::error(format!("Parameter Store: {}", message));
+                        self.error_message = Some(message);
+                        self.navigator
+                            .show_placeholder("Parameters could not be listed");
+                    }
+                }
+            }
+            // Read the parameter without decrypting it, so nothing secret is fetched yet
+            ComponentAction::SelectParameter(name) => {
+                let Some(client) = &self.ssm_client else {
+                    return;
+                };
+                let client = Arc::clone(client);
+                self.revealed = false;
+                self.decryption_denied = false;
+                self.results_navigator
+                    .set_title(format!("{} (Loading...)", name));
+                let task = spawn_fetch(
+                    &self.event_sender,
+                    self.component_type.clone(),
+                    &mut self.parameter_generation,
+                    async move {
+                        let result = client
+                            .lock()
+                            .await
+                            .get_parameter(&name, false)
+                            .await
+                            .map_err(|err| err.to_string());
+                        vec![ComponentAction::ParameterLoaded(result)]
+                    },
+                );
+                self.parameter_task = Some(task);
+                self.focus(ComponentFocus::Results);
+            }
+            ComponentAction::ParameterLoaded(result) => match result {
+                Ok(parameter) => {
+                    self.error_message = None;
+                    self.shown = Some(parameter);
+                    self.show_parameter();
+                }
+                Err(err) => {
+                    let message = format!("Reading the parameter failed: {}", err);
+                    diagnostics::error(format!("Parameter Store: {}", message));
+                    self.error_message = Some(message);
+                    self.results_navigator.set_title(String::from("Parameter"));
+                }
+            },
+            ComponentAction::ToggleReveal => self.toggle_reveal(),
+            ComponentAction::ParameterRevealed(result) => match result {
+                Ok(parameter) => {
+                    diagnostics::info(format!(
+                        "Parameter Store: revealed the value of {}",
+                        parameter.name
+                    ));
+                    self.error_message = None;
+                    self.revealed = true;
+                    self.shown = Some(parameter);
+                    self.show_parameter();
+                }
+                Err(err) => {
+                    let message = format!("Decrypting the parameter failed: {}", err);
+                    diagnostics::error(format!("Parameter Store: {}", message));
+                    self.error_message = Some(message);
+                    self.show_parameter();
+                }
+            },
+            // Missing KMS permissions are common and expected, so they get a note, not a banner
+            ComponentAction::DecryptionDenied(name) => {
+                diagnostics::warn(format!(
+                    "Parameter Store: kms:Decrypt was denied for {}",
+                    name
+                ));
+                self.decryption_denied = true;
+                self.show_parameter();
+                self.send(ComponentAction::WidgetAction(WidgetAction::Notify(
+                    format!(
+                        "Cannot decrypt {}: the profile needs kms:Decrypt on its key",
+                        name
+                    ),
+                )));
+            }
+            ComponentAction::PopupDetails(_) => {
+                if let Some(parameter) = &self.shown {
+                    self.details_popup
+                        .set_content(PopupContent::Details(parameter_details(
+                            parameter,
+                            self.revealed,
+                        )));
+                    self.details_popup.set_visible(true);
+                    self.details_popup.set_active(true);
+                }
+            }
+            ComponentAction::WidgetAction(widget_action) => match widget_action {
+                WidgetAction::ServiceNavigatorEvent(_, WidgetType::AWSServiceNavigator) => {
+                    if let Some(WidgetAction::ServiceNavigatorEvent(
+                        ServiceNavigatorEvent::ItemSelected(WidgetEventType::RecordSelected(name)),
+                        _,
+                    )) = self.navigator.process_event(widget_action)
+                    {
+                        self.send(ComponentAction::SelectParameter(name));
+                    }
+                }
+                WidgetAction::ServiceNavigatorEvent(_, WidgetType::QueryResultsNavigator) => {
+                    if let Some(WidgetAction::ServiceNavigatorEvent(
+                        ServiceNavigatorEvent::ItemSelected(WidgetEventType::RecordSelected(line)),
+                        _,
+                    )) = self.results_navigator.process_event(widget_action)
+                    {
+                        self.send(ComponentAction::PopupDetails(line));
+                    }
+                }
+                WidgetAction::InputBoxEvent(_, InputBoxType::Text) => {
+                    if let Some(WidgetAction::InputBoxEvent(InputBoxEvent::Written(path), _)) =
+                        self.input.process_event(widget_action)
+                    {
+                        self.send(ComponentAction::ListParameters(path));
+                        self.focus(ComponentFocus::Navigation);
+                    }
+                }
+                WidgetAction::PopupAction(_) => {
+                    self.details_popup.process_event(widget_action);
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Sets the active state of this component
+    fn set_active(&mut self, active: bool) {
+        self.active = active;
+        self.update_widget_states();
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Lists the parameters under the path in the input again
+    async fn update(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = self.input.get_content().unwrap_or_default();
+        self.list_parameters(path);
+        Ok(())
+    }
+
+    /// Resets focus to the navigation pane
+    fn reset_focus(&mut self) {
+        self.focus(ComponentFocus::Navigation);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_help_items(&self) -> Vec<(String, String)> {
+        self.get_base_help_items()
+    }
+
+    fn last_updated(&self) -> Option<Instant> {
+        self.last_updated
+    }
+
+    fn is_busy(&self) -> bool {
+        fetch_running(&self.list_task) || fetch_running(&self.parameter_task)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameter(parameter_type: &str, decrypted: bool) -> Parameter {
+        Parameter {
+            name: "/prod/db/password".to_string(),
+            parameter_type: parameter_type.to_string(),
+            value: "hunter2".to_string(),
+            version: Some(3),
+            last_modified: None,
+            arn: None,
+            decrypted,
+        }
+    }
+
+    #[test]
+    fn values_are_masked_until_revealed() {
+        let secret = parameter("SecureString", true);
+        let masked = parameter_lines(&secret, false, false);
+        assert_eq!(masked[2], "Value: ••••");
+        assert!(!masked.iter().any(|line| line.contains("hunter2")));
+        assert!(!parameter_details(&secret, false).contains("hunter2"));
+
+        assert_eq!(parameter_lines(&secret, true, false)[2], "Value: hunter2");
+        assert!(parameter_details(&secret, true).contains("hunter2"));
+        assert!(parameter_lines(&secret, false, true)[2].contains("kms:Decrypt"));
+    }
+
+    #[tokio::test]
+    async fn reveals_toggle_and_end_when_focus_leaves() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = ParameterStore::new(sender);
+        component.shown = Some(parameter("String", false));
+
+        component.process_event(ComponentAction::ToggleReveal).await;
+        assert!(component.revealed);
+        assert_eq!(
+            component.results_navigator.selected_label(),
+            Some("Name: /prod/db/password".to_string())
+        );
+        component.process_event(ComponentAction::ToggleReveal).await;
+        assert!(!component.revealed);
+
+        component.process_event(ComponentAction::ToggleReveal).await;
+        component.process_event(ComponentAction::Unfocused).await;
+        assert!(!component.revealed);
+    }
+
+    #[tokio::test]
+    async fn denied_decryption_keeps_the_value_masked() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = ParameterStore::new(sender);
+        component.shown = Some(parameter("SecureString", false));
+
+        component
+            .process_event(ComponentAction::DecryptionDenied(
+                "/prod/db/password".to_string(),
+            ))
+            .await;
+        assert!(!component.revealed);
+        assert_eq!(component.error_message, None);
+        assert!(matches!(
+            receiver.recv().await,
+            Some(Event::Tab(TabEvent::ComponentActions(
+                ComponentAction::WidgetAction(WidgetAction::Notify(message)),
+                ComponentType::ParameterStore,
+            ))) if message.contains("kms:Decrypt")
+        ));
+    }
+}
@@ -1,22 +1,42 @@
-use crate::components::{AWSComponent};
+use crate::components::{
+    AWSComponent, ComponentFocus, SplitRatio, copy_cli_command, copy_console_link,
+    fetch_running, focus_jump_help, next_generation, render_error_banner, spawn_fetch,
+    spawn_task,
+};
+use crate::diagnostics;
 use crate::event_managment::event::{
-    ComponentAction, ComponentType, Event, InputBoxEvent, ServiceNavigatorEvent, TabEvent,
-    WidgetAction, WidgetEventType, WidgetType, InputBoxType,
+    ComponentAction, ComponentType, Event, InputBoxEvent, PopupAction, ServiceNavigatorEvent,
+    SendOrLog, TabAction, TabEvent, WidgetAction, WidgetEventType, WidgetType, InputBoxType,
 };
 use crate::services::aws::TabClients;
-use crate::services::aws::s3_client::S3Client;
+use crate::services::aws::cli_command::CliCommand;
+use crate::services::aws::console_link::ConsoleResource;
+use crate::services::aws::s3_client::{
+    DeleteOutcome, MAX_PRESIGN_EXPIRY, ObjectPage, S3Client, S3ClientError, is_archived_class,
+    parse_expiry,
+};
+use crate::services::read_config;
+use crate::theme::theme;
 use crate::widgets::WidgetExt;
+use crate::widgets::clipboard;
 use crate::widgets::popup::{PopupContent, PopupWidget};
+use crate::widgets::record_sort::SortKey;
 use crate::widgets::service_navigator::{NavigatorContent, ServiceNavigator};
 use crate::widgets::input_box::InputBoxWidget;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
 };
 use std::any::Any;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::Mutex;
+use tokio::task::AbortHandle;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum S3Focus {
@@ -31,6 +51,19 @@ pub enum S3Focus {
 }
 
 
+/// Number of keys listed in the delete confirmation before the rest are summarized
+const MAX_CONFIRM_KEYS: usize = 10;
+
+/// Table view columns of a folder listing, the fields that matter when browsing
+const OBJECT_COLUMNS: [&str; 4] = ["key", "size", "last_modified", "storage_class"];
+/// Title of the query input, which searches by key prefix in S3 rather than filtering
+const PREFIX_INPUT_TITLE: &str = "Key prefix (searched in S3)";
+/// How long presigned URLs stay valid unless another expiry is typed
+const DEFAULT_PRESIGN_EXPIRY: &str = "15m";
+/// Table view columns of an object's versions
+const VERSION_COLUMNS: [&str; 5] =
+    ["version_id", "last_modified", "size", "latest", "delete_marker"];
+
 /// Component for interacting with AWS S3 storage
 pub struct S3Component {
     /// Component type identifier
@@ -59,6 +92,46 @@ pub struct S3Component {
     selected_bucket: Option<String>,
     /// AWS service client
     aws_clients: Option<TabClients>,
+    /// Error shown in a banner above the component's widgets
+    error_message: Option<String>,
+    /// Width of the left navigator relative to the right panel
+    split: SplitRatio,
+    /// Object keys awaiting delete confirmation
+    pending_delete: Option<Vec<String>>,
+    /// Continuation token of the next page of the current listing
+    next_token: Option<String>,
+    /// Number of objects listed so far in the current listing
+    object_count: usize,
+    /// Title of the current listing, before the object count
+    listing_title: String,
+    /// Objects requested per page, S3's default of 1000 if unset
+    page_size: Option<i32>,
+    /// Key of the object whose versions are listed instead of the folder
+    versions_of: Option<String>,
+    /// Key prefix searched in S3, listed instead of the folder
+    search_prefix: Option<String>,
+    /// When buckets, objects or versions were last listed successfully
+    last_updated: Option<Instant>,
+    /// Generation of the latest background listing; older results are ignored
+    generation: u64,
+    /// Input asking for the name of a new bucket, shown instead of the prefix input
+    bucket_name_input: InputBoxWidget,
+    /// Whether the name of a new bucket is being typed
+    creating_bucket: bool,
+    /// Bucket awaiting delete confirmation
+    pending_bucket_delete: Option<String>,
+    /// Whether read-only mode disables creating and deleting buckets and objects
+    read_only: bool,
+    /// Whether power-user mode deletes without asking for confirmation first
+    power_user: bool,
+    /// Generation of the latest prefix completion, which does not supersede listings
+    completion_generation: u64,
+    /// Latest background listing, while it may still be running
+    fetch_task: Option<AbortHandle>,
+    /// Input asking how long a presigned URL stays valid, shown instead of the prefix input
+    expiry_input: InputBoxWidget,
+    /// Key of the object a presigned URL is being asked for
+    presign_key: Option<String>,
 }
 
 impl S3Component {
@@ -66,19 +139,41 @@ impl S3Component {
     pub fn new(event_sender: tokio::sync::mpsc::UnboundedSender<Event>) -> Self {
         let popup_content = PopupContent::Profiles(vec!["No content".to_string()]);
 
+        // Objects can be marked with Space for bulk download/delete
+        let mut results_navigator = ServiceNavigator::new(
+            crate::event_managment::event::WidgetType::QueryResultsNavigator,
+            false,
+            NavigatorContent::Records(vec![]),
+        );
+        results_navigator.set_multi_select(true);
+        // `t` shows the listing as a table of the fields that matter when browsing
+        results_navigator.enable_table_view(Some(OBJECT_COLUMNS.map(String::from).to_vec()));
+        results_navigator.enable_pretty_json();
+        // `s` orders the fetched objects, or versions, by these fields
+        results_navigator.enable_sorting(Some(vec![
+            SortKey::attribute("key"),
+            SortKey::attribute("size"),
+            SortKey::new("modified", &["last_modified"]),
+        ]));
+        // Archived objects need a restore before they can be downloaded
+        results_navigator.set_record_highlight(Some(Self::is_archived));
+
         Self {
-            component_type: ComponentType::DynamoDB,
+            component_type: ComponentType::S3,
             navigator: ServiceNavigator::new(
                 crate::event_managment::event::WidgetType::AWSServiceNavigator,
                 false,
                 NavigatorContent::Records(vec![]),
             ),
-            input: InputBoxWidget::new(InputBoxType::Text, "Query Input", false),
-            results_navigator: ServiceNavigator::new(
-                crate::event_managment::event::WidgetType::QueryResultsNavigator,
-                false,
-                NavigatorContent::Records(vec![]),
-            ),
+            input: {
+                let mut input =
+                    InputBoxWidget::new(InputBoxType::Text, PREFIX_INPUT_TITLE, false);
+                // Prefixes are recalled with Up/Down, per profile, and completed with Tab
+                input.enable_history("s3.prefix");
+                input.enable_tab_completion();
+                input
+            },
+            results_navigator,
             details_popup: PopupWidget::new(popup_content, "Details", false, false),
             active: false,
             visible: true,
@@ -88,6 +183,26 @@ impl S3Component {
             current_path: String::new(),
             selected_bucket: None,
             aws_clients: None,
+            error_message: None,
+            split: SplitRatio::load("s3", 30),
+            pending_delete: None,
+            next_token: None,
+            object_count: 0,
+            listing_title: String::from("Objects"),
+            page_size: read_config::get_s3_page_size(),
+            versions_of: None,
+            search_prefix: None,
+            last_updated: None,
+            generation: 0,
+            bucket_name_input: InputBoxWidget::new(InputBoxType::BucketName, "New bucket name", false),
+            creating_bucket: false,
+            pending_bucket_delete: None,
+            read_only: read_config::get_read_only(),
+            power_user: read_config::get_power_user(),
+            completion_generation: 0,
+            fetch_task: None,
+            expiry_input: InputBoxWidget::new(InputBoxType::PresignExpiry, "URL valid for", false),
+            presign_key: None,
         }
     }
 
@@ -97,6 +212,10 @@ impl S3Component {
             .set_active(self.active & (self.current_focus == S3Focus::Navigation));
         self.input
             .set_active(self.active & (self.current_focus == S3Focus::Input));
+        self.bucket_name_input
+            .set_active(self.active & (self.current_focus == S3Focus::Input));
+        self.expiry_input
+            .set_active(self.active & (self.current_focus == S3Focus::Input));
         self.results_navigator
             .set_active(self.active & (self.current_focus == S3Focus::Results));
     }
@@ -104,10 +223,10 @@ impl S3Component {
     /// Shifts focus to the previous widget in the cyclic order
     fn focus_previous(&mut self) -> S3Focus {
         self.current_focus = match self.current_focus {
-            S3Focus::Navigation => S3Focus::Input,
-            S3Focus::Input => S3Focus::Results,
-            S3Focus::Results => S3Focus::None,
-            S3Focus::None => S3Focus::Navigation,
+            S3Focus::Navigation => S3Focus::None,
+            S3Focus::Input => S3Focus::Navigation,
+            S3Focus::Results => S3Focus::Input,
+            S3Focus::None => S3Focus::Results,
         };
         self.current_focus
     }
@@ -115,9 +234,9 @@ impl S3Component {
     /// Shifts focus to the next widget in the cyclic order
     fn focus_next(&mut self) -> S3Focus {
         self.current_focus = match self.current_focus {
-            S3Focus::Navigation => S3Focus::Results,
-            S3Focus::Results => S3Focus::Input,
-            S3Focus::Input => S3Focus::None,
+            S3Focus::Navigation => S3Focus::Input,
+            S3Focus::Input => S3Focus::Results,
+            S3Focus::Results => S3Focus::None,
             S3Focus::None => S3Focus::Navigation,
         };
         self.current_focus
@@ -130,18 +249,534 @@ impl S3Component {
         self.navigator
             .set_title(format!("Bucket: {}", bucket_name));
 
-        if let Some(client) = &self.s3_client {
-            let objects = client
-                .lock()
-                .await
-                .list_objects(&bucket_name, "")
-                .await
-                .unwrap_or_else(|_| vec!["Error listing objects".to_string()]);
+        self.load_objects(&bucket_name, "", String::from("Objects"), None);
+    }
 
+    /// Lists the first page of objects under the path in the background, replacing the
+    /// results once it arrives
+    ///
+    /// `search_prefix` is the searched prefix when the path comes from a prefix search.
+    fn load_objects(
+        &mut self,
+        bucket: &str,
+        path: &str,
+        title: String,
+        search_prefix: Option<String>,
+    ) {
+        let Some(client) = &self.s3_client else {
+            return;
+        };
+        // Details viewed in the previous listing are not worth going back to
+        self.details_popup.clear_history();
+        self.results_navigator
+            .set_title(format!("{} (Loading...)", title));
+        // Pages of the listing being replaced must not be appended to the new one, and
+        // completions of the previous folder must not replace it
+        self.next_token = None;
+        self.completion_generation = next_generation();
+
+        let client = Arc::clone(client);
+        let bucket = bucket.to_string();
+        let path = path.to_string();
+        let page_size = self.page_size;
+        let task = spawn_fetch(
+            &self.event_sender,
+            self.component_type.clone(),
+            &mut self.generation,
+            async move {
+                let page = client
+                    .lock()
+                    .await
+                    .list_objects(&bucket, &path, None, page_size)
+                    .await
+                    .map_err(|err| {
+                        diagnostics::error(format!("Listing s3://{}/{}: {}", bucket, path, err));
+                        err.to_string()
+                    });
+                vec![ComponentAction::ObjectsListed(title, search_prefix, page)]
+            },
+        );
+        self.fetch_task = Some(task);
+    }
+
+    /// Shows the first page of a listing, replacing the results
+    fn apply_object_listing(
+        &mut self,
+        title: String,
+        search_prefix: Option<String>,
+        page: Result<ObjectPage, String>,
+    ) {
+        self.leave_versions();
+        self.search_prefix = search_prefix;
+        self.listing_title = title;
+        if page.is_ok() {
+            self.last_updated = Some(Instant::now());
+        }
+        match page {
+            Ok(page) if page.records.is_empty() => {
+                self.next_token = None;
+                self.object_count = 0;
+                self.results_navigator.show_placeholder("No objects found");
+            }
+            Ok(page) => {
+                self.next_token = page.next_token;
+                self.object_count = page.records.len();
+                self.results_navigator
+                    .set_content(NavigatorContent::Records(page.records));
+            }
+            Err(err) => {
+                self.next_token = None;
+                self.object_count = 0;
+                self.results_navigator
+                    .show_placeholder(format!("Error listing objects: {}", err));
+            }
+        }
+        self.update_listing_title();
+    }
+
+    /// Appends the next page of the current listing, if there is one
+    async fn load_more_objects(&mut self) {
+        let (Some(client), Some(bucket), Some(token)) = (
+            self.s3_client.clone(),
+            self.selected_bucket.clone(),
+            self.next_token.clone(),
+        ) else {
+            return;
+        };
+
+        let page = client
+            .lock()
+            .await
+            .list_objects(&bucket, self.listing_prefix(), Some(token), self.page_size)
+            .await;
+        match page {
+            Ok(page) => {
+                self.last_updated = Some(Instant::now());
+                self.next_token = page.next_token;
+                self.object_count += page.records.len();
+                self.results_navigator.append_records(page.records);
+                self.update_listing_title();
+            }
+            // Keep the token so loading more can be retried
+            Err(err) => {
+                self.error_message = Some(format!("Failed to load more objects: {}", err));
+            }
+        }
+    }
+
+    /// Prefix of the current listing: the searched prefix, or else the open folder
+    fn listing_prefix(&self) -> &str {
+        self.search_prefix.as_deref().unwrap_or(&self.current_path)
+    }
+
+    /// Lists the objects whose keys start with the query in the open folder
+    ///
+    /// S3 searches the whole bucket by prefix, unlike the results filter (`/`), which
+    /// only narrows the objects already loaded. An empty query lists the folder again.
+    async fn search_prefix(&mut self, query: String) {
+        let Some(bucket) = self.selected_bucket.clone() else {
+            return;
+        };
+        if query.is_empty() {
+            self.event_sender
+                .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                    ComponentAction::LoadPath(bucket, self.current_path.clone()),
+                    self.component_type.clone(),
+                )));
+            return;
+        }
+
+        let prefix = self.folder_prefix(&query);
+        let title = format!("Matching prefix: {}", prefix);
+        self.load_objects(&bucket, &prefix, title, Some(prefix.clone()));
+    }
+
+    /// Full key prefix of text typed relative to the open folder
+    fn folder_prefix(&self, query: &str) -> String {
+        if self.current_path.is_empty() {
+            query.to_string()
+        } else {
+            format!("{}/{}", self.current_path.trim_end_matches('/'), query)
+        }
+    }
+
+    /// Looks up the folders and keys the typed prefix may complete to in the background
+    fn complete_prefix(&mut self, typed: String) {
+        let (Some(client), Some(bucket)) = (self.s3_client.clone(), self.selected_bucket.clone())
+        else {
+            return;
+        };
+        let prefix = self.folder_prefix(&typed);
+        spawn_fetch(
+            &self.event_sender,
+            self.component_type.clone(),
+            &mut self.completion_generation,
+            async move {
+                let candidates = client
+                    .lock()
+                    .await
+                    .list_key_segments(&bucket, &prefix)
+                    .await
+                    .map_err(|err| err.to_string());
+                vec![ComponentAction::PrefixCompletions(typed, candidates)]
+            },
+        );
+    }
+
+    /// Completes the typed prefix as far as all candidates agree, listing the candidates
+    /// in the results when there is more than one
+    fn apply_completions(&mut self, typed: String, candidates: Result<Vec<String>, String>) {
+        // Text typed since the request is not what the candidates complete
+        if self.input.get_content().unwrap_or_default() != typed {
+            return;
+        }
+        let prefix = self.folder_prefix(&typed);
+        let candidates = match candidates {
+            Ok(candidates) if candidates.is_empty() => {
+                self.notify(format!("No keys start with '{}'", prefix));
+                return;
+            }
+            Ok(candidates) => candidates,
+            Err(err) => {
+                self.notify(format!("Could not complete '{}': {}", prefix, err));
+                return;
+            }
+        };
+
+        let common = common_prefix(&candidates);
+        if common.len() > prefix.len() {
+            let folder_len = prefix.len() - typed.len();
+            self.input.set_content(&common[folder_len..]);
+        }
+        if candidates.len() > 1 {
+            let records = candidates
+                .iter()
+                .map(|key| serde_json::json!({ "key": key }).to_string())
+                .collect();
+            self.apply_object_listing(
+                format!("Completions of {}", prefix),
+                Some(prefix),
+                Ok(ObjectPage {
+                    records,
+                    next_token: None,
+                }),
+            );
+        }
+    }
+
+    /// Shows how many objects are listed and whether more are available
+    fn update_listing_title(&mut self) {
+        let count = if self.next_token.is_some() {
+            format!("showing {}, more available", self.object_count)
+        } else {
+            format!("{} objects", self.object_count)
+        };
+        self.results_navigator
+            .set_title(format!("{} ({})", self.listing_title, count));
+    }
+
+    /// Lists the versions of the selected object, or returns to the folder listing
+    /// when they are already shown
+    async fn toggle_versions(&mut self) {
+        let (Some(client), Some(bucket)) = (self.s3_client.clone(), self.selected_bucket.clone())
+        else {
+            return;
+        };
+        if self.versions_of.is_some() {
+            let path = self.current_path.clone();
+            let title = format!("Path: {}", if path.is_empty() { "/" } else { &path });
+            self.load_objects(&bucket, &path, title, None);
+            return;
+        }
+
+        let Some(key) = self
+            .results_navigator
+            .selected_label()
+            .map(|record| Self::object_key(&record))
+            .filter(|key| !key.ends_with('/'))
+        else {
+            self.notify(String::from("Select an object to list its versions"));
+            return;
+        };
+
+        let client = client.lock().await;
+        match client.is_versioned(&bucket).await {
+            Ok(true) => {}
+            Ok(false) => {
+                self.notify(format!("Versioning is disabled for bucket '{}'", bucket));
+                return;
+            }
+            Err(err) => {
+                self.notify(format!("Could not check versioning of '{}': {}", bucket, err));
+                return;
+            }
+        }
+        let versions = client.list_object_versions(&bucket, &key).await;
+        drop(client);
+
+        // Listing by prefix also returns longer keys starting with this one
+        self.next_token = None;
+        self.versions_of = Some(key.clone());
+        self.results_navigator
+            .enable_table_view(Some(VERSION_COLUMNS.map(String::from).to_vec()));
+        match versions {
+            Ok(versions) => {
+                self.last_updated = Some(Instant::now());
+                let versions: Vec<String> = versions
+                    .into_iter()
+                    .filter(|record| Self::object_key(record) == key)
+                    .collect();
+                self.results_navigator
+                    .set_title(format!("Versions: {} ({})", key, versions.len()));
+                if versions.is_empty() {
+                    self.results_navigator.show_placeholder("No versions found");
+                } else {
+                    self.results_navigator
+                        .set_content(NavigatorContent::Records(versions));
+                }
+            }
+            Err(err) => {
+                diagnostics::error(format!("Listing versions of s3://{}/{}: {}", bucket, key, err));
+                self.results_navigator
+                    .set_title(format!("Versions: {}", key));
+                self.results_navigator
+                    .show_placeholder(format!("Error listing versions: {}", err));
+            }
+        }
+    }
+
+    /// Stops showing an object's versions, restoring the listing's table columns
+    fn leave_versions(&mut self) {
+        if self.versions_of.take().is_some() {
             self.results_navigator
-                .set_title(String::from("Objects"));
-            self.results_navigator
-                .set_content(NavigatorContent::Records(objects));
+                .enable_table_view(Some(OBJECT_COLUMNS.map(String::from).to_vec()));
+        }
+    }
+
+    /// Shows a message in the tab's status line
+    fn notify(&self, message: String) {
+        self.event_sender
+            .send_or_log(Event::Tab(TabEvent::TabAction(TabAction::Notify(message))));
+    }
+
+    /// Tells the user an action is disabled in read-only mode; returns whether it is
+    fn refuse_in_read_only(&self, action: &str) -> bool {
+        if self.read_only {
+            self.notify(format!("Read-only mode: {} is disabled", action));
+        }
+        self.read_only
+    }
+
+    /// Asks for the name of a new bucket in the input, in place of the prefix search
+    fn start_bucket_creation(&mut self) {
+        if self.refuse_in_read_only("creating buckets") || self.s3_client.is_none() {
+            return;
+        }
+        let region = self
+            .aws_clients
+            .as_ref()
+            .map(|clients| clients.region().to_string())
+            .unwrap_or_default();
+        self.bucket_name_input.set_content("");
+        self.bucket_name_input.set_title(format!(
+            "New bucket name in {} (Enter creates, Esc cancels)",
+            region
+        ));
+        self.creating_bucket = true;
+        self.current_focus = S3Focus::Input;
+        self.update_widget_states();
+    }
+
+    /// Stops asking for a bucket name, showing the prefix input again
+    fn stop_bucket_creation(&mut self) {
+        self.creating_bucket = false;
+        self.current_focus = S3Focus::Navigation;
+        self.update_widget_states();
+    }
+
+    /// Creates a bucket in the tab's region and lists the buckets again
+    async fn create_bucket(&mut self, name: String) {
+        let name = name.trim().to_string();
+        if name.is_empty() || self.refuse_in_read_only("creating buckets") {
+            return;
+        }
+        let (Some(client), Some(clients)) = (self.s3_client.clone(), &self.aws_clients) else {
+            return;
+        };
+        let region = clients.region().to_string();
+
+        let created = client.lock().await.create_bucket(&name, &region).await;
+        match created {
+            Ok(()) => {
+                diagnostics::info(format!("Created bucket {} in {}", name, region));
+                self.stop_bucket_creation();
+                self.error_message = None;
+                self.update().await.ok();
+                self.notify(format!("Created bucket '{}' in {}", name, region));
+            }
+            // Keep the name so a typo can be fixed
+            Err(err) => {
+                diagnostics::error(format!("Creating bucket {}: {}", name, err));
+                self.error_message = Some(format!("Failed to create bucket: {}", err));
+            }
+        }
+    }
+
+    /// Asks how long a presigned URL of the highlighted object stays valid, in the input
+    /// in place of the prefix search
+    fn start_presigning(&mut self) {
+        if self.s3_client.is_none() || self.versions_of.is_some() {
+            return;
+        }
+        let Some(record) = self.results_navigator.selected_label() else {
+            return;
+        };
+        let key = Self::object_key(&record);
+        if key.ends_with('/') {
+            self.notify(String::from("Presigned URLs are for objects, not folders"));
+            return;
+        }
+        self.expiry_input.set_content(DEFAULT_PRESIGN_EXPIRY);
+        self.expiry_input.set_title(format!(
+            "URL of {} valid for, at most 7d (Enter copies, Esc cancels)",
+            key
+        ));
+        self.presign_key = Some(key);
+        self.current_focus = S3Focus::Input;
+        self.update_widget_states();
+    }
+
+    /// `s3://bucket/key` URI of the highlighted object or folder
+    fn selected_object_uri(&self) -> Option<String> {
+        let bucket = self.selected_bucket.as_ref()?;
+        let record = self.results_navigator.selected_label()?;
+        Some(format!("s3://{}/{}", bucket, Self::object_key(&record)))
+    }
+
+    /// Copies the URI of the highlighted object and reports it in the status line
+    fn copy_object_uri(&self) {
+        let Some(uri) = self.selected_object_uri() else {
+            return;
+        };
+        let action = match clipboard::copy_text(&uri) {
+            Ok(()) => WidgetAction::ServiceNavigatorEvent(
+                ServiceNavigatorEvent::Copied(uri),
+                WidgetType::AWSServiceNavigator,
+            ),
+            Err(err) => WidgetAction::Notify(err.to_string()),
+        };
+        self.event_sender
+            .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                ComponentAction::WidgetAction(action),
+                self.component_type.clone(),
+            )));
+    }
+
+    /// Stops asking for an expiry, showing the prefix input again
+    fn stop_presigning(&mut self) {
+        self.presign_key = None;
+        self.current_focus = S3Focus::Results;
+        self.update_widget_states();
+    }
+
+    /// Copies a presigned GET URL of the object being asked about and shows it in the
+    /// popup, so it can still be copied by hand without a clipboard
+    ///
+    /// Expiries beyond 7 days are cut to 7 days, the longest a signature is valid.
+    async fn create_presigned_url(&mut self, expiry: String) {
+        let (Some(client), Some(bucket), Some(key)) = (
+            self.s3_client.clone(),
+            self.selected_bucket.clone(),
+            self.presign_key.clone(),
+        ) else {
+            return;
+        };
+        let Some(requested) = parse_expiry(&expiry) else {
+            self.error_message = Some(format!(
+                "Invalid expiry '{}': expected e.g. 90s, 15m, 12h or 7d",
+                expiry.trim()
+            ));
+            return;
+        };
+        let validity = if requested > MAX_PRESIGN_EXPIRY {
+            String::from("7 days, the longest S3 allows")
+        } else {
+            expiry.trim().to_string()
+        };
+
+        let presigned = client
+            .lock()
+            .await
+            .generate_presigned_url(&bucket, &key, requested.min(MAX_PRESIGN_EXPIRY))
+            .await;
+        match presigned {
+            Ok(url) => {
+                self.stop_presigning();
+                self.error_message = None;
+                let copied = match clipboard::copy_text(&url) {
+                    Ok(()) => String::from("Copied to the clipboard"),
+                    Err(err) => format!("{}; copy the URL from here", err),
+                };
+                self.show_popup(PopupContent::Details(format!(
+                    "{}. Valid for {}:\n\n{}",
+                    copied, validity, url
+                )));
+            }
+            // Keep the expiry so it can be corrected
+            Err(err) => {
+                diagnostics::error(format!("Presigning {}/{}: {}", bucket, key, err));
+                self.error_message = Some(format!("Failed to presign the URL: {}", err));
+            }
+        }
+    }
+
+    /// Asks for confirmation before deleting a bucket, or deletes it right away in
+    /// power-user mode
+    async fn request_bucket_delete(&mut self, name: String) {
+        if self.refuse_in_read_only("deleting buckets") {
+            return;
+        }
+        if self.power_user {
+            self.delete_bucket(name).await;
+            return;
+        }
+        let prompt = format!(
+            "Delete bucket '{}'?\n\nOnly empty buckets can be deleted, and the name may not be \
+             available again right away.",
+            name
+        );
+        self.pending_bucket_delete = Some(name);
+        self.show_popup(PopupContent::Confirm(prompt));
+    }
+
+    /// Deletes the bucket confirmed by the user and lists the buckets again
+    async fn delete_bucket(&mut self, name: String) {
+        let Some(client) = self.s3_client.clone() else {
+            self.error_message = Some(format!(
+                "Failed to delete bucket '{}': the S3 client is not ready",
+                name
+            ));
+            return;
+        };
+        let deleted = client.lock().await.delete_bucket(&name).await;
+        match deleted {
+            Ok(()) => {
+                diagnostics::info(format!("Deleted bucket {}", name));
+                self.error_message = None;
+                if self.selected_bucket.as_deref() == Some(name.as_str()) {
+                    self.selected_bucket = None;
+                    self.current_path.clear();
+                    self.search_prefix = None;
+                    self.leave_versions();
+                    self.navigator.set_title(String::from("Services"));
+                }
+                self.update().await.ok();
+                self.notify(format!("Deleted bucket '{}'", name));
+            }
+            Err(err) => {
+                diagnostics::error(format!("Deleting bucket {}: {}", name, err));
+                self.error_message = Some(format!("Failed to delete bucket: {}", err));
+            }
         }
     }
 
@@ -157,24 +792,25 @@ impl S3Component {
 
             self.current_path = full_path.clone();
 
-            if let Some(client) = &self.s3_client {
-                let objects = client
-                    .lock()
-                    .await
-                    .list_objects(bucket, &full_path)
-                    .await
-                    .unwrap_or_else(|_| vec!["Error listing objects".to_string()]);
-
-                self.results_navigator
-                    .set_title(format!("Path: {}", full_path));
-                self.results_navigator
-                    .set_content(NavigatorContent::Records(objects));
-            }
+            let bucket = bucket.clone();
+            let title = format!("Path: {}", full_path);
+            self.load_objects(&bucket, &full_path, title, None);
         }
     }
 
     /// Navigate up one directory level
     fn navigate_up(&mut self) {
+        // Leave a prefix search for the folder it searched in first
+        if self.search_prefix.is_some() {
+            if let Some(bucket) = &self.selected_bucket {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::LoadPath(bucket.clone(), self.current_path.clone()),
+                        self.component_type.clone(),
+                    )));
+            }
+            return;
+        }
         if !self.current_path.is_empty() {
             // Remove the last directory from the path
             if let Some(last_slash) = self.current_path.rfind('/') {
@@ -186,46 +822,337 @@ impl S3Component {
             // Send event to update objects list with new path
             if let Some(bucket) = &self.selected_bucket {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::LoadPath(bucket.clone(), self.current_path.clone()),
                         self.component_type.clone(),
-                    )))
-                    .unwrap();
+                    )));
             }
         }
     }
     
+    /// Renders the breadcrumb of the open folder, numbering the levels digits jump to
+    fn breadcrumb_line(&self, bucket: &str) -> Line<'static> {
+        let crumbs = breadcrumb(bucket, &self.current_path);
+        let last = crumbs.len() - 1;
+        let mut spans = Vec::new();
+        for (level, (name, _)) in crumbs.into_iter().enumerate() {
+            if level > 0 {
+                spans.push(Span::styled(" / ", Style::default().fg(theme().help_fg)));
+            }
+            if level <= 9 {
+                spans.push(Span::styled(
+                    format!("{} ", level),
+                    Style::default().fg(theme().help_fg),
+                ));
+            }
+            let style = if level == last {
+                Style::default()
+                    .fg(theme().selection_fg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme().text_fg)
+            };
+            spans.push(Span::styled(name, style));
+        }
+        Line::from(spans)
+    }
+
+    /// Extracts the object key from a results record
+    ///
+    /// Records are JSON objects from `list_objects`; anything else is treated as a raw key
+    fn object_key(record: &str) -> String {
+        serde_json::from_str::<serde_json::Value>(record)
+            .ok()
+            .and_then(|json| json.get("key").and_then(|key| key.as_str()).map(String::from))
+            .unwrap_or_else(|| record.to_string())
+    }
+
+    /// Returns whether a results record is an object in an archive storage class
+    fn is_archived(record: &str) -> bool {
+        serde_json::from_str::<serde_json::Value>(record)
+            .ok()
+            .and_then(|json| json.get("storage_class")?.as_str().map(is_archived_class))
+            .unwrap_or(false)
+    }
+
+    /// Extracts the version id from a record of `list_object_versions`
+    fn version_id(record: &str) -> String {
+        serde_json::from_str::<serde_json::Value>(record)
+            .ok()
+            .and_then(|json| json.get("version_id").and_then(|id| id.as_str()).map(String::from))
+            .unwrap_or_default()
+    }
+
+    /// Shows the given content in the details popup
+    fn show_popup(&mut self, content: PopupContent) {
+        self.details_popup.set_content(content);
+        self.details_popup.set_visible(true);
+        self.details_popup.set_active(true);
+    }
+
+    /// Downloads all marked objects into the user's download directory in the background,
+    /// showing a summary once they are done
+    fn download_selected(&mut self) {
+        let keys: Vec<String> = self
+            .results_navigator
+            .marked_records()
+            .iter()
+            .map(|record| Self::object_key(record))
+            .collect();
+        let (Some(client), Some(bucket)) = (self.s3_client.clone(), self.selected_bucket.clone())
+        else {
+            return;
+        };
+        if keys.is_empty() {
+            return;
+        }
+
+        let destination = dirs::download_dir().unwrap_or_else(|| PathBuf::from("."));
+        self.results_navigator.clear_marked_records();
+        self.notify(format!(
+            "Downloading {} objects to {}...",
+            keys.len(),
+            destination.display()
+        ));
+        spawn_task(&self.event_sender, self.component_type.clone(), async move {
+            let mut downloaded = 0;
+            let mut kept = Vec::new();
+            let mut failures = Vec::new();
+            for key in &keys {
+                // Locked per object so listings are not held up until the last one
+                let result = client.lock().await.download_object(&bucket, key, &destination).await;
+                match result {
+                    Ok(_) => downloaded += 1,
+                    Err(S3ClientError::FileExists(path)) => kept.push(path),
+                    Err(err) => failures.push(format!("{}: {}", key, err)),
+                }
+            }
+
+            let mut summary = format!(
+                "Downloaded {} of {} objects to {}",
+                downloaded,
+                keys.len(),
+                destination.display()
+            );
+            if !kept.is_empty() {
+                summary.push_str(&format!(
+                    "\nSkipped {} objects whose files already exist:",
+                    kept.len()
+                ));
+                for path in kept {
+                    summary.push_str(&format!("\n{}", path.display()));
+                }
+            }
+            for failure in failures {
+                summary.push_str(&format!("\n{}", failure));
+            }
+            vec![ComponentAction::ObjectsDownloaded(summary)]
+        });
+    }
+
+    /// Asks for confirmation before deleting all marked objects, or deletes them right away
+    /// in power-user mode
+    ///
+    /// The prompt names the bucket and every full key, and warns when the bucket is not
+    /// versioned because the objects cannot be recovered afterwards.
+    async fn request_delete_selected(&mut self) {
+        if self.refuse_in_read_only("deleting objects") {
+            return;
+        }
+        let keys: Vec<String> = self
+            .results_navigator
+            .marked_records()
+            .iter()
+            .map(|record| Self::object_key(record))
+            .collect();
+        let (Some(client), Some(bucket)) = (self.s3_client.clone(), self.selected_bucket.clone())
+        else {
+            return;
+        };
+        if keys.is_empty() {
+            return;
+        }
+        if self.power_user {
+            self.delete_objects(keys);
+            return;
+        }
+
+        let versioning = match client.lock().await.is_versioned(&bucket).await {
+            Ok(true) => String::from(
+                "Versioning is enabled: S3 adds delete markers and keeps earlier versions.",
+            ),
+            Ok(false) => String::from(
+                "WARNING: versioning is off for this bucket, deletion is PERMANENT.",
+            ),
+            Err(err) => format!(
+                "WARNING: could not check versioning ({}), deletion may be permanent.",
+                err
+            ),
+        };
+
+        let mut listed: Vec<String> = keys
+            .iter()
+            .take(MAX_CONFIRM_KEYS)
+            .map(|key| format!("  s3://{}/{}", bucket, key))
+            .collect();
+        if keys.len() > MAX_CONFIRM_KEYS {
+            listed.push(format!("  ...and {} more", keys.len() - MAX_CONFIRM_KEYS));
+        }
+        let prompt = format!(
+            "Delete {} objects from bucket '{}'?\n{}\n\n{}",
+            keys.len(),
+            bucket,
+            listed.join("\n"),
+            versioning
+        );
+        self.pending_delete = Some(keys);
+        self.show_popup(PopupContent::Confirm(prompt));
+    }
+
+    /// Deletes the objects confirmed by the user in the background
+    fn delete_objects(&mut self, keys: Vec<String>) {
+        let (Some(client), Some(bucket)) = (self.s3_client.clone(), self.selected_bucket.clone())
+        else {
+            return;
+        };
+        self.results_navigator.clear_marked_records();
+        self.notify(format!("Deleting {} objects from {}...", keys.len(), bucket));
+        spawn_task(&self.event_sender, self.component_type.clone(), async move {
+            let outcome = client
+                .lock()
+                .await
+                .delete_objects(&bucket, &keys)
+                .await
+                .map_err(|err| err.to_string());
+            vec![ComponentAction::ObjectsDeleted(bucket, keys.len(), outcome)]
+        });
+    }
+
+    /// Reports a finished delete in the status line and lists the bucket again if it is
+    /// still open
+    fn apply_delete_outcome(
+        &mut self,
+        bucket: String,
+        requested: usize,
+        outcome: Result<DeleteOutcome, String>,
+    ) {
+        let summary = match outcome {
+            Ok(outcome) => {
+                for (key, version) in &outcome.delete_markers {
+                    diagnostics::info(format!(
+                        "Delete marker {} created for s3://{}/{}",
+                        version.as_deref().unwrap_or("(no version id)"),
+                        bucket,
+                        key
+                    ));
+                }
+                for (key, reason) in &outcome.failed {
+                    diagnostics::error(format!("Deleting s3://{}/{}: {}", bucket, key, reason));
+                }
+                let mut summary = format!(
+                    "Deleted {} of {} objects from {}",
+                    outcome.deleted.len(),
+                    requested,
+                    bucket
+                );
+                if !outcome.delete_markers.is_empty() {
+                    summary.push_str(&format!(
+                        "; {} delete markers created, earlier versions are recoverable",
+                        outcome.delete_markers.len()
+                    ));
+                }
+                if let Some((key, reason)) = outcome.failed.first() {
+                    summary.push_str(&format!("; {} failed", outcome.failed.len()));
+                    self.error_message = Some(format!(
+                        "Failed to delete {} objects, e.g. {}: {}",
+                        outcome.failed.len(),
+                        key,
+                        reason
+                    ));
+                }
+                summary
+            }
+            Err(err) => {
+                self.error_message = Some(format!("Failed to delete objects: {}", err));
+                format!("Failed to delete objects from {}", bucket)
+            }
+        };
+
+        if self.selected_bucket.as_deref() == Some(bucket.as_str()) {
+            self.event_sender
+                .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                    ComponentAction::LoadPath(bucket, self.current_path.clone()),
+                    self.component_type.clone(),
+                )));
+        }
+        self.notify(summary);
+    }
+
     /// Returns contextual help items based on current component state
     fn get_base_help_items(&self) -> Vec<(String, String)> {
         let mut items = vec![];
 
         // Check if the popup is visible
         if self.details_popup.is_visible() {
-            items.push(("Esc".to_string(), "Close details".to_string()));
-            items.push(("PgUp/PgDn".to_string(), "Scroll content".to_string()));
-            return items;
+            return self.details_popup.get_help_items();
         }
 
         // Different help items based on current focus
         match self.current_focus {
             S3Focus::Navigation => {
                 items.push(("Enter".to_string(), "Select bucket".to_string()));
-                items.push(("Alt+2".to_string(), "Focus results".to_string()));
-                items.push(("Alt+3".to_string(), "Focus input".to_string()));
+                items.push(("o".to_string(), "Copy console link".to_string()));
+                if !self.read_only {
+                    items.push(("c".to_string(), "Create bucket".to_string()));
+                    items.push(("Del".to_string(), "Delete bucket".to_string()));
+                }
+            }
+            S3Focus::Results if self.versions_of.is_some() => {
+                items.push(("Enter".to_string(), "View version details".to_string()));
+                items.push(("v".to_string(), "Back to objects".to_string()));
             }
             S3Focus::Results => {
                 items.push(("Enter".to_string(), "View object details".to_string()));
-                items.push(("Backspace".to_string(), "Navigate up".to_string()));
-                items.push(("Alt+1".to_string(), "Focus buckets".to_string()));
-                items.push(("Alt+3".to_string(), "Focus input".to_string()));
+                if self.search_prefix.is_some() {
+                    items.push(("Backspace".to_string(), "Back to folder".to_string()));
+                } else {
+                    items.push(("Backspace".to_string(), "Navigate up".to_string()));
+                }
+                items.push(("0-9".to_string(), "Jump to path level".to_string()));
+                items.push(("/".to_string(), "Filter loaded objects".to_string()));
+                items.push(("Space".to_string(), "Mark object".to_string()));
+                items.push(("v".to_string(), "Show versions".to_string()));
+                items.push(("u".to_string(), "Copy presigned URL".to_string()));
+                items.push(("Ctrl+D".to_string(), "Download marked".to_string()));
+                if !self.read_only {
+                    items.push(("Del".to_string(), "Delete marked".to_string()));
+                }
+                if self.next_token.is_some() {
+                    items.push(("Ctrl+N".to_string(), "Load more".to_string()));
+                }
+            }
+            S3Focus::Input if self.creating_bucket => {
+                items.push(("Enter".to_string(), "Create bucket".to_string()));
+                items.push(("Esc".to_string(), "Cancel".to_string()));
+            }
+            S3Focus::Input if self.presign_key.is_some() => {
+                items.push(("Enter".to_string(), "Copy presigned URL".to_string()));
+                items.push(("Esc".to_string(), "Cancel".to_string()));
             }
             S3Focus::Input => {
-                items.push(("Enter".to_string(), "Search objects".to_string()));
-                items.push(("Alt+1".to_string(), "Focus buckets".to_string()));
-                items.push(("Alt+2".to_string(), "Focus results".to_string()));
+                items.push(("Enter".to_string(), "Search key prefix in S3".to_string()));
             }
             _ => {}
         }
+        let focused = match self.current_focus {
+            S3Focus::Navigation => 1,
+            S3Focus::Input => 2,
+            _ => 4,
+        };
+        items.extend(focus_jump_help(
+            [Some("Focus buckets"), Some("Focus input"), None, Some("Focus results")],
+            focused,
+        ));
         items
     }
 }
@@ -237,13 +1164,16 @@ impl AWSComponent for S3Component {
             return;
         }
 
+        // Reserve space for the error banner if a client failed to initialize
+        let area = match &self.error_message {
+            Some(message) => render_error_banner(message, area, buf),
+            None => area,
+        };
+
         // Create a horizontal split for left panel (buckets) and right panel (objects)
         let horizontal_split = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(30), // Left panel - buckets list
-                Constraint::Percentage(70), // Right panel - objects and details
-            ])
+            .constraints(self.split.constraints())
             .split(area);
 
         // Create a vertical split for the right panel
@@ -255,11 +1185,40 @@ impl AWSComponent for S3Component {
             ])
             .split(horizontal_split[1]);
 
+        // The breadcrumb of the open folder heads the results
+        let results_area = match &self.selected_bucket {
+            Some(bucket) => {
+                let [crumb_area, results_area] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Min(0)])
+                        .areas(right_vertical_split[1]);
+                Paragraph::new(self.breadcrumb_line(bucket)).render(crumb_area, buf);
+                results_area
+            }
+            None => right_vertical_split[1],
+        };
+
+        // Guide newcomers until there is something to list
+        let bucket_prompt = self
+            .s3_client
+            .is_none()
+            .then_some("Select a profile to list its buckets");
+        let object_prompt = self
+            .selected_bucket
+            .is_none()
+            .then_some("Select a bucket from the left to browse its objects");
+
         // Render components
-        self.navigator.render(horizontal_split[0], buf);
+        self.navigator
+            .render_with_prompt(horizontal_split[0], buf, bucket_prompt);
         self.results_navigator
-            .render(right_vertical_split[1], buf);
-        self.input.render(right_vertical_split[0], buf);
+            .render_with_prompt(results_area, buf, object_prompt);
+        if self.creating_bucket {
+            self.bucket_name_input.render(right_vertical_split[0], buf);
+        } else if self.presign_key.is_some() {
+            self.expiry_input.render(right_vertical_split[0], buf);
+        } else {
+            self.input.render(right_vertical_split[0], buf);
+        }
 
         if self.details_popup.is_visible() {
             self.details_popup.render(area, buf);
@@ -272,58 +1231,229 @@ impl AWSComponent for S3Component {
         if self.details_popup.is_visible() {
             if let Some(signal) = self.details_popup.handle_input(key_event) {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::WidgetAction(signal),
                         self.component_type.clone(),
-                    )))
-                    .unwrap();
+                    )));
                 return;
             }
         }
 
         match key_event.code {
+            // Tab completes the typed prefix while a bucket is open, and moves focus otherwise
+            KeyCode::Tab
+                if self.current_focus == S3Focus::Input
+                    && !self.creating_bucket
+                    && self.presign_key.is_none()
+                    && self.selected_bucket.is_some()
+                    && self.input.completes_on_tab() =>
+            {
+                if let Some(signal) = self.input.handle_input(key_event) {
+                    self.event_sender
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                            ComponentAction::WidgetAction(signal),
+                            self.component_type.clone(),
+                        )));
+                }
+            }
             KeyCode::Tab => {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::NextFocus,
                         self.component_type.clone(),
-                    )))
-                    .unwrap();
+                    )));
             }
             KeyCode::BackTab => {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::PreviousFocus,
                         self.component_type.clone(),
-                    )))
-                    .unwrap();
+                    )));
             }
             KeyCode::Backspace => {
-                // Navigate up one directory level
+                // Navigate up one directory level, or back from an object's versions
                 if self.current_focus == S3Focus::Results {
+                    let action = if self.versions_of.is_some() {
+                        ComponentAction::ToggleVersions
+                    } else {
+                        ComponentAction::NavigateUp
+                    };
+                    self.event_sender
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                            action,
+                            self.component_type.clone(),
+                        )));
+                }
+            }
+            // Bulk operations over the marked objects
+            KeyCode::Char('d')
+                if key_event.modifiers == KeyModifiers::CONTROL
+                    && self.current_focus == S3Focus::Results
+                    && self.versions_of.is_none()
+                    && !self.results_navigator.is_capturing_input() =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::DownloadSelected,
+                        self.component_type.clone(),
+                    )));
+            }
+            // Digits jump to a level of the breadcrumb, 0 being the bucket itself
+            KeyCode::Char(digit @ '0'..='9')
+                if key_event.modifiers.is_empty()
+                    && self.current_focus == S3Focus::Results
+                    && !self.results_navigator.is_capturing_input() =>
+            {
+                let level = digit as usize - '0' as usize;
+                if let Some(bucket) = &self.selected_bucket
+                    && let Some((_, prefix)) = breadcrumb(bucket, &self.current_path).get(level)
+                {
+                    self.event_sender
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                            ComponentAction::LoadPath(bucket.clone(), prefix.clone()),
+                            self.component_type.clone(),
+                        )));
+                }
+            }
+            // Ctrl+N appends the next page of a truncated listing
+            KeyCode::Char('n')
+                if key_event.modifiers == KeyModifiers::CONTROL
+                    && self.current_focus == S3Focus::Results
+                    && !self.results_navigator.is_capturing_input() =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::LoadMoreObjects,
+                        self.component_type.clone(),
+                    )));
+            }
+            KeyCode::Delete
+                if self.current_focus == S3Focus::Results
+                    && self.versions_of.is_none()
+                    && !self.results_navigator.is_capturing_input() =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::DeleteSelected,
+                        self.component_type.clone(),
+                    )));
+            }
+            // Create a bucket, or delete the highlighted one
+            KeyCode::Char('c')
+                if key_event.modifiers.is_empty()
+                    && self.current_focus == S3Focus::Navigation
+                    && !self.navigator.is_capturing_input() =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::NewBucket,
+                        self.component_type.clone(),
+                    )));
+            }
+            KeyCode::Delete
+                if self.current_focus == S3Focus::Navigation
+                    && !self.navigator.is_capturing_input() =>
+            {
+                if let Some(name) = self.navigator.selected_label() {
                     self.event_sender
-                        .send(Event::Tab(TabEvent::ComponentActions(
-                            ComponentAction::NavigateUp,
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                            ComponentAction::RequestBucketDelete(name),
                             self.component_type.clone(),
-                        )))
-                        .unwrap();
+                        )));
                 }
             }
+            // Copy a presigned URL of the highlighted object, asking for its expiry
+            KeyCode::Char('u')
+                if key_event.modifiers.is_empty()
+                    && self.current_focus == S3Focus::Results
+                    && !self.results_navigator.is_capturing_input() =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::NewPresignedUrl,
+                        self.component_type.clone(),
+                    )));
+            }
+            // Copy the S3 URI of the highlighted object, rather than its whole record
+            KeyCode::Char('y')
+                if key_event.modifiers.is_empty()
+                    && self.current_focus == S3Focus::Results
+                    && !self.results_navigator.is_capturing_input() =>
+            {
+                self.copy_object_uri();
+            }
+            // Show or hide the versions of the highlighted object
+            KeyCode::Char('v')
+                if key_event.modifiers.is_empty()
+                    && self.current_focus == S3Focus::Results
+                    && !self.results_navigator.is_capturing_input() =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::ToggleVersions,
+                        self.component_type.clone(),
+                    )));
+            }
             // Alt+number shortcuts to switch focus between areas
             KeyCode::Char('1') if key_event.modifiers == KeyModifiers::ALT => {
                 self.current_focus = S3Focus::Navigation;
                 self.update_widget_states();
             }
             KeyCode::Char('2') if key_event.modifiers == KeyModifiers::ALT => {
-                self.current_focus = S3Focus::Results;
+                self.current_focus = S3Focus::Input;
                 self.update_widget_states();
             }
-            KeyCode::Char('3') if key_event.modifiers == KeyModifiers::ALT => {
-                self.current_focus = S3Focus::Input;
+            // S3 has no second input, so Alt+3 is not used
+            KeyCode::Char('4') if key_event.modifiers == KeyModifiers::ALT => {
+                self.current_focus = S3Focus::Results;
                 self.update_widget_states();
             }
+            // Copy the console link of the highlighted bucket
+            KeyCode::Char('o')
+                if key_event.modifiers.is_empty()
+                    && self.current_focus == S3Focus::Navigation
+                    && !self.navigator.is_capturing_input() =>
+            {
+                if let Some(name) = self.navigator.selected_label() {
+                    copy_console_link(
+                        ConsoleResource::S3Bucket(name),
+                        self.aws_clients.as_ref(),
+                        &self.event_sender,
+                        self.component_type.clone(),
+                    );
+                }
+            }
+            // Copy the AWS CLI command repeating the current listing or query
+            KeyCode::Char('a')
+                if key_event.modifiers.is_empty()
+                    && match self.current_focus {
+                        S3Focus::Navigation => !self.navigator.is_capturing_input(),
+                        S3Focus::Results => !self.results_navigator.is_capturing_input(),
+                        _ => false,
+                    } =>
+            {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::CopyCliCommand,
+                        self.component_type.clone(),
+                    )));
+            }
+            // Ctrl+Left/Right resize the panes; input boxes keep them for word movement
+            KeyCode::Left | KeyCode::Right
+                if key_event.modifiers == KeyModifiers::CONTROL
+                    && matches!(
+                        self.current_focus,
+                        S3Focus::Navigation | S3Focus::Results
+                    ) =>
+            {
+                self.split.nudge(key_event.code == KeyCode::Right);
+            }
             KeyCode::Esc => {
-                if self.current_focus != S3Focus::Navigation {
+                if self.creating_bucket {
+                    self.stop_bucket_creation();
+                } else if self.presign_key.is_some() {
+                    self.stop_presigning();
+                } else if self.current_focus != S3Focus::Navigation {
                     self.current_focus = S3Focus::Navigation;
                     self.update_widget_states();
                 }
@@ -332,17 +1462,22 @@ impl AWSComponent for S3Component {
                 // Forward input to the currently focused widget
                 if let Some(signal) = match self.current_focus {
                     S3Focus::Navigation => self.navigator.handle_input(key_event),
+                    S3Focus::Input if self.creating_bucket => {
+                        self.bucket_name_input.handle_input(key_event)
+                    }
+                    S3Focus::Input if self.presign_key.is_some() => {
+                        self.expiry_input.handle_input(key_event)
+                    }
                     S3Focus::Input => self.input.handle_input(key_event),
                     S3Focus::Results => self.results_navigator.handle_input(key_event),
                     S3Focus::None => None,
                     _ => None,
                 } {
                     self.event_sender
-                        .send(Event::Tab(TabEvent::ComponentActions(
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
                             ComponentAction::WidgetAction(signal),
                             self.component_type.clone(),
-                        )))
-                        .unwrap();
+                        )));
                 }
             }
         }
@@ -353,34 +1488,74 @@ impl AWSComponent for S3Component {
         match event {
             s3_event => match s3_event {
                 // Handle bucket selection
-                ComponentAction::Active(aws_profile) => {
+                ComponentAction::Active(aws_profile, region) => {
+                    // Listings for the previous profile must not land in the new view
+                    self.generation = next_generation();
+                    self.completion_generation = next_generation();
+                    self.details_popup.clear_history();
+                    self.input.load_history(&aws_profile);
                     self.aws_clients =
-                        Some(TabClients::new(aws_profile, String::from("eu-west-1")));
+                        Some(TabClients::new(aws_profile, region));
+                    self.last_updated = None;
 
                     // Unwrap the Result and handle errors properly
                     if let Some(clients) = &mut self.aws_clients {
                         match clients.get_s3_client().await {
                             Ok(client) => {
                                 self.s3_client = Some(client);
+                                self.error_message = None;
                                 self.update().await.ok();
                             }
                             Err(err) => {
-                                // Handle the error (show error in UI)
-                                self.results_navigator
-                                    .set_title(String::from("Error connecting to CloudWatch"));
-                                self.results_navigator
-                                    .set_content(NavigatorContent::Records(vec![format!(
-                                        "Failed to initialize CloudWatch client: {}",
-                                        err
-                                    )]));
+                                let message = format!(
+                                    "Failed to initialize S3 client (profile: {}, region: {}): {}",
+                                    clients.profile(),
+                                    clients.region(),
+                                    err
+                                );
+                                // The error banner shows it instead of clobbering the navigators
+                                self.event_sender
+                                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                        ComponentAction::ShowError(message),
+                                        self.component_type.clone(),
+                                    )));
                             }
                         }
                     }
                 }
+                ComponentAction::ShowError(message) => {
+                    diagnostics::error(format!("S3: {}", message));
+                    self.error_message = Some(message);
+                }
+                ComponentAction::Reset => {
+                    // A listing still in flight must not refill the cleared view
+                    self.generation = next_generation();
+                    self.details_popup.clear_history();
+                    self.completion_generation = next_generation();
+                    self.navigator.reset_view();
+                    self.results_navigator.reset_view();
+                    self.results_navigator
+                        .set_content(NavigatorContent::Records(vec![]));
+                    self.results_navigator
+                        .set_title(String::from("Select a bucket"));
+                    self.input.set_content("");
+                    self.current_path.clear();
+                    self.search_prefix = None;
+                    self.selected_bucket = None;
+                    self.next_token = None;
+                    self.object_count = 0;
+                    self.pending_delete = None;
+                    self.pending_bucket_delete = None;
+                    self.creating_bucket = false;
+                    self.presign_key = None;
+                    self.leave_versions();
+                    self.error_message = None;
+                    self.reset_focus();
+                }
 
                 ComponentAction::Focused => {
-                    // Set the component as inactive
-                    self.set_active(false);
+                    // Set the component as active
+                    self.set_active(true);
                 }
                 ComponentAction::Unfocused => {
                     self.reset_focus();
@@ -398,28 +1573,86 @@ impl AWSComponent for S3Component {
                 ComponentAction::NavigateFolder(path) => {
                     self.navigate_folder(path).await;
                 }
+                // Bulk operations over marked objects
+                ComponentAction::DownloadSelected => self.download_selected(),
+                ComponentAction::ObjectsDownloaded(summary) => {
+                    self.show_popup(PopupContent::Details(summary));
+                }
+                ComponentAction::DeleteSelected => {
+                    self.request_delete_selected().await;
+                }
+                ComponentAction::ObjectsDeleted(bucket, requested, outcome) => {
+                    self.apply_delete_outcome(bucket, requested, outcome);
+                }
+                ComponentAction::NewBucket => self.start_bucket_creation(),
+                ComponentAction::CreateBucket(name) => self.create_bucket(name).await,
+                ComponentAction::RequestBucketDelete(name) => self.request_bucket_delete(name).await,
+                ComponentAction::NewPresignedUrl => self.start_presigning(),
+                ComponentAction::CreatePresignedUrl(expiry) => {
+                    self.create_presigned_url(expiry).await
+                }
                 // Navigate up to parent directory
                 ComponentAction::NavigateUp => {
                     self.navigate_up();
                 }
                 // Load contents at a specific path
                 ComponentAction::LoadPath(bucket, path) => {
-                    if let Some(client) = &self.s3_client {
-                        let objects = client
+                    self.current_path = path.trim_end_matches('/').to_string();
+                    let title = format!("Path: {}", if path.is_empty() { "/" } else { &path });
+                    self.load_objects(&bucket, &path, title, None);
+                }
+                // Apply background results only if no newer request has started since;
+                // stale ones fall through and are dropped
+                ComponentAction::BackgroundResult(generation, action)
+                    if generation == self.generation
+                        || generation == self.completion_generation =>
+                {
+                    self.process_event(*action).await;
+                }
+                ComponentAction::ObjectsListed(title, search_prefix, page) => {
+                    self.apply_object_listing(title, search_prefix, page);
+                }
+                ComponentAction::CompletePrefix(typed) => self.complete_prefix(typed),
+                ComponentAction::PrefixCompletions(typed, candidates) => {
+                    self.apply_completions(typed, candidates);
+                }
+                ComponentAction::LoadMoreObjects => self.load_more_objects().await,
+                ComponentAction::SearchPrefix(query) => self.search_prefix(query).await,
+                ComponentAction::ToggleVersions => self.toggle_versions().await,
+                ComponentAction::VersionDetails(key, version_id) => {
+                    if let (Some(client), Some(bucket)) = (&self.s3_client, &self.selected_bucket) {
+                        let details = client
                             .lock()
                             .await
-                            .list_objects(&bucket, &path)
+                            .get_object_version_details(bucket, &key, &version_id)
                             .await
-                            .unwrap_or_else(|_| vec!["Error listing objects".to_string()]);
-
-                        self.results_navigator.set_title(format!(
-                            "Path: {}",
-                            if path.is_empty() { "/" } else { &path }
-                        ));
-                        self.results_navigator
-                            .set_content(NavigatorContent::Records(objects));
+                            .unwrap_or_else(|err| {
+                                format!("Error fetching version {}: {}", version_id, err)
+                            });
+                        self.show_popup(PopupContent::Details(details));
                     }
                 }
+                ComponentAction::CopyCliCommand => {
+                    // Repeats the listing of the open bucket and folder; without the
+                    // trailing slash `aws s3 ls` would list the folder itself
+                    let prefix = match &self.search_prefix {
+                        Some(prefix) => prefix.clone(),
+                        None if self.current_path.is_empty() => String::new(),
+                        None => format!("{}/", self.current_path.trim_end_matches('/')),
+                    };
+                    let command = self.selected_bucket.clone().map(|bucket| {
+                        match self.versions_of.clone() {
+                            Some(key) => CliCommand::S3ListVersions { bucket, key },
+                            None => CliCommand::S3List { bucket, prefix },
+                        }
+                    });
+                    copy_cli_command(
+                        command,
+                        self.aws_clients.as_ref(),
+                        &self.event_sender,
+                        self.component_type.clone(),
+                    );
+                }
                 // Display object details in popup
                 ComponentAction::PopupDetails(key) => {
                     if let (Some(client), Some(bucket)) = (&self.s3_client, &self.selected_bucket) {
@@ -478,11 +1711,10 @@ impl AWSComponent for S3Component {
                                         WidgetType::AWSServiceNavigator,
                                     ) => {
                                         self.event_sender
-                                            .send(Event::Tab(TabEvent::ComponentActions(
+                                            .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                                 ComponentAction::SelectBucket(bucket),
                                                 self.component_type.clone(),
-                                            )))
-                                            .unwrap();
+                                            )));
                                     }
                                     _ => {}
                                 }
@@ -500,24 +1732,30 @@ impl AWSComponent for S3Component {
                                         ),
                                         WidgetType::QueryResultsNavigator,
                                     ) => {
-                                        // Check if it's a folder (ends with /) or a file
-                                        if path.ends_with('/') {
+                                        // Versions open their own details; otherwise check if
+                                        // it's a folder (ends with /) or a file
+                                        if let Some(key) = self.versions_of.clone() {
+                                            let version_id = Self::version_id(&path);
+                                            self.event_sender
+                                                .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                                    ComponentAction::VersionDetails(key, version_id),
+                                                    self.component_type.clone(),
+                                                )));
+                                        } else if path.ends_with('/') {
                                             let folder_name =
                                                 path.trim_end_matches('/').to_string();
                                             self.event_sender
-                                                .send(Event::Tab(TabEvent::ComponentActions(
+                                                .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                                     ComponentAction::NavigateFolder(folder_name),
                                                     self.component_type.clone(),
-                                                )))
-                                                .unwrap();
+                                                )));
                                         } else {
                                             // Show object details in popup
                                             self.event_sender
-                                                .send(Event::Tab(TabEvent::ComponentActions(
+                                                .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                                     ComponentAction::PopupDetails(path),
                                                     self.component_type.clone(),
-                                                )))
-                                                .unwrap();
+                                                )));
                                         }
                                     }
                                     _ => {}
@@ -525,33 +1763,71 @@ impl AWSComponent for S3Component {
                             }
                         }
                     }
+                    WidgetAction::InputBoxEvent(_, InputBoxType::BucketName) => {
+                        if let Some(WidgetAction::InputBoxEvent(InputBoxEvent::Written(name), _)) =
+                            self.bucket_name_input.process_event(widget_action)
+                        {
+                            self.event_sender
+                                .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                    ComponentAction::CreateBucket(name),
+                                    self.component_type.clone(),
+                                )));
+                        }
+                    }
+                    WidgetAction::InputBoxEvent(_, InputBoxType::PresignExpiry) => {
+                        if let Some(WidgetAction::InputBoxEvent(InputBoxEvent::Written(expiry), _)) =
+                            self.expiry_input.process_event(widget_action)
+                        {
+                            self.event_sender
+                                .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                    ComponentAction::CreatePresignedUrl(expiry),
+                                    self.component_type.clone(),
+                                )));
+                        }
+                    }
                     WidgetAction::InputBoxEvent(ref _input_box_event, _) => {
                         if let Some(signal) = self.input.process_event(widget_action) {
-                            if let WidgetAction::InputBoxEvent(InputBoxEvent::Written(content), _) =
-                                signal
-                            {
-                                // Handle search input when a bucket is selected
-                                if let Some(bucket) = &self.selected_bucket {
-                                    let search_path = if self.current_path.is_empty() {
-                                        content.clone()
-                                    } else {
-                                        format!("{}/{}", self.current_path, content)
-                                    };
-
-                                    self.event_sender
-                                        .send(Event::Tab(TabEvent::ComponentActions(
-                                            ComponentAction::LoadPath(bucket.clone(), search_path),
-                                            self.component_type.clone(),
-                                        )))
-                                        .unwrap();
+                            let action = match signal {
+                                // Search the open folder by key prefix in S3
+                                WidgetAction::InputBoxEvent(InputBoxEvent::Written(content), _) => {
+                                    Some(ComponentAction::SearchPrefix(content))
                                 }
+                                WidgetAction::InputBoxEvent(
+                                    InputBoxEvent::CompletionRequested(content),
+                                    _,
+                                ) => Some(ComponentAction::CompletePrefix(content)),
+                                _ => None,
+                            };
+                            if let Some(action) = action
+                                && self.selected_bucket.is_some()
+                            {
+                                self.event_sender
+                                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                        action,
+                                        self.component_type.clone(),
+                                    )));
                             }
                         }
                     }
-                    // Close popup when exit event received
+                    // Let the popup handle navigation; once it closes, run a confirmed delete
                     WidgetAction::PopupAction(_) => {
-                        self.details_popup.set_visible(false);
-                        self.details_popup.set_active(false);
+                        // The popup closes itself on Esc, on Enter over details and on `y`
+                        let confirmed = matches!(
+                            self.details_popup.process_event(widget_action),
+                            Some(WidgetAction::PopupAction(PopupAction::Confirmed))
+                        );
+                        if !self.details_popup.is_visible()
+                            && let Some(keys) = self.pending_delete.take()
+                            && confirmed
+                        {
+                            self.delete_objects(keys);
+                        }
+                        if !self.details_popup.is_visible()
+                            && let Some(name) = self.pending_bucket_delete.take()
+                            && confirmed
+                        {
+                            self.delete_bucket(name).await;
+                        }
                     }
                     _ => {}
                 },
@@ -579,27 +1855,37 @@ impl AWSComponent for S3Component {
     }
 
     
-    fn allows_focus_continuation(&self) -> bool {
-        if self.current_focus == S3Focus::None {
-            return  true;
-        }
-        false
-    }
-
-    fn allows_focus_continuation_backward(&self) -> bool {
-        if self.current_focus != S3Focus::Navigation {
-            return  true;
+    fn current_focus(&self) -> ComponentFocus {
+        match self.current_focus {
+            S3Focus::Navigation => ComponentFocus::Navigation,
+            S3Focus::Input => ComponentFocus::Input,
+            S3Focus::Results => ComponentFocus::Results,
+            S3Focus::None => ComponentFocus::None,
         }
-        false
     }
 
     /// Fetches and displays the list of S3 buckets
     async fn update(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(client) = &self.s3_client {
             let client = client.lock().await;
-            let buckets = client.list_buckets().await?;
-            self.navigator
-                .set_content(NavigatorContent::Records(buckets));
+            let buckets = client.list_buckets().await;
+            if buckets.is_ok() {
+                self.last_updated = Some(Instant::now());
+            }
+            match buckets {
+                Ok(buckets) if buckets.is_empty() => {
+                    self.navigator.show_placeholder("No buckets found");
+                }
+                Ok(buckets) => {
+                    self.navigator
+                        .set_content(NavigatorContent::Records(buckets));
+                }
+                Err(err) => {
+                    self.navigator
+                        .show_placeholder(format!("Error listing buckets: {}", err));
+                    return Err(err.into());
+                }
+            }
 
             // Reset results area
             self.results_navigator
@@ -628,4 +1914,292 @@ impl AWSComponent for S3Component {
         // Return help items based on the component's state
         self.get_base_help_items()
     }
+
+    fn last_updated(&self) -> Option<Instant> {
+        self.last_updated
+    }
+
+    fn is_busy(&self) -> bool {
+        fetch_running(&self.fetch_task)
+    }
+}
+
+/// Longest prefix all candidates share, which completion extends the typed text to
+fn common_prefix(candidates: &[String]) -> String {
+    let Some((first, rest)) = candidates.split_first() else {
+        return String::new();
+    };
+    let mut len = first.len();
+    for candidate in rest {
+        len = first
+            .char_indices()
+            .zip(candidate.chars())
+            .take_while(|((_, a), b)| a == b)
+            .map(|((index, a), _)| index + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+            .min(len);
+    }
+    first[..len].to_string()
+}
+
+/// Levels of the path from the bucket down, each with the prefix `LoadPath` lists it by
+fn breadcrumb(bucket: &str, path: &str) -> Vec<(String, String)> {
+    let mut crumbs = vec![(bucket.to_string(), String::new())];
+    let mut prefix = String::new();
+    for folder in path.split('/').filter(|folder| !folder.is_empty()) {
+        if !prefix.is_empty() {
+            prefix.push('/');
+        }
+        prefix.push_str(folder);
+        crumbs.push((folder.to_string(), prefix.clone()));
+    }
+    crumbs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archived_storage_classes_are_detected_in_records() {
+        let record = |class: &str| format!(r#"{{"key":"a.log","storage_class":"{}"}}"#, class);
+        assert!(S3Component::is_archived(&record("GLACIER")));
+        assert!(S3Component::is_archived(&record("DEEP_ARCHIVE")));
+        assert!(!S3Component::is_archived(&record("GLACIER_IR")));
+        assert!(!S3Component::is_archived(&record("STANDARD")));
+        assert!(!S3Component::is_archived("a.log"));
+    }
+
+    #[tokio::test]
+    async fn listings_of_a_superseded_fetch_are_dropped() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = S3Component::new(sender);
+        component.generation = next_generation();
+        let listing = |title: &str| {
+            Box::new(ComponentAction::ObjectsListed(
+                title.to_string(),
+                None,
+                Ok(ObjectPage::default()),
+            ))
+        };
+
+        let stale = component.generation - 1;
+        component
+            .process_event(ComponentAction::BackgroundResult(stale, listing("Old")))
+            .await;
+        assert_eq!(component.listing_title, "Objects");
+
+        let current = component.generation;
+        component
+            .process_event(ComponentAction::BackgroundResult(current, listing("Path: /")))
+            .await;
+        assert_eq!(component.listing_title, "Path: /");
+    }
+
+    #[test]
+    fn breadcrumb_levels_list_their_prefixes() {
+        assert_eq!(breadcrumb("bucket", ""), [("bucket".to_string(), String::new())]);
+        assert_eq!(
+            breadcrumb("bucket", "logs/2024/"),
+            [
+                ("bucket".to_string(), String::new()),
+                ("logs".to_string(), "logs".to_string()),
+                ("2024".to_string(), "logs/2024".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn digits_jump_to_a_breadcrumb_level() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = S3Component::new(sender);
+        component.selected_bucket = Some("bucket".to_string());
+        component.current_path = "logs/2024/app".to_string();
+        component.current_focus = S3Focus::Results;
+
+        component.handle_input(KeyEvent::new(KeyCode::Char('1'), KeyModifiers::NONE));
+        let Ok(Event::Tab(TabEvent::ComponentActions(action, _))) = receiver.try_recv() else {
+            panic!("no action for the digit");
+        };
+        assert!(matches!(&action, ComponentAction::LoadPath(_, path) if path == "logs"));
+
+        component.process_event(action).await;
+        assert_eq!(component.current_path, "logs");
+
+        // Levels beyond the open folder do nothing
+        component.handle_input(KeyEvent::new(KeyCode::Char('5'), KeyModifiers::NONE));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn navigating_up_from_a_prefix_search_returns_to_its_folder() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = S3Component::new(sender);
+        component.selected_bucket = Some("bucket".to_string());
+        component.current_path = "logs/2024".to_string();
+        component.search_prefix = Some("logs/2024/app-".to_string());
+        assert_eq!(component.listing_prefix(), "logs/2024/app-");
+
+        component.process_event(ComponentAction::NavigateUp).await;
+        assert_eq!(component.current_path, "logs/2024");
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Event::Tab(TabEvent::ComponentActions(ComponentAction::LoadPath(bucket, path), _)))
+                if bucket == "bucket" && path == "logs/2024"
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_only_mode_refuses_bucket_changes() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = S3Component::new(sender);
+        component.read_only = true;
+
+        component.process_event(ComponentAction::NewBucket).await;
+        assert!(!component.creating_bucket);
+        component
+            .process_event(ComponentAction::RequestBucketDelete("logs".to_string()))
+            .await;
+        assert_eq!(component.pending_bucket_delete, None);
+        assert!(!component.details_popup.is_visible());
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Event::Tab(TabEvent::TabAction(TabAction::Notify(message))))
+                if message == "Read-only mode: creating buckets is disabled"
+        ));
+
+        component.read_only = false;
+        component
+            .process_event(ComponentAction::RequestBucketDelete("logs".to_string()))
+            .await;
+        assert_eq!(component.pending_bucket_delete.as_deref(), Some("logs"));
+        assert!(component.details_popup.is_visible());
+    }
+
+    #[tokio::test]
+    async fn power_user_mode_skips_confirmations_unless_read_only() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = S3Component::new(sender);
+        component.read_only = true;
+        component.power_user = true;
+
+        // Read-only mode wins over power-user mode
+        component
+            .process_event(ComponentAction::RequestBucketDelete("logs".to_string()))
+            .await;
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Event::Tab(TabEvent::TabAction(TabAction::Notify(message))))
+                if message == "Read-only mode: deleting buckets is disabled"
+        ));
+
+        component.read_only = false;
+        component
+            .process_event(ComponentAction::RequestBucketDelete("logs".to_string()))
+            .await;
+        // Without a client the delete fails, which shows it went ahead unconfirmed
+        assert_eq!(component.pending_bucket_delete, None);
+        assert!(!component.details_popup.is_visible());
+        assert_eq!(
+            component.error_message.as_deref(),
+            Some("Failed to delete bucket 'logs': the S3 client is not ready")
+        );
+    }
+
+    #[test]
+    fn delete_outcomes_report_failures_and_list_the_open_bucket_again() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = S3Component::new(sender);
+        component.selected_bucket = Some("logs".to_string());
+        component.current_path = "2024".to_string();
+
+        let outcome = DeleteOutcome {
+            deleted: vec!["2024/a.log".to_string()],
+            delete_markers: vec![],
+            failed: vec![("2024/b.log".to_string(), "Access Denied".to_string())],
+        };
+        component.apply_delete_outcome("logs".to_string(), 2, Ok(outcome));
+        assert_eq!(
+            component.error_message.as_deref(),
+            Some("Failed to delete 1 objects, e.g. 2024/b.log: Access Denied")
+        );
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Event::Tab(TabEvent::ComponentActions(ComponentAction::LoadPath(bucket, path), _)))
+                if bucket == "logs" && path == "2024"
+        ));
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Event::Tab(TabEvent::TabAction(TabAction::Notify(message))))
+                if message == "Deleted 1 of 2 objects from logs; 1 failed"
+        ));
+
+        // A delete finishing after another bucket was opened only reports
+        component.apply_delete_outcome("other".to_string(), 1, Err("timed out".to_string()));
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Event::Tab(TabEvent::TabAction(TabAction::Notify(_))))
+        ));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn copies_take_the_uri_of_the_selected_object() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = S3Component::new(sender);
+        component.results_navigator.set_content(NavigatorContent::Records(vec![
+            r#"{"key":"logs/2024/app.log","size":"12 bytes"}"#.to_string(),
+        ]));
+        assert_eq!(component.selected_object_uri(), None);
+
+        component.selected_bucket = Some("bucket".to_string());
+        assert_eq!(
+            component.selected_object_uri().as_deref(),
+            Some("s3://bucket/logs/2024/app.log")
+        );
+    }
+
+    #[test]
+    fn candidates_share_their_common_prefix() {
+        let keys = |keys: &[&str]| keys.iter().map(|key| key.to_string()).collect::<Vec<_>>();
+        assert_eq!(common_prefix(&keys(&["logs/2024/", "logs/2023/"])), "logs/202");
+        assert_eq!(common_prefix(&keys(&["logs/app.log"])), "logs/app.log");
+        assert_eq!(common_prefix(&keys(&["a/é1", "a/é2"])), "a/é");
+        assert_eq!(common_prefix(&keys(&["a", "b"])), "");
+        assert_eq!(common_prefix(&[]), "");
+    }
+
+    #[test]
+    fn completions_extend_the_typed_prefix_in_the_open_folder() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut component = S3Component::new(sender);
+        component.selected_bucket = Some("bucket".to_string());
+        component.current_path = "logs".to_string();
+
+        // A unique candidate completes the next path segment
+        component.input.set_content("20");
+        component.apply_completions("20".to_string(), Ok(vec!["logs/2024/".to_string()]));
+        assert_eq!(component.input.get_content().as_deref(), Some("2024/"));
+
+        // Several candidates complete as far as they agree and are listed
+        component.apply_completions(
+            "2024/".to_string(),
+            Ok(vec!["logs/2024/app-1.log".to_string(), "logs/2024/app-2.log".to_string()]),
+        );
+        assert_eq!(component.input.get_content().as_deref(), Some("2024/app-"));
+        assert_eq!(component.object_count, 2);
+        assert_eq!(component.search_prefix.as_deref(), Some("logs/2024/"));
+
+        // Completions of text that was edited since are dropped
+        component.apply_completions("2024/x".to_string(), Ok(vec!["logs/2024/xyz".to_string()]));
+        assert_eq!(component.input.get_content().as_deref(), Some("2024/app-"));
+
+        component.apply_completions("2024/app-".to_string(), Ok(vec![]));
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Event::Tab(TabEvent::TabAction(TabAction::Notify(message))))
+                if message == "No keys start with 'logs/2024/app-'"
+        ));
+    }
 }
@@ -1,29 +1,35 @@
-use crate::components::AWSComponent;
+use crate::components::{AWSComponent, SplitRatio, format_age};
 use crate::components::cloudwatch::CloudWatch;
+use crate::components::ecs::Ecs;
 use crate::components::s3::S3Component;
+use crate::components::parameter_store::ParameterStore;
 use crate::services::aws::TabClients;
 use crate::{
+    cli,
     components::dynamodb::DynamoDB,
+    diagnostics,
     event_managment::event::{
         ComponentAction, ComponentType,
-        Event, PopupAction, ServiceNavigatorEvent, TabAction, TabEvent, WidgetAction,
-        WidgetEventType, WidgetType,
+        Event, InputBoxEvent, InputBoxType, PopupAction, SendOrLog, ServiceNavigatorEvent,
+        TabAction, TabEvent, WidgetAction, WidgetEventType, WidgetType,
     },
     services::read_config,
+    theme::theme,
     widgets::{
         WidgetExt,
-        popup::{PopupContent, PopupWidget},
+        input_box::InputBoxWidget,
+        popup::{MIN_POPUP_HEIGHT, MIN_POPUP_WIDTH, PopupContent, PopupWidget},
         service_navigator::{NavigatorContent, ServiceNavigator},
     },
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::widgets::Borders;
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Paragraph, Tabs, Widget},
+    widgets::{Block, BorderType, Clear, Paragraph, Tabs, Widget},
 };
 use std::collections::HashMap;
 
@@ -31,6 +37,27 @@ use std::collections::HashMap;
 const TAB_HEIGHT: u16 = 3;
 const POPUP_PADDING: u16 = 5;
 const HELP_HEIGHT: u16 = 2;
+const STATUS_HEIGHT: u16 = 1;
+/// Smallest terminal width the tab layout fits in, with room for the profile popup
+pub const MIN_WIDTH: u16 = 2 * POPUP_PADDING + MIN_POPUP_WIDTH + 1;
+/// Smallest terminal height the tab layout fits in, with room for the profile popup
+pub const MIN_HEIGHT: u16 =
+    TAB_HEIGHT + 2 * POPUP_PADDING + MIN_POPUP_HEIGHT + 1 + STATUS_HEIGHT + HELP_HEIGHT;
+/// Title of the input for renaming a tab
+const RENAME_INPUT_TITLE: &str = "Rename tab (empty for the profile name)";
+/// Size of the input for renaming a tab
+const RENAME_INPUT_WIDTH: u16 = 50;
+const RENAME_INPUT_HEIGHT: u16 = 3;
+/// Title of the input for switching the tab's region
+const REGION_INPUT_TITLE: &str = "Switch region (e.g. us-east-1)";
+/// Region used when neither the app config nor the profile's AWS config sets one
+const DEFAULT_REGION: &str = "eu-west-1";
+/// Spinner frames after the title of a tab with a fetch running
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+/// Spinner frames for terminals or fonts without emoji, as set by `service_icons`
+const ASCII_SPINNER_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
+/// How long each spinner frame is shown
+const SPINNER_FRAME_MILLIS: u128 = 100;
 
 /// Indicates which side of the tab is currently in focus
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -41,10 +68,20 @@ pub enum TabFocus {
 
 /// Represents a tab within the application containing AWS service components
 pub struct Tab {
-    /// Display name for the tab (usually AWS profile name)
+    /// Identifies the tab, so events raised inside it are routed back to it
+    id: usize,
+    /// Display name for the tab, the AWS profile name unless the tab was renamed
     pub name: String,
+    /// Whether the name was chosen with F2 rather than taken from the profile
+    renamed: bool,
+    /// Input for a new tab name, open while renaming
+    rename_input: Option<InputBoxWidget>,
+    /// Input for another region, open while switching regions
+    region_input: Option<InputBoxWidget>,
     /// Whether the profile selection popup is active
     popup_mod: bool,
+    /// Profile selected automatically, opened once the tab is first shown
+    pending_profile: Option<String>,
     /// Optional popup widget for profile selection
     popup_widget: Option<Box<dyn WidgetExt>>,
     /// Map of service components on the right side
@@ -59,11 +96,24 @@ pub struct Tab {
     current_focus: TabFocus,
     /// AWS service clients for this tab
     aws_clients: TabClients,
+    /// Caller identity for the selected profile, or the error from resolving it
+    identity: Option<Result<String, String>>,
+    /// Short-lived status message, cleared on the next key press
+    notification: Option<String>,
+    /// Whether the service navigator is hidden so the component gets the full width
+    zen_mode: bool,
+    /// Focus to restore when leaving zen mode
+    focus_before_zen: TabFocus,
+    /// Width of the service navigator relative to the component
+    split: SplitRatio,
+    /// Whether power-user mode skips confirmations, warned about in the status line
+    power_user: bool,
 }
 
 impl Tab {
     /// Creates a new tab with initial AWS service components
     pub fn new(
+        id: usize,
         name: &str,
         content: &str,
         event_sender: tokio::sync::mpsc::UnboundedSender<Event>,
@@ -81,30 +131,73 @@ impl Tab {
             WidgetType::CloudWatch,
             Box::new(CloudWatch::new(event_sender.clone())),
         );
+        right_widgets.insert(
+            WidgetType::ParameterStore,
+            Box::new(ParameterStore::new(event_sender.clone())),
+        );
+        right_widgets.insert(WidgetType::ECS, Box::new(Ecs::new(event_sender.clone())));
 
+        let mut auto_profile = None;
         let profiles = match read_config::get_aws_profiles() {
-            Ok(profiles) => PopupContent::Profiles(profiles),
-            Err(_) => PopupContent::Profiles(vec!["No profiles found".to_string()]),
+            Ok(profiles) => {
+                auto_profile = read_config::get_auto_selected_profile(&profiles);
+                PopupContent::Profiles(profiles)
+            }
+            Err(err) => {
+                diagnostics::error(format!("Reading AWS profiles failed: {}", err));
+                PopupContent::Profiles(vec![err.to_string()])
+            }
         };
+        let mut popup = PopupWidget::new(profiles, content, true, true);
+        popup.set_recent_profiles(read_config::get_recent_profiles());
 
-        Self {
+        let mut tab = Self {
+            id,
             name: name.to_string(),
+            renamed: false,
+            rename_input: None,
+            region_input: None,
             popup_mod: true,
+            pending_profile: None,
             left_widgets: Box::new(ServiceNavigator::new(
                 WidgetType::AWSServiceNavigator,
                 false,
                 NavigatorContent::Services(WidgetEventType::VALUES.to_vec()),
             )),
 
-            popup_widget: Some(Box::new(PopupWidget::new(profiles, content, true, true))),
+            popup_widget: Some(Box::new(popup)),
             right_widgets,
             active_right_widget: WidgetType::DynamoDB,
             event_sender,
             current_focus: TabFocus::Left, // Default to left widget
-            aws_clients: TabClients::new(String::new(), String::from("eu-west-1")),
+            aws_clients: TabClients::new(String::new(), String::from(DEFAULT_REGION)),
+            identity: None,
+            notification: None,
+            zen_mode: false,
+            focus_before_zen: TabFocus::Left,
+            split: SplitRatio::load("services", 20),
+            power_user: read_config::get_power_user(),
+        };
+        // Skip the popup; the profile is opened when the tab becomes active
+        if let Some(profile) = auto_profile {
+            tab.set_profile(profile.clone());
+            tab.pending_profile = Some(profile);
+        }
+        tab
+    }
+
+    /// Opens the automatically selected profile, if it was not opened yet
+    pub async fn open_pending_profile(&mut self) {
+        if let Some(profile) = self.pending_profile.take() {
+            self.open_profile(profile, None, None).await;
         }
     }
 
+    /// Id that events raised inside the tab carry back to it
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
     /// Changes the active AWS service
     pub fn set_active_service(&mut self, service_type: WidgetType) {
         self.active_right_widget = service_type;
@@ -112,34 +205,80 @@ impl Tab {
 
     /// Handles keyboard input events for the tab
     pub fn handle_input(&mut self, event: KeyEvent) {
+        self.notification = None;
         if self.popup_mod {
             if let Some(popup) = self.popup_widget.as_mut() {
                 if let Some(signal) = popup.handle_input(event) {
                     self.event_sender
-                        .send(Event::Tab(TabEvent::WidgetActions(signal)))
-                        .unwrap();
+                        .send_or_log(Event::Tab(TabEvent::WidgetActions(signal)));
                 }
             }
+        } else if self.rename_input.is_some() {
+            self.handle_rename_input(event);
+        } else if self.region_input.is_some() {
+            self.handle_region_input(event);
         } else {
             match event.code {
+                // F2 renames the tab, starting from its current name
+                KeyCode::F(2) => {
+                    let mut input = InputBoxWidget::new(InputBoxType::Text, RENAME_INPUT_TITLE, true);
+                    input.set_content(&self.name);
+                    self.rename_input = Some(input);
+                }
+                // F3 or Ctrl+Shift+R switches the tab to another region, keeping the service
+                KeyCode::F(3) => self.start_region_switch(),
+                KeyCode::Char('r' | 'R')
+                    if event.modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT =>
+                {
+                    self.start_region_switch();
+                }
                 // Use Tab for focus switching (standard macOS behavior)
                 KeyCode::Tab => {
                     self.event_sender
-                        .send(Event::Tab(TabEvent::TabAction(TabAction::NextFocus)))
-                        .unwrap();
+                        .send_or_log(Event::Tab(TabEvent::TabAction(TabAction::NextFocus)));
                 }
                 KeyCode::BackTab => {
                     // Shift+Tab for reverse focus
                     self.event_sender
-                        .send(Event::Tab(TabEvent::TabAction(TabAction::PreviousFocus)))
-                        .unwrap();
+                        .send_or_log(Event::Tab(TabEvent::TabAction(TabAction::PreviousFocus)));
+                }
+                // Ctrl+B hides or shows the service navigator
+                KeyCode::Char('b') if event.modifiers == KeyModifiers::CONTROL => {
+                    self.event_sender
+                        .send_or_log(Event::Tab(TabEvent::TabAction(TabAction::ToggleZenMode)));
+                }
+                // Ctrl+R resets the focused service to the state right after it was opened
+                KeyCode::Char('r')
+                    if event.modifiers == KeyModifiers::CONTROL
+                        && self.current_focus == TabFocus::Right =>
+                {
+                    let component_type = match self.active_right_widget {
+                        WidgetType::S3 => Some(ComponentType::S3),
+                        WidgetType::DynamoDB => Some(ComponentType::DynamoDB),
+                        WidgetType::CloudWatch => Some(ComponentType::CloudWatch),
+                        WidgetType::ParameterStore => Some(ComponentType::ParameterStore),
+                        WidgetType::ECS => Some(ComponentType::ECS),
+                        _ => None,
+                    };
+                    if let Some(component_type) = component_type {
+                        self.event_sender.send_or_log(Event::Tab(TabEvent::ComponentActions(
+                            ComponentAction::Reset,
+                            component_type,
+                        )));
+                    }
+                }
+                // Ctrl+Left/Right resize the service navigator while it is focused
+                KeyCode::Left | KeyCode::Right
+                    if event.modifiers == KeyModifiers::CONTROL
+                        && self.current_focus == TabFocus::Left =>
+                {
+                    self.split.nudge(event.code == KeyCode::Right);
                 }
                 _ => {
                     if self.current_focus == TabFocus::Left {
                         if let Some(signal) = self.left_widgets.handle_input(event) {
                             self.event_sender
-                                .send(Event::Tab(TabEvent::WidgetActions(signal)))
-                                .unwrap();
+                                .send_or_log(Event::Tab(TabEvent::WidgetActions(signal)));
                         }
                     } else {
                         if let Some(widget) = self.right_widgets.get_mut(&self.active_right_widget)
@@ -152,8 +291,106 @@ impl Tab {
         }
     }
 
+    /// Edits the new tab name; Enter applies it and Esc keeps the current one
+    fn handle_rename_input(&mut self, event: KeyEvent) {
+        if event.code == KeyCode::Esc {
+            self.rename_input = None;
+            return;
+        }
+        let Some(input) = self.rename_input.as_mut() else {
+            return;
+        };
+        if let Some(name) = submitted_text(input, event, &mut self.notification) {
+            self.rename(&name);
+            self.rename_input = None;
+        }
+    }
+
+    /// Opens the region input, starting from the current region, once a profile is open
+    fn start_region_switch(&mut self) {
+        if self.popup_mod {
+            return;
+        }
+        let mut input = InputBoxWidget::new(InputBoxType::Text, REGION_INPUT_TITLE, true);
+        input.set_content(self.aws_clients.region());
+        self.region_input = Some(input);
+    }
+
+    /// Edits the region to switch to; Enter switches and Esc keeps the current one
+    fn handle_region_input(&mut self, event: KeyEvent) {
+        if event.code == KeyCode::Esc {
+            self.region_input = None;
+            return;
+        }
+        let Some(input) = self.region_input.as_mut() else {
+            return;
+        };
+        let Some(region) = submitted_text(input, event, &mut self.notification) else {
+            return;
+        };
+        let region = region.trim().to_lowercase();
+        // A typo would only show up as failed requests, so it keeps the input open
+        if !cli::is_region_name(&region) {
+            self.notification = Some(format!("Not a region name: {}", region));
+            return;
+        }
+        self.region_input = None;
+        self.event_sender
+            .send_or_log(Event::Tab(TabEvent::TabAction(TabAction::SwitchRegion(region))));
+    }
+
+    /// Moves the tab's clients to another region and reopens the service that is shown,
+    /// leaving the profile and the service selection as they are
+    fn switch_region(&mut self, region: String) {
+        if self.popup_mod || region == self.aws_clients.region() {
+            return;
+        }
+        self.aws_clients.set_region(region);
+        diagnostics::info(format!(
+            "Switched profile {} to region {}",
+            self.aws_clients.profile(),
+            self.aws_clients.region()
+        ));
+        self.notification = Some(format!("Switched to region {}", self.aws_clients.region()));
+        // Opening the service again gives it clients for the region and drops the results
+        // still in flight for the previous one
+        if let Some(service) = self.active_right_widget.service() {
+            self.event_sender
+                .send_or_log(Event::Tab(TabEvent::TabAction(TabAction::SelectService(
+                    service,
+                ))));
+        }
+    }
+
     /// Processes tab events and routes them to appropriate handlers
     pub async fn process_event(&mut self, tab_event: TabEvent) {
+        // Navigators in the tab or its components report clipboard copies here
+        if let TabEvent::WidgetActions(WidgetAction::ServiceNavigatorEvent(
+            ServiceNavigatorEvent::Copied(text),
+            _,
+        ))
+        | TabEvent::ComponentActions(
+            ComponentAction::WidgetAction(WidgetAction::ServiceNavigatorEvent(
+                ServiceNavigatorEvent::Copied(text),
+                _,
+            )),
+            _,
+        ) = &tab_event
+        {
+            self.notification = Some(format!("Copied: {}", text));
+            return;
+        }
+        // As well as any other message a widget has for the status line
+        if let TabEvent::WidgetActions(WidgetAction::Notify(message))
+        | TabEvent::ComponentActions(
+            ComponentAction::WidgetAction(WidgetAction::Notify(message)),
+            _,
+        ) = &tab_event
+        {
+            self.notification = Some(message.clone());
+            return;
+        }
+
         match tab_event {
             // Handle tab-level actions like focus changes and profile selection
             TabEvent::TabAction(tab_action) => {
@@ -191,6 +428,16 @@ impl Tab {
                     widget.process_event(component_action).await;
                 }
             }
+            ComponentType::ParameterStore => {
+                if let Some(widget) = self.right_widgets.get_mut(&WidgetType::ParameterStore) {
+                    widget.process_event(component_action).await;
+                }
+            }
+            ComponentType::ECS => {
+                if let Some(widget) = self.right_widgets.get_mut(&WidgetType::ECS) {
+                    widget.process_event(component_action).await;
+                }
+            }
         }
     }
 
@@ -203,10 +450,9 @@ impl Tab {
                             match signal {
                                 WidgetAction::PopupAction(PopupAction::ItemSelected(selected)) => {
                                     self.event_sender
-                                        .send(Event::Tab(TabEvent::TabAction(
+                                        .send_or_log(Event::Tab(TabEvent::TabAction(
                                             TabAction::SelectProfile(selected),
-                                        )))
-                                        .unwrap();
+                                        )));
                                 }
                                 _ => {}
                             }
@@ -222,10 +468,9 @@ impl Tab {
                             _widget_type,
                         ) => {
                             self.event_sender
-                                .send(Event::Tab(TabEvent::TabAction(TabAction::SelectService(
+                                .send_or_log(Event::Tab(TabEvent::TabAction(TabAction::SelectService(
                                     selected,
-                                ))))
-                                .unwrap();
+                                ))));
                         }
                         _ => {}
                     }
@@ -240,40 +485,82 @@ impl Tab {
         match tab_action {
             // Handle AWS profile selection
             TabAction::SelectProfile(profile) => {
-                self.set_name(profile);
+                self.open_profile(profile, None, None).await;
+            }
+            // A profile given on the command line, whose region and service take precedence
+            TabAction::OpenProfile(profile, region, service) => {
+                self.open_profile(profile, region, service).await;
+            }
+            // Only accept identities for the profile this tab is still using
+            TabAction::IdentityResolved(profile, identity) => {
+                if profile == self.aws_clients.profile() {
+                    if let Err(err) = &identity {
+                        diagnostics::warn(format!("Caller identity for {}: {}", profile, err));
+                    }
+                    self.identity = Some(identity);
+                }
             }
             // Handle AWS service selection from the left navigator
             TabAction::SelectService(service) => match service {
                 WidgetEventType::DynamoDB => {
                     self.active_right_widget = WidgetType::DynamoDB;
                     self.event_sender
-                        .send(Event::Tab(TabEvent::ComponentActions(
-                            ComponentAction::Active(self.name.clone()),
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                            ComponentAction::Active(
+                                self.aws_clients.profile().to_string(),
+                                self.aws_clients.region().to_string(),
+                            ),
                             ComponentType::DynamoDB,
-                        )))
-                        .unwrap();
+                        )));
                 }
                 WidgetEventType::S3 => {
                     self.active_right_widget = WidgetType::S3;
                     self.event_sender
-                        .send(Event::Tab(TabEvent::ComponentActions(
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
                             ComponentAction::Active(
-                                self.name.clone(),
+                                self.aws_clients.profile().to_string(),
+                                self.aws_clients.region().to_string(),
                             ),
                             ComponentType::S3,
-                        )))
-                        .unwrap();
+                        )));
                 }
                 WidgetEventType::CloudWatch => {
                     self.active_right_widget = WidgetType::CloudWatch;
                     self.event_sender
-                        .send(Event::Tab(TabEvent::ComponentActions(
-                            ComponentAction::Active(self.name.clone()),
-                            ComponentType::CloudWatch,)))
-                        .unwrap();
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                            ComponentAction::Active(
+                                self.aws_clients.profile().to_string(),
+                                self.aws_clients.region().to_string(),
+                            ),
+                            ComponentType::CloudWatch,)));
+                }
+                WidgetEventType::ParameterStore => {
+                    self.active_right_widget = WidgetType::ParameterStore;
+                    self.event_sender
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                            ComponentAction::Active(
+                                self.aws_clients.profile().to_string(),
+                                self.aws_clients.region().to_string(),
+                            ),
+                            ComponentType::ParameterStore,
+                        )));
+                }
+                WidgetEventType::ECS => {
+                    self.active_right_widget = WidgetType::ECS;
+                    self.event_sender
+                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                            ComponentAction::Active(
+                                self.aws_clients.profile().to_string(),
+                                self.aws_clients.region().to_string(),
+                            ),
+                            ComponentType::ECS,
+                        )));
                 }
                 _ => {}
             },
+            TabAction::SwitchRegion(region) => self.switch_region(region),
+            TabAction::ToggleZenMode => self.toggle_zen_mode(),
+            TabAction::Notify(message) => self.notification = Some(message),
             // Forward tab focus to the next widget
             TabAction::NextFocus => {
                 if self.current_focus == TabFocus::Left {
@@ -282,7 +569,11 @@ impl Tab {
                     self.forward_focus_event_to_component(self.active_right_widget);
                 } else {
                     if let Some(widget) = self.right_widgets.get_mut(&self.active_right_widget) {
-                        if widget.allows_focus_continuation() {
+                        if widget.allows_focus_continuation() && self.zen_mode {
+                            // The navigator is hidden, so wrap around to the component's first widget
+                            self.forward_unfocus_event_to_component(self.active_right_widget);
+                            self.forward_focus_event_to_component(self.active_right_widget);
+                        } else if widget.allows_focus_continuation() {
                             self.current_focus = TabFocus::Left;
                             self.forward_unfocus_event_to_component(self.active_right_widget);
                         } else {
@@ -291,26 +582,37 @@ impl Tab {
                             match self.active_right_widget {
                                 WidgetType::S3 => {
                                     self.event_sender
-                                        .send(Event::Tab(TabEvent::ComponentActions(
+                                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                             ComponentAction::NextFocus,
                                             ComponentType::S3,
-                                        )))
-                                        .unwrap();
+                                        )));
                                 }
                                 WidgetType::DynamoDB => {
                                     self.event_sender
-                                        .send(Event::Tab(TabEvent::ComponentActions(
+                                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                             ComponentAction::NextFocus,
                                                 ComponentType::DynamoDB,
-                                            )))
-                                        .unwrap();
+                                            )));
                                 }
                                 WidgetType::CloudWatch => {
                                     self.event_sender
-                                        .send(Event::Tab(TabEvent::ComponentActions(
+                                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                             ComponentAction::NextFocus, ComponentType::CloudWatch
-                                        )))
-                                        .unwrap();
+                                        )));
+                                }
+                                WidgetType::ParameterStore => {
+                                    self.event_sender
+                                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                            ComponentAction::NextFocus,
+                                            ComponentType::ParameterStore,
+                                        )));
+                                }
+                                WidgetType::ECS => {
+                                    self.event_sender
+                                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                            ComponentAction::NextFocus,
+                                            ComponentType::ECS,
+                                        )));
                                 }
                                 _ => {}
                             }
@@ -327,39 +629,52 @@ impl Tab {
                             match self.active_right_widget {
                                 WidgetType::S3 => {
                                     self.event_sender
-                                        .send(Event::Tab(TabEvent::ComponentActions(
+                                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                             ComponentAction::PreviousFocus, ComponentType::S3
-                                        )))
-                                        .unwrap();
+                                        )));
                                 }
                                 WidgetType::DynamoDB => {
                                     self.event_sender
-                                        .send(Event::Tab(TabEvent::ComponentActions(
+                                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                             ComponentAction::PreviousFocus,ComponentType::DynamoDB
-                                        )))
-                                        .unwrap();
+                                        )));
                                 }
 
                                 WidgetType::CloudWatch => {
                                     self.event_sender
-                                        .send(Event::Tab(TabEvent::ComponentActions(
+                                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
                                             ComponentAction::PreviousFocus, ComponentType::CloudWatch
-                                        )))
-                                        .unwrap();
+                                        )));
+                                }
+                                WidgetType::ParameterStore => {
+                                    self.event_sender
+                                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                            ComponentAction::PreviousFocus,
+                                            ComponentType::ParameterStore,
+                                        )));
+                                }
+                                WidgetType::ECS => {
+                                    self.event_sender
+                                        .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                                            ComponentAction::PreviousFocus,
+                                            ComponentType::ECS,
+                                        )));
                                 }
                                 _ => {}
                             }
+                        } else if self.zen_mode {
+                            // The navigator is hidden, so wrap around to the component's last widget
+                            widget.set_focus_to_last();
                         } else {
                             // Go back to left component
                             self.current_focus = TabFocus::Left;
                             self.event_sender
-                                .send(Event::Tab(TabEvent::WidgetActions(
+                                .send_or_log(Event::Tab(TabEvent::WidgetActions(
                                     WidgetAction::ServiceNavigatorEvent(
                                         ServiceNavigatorEvent::Unfocused,
                                         WidgetType::AWSServiceNavigator,
                                     ),
-                                )))
-                                .unwrap();
+                                )));
                         }
                     }
                 } else {
@@ -374,28 +689,54 @@ impl Tab {
         }
     }
 
+    /// Hides or shows the service navigator, keeping focus on a visible pane
+    fn toggle_zen_mode(&mut self) {
+        self.zen_mode = !self.zen_mode;
+        if self.zen_mode {
+            self.focus_before_zen = self.current_focus;
+            if self.current_focus == TabFocus::Left {
+                self.current_focus = TabFocus::Right;
+                self.forward_focus_event_to_component(self.active_right_widget);
+            }
+        } else if self.focus_before_zen == TabFocus::Left && self.current_focus == TabFocus::Right {
+            self.current_focus = TabFocus::Left;
+            self.forward_unfocus_event_to_component(self.active_right_widget);
+        }
+    }
+
     pub fn forward_focus_event_to_component(&mut self, component: WidgetType) {
         match component {
             WidgetType::S3 => {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::Focused,ComponentType::S3,
-                    )))
-                    .unwrap();
+                    )));
             }
             WidgetType::DynamoDB => {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::Focused,ComponentType::DynamoDB,
-                    )))
-                    .unwrap();
+                    )));
             }
             WidgetType::CloudWatch => {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::Focused,ComponentType::CloudWatch,
-                    )))
-                    .unwrap();
+                    )));
+            }
+            WidgetType::ParameterStore => {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::Focused,
+                        ComponentType::ParameterStore,
+                    )));
+            }
+            WidgetType::ECS => {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::Focused,
+                        ComponentType::ECS,
+                    )));
             }
             _ => {}
         }
@@ -405,24 +746,35 @@ impl Tab {
         match component {
             WidgetType::S3 => {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::Unfocused,ComponentType::S3
-                    )))
-                    .unwrap();
+                    )));
             }
             WidgetType::DynamoDB => {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::Unfocused,ComponentType::DynamoDB
-                    )))
-                    .unwrap();
+                    )));
             }
             WidgetType::CloudWatch => {
                 self.event_sender
-                    .send(Event::Tab(TabEvent::ComponentActions(
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
                         ComponentAction::Unfocused,ComponentType::CloudWatch
-                    )))
-                    .unwrap();
+                    )));
+            }
+            WidgetType::ParameterStore => {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::Unfocused,
+                        ComponentType::ParameterStore,
+                    )));
+            }
+            WidgetType::ECS => {
+                self.event_sender
+                    .send_or_log(Event::Tab(TabEvent::ComponentActions(
+                        ComponentAction::Unfocused,
+                        ComponentType::ECS,
+                    )));
             }
             _ => {}
         }
@@ -433,11 +785,141 @@ impl Tab {
         &self.name
     }
 
-    /// Set the tab name and configure the AWS profile
-    pub fn set_name(&mut self, name: String) {
-        self.name = name;
+    /// Forwards a tick of the event loop to the open service
+    pub fn tick(&mut self) {
+        if let Some(widget) = self.right_widgets.get_mut(&self.active_right_widget) {
+            widget.tick();
+        }
+    }
+
+    /// Whether the open service animates and needs the faster tick rate
+    pub fn is_animating(&self) -> bool {
+        self.right_widgets
+            .get(&self.active_right_widget)
+            .is_some_and(|widget| widget.is_animating())
+    }
+
+    /// Whether any service of the tab, shown or not, still has a background fetch running
+    pub fn is_busy(&self) -> bool {
+        self.right_widgets.values().any(|widget| widget.is_busy())
+    }
+
+    /// Title in the tab bar: the name after the open service's icon once a profile is open,
+    /// followed by a spinner while the tab is busy
+    pub fn title(&self) -> String {
+        let title = match self.active_right_widget.service() {
+            Some(service) if !self.popup_mod => format!("{} {}", service.icon(), self.name),
+            _ => self.name.clone(),
+        };
+        if self.is_busy() {
+            format!("{} {}", title, spinner_frame())
+        } else {
+            title
+        }
+    }
+
+    /// One-line summary of the tab's profile, region and active service for the tab overview
+    pub fn overview_label(&self) -> String {
+        let service = self.active_right_widget.service();
+        let service = service.as_ref().map_or("-", WidgetEventType::display_label);
+        if self.popup_mod {
+            return format!("{}  (no profile selected)", self.name);
+        }
+        format!(
+            "{}  profile: {}  region: {}  service: {}",
+            self.name,
+            self.aws_clients.profile(),
+            self.aws_clients.region(),
+            service
+        )
+    }
+
+    /// Switches the tab to an AWS profile, naming the tab after it unless it was renamed
+    pub fn set_profile(&mut self, profile: String) {
+        if !self.renamed {
+            self.name = profile.clone();
+        }
         self.popup_mod = false;
-        self.aws_clients.set_profile(self.name.clone());
+        self.pending_profile = None;
+        self.aws_clients.set_profile(profile);
+    }
+
+    /// Shows the tab under a name of its own; API calls keep using its profile and an
+    /// empty name goes back to the profile's name
+    pub fn rename(&mut self, name: &str) {
+        let name = name.trim();
+        self.renamed = !name.is_empty();
+        self.name = if self.renamed {
+            name.to_string()
+        } else {
+            self.aws_clients.profile().to_string()
+        };
+    }
+
+    /// Switches the tab to a profile, in the given region and service or else the
+    /// profile's configured defaults
+    async fn open_profile(
+        &mut self,
+        profile: String,
+        region: Option<String>,
+        service: Option<WidgetEventType>,
+    ) {
+        let defaults = read_config::get_profile_defaults(&profile);
+        if let Err(err) = read_config::save_recent_profile(&profile) {
+            diagnostics::warn(format!("Saving recent profiles failed: {}", err));
+        }
+        let region = region
+            .or(defaults.region)
+            .or_else(|| read_config::get_profile_region(&profile))
+            .unwrap_or_else(|| String::from(DEFAULT_REGION));
+        self.set_profile(profile);
+        self.aws_clients.set_region(region);
+        diagnostics::info(format!(
+            "Selected profile {} in region {}",
+            self.aws_clients.profile(),
+            self.aws_clients.region()
+        ));
+        self.resolve_identity().await;
+
+        // Open the preferred service instead of leaving the service list
+        if let Some(service) = service.or_else(|| {
+            defaults
+                .service
+                .as_deref()
+                .and_then(WidgetEventType::from_service_name)
+        }) {
+            self.event_sender
+                .send_or_log(Event::Tab(TabEvent::TabAction(TabAction::SelectService(
+                    service,
+                ))));
+        }
+    }
+
+    /// Looks up the caller identity for the current profile without blocking the UI
+    async fn resolve_identity(&mut self) {
+        self.identity = None;
+        match self.aws_clients.get_sts_client().await {
+            Ok(client) => {
+                let event_sender = self.event_sender.clone();
+                let profile = self.aws_clients.profile().to_string();
+                tokio::spawn(async move {
+                    let identity = client
+                        .lock()
+                        .await
+                        .get_caller_identity()
+                        .await
+                        .map_err(|err| err.to_string());
+                    event_sender
+                        .send_or_log(Event::Tab(TabEvent::TabAction(TabAction::IdentityResolved(
+                            profile, identity,
+                        ))));
+                });
+            }
+            Err(err) => {
+                diagnostics::error(format!("Failed to create STS client: {}", err));
+                self.identity = Some(Err(err.to_string()));
+            }
+        }
     }
 
     /// Renders the entire tab including tab bar, content, and help toolbar
@@ -449,30 +931,104 @@ impl Tab {
         let main_layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Min(1),              // Main content
-                Constraint::Length(HELP_HEIGHT), // Help toolbar
+                Constraint::Min(1),                // Main content
+                Constraint::Length(STATUS_HEIGHT), // Profile/region/identity status line
+                Constraint::Length(HELP_HEIGHT),   // Help toolbar
             ])
             .split(content_area);
 
         // Render the main widgets in the upper area
         self.render_widgets(main_layout[0], buf);
 
+        // Render the status line between the content and the help toolbar
+        self.render_status_line(main_layout[1], buf);
+
         // Render the help toolbar in the lower area
-        self.render_help_toolbar(main_layout[1], buf);
+        self.render_help_toolbar(main_layout[2], buf);
+
+        // The rename and region inputs float at the top of the content, below the tab bar
+        if let Some(input) = self.rename_input.as_ref().or(self.region_input.as_ref()) {
+            let width = RENAME_INPUT_WIDTH.min(main_layout[0].width);
+            let input_area = Rect::new(
+                main_layout[0].x + (main_layout[0].width - width) / 2,
+                main_layout[0].y,
+                width,
+                RENAME_INPUT_HEIGHT.min(main_layout[0].height),
+            );
+            Clear.render(input_area, buf);
+            input.render(input_area, buf);
+        }
+    }
+
+    /// Renders the active profile, region and caller identity
+    fn render_status_line(&self, area: Rect, buf: &mut Buffer) {
+        let label_style = Style::default().fg(theme().help_fg);
+        let value_style = Style::default().fg(theme().text_fg);
+
+        let mut status = Vec::new();
+        if !self.popup_mod {
+            status.push(Span::styled("Profile: ", label_style));
+            status.push(Span::styled(self.aws_clients.profile(), value_style));
+            status.push(Span::styled("  Region: ", label_style));
+            status.push(Span::styled(self.aws_clients.region(), value_style));
+            if let Some(endpoint_url) = self.aws_clients.endpoint_url() {
+                status.push(Span::styled("  Endpoint: ", label_style));
+                status.push(Span::styled(endpoint_url, Style::default().fg(theme().warning_fg)));
+            }
+            if self.power_user {
+                status.push(Span::styled(
+                    "  Confirmations off",
+                    Style::default().fg(theme().warning_fg),
+                ));
+            }
+            // How fresh the open service's data is, recomputed on every tick's redraw
+            if let Some(updated) = self
+                .right_widgets
+                .get(&self.active_right_widget)
+                .and_then(|component| component.last_updated())
+            {
+                status.push(Span::styled("  Updated: ", label_style));
+                status.push(Span::styled(format_age(updated.elapsed()), value_style));
+            }
+            status.push(Span::styled("  ", label_style));
+            // Notifications temporarily take the identity's place so they are never clipped
+            match &self.identity {
+                _ if self.notification.is_some() => status.push(Span::styled(
+                    self.notification.clone().unwrap_or_default(),
+                    Style::default().fg(theme().info_fg),
+                )),
+                Some(Ok(identity)) => {
+                    status.push(Span::styled(identity.as_str(), Style::default().fg(theme().success_fg)))
+                }
+                Some(Err(err)) => status.push(Span::styled(
+                    format!("Identity check failed: {}", err),
+                    Style::default().fg(theme().error_fg),
+                )),
+                None => status.push(Span::styled("Checking identity...", label_style)),
+            }
+        }
+
+        Paragraph::new(Line::from(status)).render(area, buf);
     }
 
     /// Renders a contextual help toolbar at the bottom of the tab
     fn render_help_toolbar(&self, area: Rect, buf: &mut Buffer) {
-        let help_style = Style::default().fg(Color::DarkGray);
+        let help_style = Style::default().fg(theme().help_fg);
         let key_style = Style::default()
-            .fg(Color::White)
+            .fg(theme().text_fg)
             .add_modifier(Modifier::BOLD);
 
         // Get help items from the active component or widget
         let mut help_items = Vec::new();
 
         // If popup is active, get help from popup
-        if self.popup_mod && self.popup_widget.is_some() {
+        if self.rename_input.is_some() {
+            help_items.push(("Enter".to_string(), "Rename tab".to_string()));
+            help_items.push(("Esc".to_string(), "Cancel".to_string()));
+        } else if self.region_input.is_some() {
+            help_items.push(("Enter".to_string(), "Switch region".to_string()));
+            help_items.push(("Esc".to_string(), "Cancel".to_string()));
+        } else if self.popup_mod && self.popup_widget.is_some() {
             if let Some(popup) = &self.popup_widget {
                 help_items = popup.get_help_items();
             }
@@ -486,6 +1042,7 @@ impl Tab {
                     // Get help items from active right component based on its type
                     if let Some(widget) = self.right_widgets.get(&self.active_right_widget) {
                         help_items = widget.get_help_items();
+                        help_items.push(("Ctrl+R".to_string(), "Reset view".to_string()));
                     }
                 }
             }
@@ -493,32 +1050,31 @@ impl Tab {
             // Always add global shortcuts if not in popup mode
             if !self.popup_mod {
                 help_items.push(("Tab".to_string(), "Switch focus".to_string()));
+                help_items.push((
+                    "Ctrl+B".to_string(),
+                    if self.zen_mode { "Show services" } else { "Hide services" }.to_string(),
+                ));
                 help_items.push(("⌘+T".to_string(), "New tab".to_string()));
                 help_items.push(("⌘+W".to_string(), "Close tab".to_string()));
                 help_items.push(("⌘+L".to_string(), "Next tab".to_string()));
                 help_items.push(("⌘+J".to_string(), "Previous tab".to_string()));
+                help_items.push(("⌘+O".to_string(), "Tab overview".to_string()));
+                help_items.push(("F2".to_string(), "Rename tab".to_string()));
+                help_items.push(("F3".to_string(), "Switch region".to_string()));
+                help_items.push(("⌘+G".to_string(), "Diagnostics".to_string()));
                 help_items.push(("⌘+Q".to_string(), "Quit".to_string()));
             }
         }
 
-        // Convert help items to styled spans
-        let mut help_text = Vec::new();
-        for (i, (key, description)) in help_items.iter().enumerate() {
-            if i > 0 {
-                help_text.push(Span::styled("  ", help_style));
-            }
-            help_text.push(Span::styled(key, key_style));
-            help_text.push(Span::styled(format!(":{}", description), help_style));
-        }
-
-        // Create the help bar
-        let help_paragraph = Paragraph::new(Line::from(help_text))
+        // Create the help bar, fitted to the width so the context items stay visible
+        let help_text = fit_help_items(&help_items, area.width as usize, key_style, help_style);
+        let help_paragraph = Paragraph::new(help_text)
             .style(help_style)
             .alignment(Alignment::Left)
             .block(
                 Block::default()
                     .borders(ratatui::widgets::Borders::TOP)
-                    .border_style(Style::default().fg(Color::DarkGray)),
+                    .border_style(Style::default().fg(theme().inactive_border)),
             );
 
         // Render the help toolbar
@@ -539,12 +1095,12 @@ impl Tab {
 
         let tab_titles: Vec<Line> = tab_titles
             .iter()
-            .map(|t| Line::from(Span::styled(t, Style::default().fg(Color::Yellow))))
+            .map(|t| Line::from(Span::styled(t, Style::default().fg(theme().title_fg))))
             .collect();
 
         let tabs = Tabs::new(tab_titles)
             .block(tab_block)
-            .highlight_style(Style::default().fg(Color::LightGreen))
+            .highlight_style(Style::default().fg(theme().active_tab_fg))
             .select(active_tab);
 
         let tab_area = Rect::new(area.x, area.y, area.width, TAB_HEIGHT);
@@ -555,17 +1111,24 @@ impl Tab {
     fn get_content_area(&self, area: Rect) -> Rect {
         Rect::new(
             area.x,
-            area.y + TAB_HEIGHT,
+            area.y.saturating_add(TAB_HEIGHT),
             area.width,
-            area.height - TAB_HEIGHT,
+            area.height.saturating_sub(TAB_HEIGHT),
         )
     }
 
     /// Creates the main horizontal layout for left/right panels
+    ///
+    /// In zen mode the left panel collapses to zero width and the right panel takes the whole area
     fn create_layout(&self, area: Rect) -> Vec<Rect> {
+        let constraints = if self.zen_mode {
+            [Constraint::Percentage(0), Constraint::Percentage(100)]
+        } else {
+            self.split.constraints()
+        };
         Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(20), Constraint::Percentage(80)])
+            .constraints(constraints)
             .split(area)
             .to_vec()
     }
@@ -580,9 +1143,9 @@ impl Tab {
             .border_type(BorderType::Plain)
             .border_style(
                 Style::default().fg(if self.current_focus == TabFocus::Left {
-                    Color::Red
+                    theme().active_border
                 } else {
-                    Color::DarkGray
+                    theme().inactive_border
                 }),
             );
 
@@ -591,18 +1154,20 @@ impl Tab {
             .border_type(BorderType::Plain)
             .border_style(
                 Style::default().fg(if self.current_focus == TabFocus::Right {
-                    Color::Red
+                    theme().active_border
                 } else {
-                    Color::DarkGray
+                    theme().inactive_border
                 }),
             );
 
         let left_inner = layout[0].inner(Margin::new(1, 1));
         let right_inner = layout[1].inner(Margin::new(1, 1));
 
-        left_block.render(layout[0], buf);
+        if !self.zen_mode {
+            left_block.render(layout[0], buf);
+            self.left_widgets.render(left_inner, buf);
+        }
         right_block.render(layout[1], buf);
-        self.left_widgets.render(left_inner, buf);
 
         if let Some(widget) = self.right_widgets.get(&self.active_right_widget) {
             widget.render(right_inner, buf);
@@ -618,10 +1183,214 @@ impl Tab {
     /// Calculates the centered area for the popup window
     fn calculate_popup_area(&self, base_area: Rect) -> Rect {
         Rect::new(
-            base_area.x + POPUP_PADDING,
-            base_area.y + POPUP_PADDING,
-            base_area.width - 2 * POPUP_PADDING,
-            base_area.height - 2 * POPUP_PADDING,
+            base_area.x.saturating_add(POPUP_PADDING),
+            base_area.y.saturating_add(POPUP_PADDING),
+            base_area.width.saturating_sub(2 * POPUP_PADDING),
+            base_area.height.saturating_sub(2 * POPUP_PADDING),
         )
     }
 }
+
+/// Lays out help items on one line of at most `width` columns. Items are kept in order, so
+/// the context items listed first win: they keep their descriptions while they fit, later
+/// items shrink to just their key, and whatever is still left is replaced by an ellipsis.
+fn fit_help_items(
+    items: &[(String, String)],
+    width: usize,
+    key_style: Style,
+    help_style: Style,
+) -> Line<'static> {
+    const SEPARATOR: &str = "  ";
+    const ELLIPSIS: &str = "…";
+
+    let ellipsis_width = Span::raw(ELLIPSIS).width();
+    let mut spans = Vec::new();
+    let mut used = 0;
+    let mut compact = false;
+    for (i, (key, description)) in items.iter().enumerate() {
+        let separator = if i > 0 { SEPARATOR.len() } else { 0 };
+        let key_width = Span::raw(key.as_str()).width();
+        let full_width = key_width + 1 + Span::raw(description.as_str()).width();
+        // Room must remain for the ellipsis unless this is the last item
+        let reserve = if i + 1 < items.len() { SEPARATOR.len() + ellipsis_width } else { 0 };
+        let fits = |item_width: usize| used + separator + item_width + reserve <= width;
+
+        compact |= !fits(full_width);
+        let item_width = if compact { key_width } else { full_width };
+        if !fits(item_width) {
+            if used + separator + ellipsis_width <= width {
+                if i > 0 {
+                    spans.push(Span::styled(SEPARATOR, help_style));
+                }
+                spans.push(Span::styled(ELLIPSIS, help_style));
+            }
+            break;
+        }
+
+        if i > 0 {
+            spans.push(Span::styled(SEPARATOR, help_style));
+        }
+        spans.push(Span::styled(key.clone(), key_style));
+        if !compact {
+            spans.push(Span::styled(format!(":{}", description), help_style));
+        }
+        used += separator + item_width;
+    }
+    Line::from(spans)
+}
+
+/// Passes a key to a prompt's input and returns its text once Enter submits it, putting
+/// any message the input has, e.g. about a failed paste, in the status line
+fn submitted_text(
+    input: &mut InputBoxWidget,
+    event: KeyEvent,
+    notification: &mut Option<String>,
+) -> Option<String> {
+    let written = input
+        .handle_input(event)
+        .and_then(|action| input.process_event(action));
+    match written {
+        // Copy and paste report the content as written too, so only Enter submits
+        Some(WidgetAction::InputBoxEvent(InputBoxEvent::Written(text), _))
+            if event.code == KeyCode::Enter =>
+        {
+            Some(text)
+        }
+        Some(WidgetAction::Notify(message)) => {
+            *notification = Some(message);
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Frame of the busy spinner to show now, so every busy tab turns in step
+fn spinner_frame() -> &'static str {
+    let frames: &[&str] = if theme().service_icons {
+        &SPINNER_FRAMES
+    } else {
+        &ASCII_SPINNER_FRAMES
+    };
+    let elapsed = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    frames[(elapsed / SPINNER_FRAME_MILLIS) as usize % frames.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer_text(buf: &Buffer) -> String {
+        buf.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    fn press(tab: &mut Tab, code: KeyCode, modifiers: KeyModifiers) {
+        tab.handle_input(KeyEvent::new(code, modifiers));
+    }
+
+    fn help_text(items: &[(&str, &str)], width: usize) -> String {
+        let items: Vec<(String, String)> =
+            items.iter().map(|(key, description)| (key.to_string(), description.to_string())).collect();
+        let line = fit_help_items(&items, width, Style::default(), Style::default());
+        assert!(line.width() <= width);
+        line.to_string()
+    }
+
+    #[test]
+    fn help_items_shrink_to_the_width() {
+        let items = [("Enter", "Open"), ("Esc", "Back"), ("⌘+Q", "Quit")];
+        assert_eq!(help_text(&items, 80), "Enter:Open  Esc:Back  ⌘+Q:Quit");
+        // Later items lose their descriptions before the first ones do
+        assert_eq!(help_text(&items, 24), "Enter:Open  Esc:Back  …");
+        assert_eq!(help_text(&items, 20), "Enter:Open  Esc  ⌘+Q");
+        assert_eq!(help_text(&items, 15), "Enter:Open  …");
+        assert_eq!(help_text(&items, 3), "…");
+        assert_eq!(help_text(&items, 0), "");
+    }
+
+    #[test]
+    fn renaming_keeps_the_profile() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut tab = Tab::new(0, "Tab 1", "Profiles", sender);
+        tab.set_profile("prod".to_string());
+        assert_eq!(tab.name, "prod");
+
+        press(&mut tab, KeyCode::F(2), KeyModifiers::NONE);
+        press(&mut tab, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        for c in "prod eu".chars() {
+            press(&mut tab, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        press(&mut tab, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(tab.name, "prod eu");
+        assert_eq!(tab.aws_clients.profile(), "prod");
+
+        // Switching profiles keeps the custom name; an empty one restores the profile's
+        tab.set_profile("dev".to_string());
+        assert_eq!(tab.name, "prod eu");
+        press(&mut tab, KeyCode::F(2), KeyModifiers::NONE);
+        press(&mut tab, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        press(&mut tab, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(tab.name, "dev");
+
+        // Esc leaves the name as it was
+        press(&mut tab, KeyCode::F(2), KeyModifiers::NONE);
+        press(&mut tab, KeyCode::Char('x'), KeyModifiers::NONE);
+        press(&mut tab, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(tab.name, "dev");
+        assert!(tab.rename_input.is_none());
+    }
+
+    #[tokio::test]
+    async fn switching_regions_keeps_the_profile_and_service() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut tab = Tab::new(0, "Tab 1", "Profiles", sender);
+        tab.set_profile("prod".to_string());
+        tab.set_active_service(WidgetType::S3);
+
+        // A typo keeps the input open instead of switching
+        press(&mut tab, KeyCode::F(3), KeyModifiers::NONE);
+        press(&mut tab, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        for c in "us-east".chars() {
+            press(&mut tab, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        press(&mut tab, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(tab.region_input.is_some());
+        assert!(receiver.try_recv().is_err());
+
+        press(&mut tab, KeyCode::Char('-'), KeyModifiers::NONE);
+        press(&mut tab, KeyCode::Char('1'), KeyModifiers::NONE);
+        press(&mut tab, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(tab.region_input.is_none());
+        let Ok(Event::Tab(TabEvent::TabAction(action))) = receiver.try_recv() else {
+            panic!("expected a tab action");
+        };
+        tab.process_tab_action(action).await;
+        assert_eq!(tab.aws_clients.region(), "us-east-1");
+        assert_eq!(tab.aws_clients.profile(), "prod");
+
+        // The shown service is opened again in the new region
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Event::Tab(TabEvent::TabAction(TabAction::SelectService(WidgetEventType::S3))))
+        ));
+    }
+
+    #[test]
+    fn layout_shrinks_without_panicking() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let tab = Tab::new(0, "Tab 1", "Profiles", sender);
+
+        for (width, height) in [(0, 0), (5, 2), (MIN_WIDTH - 1, MIN_HEIGHT - 1), (80, 4)] {
+            let area = Rect::new(0, 0, width, height);
+            tab.render(area, &mut Buffer::empty(area), vec!["Tab 1".into()], 0);
+        }
+
+        // At the minimum size there is still room for the profile popup
+        let area = Rect::new(0, 0, MIN_WIDTH, MIN_HEIGHT);
+        let mut buf = Buffer::empty(area);
+        tab.render(area, &mut buf, vec!["Tab 1".into()], 0);
+        assert!(buffer_text(&buf).contains("Profiles"));
+    }
+}
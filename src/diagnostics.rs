@@ -0,0 +1,144 @@
+//! Diagnostics module
+//!
+//! Keeps a bounded, in-memory log of what components and clients did so problems
+//! can be inspected inside the TUI (Ctrl+G) instead of being printed over the screen.
+//! Once the buffer is full the oldest entries are dropped.
+
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+/// Maximum number of entries kept in the log
+pub const CAPACITY: usize = 500;
+
+/// Log shared by the whole application
+static LOG: OnceLock<Mutex<DiagnosticsLog>> = OnceLock::new();
+
+/// Severity of a diagnostics entry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Info,
+    Warn,
+    Error,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Level::Info => "INFO",
+            Level::Warn => "WARN",
+            Level::Error => "ERROR",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// A single diagnostics message
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub message: String,
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:<5} {}",
+            self.time.format("%H:%M:%S%.3f"),
+            self.level,
+            self.message
+        )
+    }
+}
+
+/// Ring buffer of diagnostics entries
+#[derive(Debug)]
+pub struct DiagnosticsLog {
+    entries: VecDeque<Entry>,
+    capacity: usize,
+}
+
+impl DiagnosticsLog {
+    /// Creates an empty log that keeps at most `capacity` entries
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Appends an entry, dropping the oldest one when the log is full
+    pub fn push(&mut self, level: Level, message: String) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Entry {
+            time: Local::now(),
+            level,
+            message,
+        });
+    }
+
+    /// Entries from oldest to newest
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &Entry> {
+        self.entries.iter()
+    }
+}
+
+/// Adds an entry to the application log
+pub fn log(level: Level, message: impl Into<String>) {
+    let log = LOG.get_or_init(|| Mutex::new(DiagnosticsLog::new(CAPACITY)));
+    // A poisoned lock only means another thread panicked while logging; keep the entries
+    let mut log = log.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    log.push(level, message.into());
+}
+
+pub fn info(message: impl Into<String>) {
+    log(Level::Info, message);
+}
+
+pub fn warn(message: impl Into<String>) {
+    log(Level::Warn, message);
+}
+
+pub fn error(message: impl Into<String>) {
+    log(Level::Error, message);
+}
+
+/// Formatted entries of the application log, newest first
+pub fn recent_entries() -> Vec<String> {
+    let Some(log) = LOG.get() else {
+        return Vec::new();
+    };
+    let log = log.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    log.entries().rev().map(|entry| entry.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oldest_entries_are_dropped_when_full() {
+        let mut log = DiagnosticsLog::new(3);
+        for i in 0..5 {
+            log.push(Level::Info, format!("message {}", i));
+        }
+        let messages: Vec<&str> = log.entries().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["message 2", "message 3", "message 4"]);
+    }
+
+    #[test]
+    fn entries_show_level_and_message() {
+        let mut log = DiagnosticsLog::new(1);
+        log.push(Level::Error, "client failed".into());
+        let line = log.entries().next().unwrap().to_string();
+        assert!(line.ends_with("ERROR client failed"));
+    }
+}
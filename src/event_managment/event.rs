@@ -1,12 +1,22 @@
+use crate::diagnostics;
+use crate::services::read_config::{self, TickRates};
+use crate::theme::theme;
+use crate::services::aws::CappedRecords;
+use crate::services::aws::cloudwatch_client::{AlarmSummary, MetricId};
+use crate::services::aws::dynamo_client::QueryFailure;
+use crate::services::aws::ecs_client::ServiceDetails;
+use crate::services::aws::log_link::LogView;
+use crate::services::aws::s3_client::{DeleteOutcome, ObjectPage};
+use crate::services::aws::ssm_client::Parameter;
+use chrono::{DateTime, Utc};
 use color_eyre::eyre::OptionExt;
 use futures::{FutureExt, StreamExt};
 use ratatui::crossterm::event::Event as CrosstermEvent;
-use ratatui::crossterm::event::KeyEvent;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
-use tokio::sync::mpsc;
-
-/// The frequency at which tick events are emitted.
-const TICK_RATE: f64 = 30.0;
+use tokio::sync::{Notify, mpsc};
 
 /// Main event enum for the application
 #[derive(Clone)]
@@ -17,8 +27,11 @@ pub enum Event {
     Crossterm(CrosstermEvent),
     /// Custom application-level events
     App(AppEvent),
-    /// Tab-related events
+    /// Tab-related events for the active tab
     Tab(TabEvent),
+    /// Tab-related events raised inside the tab with this id, routed back to it even once
+    /// another tab is active
+    FromTab(usize, TabEvent),
 }
 
 #[derive(Clone)]
@@ -26,6 +39,9 @@ pub enum ComponentType {
     S3,
     DynamoDB,
     CloudWatch,
+    ParameterStore,
+    #[allow(clippy::upper_case_acronyms)] // spelled like the service, as S3 is
+    ECS,
 }
 
 /// Events related to tab functionality
@@ -38,14 +54,26 @@ pub enum TabEvent {
 #[derive(Clone)]
 pub enum ComponentAction {
     // Common actions
+    // Focus contract: the tab sends `Focused` when focus moves onto a component, which
+    // must mark itself active; `Unfocused` when focus leaves, which must mark it inactive.
     Unfocused,
     FocusedToLast,
     Focused,
-    Active(String), // aws profile
+    Active(String, String), // aws profile, region
     NextFocus,
     PreviousFocus,
     PopupDetails(String),
+    // Shows the message in the component's error banner and the diagnostics log
+    ShowError(String),
+    // Clears filters, selections and results, as if the service had just been opened
+    Reset,
+    // Copies the AWS CLI command repeating the component's current listing or query
+    CopyCliCommand,
     WidgetAction(WidgetAction),
+    // Result of a background task, dropped unless the generation is still current
+    BackgroundResult(u64, Box<ComponentAction>),
+    // A background fetch succeeded, so the component's data is fresh
+    MarkUpdated,
 
     // Navigation actions
     ArrowUp,
@@ -57,15 +85,68 @@ pub enum ComponentAction {
     SearchLogs(String),
     ViewLogDetails(String),
     SetTimeRange(String),
+    DescribeLogGroup(String),
+    ToggleMetrics, // switch between log groups and metrics
+    ListMetrics(String), // namespace, all metrics when empty
+    MetricsListed(Result<Vec<MetricId>, String>),
+    SelectMetric(String), // label of the metric to chart
+    MetricDataLoaded(String, Result<Vec<(DateTime<Utc>, f64)>, String>), // label, datapoints or error
+    ToggleAlarms, // switch between log groups and alarms
+    ListAlarms(String), // alarm name prefix, all alarms when empty
+    AlarmsListed(Result<Vec<AlarmSummary>, String>),
+    SelectAlarm(String), // label of the alarm to show the details of
+    ToggleLiveTail, // follow new events of the selected log group, or stop
+    LogsTailed(Result<(Vec<String>, Option<i64>), String>), // new events and the newest timestamp, or error
+    OpenLogView(LogView), // search a log group as a cw:// link describes
+    CopyLogLink, // copy the current search as a cw:// link
+    FocusResults, // a search typed into the inputs succeeded, so its results take the focus
 
     // S3 specific actions
     SelectBucket(String),
     NavigateFolder(String),
     LoadPath(String, String), // bucket, path
+    LoadMoreObjects,
+    ObjectsListed(String, Option<String>, Result<ObjectPage, String>), // title, searched prefix, first page or error
+    SearchPrefix(String), // list the keys starting with this in the open folder
+    DownloadSelected,
+    ObjectsDownloaded(String), // summary of a background download
+    DeleteSelected,
+    ObjectsDeleted(String, usize, Result<DeleteOutcome, String>), // bucket, keys requested, outcome or error
+    ToggleVersions, // list the selected object's versions, or go back to the listing
+    VersionDetails(String, String), // key, version id
+    CompletePrefix(String), // complete the typed key prefix against the keys in the bucket
+    PrefixCompletions(String, Result<Vec<String>, String>), // typed text, keys and folders it may complete to, or error
+    NewBucket, // ask for the name of a bucket to create in the tab's region
+    CreateBucket(String),
+    RequestBucketDelete(String), // ask to confirm deleting this bucket
+    NewPresignedUrl, // ask how long a URL of the highlighted object stays valid
+    CreatePresignedUrl(String), // typed expiry
 
     // DynamoDB specific actions
     SetTitle(String),
     SetQuery(String),
+    QueryCompleted(Result<CappedRecords, QueryFailure>), // items or error of a background query
+    KeysNotFound(Vec<String>), // keys of a batch get that no item has
+    DescribeTable(String),
+    TableDescribed(String, Result<String, String>), // table, summary or error
+    SortKeyLoaded(String, Result<Option<String>, String>), // table, its sort key attribute or error
+    RefineQuery(String), // item JSON whose partition key value goes into the query input
+
+    // SSM Parameter Store specific actions
+    ListParameters(String), // path prefix, every parameter when empty
+    ParametersListed(Result<Vec<String>, String>), // names or error
+    SelectParameter(String),
+    ParameterLoaded(Result<Parameter, String>), // parameter with its value still masked, or error
+    ToggleReveal, // show the selected parameter's value, or mask it again
+    ParameterRevealed(Result<Parameter, String>), // parameter read for revealing, or error
+    DecryptionDenied(String), // name of a SecureString the profile may not decrypt
+
+    // ECS specific actions
+    ClustersListed(Result<Vec<String>, String>), // names or error
+    SelectCluster(String),
+    ServicesListed(String, Result<Vec<String>, String>), // cluster, service names or error
+    SelectService(String),
+    ServiceDescribed(Result<ServiceDetails, String>),
 }
 
 #[derive(Clone)]
@@ -73,6 +154,9 @@ pub enum InputBoxType {
     Text,
     TimeRange,
     SortKey,
+    Projection,
+    BucketName,
+    PresignExpiry,
 }
 /// Actions that can be performed on widgets
 #[derive(Clone)]
@@ -82,6 +166,7 @@ pub enum WidgetAction {
     ParagraphEvent(ParagraphEvent),
     ToggleFocusState,
     PopupAction(PopupAction),
+    Notify(String), // message for the tab's status line, e.g. that the clipboard failed
 }
 
 /// Actions specific to tab navigation and selection
@@ -90,16 +175,30 @@ pub enum TabAction {
     NextFocus,
     PreviousFocus,
     SelectProfile(String),
+    OpenProfile(String, Option<String>, Option<WidgetEventType>), // profile, region and service overriding its defaults
     SelectService(WidgetEventType),
+    SwitchRegion(String), // region to move the tab's clients to, keeping profile and service
+    ToggleZenMode,
+    IdentityResolved(String, Result<String, String>), // profile, caller identity or error
+    Notify(String), // short message shown in the status line until the next key press
 }
 
 /// Events for popup widgets
 #[derive(Clone)]
 pub enum PopupAction {
     ItemSelected(String),
+    Confirmed,
     ArrowUp,
     ArrowDown,
     Enter,
+    ToggleNode,
+    ToggleRaw,
+    ToggleDecoded, // show an encoded message decoded, or raw again
+    ToggleComplete, // show the complete details instead of simplified ones, or back
+    HistoryBack,    // show the details viewed before the current ones
+    HistoryForward, // show the details viewed after the current ones
+    FilterInput(char), // character typed into the profile filter
+    FilterBackspace,
     Escape,
     Cancel,
 }
@@ -107,12 +206,18 @@ pub enum PopupAction {
 /// Events for AWS service navigation
 #[derive(Clone)]
 pub enum ServiceNavigatorEvent {
-    UpdateContent(Vec<String>, bool), // tile, reset
+    UpdateContent(Vec<String>, bool, Option<usize>), // records, append instead of replacing, total if known
     UpdateTitle(String),
+    ShowPlaceholder(String), // message shown instead of records, e.g. for an empty listing
     Unfocused,
     Focused,
     ItemSelected(WidgetEventType),
     FilterTextChanged(String),
+    SearchTextChanged(String),
+    SearchNext,
+    SearchPrevious,
+    ToggleSelection,
+    Copied(String), // text copied to the clipboard
     ArrowUp,
     ArrowDown,
     Backspace,
@@ -138,6 +243,15 @@ pub enum InputBoxEvent {
     Delete,
     Left,
     Right,
+    WordLeft,
+    WordRight,
+    Home,
+    End,
+    DeleteToStart,
+    DeleteToEnd,
+    ClearHistory,
+    Complete, // Tab in an input that completes its text
+    CompletionRequested(String),
     Written(String),
     KeyPress(KeyEvent),
 }
@@ -158,11 +272,64 @@ pub enum WidgetEventType {
     S3,
     DynamoDB,
     CloudWatch,
+    ParameterStore,
+    #[allow(clippy::upper_case_acronyms)] // spelled like the service, as S3 is
+    ECS,
     RecordSelected(String),
 }
 
 impl WidgetEventType {
-    pub const VALUES: [Self; 3] = [Self::S3, Self::DynamoDB, Self::CloudWatch];
+    pub const VALUES: [Self; 5] = [
+        Self::S3,
+        Self::DynamoDB,
+        Self::CloudWatch,
+        Self::ParameterStore,
+        Self::ECS,
+    ];
+
+    /// Looks up a service by its display name, ignoring case (e.g. "cloudwatch")
+    pub fn from_service_name(name: &str) -> Option<Self> {
+        Self::VALUES
+            .iter()
+            .find(|service| service.to_string().eq_ignore_ascii_case(name.trim()))
+            .cloned()
+    }
+
+    /// Short glyph for the service, an ASCII tag like `[S3]` when the theme turns icons off
+    pub fn icon(&self) -> &str {
+        let icons = theme().service_icons;
+        match self {
+            Self::S3 if icons => "🪣",
+            Self::S3 => "[S3]",
+            Self::DynamoDB if icons => "📇",
+            Self::DynamoDB => "[DDB]",
+            Self::CloudWatch if icons => "📊",
+            Self::CloudWatch => "[CW]",
+            Self::ParameterStore if icons => "🔐",
+            Self::ParameterStore => "[SSM]",
+            Self::ECS if icons => "🐳",
+            Self::ECS => "[ECS]",
+            Self::RecordSelected(_) => "",
+        }
+    }
+
+    /// Name shown in lists and titles: the service after its icon, or a record as is
+    pub fn display_label(&self) -> &str {
+        let icons = theme().service_icons;
+        match self {
+            Self::S3 if icons => "🪣 S3",
+            Self::S3 => "[S3] S3",
+            Self::DynamoDB if icons => "📇 DynamoDB",
+            Self::DynamoDB => "[DDB] DynamoDB",
+            Self::CloudWatch if icons => "📊 CloudWatch",
+            Self::CloudWatch => "[CW] CloudWatch",
+            Self::ParameterStore if icons => "🔐 SSM",
+            Self::ParameterStore => "[SSM] SSM",
+            Self::ECS if icons => "🐳 ECS",
+            Self::ECS => "[ECS] ECS",
+            Self::RecordSelected(record) => record,
+        }
+    }
 }
 
 impl std::fmt::Display for WidgetEventType {
@@ -171,6 +338,8 @@ impl std::fmt::Display for WidgetEventType {
             WidgetEventType::S3 => write!(f, "S3"),
             WidgetEventType::DynamoDB => write!(f, "DynamoDB"),
             WidgetEventType::CloudWatch => write!(f, "CloudWatch"),
+            WidgetEventType::ParameterStore => write!(f, "SSM"),
+            WidgetEventType::ECS => write!(f, "ECS"),
             WidgetEventType::RecordSelected(record) => write!(f, "{}", record),
         }
     }
@@ -183,6 +352,11 @@ pub enum AppEvent {
     PreviousTab,
     CreateTab,
     CloseTab,
+    JumpToTab(usize), // zero-based tab index
+    MoveTabLeft,
+    MoveTabRight,
+    ShowTabOverview,
+    ShowDiagnostics,
     Quit,
 }
 
@@ -195,10 +369,75 @@ pub enum WidgetType {
     S3,
     DynamoDB,
     CloudWatch,
+    ParameterStore,
+    #[allow(clippy::upper_case_acronyms)] // spelled like the service, as S3 is
+    ECS,
     InputBox,
     QueryResultsNavigator,
 }
 
+impl WidgetType {
+    /// The AWS service a service component shows, `None` for other widgets
+    pub fn service(&self) -> Option<WidgetEventType> {
+        match self {
+            Self::S3 => Some(WidgetEventType::S3),
+            Self::DynamoDB => Some(WidgetEventType::DynamoDB),
+            Self::CloudWatch => Some(WidgetEventType::CloudWatch),
+            Self::ParameterStore => Some(WidgetEventType::ParameterStore),
+            Self::ECS => Some(WidgetEventType::ECS),
+            _ => None,
+        }
+    }
+}
+
+impl Event {
+    /// Short description of the event for the diagnostics log
+    pub fn summary(&self) -> String {
+        match self {
+            Event::Tick => "tick".to_string(),
+            Event::Crossterm(_) => "terminal input".to_string(),
+            Event::App(app_event) => format!("app event {:?}", app_event),
+            Event::Tab(tab_event) | Event::FromTab(_, tab_event) => match tab_event {
+                TabEvent::TabAction(action) => format!("tab action {:?}", action),
+                TabEvent::WidgetActions(_) => "widget action".to_string(),
+                TabEvent::ComponentActions(_, component_type) => {
+                    format!("{} component action", component_type.name())
+                }
+            },
+        }
+    }
+}
+
+impl ComponentType {
+    /// Display name of the service behind the component
+    pub fn name(&self) -> &'static str {
+        match self {
+            ComponentType::S3 => "S3",
+            ComponentType::DynamoDB => "DynamoDB",
+            ComponentType::CloudWatch => "CloudWatch",
+            ComponentType::ParameterStore => "SSM",
+            ComponentType::ECS => "ECS",
+        }
+    }
+}
+
+/// Sends events without panicking when the receiving side is gone
+pub trait SendOrLog {
+    /// Sends the event, recording a warning in the diagnostics log if nobody receives it
+    fn send_or_log(&self, event: Event);
+}
+
+impl SendOrLog for mpsc::UnboundedSender<Event> {
+    fn send_or_log(&self, event: Event) {
+        if let Err(mpsc::error::SendError(event)) = self.send(event) {
+            diagnostics::warn(format!(
+                "Dropped {}: the event loop has stopped",
+                event.summary()
+            ));
+        }
+    }
+}
+
 /// Handles event processing and distribution
 #[derive(Debug)]
 pub struct EventHandler {
@@ -206,6 +445,12 @@ pub struct EventHandler {
     pub sender: mpsc::UnboundedSender<Event>,
     /// Channel for receiving events
     receiver: mpsc::UnboundedReceiver<Event>,
+    /// Woken when ⌘+Q is pressed, even while the main loop is busy with an event
+    quit: Arc<Notify>,
+    /// Whether something animates, which switches the event task to the faster tick rate
+    animating: Arc<AtomicBool>,
+    /// Id handed to the next tab
+    next_tab_id: usize,
 }
 
 impl EventHandler {
@@ -213,9 +458,57 @@ impl EventHandler {
     /// and spawns a background task to process events
     pub fn new() -> Self {
         let (sender, receiver) = mpsc::unbounded_channel();
-        let actor = EventTask::new(sender.clone());
+        let quit = Arc::new(Notify::new());
+        let animating = Arc::new(AtomicBool::new(false));
+        let actor = EventTask::new(
+            sender.clone(),
+            Arc::clone(&quit),
+            Arc::clone(&animating),
+            read_config::get_tick_rates(),
+        );
         tokio::spawn(async { actor.run().await });
-        Self { sender, receiver }
+        Self {
+            sender,
+            receiver,
+            quit,
+            animating,
+            next_tab_id: 0,
+        }
+    }
+
+    /// Hands out an id for a new tab and the sender its components raise events on
+    ///
+    /// Tab events sent on it arrive as [`Event::FromTab`] with that id, so results of
+    /// requests still in flight reach their tab after the user switched to another one.
+    pub fn tab_sender(&mut self) -> (usize, mpsc::UnboundedSender<Event>) {
+        let tab_id = self.next_tab_id;
+        self.next_tab_id += 1;
+        let (tab_sender, mut receiver) = mpsc::unbounded_channel();
+        let sender = self.sender.clone();
+        // Ends once the tab and every task it spawned dropped their senders
+        tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                sender.send_or_log(match event {
+                    Event::Tab(tab_event) => Event::FromTab(tab_id, tab_event),
+                    event => event,
+                });
+            }
+        });
+        (tab_id, tab_sender)
+    }
+
+    /// Switches ticks to the active rate while something animates, and back to the idle
+    /// rate once nothing does
+    pub fn set_animating(&self, animating: bool) {
+        self.animating.store(animating, Ordering::Relaxed);
+    }
+
+    /// Signal the event task raises when the user asks to quit
+    ///
+    /// Only tasks already waiting on it are woken, so a quit key that was handled normally
+    /// leaves nothing behind.
+    pub fn quit_signal(&self) -> Arc<Notify> {
+        Arc::clone(&self.quit)
     }
 
     /// Waits for and returns the next event from the channel
@@ -241,25 +534,57 @@ impl EventHandler {
 struct EventTask {
     /// Channel for sending events to the main application
     sender: mpsc::UnboundedSender<Event>,
+    /// Raised for quit keys besides sending them on
+    quit: Arc<Notify>,
+    /// Set by the main loop while something animates
+    animating: Arc<AtomicBool>,
+    /// Ticks per second while idle and while animating
+    tick_rates: TickRates,
 }
 
 impl EventTask {
     /// Creates a new event task with the provided sender channel
-    fn new(sender: mpsc::UnboundedSender<Event>) -> Self {
-        Self { sender }
+    fn new(
+        sender: mpsc::UnboundedSender<Event>,
+        quit: Arc<Notify>,
+        animating: Arc<AtomicBool>,
+        tick_rates: TickRates,
+    ) -> Self {
+        Self {
+            sender,
+            quit,
+            animating,
+            tick_rates,
+        }
+    }
+
+    /// Time between ticks at the idle or the active rate
+    fn tick_period(&self, animating: bool) -> Duration {
+        let rate = if animating {
+            self.tick_rates.active
+        } else {
+            self.tick_rates.idle
+        };
+        Duration::from_secs_f64(1.0 / rate)
     }
 
     /// Runs the event thread.
     ///
-    /// This function emits tick events at a fixed rate and polls for crossterm events in between.
+    /// This function emits tick events at the idle rate, or the active rate while something
+    /// animates, and polls for crossterm events in between.
     async fn run(self) -> color_eyre::Result<()> {
-        // Configure the tick rate for UI updates
-        let tick_rate = Duration::from_secs_f64(1.0 / TICK_RATE);
         // Create an event stream for terminal input
         let mut reader = crossterm::event::EventStream::new();
         // Set up interval timer for regular tick events
-        let mut tick = tokio::time::interval(tick_rate);
+        let mut animating = self.animating.load(Ordering::Relaxed);
+        let mut tick = tokio::time::interval(self.tick_period(animating));
         loop {
+            // Restart the timer when an animation starts or stops
+            let now_animating = self.animating.load(Ordering::Relaxed);
+            if now_animating != animating {
+                animating = now_animating;
+                tick = tokio::time::interval(self.tick_period(animating));
+            }
             let tick_delay = tick.tick();
             let crossterm_event = reader.next().fuse();
             tokio::select! {
@@ -273,6 +598,10 @@ impl EventTask {
               }
               // Process terminal input events
               Some(Ok(evt)) = crossterm_event => {
+                // Reaches the main loop even while it awaits a slow tab event
+                if is_quit_key(&evt) {
+                    self.quit.notify_waiters();
+                }
                 self.send(Event::Crossterm(evt));
               }
             };
@@ -290,3 +619,43 @@ impl EventTask {
         let _ = self.sender.send(event);
     }
 }
+
+/// Whether the terminal event is a press of ⌘+Q (Ctrl+Q)
+pub fn is_quit_key(event: &CrosstermEvent) -> bool {
+    matches!(
+        event,
+        CrosstermEvent::Key(KeyEvent {
+            code: KeyCode::Char('q'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            ..
+        })
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_a_ctrl_q_press_is_a_quit_key() {
+        let key = |code, modifiers| CrosstermEvent::Key(KeyEvent::new(code, modifiers));
+        assert!(is_quit_key(&key(KeyCode::Char('q'), KeyModifiers::CONTROL)));
+        assert!(!is_quit_key(&key(KeyCode::Char('q'), KeyModifiers::NONE)));
+        assert!(!is_quit_key(&key(KeyCode::Char('w'), KeyModifiers::CONTROL)));
+    }
+
+    #[test]
+    fn sending_after_the_receiver_is_dropped_does_not_panic() {
+        let (sender, receiver) = mpsc::unbounded_channel::<Event>();
+        drop(receiver);
+
+        sender.send_or_log(Event::App(AppEvent::CloseTab));
+
+        assert!(
+            diagnostics::recent_entries()
+                .iter()
+                .any(|entry| entry.contains("Dropped app event CloseTab"))
+        );
+    }
+}
@@ -7,12 +7,20 @@ use crate::app::App;
 
 /// Application state and lifecycle management
 pub mod app;
+/// Command-line options for starting on a profile and service
+pub mod cli;
 /// UI components that represent AWS services and data
 pub mod components;
+/// Bounded in-app log for troubleshooting
+pub mod diagnostics;
 /// Event management system for handling user input and component events
 pub mod event_managment;
 /// AWS service clients and profile management
 pub mod services;
+/// Color themes loaded from the application config
+pub mod theme;
+/// Timestamp time zone and format loaded from the application config
+pub mod timestamps;
 /// UI rendering and layout modules
 pub mod ui;
 /// Reusable UI widgets for building the interface
@@ -23,11 +31,28 @@ async fn main() -> color_eyre::Result<()> {
     // Initialize error handling with detailed backtraces
     color_eyre::install()?;
 
+    // Check the arguments while errors can still be printed to a normal terminal
+    let options = match cli::parse_args(std::env::args().skip(1)).and_then(cli::check_profile) {
+        Ok(options) if options.help => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("aws-tui: {}\n\n{}", err, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if options.yes {
+        services::read_config::enable_power_user();
+    }
+
     // Initialize the terminal UI with ratatui
     let terminal = ratatui::init();
 
     // Create and run the application with the configured terminal
-    let result = App::new().run(terminal).await;
+    let result = App::with_options(options).run(terminal).await;
 
     // Restore terminal to original state before exiting
     ratatui::restore();
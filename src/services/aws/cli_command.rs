@@ -0,0 +1,307 @@
+//! AWS CLI command module
+//!
+//! Builds the AWS CLI invocation equivalent to a request made in the TUI, so what was
+//! done interactively can be repeated from a script.
+
+use crate::services::aws::dynamo_client;
+use serde_json::{Map, Value, json};
+
+/// A request made from the TUI that the AWS CLI can repeat
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    /// Listing the objects and folders under a prefix of a bucket
+    S3List { bucket: String, prefix: String },
+    /// Listing the versions of an object
+    S3ListVersions { bucket: String, key: String },
+    /// Querying a table by partition key and, optionally, sort key
+    DynamoDBQuery {
+        table: String,
+        /// Partition key attribute and value
+        partition_key: (String, String),
+        /// Sort key attribute and value
+        sort_key: Option<(String, String)>,
+        projection: Vec<String>,
+    },
+    /// Reading the items with the given partition key values
+    DynamoDBBatchGet {
+        table: String,
+        /// Partition key attribute
+        partition_key: String,
+        keys: Vec<String>,
+        projection: Vec<String>,
+    },
+    /// Reading every item of a table
+    DynamoDBScan {
+        table: String,
+        projection: Vec<String>,
+    },
+    /// Searching the events of a log group, times as epoch milliseconds
+    FilterLogEvents {
+        log_group: String,
+        filter_pattern: String,
+        start_time: i64,
+        end_time: Option<i64>,
+    },
+}
+
+impl CliCommand {
+    /// Builds the command line for the given profile and region, quoted for POSIX shells
+    pub fn command_line(&self, profile: &str, region: &str) -> String {
+        let mut args: Vec<String> = match self {
+            CliCommand::S3List { bucket, prefix } => {
+                vec![
+                    "s3".into(),
+                    "ls".into(),
+                    format!("s3://{}/{}", bucket, prefix),
+                ]
+            }
+            CliCommand::S3ListVersions { bucket, key } => {
+                vec![
+                    "s3api".into(),
+                    "list-object-versions".into(),
+                    "--bucket".into(),
+                    bucket.clone(),
+                    "--prefix".into(),
+                    key.clone(),
+                ]
+            }
+            CliCommand::DynamoDBQuery {
+                table,
+                partition_key,
+                sort_key,
+                projection,
+            } => {
+                // Key attributes go through placeholders too, in case they are reserved words
+                let mut names = Map::new();
+                let mut values = Map::new();
+                let mut condition = String::from("#pk = :pk");
+                names.insert("#pk".into(), json!(partition_key.0));
+                values.insert(":pk".into(), json!({ "S": partition_key.1 }));
+                if let Some((name, value)) = sort_key {
+                    condition.push_str(" AND #sk = :sk");
+                    names.insert("#sk".into(), json!(name));
+                    values.insert(":sk".into(), json!({ "S": value }));
+                }
+
+                let mut args = vec![
+                    "dynamodb".into(),
+                    "query".into(),
+                    "--table-name".into(),
+                    table.clone(),
+                    "--key-condition-expression".into(),
+                    condition,
+                ];
+                push_projection(&mut args, &mut names, projection);
+                args.extend([
+                    "--expression-attribute-names".into(),
+                    Value::Object(names).to_string(),
+                    "--expression-attribute-values".into(),
+                    Value::Object(values).to_string(),
+                ]);
+                args
+            }
+            CliCommand::DynamoDBBatchGet {
+                table,
+                partition_key,
+                keys,
+                projection,
+            } => {
+                let keys: Vec<Value> = keys
+                    .iter()
+                    .map(|key| json!({ partition_key.as_str(): { "S": key } }))
+                    .collect();
+                let mut request = Map::new();
+                request.insert("Keys".into(), Value::Array(keys));
+                if let Some((expression, projected)) = dynamo_client::build_projection(projection) {
+                    request.insert("ProjectionExpression".into(), json!(expression));
+                    request.insert("ExpressionAttributeNames".into(), json!(projected));
+                }
+                let mut items = Map::new();
+                items.insert(table.clone(), Value::Object(request));
+                vec![
+                    "dynamodb".into(),
+                    "batch-get-item".into(),
+                    "--request-items".into(),
+                    Value::Object(items).to_string(),
+                ]
+            }
+            CliCommand::DynamoDBScan { table, projection } => {
+                let mut names = Map::new();
+                let mut args = vec![
+                    "dynamodb".into(),
+                    "scan".into(),
+                    "--table-name".into(),
+                    table.clone(),
+                ];
+                push_projection(&mut args, &mut names, projection);
+                if !names.is_empty() {
+                    args.extend([
+                        "--expression-attribute-names".into(),
+                        Value::Object(names).to_string(),
+                    ]);
+                }
+                args
+            }
+            CliCommand::FilterLogEvents {
+                log_group,
+                filter_pattern,
+                start_time,
+                end_time,
+            } => {
+                let mut args = vec![
+                    "logs".into(),
+                    "filter-log-events".into(),
+                    "--log-group-name".into(),
+                    log_group.clone(),
+                    "--start-time".into(),
+                    start_time.to_string(),
+                ];
+                if let Some(end_time) = end_time {
+                    args.extend(["--end-time".into(), end_time.to_string()]);
+                }
+                if !filter_pattern.is_empty() {
+                    args.extend(["--filter-pattern".into(), filter_pattern.clone()]);
+                }
+                args
+            }
+        };
+        args.extend([
+            "--profile".into(),
+            profile.to_string(),
+            "--region".into(),
+            region.to_string(),
+        ]);
+
+        std::iter::once("aws".to_string())
+            .chain(args.iter().map(|arg| shell_quote(arg)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Adds the projection expression, with the same `#pN` placeholders the TUI queries use
+fn push_projection(args: &mut Vec<String>, names: &mut Map<String, Value>, projection: &[String]) {
+    if let Some((expression, projected)) = dynamo_client::build_projection(projection) {
+        args.extend(["--projection-expression".into(), expression]);
+        names.extend(
+            projected
+                .into_iter()
+                .map(|(placeholder, name)| (placeholder, json!(name))),
+        );
+    }
+}
+
+/// Quotes an argument for POSIX shells, leaving plain words as they are
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+%".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_s3_prefixes() {
+        let command = CliCommand::S3List {
+            bucket: "my-bucket".into(),
+            prefix: "logs/2024/".into(),
+        };
+        assert_eq!(
+            command.command_line("dev", "eu-west-1"),
+            "aws s3 ls s3://my-bucket/logs/2024/ --profile dev --region eu-west-1"
+        );
+    }
+
+    #[test]
+    fn lists_object_versions() {
+        let command = CliCommand::S3ListVersions {
+            bucket: "my-bucket".into(),
+            key: "reports/q1 summary.csv".into(),
+        };
+        assert_eq!(
+            command.command_line("dev", "eu-west-1"),
+            "aws s3api list-object-versions --bucket my-bucket \
+             --prefix 'reports/q1 summary.csv' --profile dev --region eu-west-1"
+        );
+    }
+
+    #[test]
+    fn queries_use_placeholders_for_keys_and_projection() {
+        let command = CliCommand::DynamoDBQuery {
+            table: "Orders".into(),
+            partition_key: ("id".into(), "42".into()),
+            sort_key: Some(("status".into(), "it's open".into())),
+            projection: vec!["name".into()],
+        };
+        assert_eq!(
+            command.command_line("dev", "us-east-1"),
+            "aws dynamodb query --table-name Orders \
+             --key-condition-expression '#pk = :pk AND #sk = :sk' \
+             --projection-expression '#p0' \
+             --expression-attribute-names '{\"#p0\":\"name\",\"#pk\":\"id\",\"#sk\":\"status\"}' \
+             --expression-attribute-values '{\":pk\":{\"S\":\"42\"},\":sk\":{\"S\":\"it'\\''s open\"}}' \
+             --profile dev --region us-east-1"
+        );
+    }
+
+    #[test]
+    fn batch_gets_list_every_key() {
+        let command = CliCommand::DynamoDBBatchGet {
+            table: "Users".into(),
+            partition_key: "id".into(),
+            keys: vec!["1".into(), "2".into()],
+            projection: vec!["name".into()],
+        };
+        assert_eq!(
+            command.command_line("dev", "us-east-1"),
+            "aws dynamodb batch-get-item --request-items \
+             '{\"Users\":{\"ExpressionAttributeNames\":{\"#p0\":\"name\"},\
+             \"Keys\":[{\"id\":{\"S\":\"1\"}},{\"id\":{\"S\":\"2\"}}],\
+             \"ProjectionExpression\":\"#p0\"}}' --profile dev --region us-east-1"
+        );
+    }
+
+    #[test]
+    fn scans_only_name_projected_attributes() {
+        let scan = |projection: Vec<String>| {
+            CliCommand::DynamoDBScan {
+                table: "Orders".into(),
+                projection,
+            }
+            .command_line("dev", "us-east-1")
+        };
+        assert_eq!(
+            scan(vec![]),
+            "aws dynamodb scan --table-name Orders --profile dev --region us-east-1"
+        );
+        assert_eq!(
+            scan(vec!["id".into()]),
+            "aws dynamodb scan --table-name Orders --projection-expression '#p0' \
+             --expression-attribute-names '{\"#p0\":\"id\"}' --profile dev --region us-east-1"
+        );
+    }
+
+    #[test]
+    fn log_searches_keep_the_window_and_pattern() {
+        let command = CliCommand::FilterLogEvents {
+            log_group: "/aws/lambda/api".into(),
+            filter_pattern: "ERROR -healthcheck".into(),
+            start_time: 1_700_000_000_000,
+            end_time: None,
+        };
+        assert_eq!(
+            command.command_line("default", "eu-west-1"),
+            "aws logs filter-log-events --log-group-name /aws/lambda/api \
+             --start-time 1700000000000 --filter-pattern 'ERROR -healthcheck' \
+             --profile default --region eu-west-1"
+        );
+    }
+}
@@ -1,141 +1,518 @@
-//! CloudWatch Logs client module
+//! CloudWatch client module
 //!
 //! Provides functionality to interact with AWS CloudWatch Logs service,
-//! including listing log groups and retrieving log events with optional filtering.
+//! including listing log groups and retrieving log events with optional filtering,
+//! and with CloudWatch metrics, listing metrics and fetching their datapoints,
+//! and listing metric and composite alarms with their state.
 
-use aws_config::{BehaviorVersion, Region};
+use crate::services::aws::CappedRecords;
+use crate::services::aws::credentials::{self, CredentialsError};
+use crate::services::aws::retry::{self, RetryError, RetryPolicy, Retryable, THROTTLING_CODES};
+use crate::services::read_config;
+use crate::timestamps::timestamps;
+use aws_config::SdkConfig;
+use aws_sdk_cloudwatch::types::{
+    AlarmType, CompositeAlarm, Dimension, MetricAlarm, StateValue, Statistic,
+};
 use aws_sdk_cloudwatchlogs::error::SdkError;
-use aws_sdk_cloudwatchlogs::{Client, config};
-use std::time::Duration;
+use aws_sdk_cloudwatchlogs::Client;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_types::error::metadata::ProvideErrorMetadata;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde_json::json;
 use thiserror::Error;
 
 /// Errors that can occur when interacting with CloudWatch Logs
 #[derive(Error, Debug)]
 pub enum CloudWatchClientError {
-    /// Error returned from the AWS SDK
+    /// Error returned from the AWS SDK with a code not mapped to a variant below
     #[error("AWS SDK error: {0}")]
     AwsError(String),
 
+    /// Request still throttled after retrying with backoff
+    #[error("CloudWatch is throttling requests, gave up after {0} retries: {1}")]
+    Throttled(u32, String),
+
+    /// The log group, log stream or metric a request named does not exist
+    #[error("Resource not found: {0}")]
+    ResourceNotFound(String),
+
+    /// The profile's credentials have expired or are no longer recognized
+    #[error("Credentials expired or invalid, refresh them (e.g. `aws sso login`): {0}")]
+    ExpiredCredentials(String),
+
     /// Authentication or connection error with AWS
     #[error("Failed to connect with profile: {0}")]
     ConnectionFailed(String),
+
+    /// No log group with the requested name exists
+    #[error("Log group not found: {0}")]
+    LogGroupNotFound(String),
+
+    /// Time range that is neither relative (e.g. "15m") nor an absolute `start..end` range
+    #[error("Invalid time range '{0}': {1}")]
+    InvalidTimeRange(String, String),
+
+    #[error("CloudWatch request timed out; raise `cloudwatch` under [timeouts] in config.toml")]
+    TimedOut,
+
+    /// A whole operation ran past its limit: seconds allowed and the config key setting it
+    #[error("Request timed out after {0} seconds; raise `{1}` under [timeouts] in config.toml")]
+    Elapsed(u64, &'static str),
+
+    /// The profile's IAM policy does not allow the named action
+    #[error("Access denied: the profile needs the `{0}` permission in its IAM policy")]
+    AccessDenied(&'static str),
+
+    /// Credentials of a role-assuming profile could not be obtained
+    #[error(transparent)]
+    Credentials(#[from] CredentialsError),
+}
+
+/// Most events FilterLogEvents returns in one page
+const MAX_EVENTS_PER_PAGE: usize = 10_000;
+
+/// Datapoints a metric graph asks for at most, fewer than GetMetricStatistics allows
+const TARGET_DATAPOINTS: i64 = 300;
+
+/// Accepted formats for the ends of an absolute time range, interpreted as UTC
+const ABSOLUTE_TIME_FORMATS: [&str; 3] = ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"];
+
+/// Explicit window for log queries, as epoch milliseconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeWindow {
+    /// Earliest event time to include
+    pub start_time: i64,
+    /// Latest event time to include, open-ended when None
+    pub end_time: Option<i64>,
+}
+
+/// A metric and the dimensions naming one of its time series
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricId {
+    pub namespace: String,
+    pub name: String,
+    /// Dimension names and values, e.g. `FunctionName=api`
+    pub dimensions: Vec<(String, String)>,
+}
+
+impl MetricId {
+    /// One-line label, e.g. `AWS/Lambda Errors FunctionName=api`
+    pub fn label(&self) -> String {
+        let mut label = format!("{} {}", self.namespace, self.name);
+        for (name, value) in &self.dimensions {
+            label.push_str(&format!(" {}={}", name, value));
+        }
+        label
+    }
+}
+
+/// An alarm and the state it is in
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlarmSummary {
+    pub name: String,
+    /// `OK`, `ALARM` or `INSUFFICIENT_DATA`
+    pub state: String,
+    /// What the alarm watches: its metric, or the rule of a composite alarm
+    pub watches: String,
+    /// Configuration and latest state change, as JSON for the details view
+    pub details: String,
+}
+
+impl AlarmSummary {
+    /// One-line label starting with the state, e.g. `ALARM api-errors (AWS/Lambda Errors)`
+    pub fn label(&self) -> String {
+        format!("{} {} ({})", self.state, self.name, self.watches)
+    }
+
+    /// Builds the summary of a metric alarm, or of a metric math alarm without a metric name
+    fn from_metric_alarm(alarm: &MetricAlarm) -> Self {
+        let metric = MetricId {
+            namespace: alarm.namespace().unwrap_or_default().to_string(),
+            name: alarm.metric_name().unwrap_or_default().to_string(),
+            dimensions: alarm
+                .dimensions()
+                .iter()
+                .filter_map(|dimension| {
+                    Some((
+                        dimension.name()?.to_string(),
+                        dimension.value()?.to_string(),
+                    ))
+                })
+                .collect(),
+        };
+        let watches = if metric.name.is_empty() {
+            format!("metric math over {} metrics", alarm.metrics().len())
+        } else {
+            metric.label()
+        };
+        let details = json!({
+            "name": alarm.alarm_name(),
+            "type": "metric",
+            "state": state_name(alarm.state_value()),
+            "state_reason": alarm.state_reason(),
+            "state_updated": alarm
+                .state_updated_timestamp()
+                .and_then(|time| timestamps().format_aws(time)),
+            "description": alarm.alarm_description(),
+            "metric": watches,
+            "statistic": alarm
+                .statistic()
+                .map(|statistic| statistic.as_str())
+                .or(alarm.extended_statistic()),
+            "period": alarm.period(),
+            "comparison": alarm.comparison_operator().map(|operator| operator.as_str()),
+            "threshold": alarm.threshold(),
+            "evaluation_periods": alarm.evaluation_periods(),
+            "datapoints_to_alarm": alarm.datapoints_to_alarm(),
+            "treat_missing_data": alarm.treat_missing_data(),
+            "actions_enabled": alarm.actions_enabled(),
+            "alarm_actions": alarm.alarm_actions(),
+            "ok_actions": alarm.ok_actions(),
+            "arn": alarm.alarm_arn(),
+        });
+        Self {
+            name: alarm.alarm_name().unwrap_or_default().to_string(),
+            state: state_name(alarm.state_value()).to_string(),
+            watches,
+            details: details.to_string(),
+        }
+    }
+
+    /// Builds the summary of a composite alarm, which watches other alarms through a rule
+    fn from_composite_alarm(alarm: &CompositeAlarm) -> Self {
+        let watches = format!("composite: {}", alarm.alarm_rule().unwrap_or_default());
+        let details = json!({
+            "name": alarm.alarm_name(),
+            "type": "composite",
+            "state": state_name(alarm.state_value()),
+            "state_reason": alarm.state_reason(),
+            "state_updated": alarm
+                .state_updated_timestamp()
+                .and_then(|time| timestamps().format_aws(time)),
+            "description": alarm.alarm_description(),
+            "alarm_rule": alarm.alarm_rule(),
+            "actions_enabled": alarm.actions_enabled(),
+            "alarm_actions": alarm.alarm_actions(),
+            "ok_actions": alarm.ok_actions(),
+            "arn": alarm.alarm_arn(),
+        });
+        Self {
+            name: alarm.alarm_name().unwrap_or_default().to_string(),
+            state: state_name(alarm.state_value()).to_string(),
+            watches,
+            details: details.to_string(),
+        }
+    }
+}
+
+/// Name of an alarm state, `UNKNOWN` when CloudWatch leaves it out
+fn state_name(state: Option<&StateValue>) -> &str {
+    state.map_or("UNKNOWN", |state| state.as_str())
+}
+
+/// Orders alarms firing first, then those lacking data, then the rest, each by name
+pub fn sort_alarms(alarms: &mut [AlarmSummary]) {
+    let rank = |state: &str| match state {
+        "ALARM" => 0,
+        "INSUFFICIENT_DATA" => 1,
+        _ => 2,
+    };
+    alarms.sort_by(|a, b| {
+        rank(&a.state)
+            .cmp(&rank(&b.state))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Period in seconds giving at most [`TARGET_DATAPOINTS`] over the window, in whole
+/// minutes as CloudWatch requires for all but high-resolution metrics
+pub fn metric_period(window: TimeWindow, now: DateTime<Utc>) -> i32 {
+    let end_time = window.end_time.unwrap_or_else(|| now.timestamp_millis());
+    let span_seconds = (end_time - window.start_time).max(0) / 1000;
+    let minutes = ((span_seconds / TARGET_DATAPOINTS + 59) / 60).max(1);
+    (minutes * 60).min(i32::MAX as i64) as i32
+}
+
+/// Parses one end of an absolute range such as `2024-01-01T00:00` or `2024-01-01`
+fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = value.trim();
+    ABSOLUTE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .map(|time| time.and_utc().timestamp_millis())
+}
+
+/// Parses a time range into an explicit window
+///
+/// Relative ranges (`30s`, `15m`, `1h`, `7d`, `2w`) end now; absolute ranges are written
+/// `start..end` with UTC timestamps like `2024-01-01T00:00..2024-01-02T00:00`
+pub fn parse_time_range(
+    range: &str,
+    now: DateTime<Utc>,
+) -> Result<TimeWindow, CloudWatchClientError> {
+    let range = range.trim();
+    let invalid = |reason: &str| {
+        CloudWatchClientError::InvalidTimeRange(range.to_string(), reason.to_string())
+    };
+
+    if let Some((start, end)) = range.split_once("..") {
+        let start_time = parse_timestamp(start)
+            .ok_or_else(|| invalid("start must look like 2024-01-01T00:00"))?;
+        let end_time =
+            parse_timestamp(end).ok_or_else(|| invalid("end must look like 2024-01-02T00:00"))?;
+        if start_time >= end_time {
+            return Err(invalid("start must be before end"));
+        }
+        return Ok(TimeWindow {
+            start_time,
+            end_time: Some(end_time),
+        });
+    }
+
+    // Split the numeric amount from the unit suffix
+    let unit_start = range
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| invalid("missing unit (s, m, h, d or w)"))?;
+    let (amount, unit) = range.split_at(unit_start);
+    let amount: i64 = amount
+        .parse()
+        .ok()
+        .filter(|amount| *amount > 0)
+        .ok_or_else(|| invalid("expected a positive amount like 15m"))?;
+
+    let unit_millis = match unit {
+        "s" => 1000,                    // seconds
+        "m" => 60 * 1000,               // minutes
+        "h" => 60 * 60 * 1000,          // hours
+        "d" => 24 * 60 * 60 * 1000,     // days
+        "w" => 7 * 24 * 60 * 60 * 1000, // weeks
+        _ => return Err(invalid("unit must be one of s, m, h, d or w")),
+    };
+
+    Ok(TimeWindow {
+        start_time: now.timestamp_millis() - amount.saturating_mul(unit_millis),
+        end_time: None,
+    })
 }
 
 /// Convert SDK errors to our application-specific error type
-impl<T, E> From<SdkError<T, E>> for CloudWatchClientError {
-    fn from(err: SdkError<T, E>) -> Self {
-        CloudWatchClientError::AwsError(err.to_string())
+/// Error codes of requests signed with expired or unrecognized credentials
+const EXPIRED_CREDENTIALS_CODES: [&str; 4] = [
+    "ExpiredTokenException",
+    "ExpiredToken",
+    "UnrecognizedClientException",
+    "InvalidClientTokenId",
+];
+
+impl<E: ProvideErrorMetadata> From<SdkError<E, HttpResponse>> for CloudWatchClientError {
+    fn from(err: SdkError<E, HttpResponse>) -> Self {
+        if let SdkError::TimeoutError(_) = err {
+            return CloudWatchClientError::TimedOut;
+        }
+        // The SDK error only displays its kind, e.g. "service error"; the message says more
+        let message = err
+            .message()
+            .map(String::from)
+            .unwrap_or_else(|| err.to_string());
+        CloudWatchClientError::from_code(err.code(), message)
     }
 }
 
-/// Client for AWS CloudWatch Logs API operations
+impl<E> From<RetryError<E>> for CloudWatchClientError
+where
+    E: Retryable + std::fmt::Display,
+    CloudWatchClientError: From<E>,
+{
+    fn from(err: RetryError<E>) -> Self {
+        match err {
+            RetryError::Exhausted(retries, err) if err.is_retryable() => {
+                CloudWatchClientError::Throttled(retries, err.to_string())
+            }
+            // Requests that kept failing on the way report their last error
+            RetryError::Permanent(err) | RetryError::Exhausted(_, err) => err.into(),
+            RetryError::TimedOut(_) => CloudWatchClientError::TimedOut,
+        }
+    }
+}
+
+impl CloudWatchClientError {
+    /// Converts a failed request, naming the IAM action it needs when it was denied
+    fn from_request<E>(action: &'static str, err: RetryError<E>) -> Self
+    where
+        E: Retryable + std::fmt::Display,
+        CloudWatchClientError: From<E>,
+    {
+        if err.is_access_denied() {
+            CloudWatchClientError::AccessDenied(action)
+        } else {
+            err.into()
+        }
+    }
+
+    /// Picks the variant for an AWS error code, keeping unknown codes as [`Self::AwsError`]
+    fn from_code(code: Option<&str>, message: String) -> Self {
+        match code {
+            Some("ResourceNotFoundException") => CloudWatchClientError::ResourceNotFound(message),
+            Some(code) if EXPIRED_CREDENTIALS_CODES.contains(&code) => {
+                CloudWatchClientError::ExpiredCredentials(message)
+            }
+            // Only requests made without retries get here while throttled
+            Some(code) if THROTTLING_CODES.contains(&code) => {
+                CloudWatchClientError::Throttled(0, message)
+            }
+            Some(code) => CloudWatchClientError::AwsError(format!("{}: {}", code, message)),
+            None => CloudWatchClientError::AwsError(message),
+        }
+    }
+}
+
+/// Client for AWS CloudWatch Logs and metrics API operations
 pub struct CloudWatchClient {
     /// AWS SDK CloudWatch Logs client
     client: Client,
+    /// AWS SDK CloudWatch client for metrics
+    metrics_client: aws_sdk_cloudwatch::Client,
+    /// Backoff used when requests are throttled
+    retry_policy: RetryPolicy,
+    /// Log events fetched at most by one search, `None` for no cap
+    max_results: Option<usize>,
 }
 
 impl CloudWatchClient {
-    /// Creates a new CloudWatch client with the specified AWS profile and region
+    /// Creates a new CloudWatch client from the shared SDK config of the given profile
     ///
     /// Attempts to connect to verify credentials are valid before returning
-    pub async fn new(profile: String, region: String) -> Result<Self, CloudWatchClientError> {
-        // Configure AWS SDK with profile, region and timeouts
-        let config = aws_config::defaults(BehaviorVersion::latest())
-            .profile_name(&profile)
-            .region(Region::new(region))
-            .timeout_config(
-                config::timeout::TimeoutConfig::builder()
-                    .operation_timeout(Duration::from_secs(30))
-                    .build(),
-            )
-            .load()
-            .await;
-
-        let client = Client::new(&config);
+    pub async fn new(config: &SdkConfig, profile: &str) -> Result<Self, CloudWatchClientError> {
+        credentials::verify_role_credentials(
+            config,
+            profile,
+            read_config::get_role_settings(profile).as_ref(),
+        )
+        .await?;
+
+        let client = Client::new(config);
 
         // Verify credentials by making a simple API call
         match client.describe_log_groups().send().await {
-            Ok(_) => Ok(Self { client }),
-            Err(err) => Err(CloudWatchClientError::ConnectionFailed(err.to_string())),
+            Ok(_) => Ok(Self {
+                client,
+                metrics_client: aws_sdk_cloudwatch::Client::new(config),
+                retry_policy: RetryPolicy::from_config(),
+                max_results: read_config::get_max_results(),
+            }),
+            // Credentials work, the profile is just not allowed to list log groups
+            Err(err) if err.is_access_denied() => Err(CloudWatchClientError::AccessDenied(
+                "logs:DescribeLogGroups",
+            )),
+            Err(err) => match CloudWatchClientError::from(err) {
+                // Expired credentials need refreshing, not a different profile
+                expired @ CloudWatchClientError::ExpiredCredentials(_) => Err(expired),
+                err => Err(CloudWatchClientError::ConnectionFailed(err.to_string())),
+            },
         }
     }
 
     /// Lists available CloudWatch log groups
     ///
-    /// Returns a vector of log group names or a friendly message if none found
+    /// Returns a vector of log group names, empty if the region has none
     pub async fn list_log_groups(&self) -> Result<Vec<String>, CloudWatchClientError> {
-        let resp = self.client.describe_log_groups().send().await?;
+        let resp = retry::with_retry(&self.retry_policy, || {
+            self.client.describe_log_groups().send()
+        })
+        .await
+        .map_err(|err| CloudWatchClientError::from_request("logs:DescribeLogGroups", err))?;
 
-        let log_groups: Vec<String> = resp
+        let log_groups = resp
             .log_groups()
             .iter()
             .filter_map(|group| group.log_group_name().map(|name| name.to_string()))
             .collect();
 
-        if log_groups.is_empty() {
-            Ok(vec!["No log groups found".to_string()])
-        } else {
-            Ok(log_groups)
-        }
+        Ok(log_groups)
     }
 
-    /// Parse a time range string (e.g., "15m", "1h", "7d") into milliseconds timestamp
-    fn parse_time_range(&self, range: &str, now: chrono::DateTime<chrono::Utc>) -> i64 {
-        // Default to 1 hour if parsing fails
-        let default_time = now.timestamp_millis() - (60 * 1000);
-
-        // Extract numeric value and unit from the time range string
-        let mut numeric = String::new();
-        let mut unit = String::new();
-
-        for c in range.chars() {
-            if c.is_digit(10) {
-                numeric.push(c);
-            } else {
-                unit.push(c);
-            }
-        }
+    /// Summarizes a log group's retention, size and metric filters
+    ///
+    /// Returns a JSON string; groups without a retention policy report that they never expire
+    pub async fn describe_log_group(&self, name: &str) -> Result<String, CloudWatchClientError> {
+        // The API only filters by prefix, so pick out the exact match
+        let request = self.client.describe_log_groups().log_group_name_prefix(name);
+        let resp = retry::with_retry(&self.retry_policy, || request.clone().send())
+            .await
+            .map_err(|err| CloudWatchClientError::from_request("logs:DescribeLogGroups", err))?;
+        let group = resp
+            .log_groups()
+            .iter()
+            .find(|group| group.log_group_name() == Some(name))
+            .ok_or_else(|| CloudWatchClientError::LogGroupNotFound(name.to_string()))?;
 
-        // Parse the numeric part
-        let amount: i64 = match numeric.parse() {
-            Ok(num) => num,
-            Err(_) => return default_time,
+        let retention = match group.retention_in_days() {
+            Some(days) => json!(days),
+            None => json!("Never expire"),
         };
+        let creation_time = group
+            .creation_time()
+            .and_then(|millis| timestamps().format_millis(millis));
 
-        // If amount is 0 or negative, return default
-        if amount <= 0 {
-            return default_time;
-        }
-
-        // Calculate milliseconds based on the unit
-        match unit.as_str() {
-            "s" => now.timestamp_millis() - (amount * 1000), // seconds
-            "m" => now.timestamp_millis() - (amount * 60 * 1000), // minutes
-            "h" => now.timestamp_millis() - (amount * 60 * 60 * 1000), // hours
-            "d" => now.timestamp_millis() - (amount * 24 * 60 * 60 * 1000), // days
-            "w" => now.timestamp_millis() - (amount * 7 * 24 * 60 * 60 * 1000), // weeks
-            _ => default_time,                               // Unrecognized unit, return default
-        }
+        Ok(json!({
+            "log_group_name": name,
+            "retention_in_days": retention,
+            "stored_bytes": group.stored_bytes(),
+            "metric_filter_count": group.metric_filter_count(),
+            "creation_time": creation_time,
+            "arn": group.arn(),
+        })
+        .to_string())
     }
 
     /// Retrieves log events from a specific log group with pagination
     ///
-    /// This method fetches all pages of results by following the nextToken
-    /// Returns formatted log entries with timestamps
+    /// This method fetches all pages of results by following the nextToken,
+    /// stopping once `max_results` events have been collected
     pub async fn list_log_events(
         &self,
         log_group_name: &str,
         filter_pattern: &str,
-        time_range: Option<&str>,
-    ) -> Result<Vec<String>, aws_sdk_cloudwatchlogs::Error> {
+        start_time: i64,
+        end_time: Option<i64>,
+    ) -> Result<CappedRecords, CloudWatchClientError> {
+        self.filter_log_events(log_group_name, filter_pattern, start_time, end_time)
+            .await
+            .map(|(logs, _)| logs)
+    }
+
+    /// Retrieves log events newer than `since` (epoch millis) for a live tail, along with
+    /// the timestamp of the newest one so the next poll can continue from there
+    pub async fn list_log_events_since(
+        &self,
+        log_group_name: &str,
+        filter_pattern: &str,
+        since: i64,
+    ) -> Result<(Vec<String>, Option<i64>), CloudWatchClientError> {
+        self.filter_log_events(log_group_name, filter_pattern, since + 1, None)
+            .await
+            .map(|(logs, newest)| (logs.records, newest))
+    }
+
+    /// Follows the pages of FilterLogEvents up to `max_results` events, returning their
+    /// messages and the timestamp of the newest event
+    async fn filter_log_events(
+        &self,
+        log_group_name: &str,
+        filter_pattern: &str,
+        start_time: i64,
+        end_time: Option<i64>,
+    ) -> Result<(CappedRecords, Option<i64>), CloudWatchClientError> {
         let mut logs = Vec::new();
+        let mut newest = None;
         let mut next_token = None;
 
-        // Parse the time range if provided
-        let effective_range = time_range.unwrap_or("1m");
-        let now = chrono::Utc::now();
-        let milliseconds = self.parse_time_range(effective_range, now);
-
         // Continue fetching pages until there are no more results
         loop {
             // Build the filter log events request
@@ -148,15 +525,23 @@ impl CloudWatchClient {
                 request = request.filter_pattern(filter_pattern);
             }
 
-            request = request.start_time(milliseconds);
+            request = request.start_time(start_time).set_end_time(end_time);
+
+            // Never ask for more events than are left under the cap
+            if let Some(max_results) = self.max_results {
+                let remaining = max_results.saturating_sub(logs.len());
+                request = request.limit(remaining.min(MAX_EVENTS_PER_PAGE) as i32);
+            }
 
             // Add the next token if we have one from a previous page
             if let Some(token) = next_token {
                 request = request.next_token(token);
             }
 
-            // Execute the request
-            let response = request.send().await?;
+            // Execute the request, backing off while throttled so long fetches keep their pages
+            let response = retry::with_retry(&self.retry_policy, || request.clone().send())
+                .await
+                .map_err(|err| CloudWatchClientError::from_request("logs:FilterLogEvents", err))?;
 
             // Process log events from this page
             let events = response.events();
@@ -164,6 +549,7 @@ impl CloudWatchClient {
                 if let Some(message) = event.message() {
                     logs.push(message.to_string());
                 }
+                newest = newest.max(event.timestamp());
             }
 
             // Get the next token for pagination
@@ -173,25 +559,378 @@ impl CloudWatchClient {
             if next_token.is_none() {
                 break;
             }
+
+            // More events are available, but the cap has been reached
+            if let Some(max_results) = self.max_results
+                && logs.len() >= max_results
+            {
+                let logs = CappedRecords {
+                    records: logs,
+                    truncated_at: Some(max_results),
+                    total: None,
+                    raw: Vec::new(),
+                };
+                return Ok((logs, newest));
+            }
         }
 
-        // Add helpful message when no logs found
-        if logs.is_empty() {
-            if !filter_pattern.is_empty() {
-                logs.push(format!(
-                    "No logs matching filter '{}' found in the time range",
-                    filter_pattern
-                ));
-            } else if let Some(range) = time_range {
-                logs.push(format!(
-                    "No logs found in the specified time range ({})",
-                    range
-                ));
-            } else {
-                logs.push("No logs found".to_string());
+        let logs = CappedRecords {
+            records: logs,
+            truncated_at: None,
+            total: None,
+            raw: Vec::new(),
+        };
+        Ok((logs, newest))
+    }
+
+    /// Lists metrics, only those in the namespace when one is given
+    ///
+    /// Follows pages until `max_results` metrics have been collected; sorted by label
+    pub async fn list_metrics(
+        &self,
+        namespace: Option<&str>,
+    ) -> Result<Vec<MetricId>, CloudWatchClientError> {
+        let mut metrics = Vec::new();
+        let mut next_token = None;
+
+        loop {
+            let request = self
+                .metrics_client
+                .list_metrics()
+                .set_namespace(namespace.map(String::from))
+                .set_next_token(next_token);
+            let response = retry::with_retry(&self.retry_policy, || request.clone().send())
+                .await
+                .map_err(|err| {
+                    CloudWatchClientError::from_request("cloudwatch:ListMetrics", err)
+                })?;
+
+            for metric in response.metrics() {
+                let (Some(namespace), Some(name)) = (metric.namespace(), metric.metric_name())
+                else {
+                    continue;
+                };
+                let dimensions = metric
+                    .dimensions()
+                    .iter()
+                    .filter_map(|dimension| {
+                        Some((
+                            dimension.name()?.to_string(),
+                            dimension.value()?.to_string(),
+                        ))
+                    })
+                    .collect();
+                metrics.push(MetricId {
+                    namespace: namespace.to_string(),
+                    name: name.to_string(),
+                    dimensions,
+                });
+            }
+
+            next_token = response.next_token().map(String::from);
+            let capped = self
+                .max_results
+                .is_some_and(|max_results| metrics.len() >= max_results);
+            if next_token.is_none() || capped {
+                break;
+            }
+        }
+
+        if let Some(max_results) = self.max_results {
+            metrics.truncate(max_results);
+        }
+        metrics.sort_by_key(MetricId::label);
+        Ok(metrics)
+    }
+
+    /// Lists metric and composite alarms, only those named with the prefix when one is
+    /// given; firing ones first
+    ///
+    /// Follows pages until `max_results` alarms have been collected
+    pub async fn describe_alarms(
+        &self,
+        name_prefix: Option<&str>,
+    ) -> Result<Vec<AlarmSummary>, CloudWatchClientError> {
+        let mut alarms = Vec::new();
+        let mut next_token = None;
+
+        loop {
+            let request = self
+                .metrics_client
+                .describe_alarms()
+                .alarm_types(AlarmType::MetricAlarm)
+                .alarm_types(AlarmType::CompositeAlarm)
+                .set_alarm_name_prefix(name_prefix.map(String::from))
+                .set_next_token(next_token);
+            let response = retry::with_retry(&self.retry_policy, || request.clone().send())
+                .await
+                .map_err(|err| {
+                    CloudWatchClientError::from_request("cloudwatch:DescribeAlarms", err)
+                })?;
+
+            alarms.extend(
+                response
+                    .metric_alarms()
+                    .iter()
+                    .map(AlarmSummary::from_metric_alarm),
+            );
+            alarms.extend(
+                response
+                    .composite_alarms()
+                    .iter()
+                    .map(AlarmSummary::from_composite_alarm),
+            );
+
+            next_token = response.next_token().map(String::from);
+            let capped = self
+                .max_results
+                .is_some_and(|max_results| alarms.len() >= max_results);
+            if next_token.is_none() || capped {
+                break;
             }
         }
 
-        Ok(logs)
+        if let Some(max_results) = self.max_results {
+            alarms.truncate(max_results);
+        }
+        sort_alarms(&mut alarms);
+        Ok(alarms)
+    }
+
+    /// Fetches the metric's average per period over the window, oldest first
+    ///
+    /// Periods without data are left out, so the points may be sparse or empty
+    pub async fn get_metric_statistics(
+        &self,
+        metric: &MetricId,
+        window: TimeWindow,
+        period: i32,
+    ) -> Result<Vec<(DateTime<Utc>, f64)>, CloudWatchClientError> {
+        let end_time = window
+            .end_time
+            .unwrap_or_else(|| Utc::now().timestamp_millis());
+        let dimensions = metric
+            .dimensions
+            .iter()
+            .map(|(name, value)| Dimension::builder().name(name).value(value).build())
+            .collect();
+        let request = self
+            .metrics_client
+            .get_metric_statistics()
+            .namespace(&metric.namespace)
+            .metric_name(&metric.name)
+            .set_dimensions(Some(dimensions))
+            .start_time(aws_smithy_types::DateTime::from_millis(window.start_time))
+            .end_time(aws_smithy_types::DateTime::from_millis(end_time))
+            .period(period)
+            .statistics(Statistic::Average);
+        let response = retry::with_retry(&self.retry_policy, || request.clone().send())
+            .await
+            .map_err(|err| {
+                CloudWatchClientError::from_request("cloudwatch:GetMetricStatistics", err)
+            })?;
+
+        let mut points: Vec<_> = response
+            .datapoints()
+            .iter()
+            .filter_map(|datapoint| {
+                let timestamp = datapoint.timestamp()?;
+                let time = DateTime::from_timestamp_millis(timestamp.to_millis().ok()?)?;
+                Some((time, datapoint.average()?))
+            })
+            .collect();
+        points.sort_by_key(|(time, _)| *time);
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        parse_timestamp("2024-01-02T00:00")
+            .and_then(DateTime::from_timestamp_millis)
+            .unwrap()
+    }
+
+    #[test]
+    fn firing_alarms_are_listed_first() {
+        let composite = CompositeAlarm::builder()
+            .alarm_name("checkout")
+            .state_value(StateValue::Alarm)
+            .alarm_rule("ALARM(api-errors)")
+            .build();
+        let metric = MetricAlarm::builder()
+            .alarm_name("api-errors")
+            .state_value(StateValue::Ok)
+            .namespace("AWS/Lambda")
+            .metric_name("Errors")
+            .dimensions(Dimension::builder().name("FunctionName").value("api").build())
+            .build();
+        let no_data = MetricAlarm::builder()
+            .alarm_name("queue-depth")
+            .state_value(StateValue::InsufficientData)
+            .build();
+        let mut alarms = vec![
+            AlarmSummary::from_metric_alarm(&metric),
+            AlarmSummary::from_metric_alarm(&no_data),
+            AlarmSummary::from_composite_alarm(&composite),
+        ];
+
+        sort_alarms(&mut alarms);
+        let labels: Vec<_> = alarms.iter().map(AlarmSummary::label).collect();
+        assert_eq!(
+            labels,
+            [
+                "ALARM checkout (composite: ALARM(api-errors))",
+                "INSUFFICIENT_DATA queue-depth (metric math over 0 metrics)",
+                "OK api-errors (AWS/Lambda Errors FunctionName=api)",
+            ]
+        );
+    }
+
+    #[test]
+    fn relative_ranges_end_now() {
+        let window = parse_time_range("15m", now()).unwrap();
+        assert_eq!(window.start_time, now().timestamp_millis() - 15 * 60 * 1000);
+        assert_eq!(window.end_time, None);
+    }
+
+    #[test]
+    fn absolute_ranges_set_both_ends() {
+        let window = parse_time_range("2024-01-01T00:00..2024-01-01T12:30:15", now()).unwrap();
+        assert_eq!(
+            window.start_time,
+            now().timestamp_millis() - 24 * 60 * 60 * 1000
+        );
+        assert_eq!(
+            window.end_time,
+            Some(window.start_time + ((12 * 60 + 30) * 60 + 15) * 1000)
+        );
+    }
+
+    #[test]
+    fn malformed_ranges_are_rejected() {
+        for range in [
+            "",
+            "15",
+            "m",
+            "0h",
+            "5y",
+            "2024-01-02..2024-01-01",
+            "yesterday..today",
+        ] {
+            assert!(
+                matches!(
+                    parse_time_range(range, now()),
+                    Err(CloudWatchClientError::InvalidTimeRange(..))
+                ),
+                "{range:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn metric_periods_are_whole_minutes_with_bounded_datapoints() {
+        let hours = |hours: i64| TimeWindow {
+            start_time: now().timestamp_millis() - hours * 60 * 60 * 1000,
+            end_time: None,
+        };
+        assert_eq!(metric_period(hours(1), now()), 60);
+        assert_eq!(metric_period(hours(3), now()), 60);
+        assert_eq!(metric_period(hours(24), now()), 300);
+        assert_eq!(metric_period(hours(24 * 7), now()), 2040);
+        let empty = TimeWindow {
+            start_time: now().timestamp_millis(),
+            end_time: Some(now().timestamp_millis()),
+        };
+        assert_eq!(metric_period(empty, now()), 60);
+    }
+
+    #[test]
+    fn metric_labels_list_dimensions() {
+        let metric = MetricId {
+            namespace: "AWS/Lambda".to_string(),
+            name: "Errors".to_string(),
+            dimensions: vec![("FunctionName".to_string(), "api".to_string())],
+        };
+        assert_eq!(metric.label(), "AWS/Lambda Errors FunctionName=api");
+    }
+
+    /// A failed FilterLogEvents request carrying the given error code
+    fn service_error(
+        code: &str,
+    ) -> SdkError<
+        aws_sdk_cloudwatchlogs::operation::filter_log_events::FilterLogEventsError,
+        HttpResponse,
+    > {
+        let metadata = aws_smithy_types::error::ErrorMetadata::builder()
+            .code(code)
+            .message("details from AWS")
+            .build();
+        SdkError::service_error(
+            aws_sdk_cloudwatchlogs::operation::filter_log_events::FilterLogEventsError::generic(
+                metadata,
+            ),
+            HttpResponse::new(
+                400.try_into().unwrap(),
+                aws_smithy_types::body::SdkBody::empty(),
+            ),
+        )
+    }
+
+    #[test]
+    fn error_codes_map_to_variants() {
+        let error = |code| CloudWatchClientError::from(service_error(code));
+        assert!(matches!(
+            error("ResourceNotFoundException"),
+            CloudWatchClientError::ResourceNotFound(message) if message == "details from AWS"
+        ));
+        assert!(matches!(
+            error("ExpiredTokenException"),
+            CloudWatchClientError::ExpiredCredentials(_)
+        ));
+        assert!(matches!(
+            error("UnrecognizedClientException"),
+            CloudWatchClientError::ExpiredCredentials(_)
+        ));
+        assert!(matches!(
+            error("ThrottlingException"),
+            CloudWatchClientError::Throttled(0, _)
+        ));
+        assert!(matches!(
+            error("InvalidParameterException"),
+            CloudWatchClientError::AwsError(message)
+                if message == "InvalidParameterException: details from AWS"
+        ));
+    }
+
+    #[test]
+    fn retried_errors_keep_their_kind() {
+        let denied = RetryError::Permanent(service_error("AccessDeniedException"));
+        assert!(matches!(
+            CloudWatchClientError::from_request("logs:FilterLogEvents", denied),
+            CloudWatchClientError::AccessDenied("logs:FilterLogEvents")
+        ));
+
+        let throttled = RetryError::Exhausted(3, service_error("ThrottlingException"));
+        assert!(matches!(
+            CloudWatchClientError::from_request("logs:FilterLogEvents", throttled),
+            CloudWatchClientError::Throttled(3, _)
+        ));
+
+        // A server error that outlasted the retries is not reported as throttling
+        let failing = RetryError::Exhausted(3, service_error("InternalFailure"));
+        assert!(!matches!(
+            CloudWatchClientError::from_request("logs:FilterLogEvents", failing),
+            CloudWatchClientError::Throttled(..)
+        ));
+
+        let missing = RetryError::Permanent(service_error("ResourceNotFoundException"));
+        assert!(matches!(
+            CloudWatchClientError::from_request("logs:FilterLogEvents", missing),
+            CloudWatchClientError::ResourceNotFound(_)
+        ));
     }
 }
@@ -0,0 +1,122 @@
+//! AWS console link module
+//!
+//! Builds region-aware deep links into the AWS Management Console for the
+//! resources shown in the TUI, and opens them in the system browser.
+
+/// A resource that can be looked up in the AWS console
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsoleResource {
+    S3Bucket(String),
+    DynamoDBTable(String),
+    LogGroup(String),
+}
+
+impl ConsoleResource {
+    /// Returns the name or identifier of the resource
+    pub fn identifier(&self) -> &str {
+        match self {
+            ConsoleResource::S3Bucket(name)
+            | ConsoleResource::DynamoDBTable(name)
+            | ConsoleResource::LogGroup(name) => name,
+        }
+    }
+
+    /// Builds the console URL of the resource in the given region
+    pub fn url(&self, region: &str) -> String {
+        let console = format!("https://{region}.console.aws.amazon.com");
+        match self {
+            ConsoleResource::S3Bucket(bucket) => format!(
+                "{console}/s3/buckets/{}?region={region}",
+                percent_encode(bucket)
+            ),
+            ConsoleResource::DynamoDBTable(table) => format!(
+                "{console}/dynamodbv2/home?region={region}#table?name={}",
+                percent_encode(table)
+            ),
+            // The logs console expects the group name percent-encoded twice, with `$` in place of `%`
+            ConsoleResource::LogGroup(log_group) => format!(
+                "{console}/cloudwatch/home?region={region}#logsV2:log-groups/log-group/{}",
+                percent_encode(&percent_encode(log_group)).replace('%', "$")
+            ),
+        }
+    }
+}
+
+/// Percent-encodes everything except RFC 3986 unreserved characters
+pub(crate) fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Opens a URL with the platform's default handler without waiting for it
+///
+/// The handler is reaped once it exits, so it does not linger as a zombie process.
+pub fn open_in_browser(url: &str) -> std::io::Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    let mut command = std::process::Command::new(opener);
+    command.arg(url);
+    spawn_reaped(command).map(|_| ())
+}
+
+/// Starts a command detached from the terminal and waits for it on a background thread;
+/// returns its process id
+fn spawn_reaped(mut command: std::process::Command) -> std::io::Result<u32> {
+    // The handler must not read the TUI's key presses or draw over it
+    let mut child = command
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+    let id = child.id();
+    std::thread::spawn(move || child.wait());
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_region_aware_links() {
+        assert_eq!(
+            ConsoleResource::S3Bucket("my-bucket".into()).url("eu-west-1"),
+            "https://eu-west-1.console.aws.amazon.com/s3/buckets/my-bucket?region=eu-west-1"
+        );
+        assert_eq!(
+            ConsoleResource::DynamoDBTable("Orders".into()).url("us-east-1"),
+            "https://us-east-1.console.aws.amazon.com/dynamodbv2/home?region=us-east-1#table?name=Orders"
+        );
+    }
+
+    #[test]
+    fn log_group_names_are_console_encoded() {
+        assert_eq!(
+            ConsoleResource::LogGroup("/aws/lambda/my-fn".into()).url("eu-west-1"),
+            "https://eu-west-1.console.aws.amazon.com/cloudwatch/home?region=eu-west-1#logsV2:log-groups/log-group/$252Faws$252Flambda$252Fmy-fn"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn exited_handlers_do_not_linger_as_zombies() {
+        let id = spawn_reaped(std::process::Command::new("true")).unwrap();
+        let status = std::path::PathBuf::from(format!("/proc/{}/status", id));
+        // Reaped processes disappear from /proc; zombies stay in state Z
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while status.exists() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(!status.exists());
+    }
+}
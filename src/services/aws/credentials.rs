@@ -0,0 +1,183 @@
+//! Credentials module
+//!
+//! The SDK resolves profiles that assume a role (`role_arn` with `source_profile` or
+//! `credential_source`) on its own, but only when the first request is sent, and its
+//! errors then read like connection failures. Clients call [`verify_role_credentials`]
+//! while they are created so a failing AssumeRole is reported as such.
+
+use crate::services::read_config::RoleSettings;
+use aws_config::SdkConfig;
+use aws_sdk_sts::config::ProvideCredentials;
+use std::error::Error as StdError;
+use thiserror::Error;
+
+/// Why the credentials of a role-assuming profile could not be obtained
+#[derive(Error, Debug, PartialEq)]
+pub enum CredentialsError {
+    /// The role needs an MFA code, which cannot be entered from the TUI
+    #[error(
+        "Profile '{profile}' needs an MFA code from {mfa_serial} to assume its role; export temporary credentials (e.g. with `aws sts assume-role`) and use a profile without mfa_serial"
+    )]
+    MfaRequired { profile: String, mfa_serial: String },
+
+    /// STS refused to let the source credentials assume the role
+    #[error("Profile '{profile}' is not allowed to assume {role_arn}: {detail}")]
+    AssumeRoleDenied {
+        profile: String,
+        role_arn: String,
+        detail: String,
+    },
+
+    /// Any other failure along the chain, e.g. a missing source profile
+    #[error("Could not get credentials for role profile '{profile}': {detail}")]
+    Unavailable { profile: String, detail: String },
+}
+
+/// Resolves the credentials of a role-assuming profile once, reporting why that failed.
+///
+/// Does nothing for profiles without a role, which keep failing on their first request.
+pub async fn verify_role_credentials(
+    config: &SdkConfig,
+    profile: &str,
+    role: Option<&RoleSettings>,
+) -> Result<(), CredentialsError> {
+    let (Some(role), Some(provider)) = (role, config.credentials_provider()) else {
+        return Ok(());
+    };
+    match provider.provide_credentials().await {
+        Ok(_) => Ok(()),
+        Err(err) => Err(explain(profile, role, &error_chain(&err))),
+    }
+}
+
+/// Messages of an error and all of its sources, outermost first
+fn error_chain(err: &(dyn StdError + 'static)) -> Vec<String> {
+    let mut messages = Vec::new();
+    let mut current = Some(err);
+    while let Some(err) = current {
+        messages.push(err.to_string());
+        current = err.source();
+    }
+    messages
+}
+
+/// Picks the failure that matches the messages of a credentials error
+fn explain(profile: &str, role: &RoleSettings, messages: &[String]) -> CredentialsError {
+    let profile = profile.to_string();
+    let detail = messages.last().cloned().unwrap_or_default();
+    let denied = messages
+        .iter()
+        .any(|message| message.contains("AccessDenied") || message.contains("not authorized"));
+
+    match (&role.mfa_serial, denied) {
+        (Some(mfa_serial), true) => CredentialsError::MfaRequired {
+            profile,
+            mfa_serial: mfa_serial.clone(),
+        },
+        (None, true) => CredentialsError::AssumeRoleDenied {
+            profile,
+            role_arn: role.role_arn.clone(),
+            detail,
+        },
+        _ => CredentialsError::Unavailable { profile, detail },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(mfa_serial: Option<&str>) -> RoleSettings {
+        RoleSettings {
+            role_arn: "arn:aws:iam::210987654321:role/Admin".into(),
+            mfa_serial: mfa_serial.map(String::from),
+        }
+    }
+
+    #[test]
+    fn denied_roles_are_told_apart_from_other_failures() {
+        let denied = vec![
+            "an error occurred while loading credentials".to_string(),
+            "service error".to_string(),
+            "AccessDenied: User is not authorized to perform: sts:AssumeRole".to_string(),
+        ];
+        assert!(matches!(
+            explain("prod", &role(None), &denied),
+            CredentialsError::AssumeRoleDenied { .. }
+        ));
+        assert!(matches!(
+            explain("prod", &role(Some("arn:aws:iam::1:mfa/me")), &denied),
+            CredentialsError::MfaRequired { .. }
+        ));
+
+        let missing_source = vec!["profile `dev` was not defined".to_string()];
+        assert_eq!(
+            explain(
+                "prod",
+                &role(Some("arn:aws:iam::1:mfa/me")),
+                &missing_source
+            ),
+            CredentialsError::Unavailable {
+                profile: "prod".into(),
+                detail: "profile `dev` was not defined".into(),
+            }
+        );
+    }
+
+    /// Assumes a role against a local STS stand-in that denies every request
+    #[cfg(feature = "mock-sts")]
+    #[tokio::test]
+    // aws-config only re-exports the profile file types under their deprecated names
+    #[allow(deprecated)]
+    async fn denied_assume_role_is_reported_by_a_mock_sts() {
+        use aws_config::profile::profile_file::{ProfileFileKind, ProfileFiles};
+        use aws_config::{BehaviorVersion, Region};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let body = r#"<ErrorResponse xmlns="https://sts.amazonaws.com/doc/2011-06-15/"><Error><Type>Sender</Type><Code>AccessDenied</Code><Message>User is not authorized to perform: sts:AssumeRole</Message></Error><RequestId>1</RequestId></ErrorResponse>"#;
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 8192];
+                let _ = socket.read(&mut request).await;
+                let response = format!(
+                    "HTTP/1.1 403 Forbidden\r\ncontent-type: text/xml\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let config_path =
+            std::env::temp_dir().join(format!("aws-tui-mock-sts-{}", std::process::id()));
+        std::fs::write(
+            &config_path,
+            format!(
+                "[profile base]\naws_access_key_id = AKIDBASE\naws_secret_access_key = secret\n\n\
+                 [profile admin]\nrole_arn = arn:aws:iam::210987654321:role/Admin\nsource_profile = base\nendpoint_url = {endpoint}\n"
+            ),
+        )
+        .unwrap();
+        let config = aws_config::defaults(BehaviorVersion::latest())
+            .profile_files(
+                ProfileFiles::builder()
+                    .with_file(ProfileFileKind::Config, &config_path)
+                    .build(),
+            )
+            .profile_name("admin")
+            .region(Region::new("us-east-1"))
+            .endpoint_url(&endpoint)
+            .load()
+            .await;
+
+        let result = verify_role_credentials(&config, "admin", Some(&role(None))).await;
+        let _ = std::fs::remove_file(&config_path);
+        assert!(
+            matches!(result, Err(CredentialsError::AssumeRoleDenied { .. })),
+            "{:?}",
+            result
+        );
+    }
+}
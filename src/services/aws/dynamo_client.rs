@@ -3,15 +3,31 @@
 //! Provides functionality to interact with AWS DynamoDB service,
 //! including listing tables, querying data, and retrieving table metadata.
 
-use aws_config::{BehaviorVersion, Region, defaults};
+use aws_config::SdkConfig;
 use aws_sdk_dynamodb::error::SdkError;
-use aws_sdk_dynamodb::operation::{list_tables::ListTablesError, query::QueryError};
-use aws_sdk_dynamodb::types::AttributeValue;
-use aws_sdk_dynamodb::{Client, Error as DynamoDBError};
+use aws_sdk_dynamodb::operation::{
+    batch_get_item::BatchGetItemError, list_tables::ListTablesError, query::QueryError,
+    scan::ScanError,
+};
+use aws_sdk_dynamodb::types::{
+    AttributeValue, KeySchemaElement, KeyType, KeysAndAttributes, TableStatus,
+};
+use aws_sdk_dynamodb::Client;
 use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
-use serde_json::Value;
+use aws_smithy_types::base64;
+use aws_smithy_types::error::metadata::ProvideErrorMetadata;
+use crate::services::aws::CappedRecords;
+use crate::services::aws::credentials::{self, CredentialsError};
+use crate::services::aws::retry::{self, RetryError, RetryPolicy, Retryable};
+use crate::services::read_config;
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
 use thiserror::Error;
 
+/// Keys one BatchGetItem request may ask for
+const BATCH_GET_LIMIT: usize = 100;
+
 /// Errors that can occur when interacting with DynamoDB
 #[derive(Error, Debug)]
 pub enum DynamoDBClientError {
@@ -20,50 +36,264 @@ pub enum DynamoDBClientError {
     AWSDynamoDBError(#[from] aws_sdk_dynamodb::Error),
 
     /// Error during ListTables operation
-    #[error("ListTables error: {0}")]
+    #[error("ListTables error: {}", service_message(.0))]
     ListTablesError(#[from] SdkError<ListTablesError, HttpResponse>),
 
     /// Error during Query operation
-    #[error("Query error: {0}")]
+    #[error("Query error: {}", service_message(.0))]
     QueryError(#[from] SdkError<QueryError, HttpResponse>),
 
+    /// Error during Scan operation
+    #[error("Scan error: {}", service_message(.0))]
+    ScanError(#[from] SdkError<ScanError, HttpResponse>),
+
+    /// Error during BatchGetItem operation
+    #[error("BatchGetItem error: {}", service_message(.0))]
+    BatchGetError(#[from] SdkError<BatchGetItemError, HttpResponse>),
+
+    /// A BatchGetItem request could not be built
+    #[error("Invalid batch get request: {0}")]
+    BatchGetRequest(#[from] aws_smithy_types::error::operation::BuildError),
+
+    /// Items of a table with a sort key cannot be read by partition key alone
+    #[error("Batch get needs a table without a sort key; query the keys one at a time")]
+    BatchGetNeedsFullKey,
+
     /// Error during DescribeTable operation
-    #[error("DescribeTable error: {0}")]
+    #[error("DescribeTable error: {}", service_message(.0))]
     DescribeTableError(
         #[from]
         SdkError<aws_sdk_dynamodb::operation::describe_table::DescribeTableError, HttpResponse>,
     ),
 
-    /// No primary key found for table - occurs when table schema is missing or incomplete
-    #[error("No primary key found for table")]
-    NoPrimaryKeyFound,
+    /// DescribeTable succeeded but returned no description of the table
+    #[error("DynamoDB returned no description of table `{0}`")]
+    TableNotDescribed(String),
+
+    /// The table's key schema has no partition (HASH) key, so it cannot be queried
+    #[error("Table `{0}` has no partition (HASH) key to query by; scan it instead")]
+    NoPrimaryKeyFound(String),
+
+    /// Request still throttled after retrying with backoff
+    #[error("DynamoDB is throttling requests, gave up after {0} retries: {1}")]
+    Throttled(u32, String),
+
+    /// Request took longer than the configured timeout
+    #[error("DynamoDB request timed out; raise `dynamodb` under [timeouts] in config.toml")]
+    TimedOut,
+
+    /// The profile's IAM policy does not allow the named action
+    #[error("Access denied: the profile needs the `{0}` permission in its IAM policy")]
+    AccessDenied(&'static str),
+
+    /// Credentials of a role-assuming profile could not be obtained
+    #[error(transparent)]
+    Credentials(#[from] CredentialsError),
+}
+
+/// Error codes of requests signed with expired or unrecognized credentials
+const EXPIRED_CREDENTIALS_CODES: [&str; 3] = [
+    "ExpiredTokenException",
+    "UnrecognizedClientException",
+    "InvalidSignatureException",
+];
+
+/// AWS error code and message of a failed request, e.g. `ValidationException: ...`
+///
+/// The SDK error itself only displays its kind, such as "service error".
+fn service_message<E: ProvideErrorMetadata>(err: &SdkError<E, HttpResponse>) -> String {
+    match (err.code(), err.message()) {
+        (Some(code), Some(message)) => format!("{}: {}", code, message),
+        (Some(code), None) => code.to_string(),
+        (None, Some(message)) => message.to_string(),
+        (None, None) => err.to_string(),
+    }
+}
+
+impl<E> From<RetryError<E>> for DynamoDBClientError
+where
+    E: Retryable + std::fmt::Display,
+    DynamoDBClientError: From<E>,
+{
+    fn from(err: RetryError<E>) -> Self {
+        match err {
+            RetryError::Exhausted(retries, err) if err.is_retryable() => {
+                DynamoDBClientError::Throttled(retries, err.to_string())
+            }
+            // Requests that kept failing on the way report their last error
+            RetryError::Permanent(err) | RetryError::Exhausted(_, err) => err.into(),
+            RetryError::TimedOut(_) => DynamoDBClientError::TimedOut,
+        }
+    }
+}
+
+impl DynamoDBClientError {
+    /// Converts a failed request, naming the IAM action it needs when it was denied
+    fn from_request<E>(action: &'static str, err: RetryError<E>) -> Self
+    where
+        E: Retryable + std::fmt::Display,
+        DynamoDBClientError: From<E>,
+    {
+        if err.is_access_denied() {
+            DynamoDBClientError::AccessDenied(action)
+        } else {
+            err.into()
+        }
+    }
+
+    /// AWS error code of a failed request
+    fn code(&self) -> Option<&str> {
+        match self {
+            DynamoDBClientError::AWSDynamoDBError(err) => err.code(),
+            DynamoDBClientError::ListTablesError(err) => err.code(),
+            DynamoDBClientError::QueryError(err) => err.code(),
+            DynamoDBClientError::ScanError(err) => err.code(),
+            DynamoDBClientError::BatchGetError(err) => err.code(),
+            DynamoDBClientError::DescribeTableError(err) => err.code(),
+            _ => None,
+        }
+    }
+
+    /// Likely cause of a common failure and what to do about it, in one line
+    pub fn hint(&self) -> Option<&'static str> {
+        if let DynamoDBClientError::Throttled(..) = self {
+            return Some(
+                "The table's read capacity is used up; try again later or narrow the query",
+            );
+        }
+        match self.code()? {
+            "ValidationException" => Some(
+                "DynamoDB rejected the request; check the key values match the table's key \
+                 schema, or did you mean to query a GSI?",
+            ),
+            "ResourceNotFoundException" => {
+                Some("The table was not found; it may have been deleted or be in another region")
+            }
+            code if EXPIRED_CREDENTIALS_CODES.contains(&code) => {
+                Some("The profile's credentials are expired or invalid; refresh them and reopen it")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Why a query, scan or batch get failed, as shown in the results
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryFailure {
+    /// One-line explanation of a common failure, see [`DynamoDBClientError::hint`]
+    pub hint: Option<&'static str>,
+    /// The error as reported, with the AWS error code and message
+    pub message: String,
+}
+
+impl From<DynamoDBClientError> for QueryFailure {
+    fn from(err: DynamoDBClientError) -> Self {
+        QueryFailure {
+            hint: err.hint(),
+            message: err.to_string(),
+        }
+    }
+}
+
+/// Items found by a batch get, and the requested keys no item has
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchGetRecords {
+    pub records: CappedRecords,
+    pub missing: Vec<String>,
+}
+
+/// Names of a table's key attributes
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeySchema {
+    pub partition_key: String,
+    pub sort_key: Option<String>,
+}
+
+impl KeySchema {
+    /// Reads the key names from a table's key schema, `None` when it has no HASH key
+    fn from_elements(elements: &[KeySchemaElement]) -> Option<Self> {
+        let key_of = |key_type: KeyType| {
+            elements
+                .iter()
+                .find(|element| *element.key_type() == key_type)
+                .map(|element| element.attribute_name().to_string())
+        };
+        Some(Self {
+            partition_key: key_of(KeyType::Hash)?,
+            sort_key: key_of(KeyType::Range),
+        })
+    }
 }
 
 /// Client for AWS DynamoDB API operations
 pub struct DynamoDBClient {
     /// AWS SDK DynamoDB client
     client: Client,
+    /// Backoff used when requests are throttled
+    retry_policy: RetryPolicy,
+    /// Items fetched at most by one query, `None` for no cap
+    max_results: Option<usize>,
+    /// Key schema of each table described so far
+    key_schemas: Mutex<HashMap<String, KeySchema>>,
 }
 
 impl DynamoDBClient {
-    /// Creates a new DynamoDB client with the specified AWS profile and region
+    /// Creates a new DynamoDB client from the shared SDK config of the given profile
     ///
     /// # Parameters
-    /// * `profile` - AWS profile name to use for authentication
-    /// * `region` - AWS region to connect to
-    pub async fn new(profile: String, region: String) -> Result<Self, DynamoDBError> {
-        // Configure AWS SDK with profile and region
-        let config = defaults(BehaviorVersion::latest())
-            .profile_name(profile)
-            .region(Region::new(region))
-            .load()
-            .await;
+    /// * `config` - SDK config with the region, timeouts and optional custom endpoint
+    /// * `profile` - AWS profile name the config was loaded for
+    pub async fn new(config: &SdkConfig, profile: &str) -> Result<Self, DynamoDBClientError> {
+        credentials::verify_role_credentials(
+            config,
+            profile,
+            read_config::get_role_settings(profile).as_ref(),
+        )
+        .await?;
 
         Ok(Self {
-            client: Client::new(&config),
+            client: Client::new(config),
+            retry_policy: RetryPolicy::from_config(),
+            max_results: read_config::get_max_results(),
+            key_schemas: Mutex::new(HashMap::new()),
         })
     }
 
+    /// Returns the partition and sort key names of a table
+    ///
+    /// The table is described on first use only; its key schema is cached for the life of
+    /// the client, as a table's keys cannot change without recreating it.
+    pub async fn key_schema(&self, table_name: &str) -> Result<KeySchema, DynamoDBClientError> {
+        if let Some(schema) = self.cached_key_schema(table_name) {
+            return Ok(schema);
+        }
+
+        let result = retry::with_retry(&self.retry_policy, || {
+            self.client.describe_table().table_name(table_name).send()
+        })
+        .await
+        .map_err(|err| DynamoDBClientError::from_request("dynamodb:DescribeTable", err))?;
+        let table = result
+            .table()
+            .ok_or_else(|| DynamoDBClientError::TableNotDescribed(table_name.to_string()))?;
+        let schema = KeySchema::from_elements(table.key_schema())
+            .ok_or_else(|| DynamoDBClientError::NoPrimaryKeyFound(table_name.to_string()))?;
+
+        self.key_schemas
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(table_name.to_string(), schema.clone());
+        Ok(schema)
+    }
+
+    fn cached_key_schema(&self, table_name: &str) -> Option<KeySchema> {
+        self.key_schemas
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(table_name)
+            .cloned()
+    }
+
     /// Retrieves the primary key (partition key) name for a DynamoDB table
     ///
     /// # Parameters
@@ -75,31 +305,7 @@ impl DynamoDBClient {
         &self,
         table_name: &str,
     ) -> Result<String, DynamoDBClientError> {
-        // Get table description from AWS
-        let result = self
-            .client
-            .describe_table()
-            .table_name(table_name)
-            .send()
-            .await?;
-
-        // Extract table schema from response
-        let table = result
-            .table()
-            .ok_or(DynamoDBClientError::NoPrimaryKeyFound)?;
-
-        let key_schema = table.key_schema();
-        if key_schema.is_empty() {
-            return Err(DynamoDBClientError::NoPrimaryKeyFound);
-        }
-
-        // Find the HASH key (partition key) in the key schema
-        let primary_key = key_schema
-            .iter()
-            .find(|k| k.key_type().as_str() == "HASH")
-            .ok_or(DynamoDBClientError::NoPrimaryKeyFound)?;
-
-        Ok(primary_key.attribute_name().to_string())
+        Ok(self.key_schema(table_name).await?.partition_key)
     }
 
     /// Retrieves the sort key (range key) name for a DynamoDB table if it exists
@@ -113,28 +319,7 @@ impl DynamoDBClient {
         &self,
         table_name: &str,
     ) -> Result<Option<String>, DynamoDBClientError> {
-        // Get table description from AWS
-        let result = self
-            .client
-            .describe_table()
-            .table_name(table_name)
-            .send()
-            .await?;
-
-        // Extract table schema from response
-        let table = result
-            .table()
-            .ok_or(DynamoDBClientError::NoPrimaryKeyFound)?;
-
-        let key_schema = table.key_schema();
-        
-        // Find the RANGE key (sort key) in the key schema
-        let sort_key = key_schema
-            .iter()
-            .find(|k| k.key_type().as_str() == "RANGE")
-            .map(|k| k.attribute_name().to_string());
-
-        Ok(sort_key)
+        Ok(self.key_schema(table_name).await?.sort_key)
     }
 
         /// Queries a DynamoDB table by its composite key (partition key + optional sort key)
@@ -143,17 +328,23 @@ impl DynamoDBClient {
     /// * `table_name` - Name of the table to query
     /// * `partition_key_value` - Value of the partition key to search for
     /// * `sort_key_value` - Optional value of the sort key for refinement
+    /// * `projection` - Attributes to return; every attribute is returned when empty
     ///
     /// # Returns
-    /// A vector of JSON strings representing the items found
+    /// JSON strings representing the items found, following result pages until
+    /// `max_results` items have been collected
     pub async fn query_table_composite(
         &self,
         table_name: String,
         partition_key_value: String,
         sort_key_value: Option<String>,
-    ) -> Result<Vec<String>, DynamoDBClientError> {
-        // First get the primary key name for this table
-        let partition_key = self.get_table_primary_key(table_name.as_str()).await?;
+        projection: &[String],
+    ) -> Result<CappedRecords, DynamoDBClientError> {
+        // First get the key names for this table, described once per table
+        let KeySchema {
+            partition_key,
+            sort_key,
+        } = self.key_schema(table_name.as_str()).await?;
         
         // Create attribute value for query parameter
         let pk_attr_value = AttributeValue::S(partition_key_value);
@@ -165,46 +356,373 @@ impl DynamoDBClient {
         
         // If sort key value is provided, add it to the query
         if let Some(sort_value) = sort_key_value {
-            if !sort_value.is_empty() {
-                // Get the sort key name
-                if let Ok(Some(sort_key)) = self.get_table_sort_key(table_name.as_str()).await {
-                    // Only add sort key condition if we found a sort key for this table
-                    let sk_attr_value = AttributeValue::S(sort_value);
-                    expression_attribute_values.insert(String::from(":sk"), sk_attr_value);
-                    
-                    // Append sort key condition to expression
-                    key_condition_expr = format!("{} AND {} = :sk", key_condition_expr, sort_key);
+            // Only add sort key condition if this table has a sort key
+            if !sort_value.is_empty()
+                && let Some(sort_key) = sort_key
+            {
+                let sk_attr_value = AttributeValue::S(sort_value);
+                expression_attribute_values.insert(String::from(":sk"), sk_attr_value);
+
+                // Append sort key condition to expression
+                key_condition_expr = format!("{} AND {} = :sk", key_condition_expr, sort_key);
+            }
+        }
+
+        // Only fetch the requested attributes, if any
+        let (projection_expr, attribute_names) = match build_projection(projection) {
+            Some((expression, names)) => (Some(expression), Some(names)),
+            None => (None, None),
+        };
+
+        let mut items = Vec::new();
+        let mut raw_items = Vec::new();
+        let mut start_key = None;
+        loop {
+            // Never ask for more items than are left under the cap
+            let limit = self.max_results.map(|max_results| {
+                max_results.saturating_sub(items.len()).min(i32::MAX as usize) as i32
+            });
+
+            // Execute the query with key condition expression, backing off while throttled
+            let output = retry::with_retry(&self.retry_policy, || {
+                self.client
+                    .query()
+                    .table_name(table_name.clone())
+                    .key_condition_expression(key_condition_expr.clone())
+                    .set_expression_attribute_values(Some(expression_attribute_values.clone()))
+                    .set_projection_expression(projection_expr.clone())
+                    .set_expression_attribute_names(attribute_names.clone())
+                    .set_exclusive_start_key(start_key.clone())
+                    .set_limit(limit)
+                    .send()
+            })
+            .await
+            .map_err(|err| DynamoDBClientError::from_request("dynamodb:Query", err))?;
+
+            // Convert DynamoDB items to JSON strings
+            DynamoDBClient::collect_items(output.items(), &mut items, &mut raw_items);
+
+            // A page is only followed by another while DynamoDB returns where to resume
+            start_key = output.last_evaluated_key().cloned();
+            if start_key.is_none() {
+                break;
+            }
+
+            // More items are available, but the cap has been reached
+            if let Some(max_results) = self.max_results
+                && items.len() >= max_results
+            {
+                return Ok(CappedRecords {
+                    records: items,
+                    truncated_at: Some(max_results),
+                    total: None,
+                    raw: raw_items,
+                });
+            }
+        }
+
+        Ok(CappedRecords {
+            records: items,
+            truncated_at: None,
+            total: None,
+            raw: raw_items,
+        })
+    }
+
+    /// Reads every item of a table, following result pages until `max_results` items have
+    /// been collected
+    ///
+    /// # Parameters
+    /// * `table_name` - Name of the table to scan
+    /// * `projection` - Attributes to return; every attribute is returned when empty
+    pub async fn scan_table(
+        &self,
+        table_name: String,
+        projection: &[String],
+    ) -> Result<CappedRecords, DynamoDBClientError> {
+        let (projection_expr, attribute_names) = match build_projection(projection) {
+            Some((expression, names)) => (Some(expression), Some(names)),
+            None => (None, None),
+        };
+
+        let mut items = Vec::new();
+        let mut raw_items = Vec::new();
+        let mut start_key = None;
+        loop {
+            // Never ask for more items than are left under the cap
+            let limit = self.max_results.map(|max_results| {
+                max_results.saturating_sub(items.len()).min(i32::MAX as usize) as i32
+            });
+
+            let output = retry::with_retry(&self.retry_policy, || {
+                self.client
+                    .scan()
+                    .table_name(table_name.clone())
+                    .set_projection_expression(projection_expr.clone())
+                    .set_expression_attribute_names(attribute_names.clone())
+                    .set_exclusive_start_key(start_key.clone())
+                    .set_limit(limit)
+                    .send()
+            })
+            .await
+            .map_err(|err| DynamoDBClientError::from_request("dynamodb:Scan", err))?;
+
+            DynamoDBClient::collect_items(output.items(), &mut items, &mut raw_items);
+
+            start_key = output.last_evaluated_key().cloned();
+            if start_key.is_none() {
+                break;
+            }
+            if let Some(max_results) = self.max_results
+                && items.len() >= max_results
+            {
+                // The table's item count is only refreshed every six hours or so, but
+                // tells how much of the table was left out
+                return Ok(CappedRecords {
+                    records: items,
+                    truncated_at: Some(max_results),
+                    total: self.table_item_count(&table_name).await,
+                    raw: raw_items,
+                });
+            }
+        }
+
+        Ok(CappedRecords {
+            records: items,
+            truncated_at: None,
+            total: None,
+            raw: raw_items,
+        })
+    }
+
+    /// Returns the item count DynamoDB reports for a table, if the table can be described
+    async fn table_item_count(&self, table_name: &str) -> Option<usize> {
+        let result = retry::with_retry(&self.retry_policy, || {
+            self.client.describe_table().table_name(table_name).send()
+        })
+        .await
+        .ok()?;
+        let count = result.table()?.item_count()?;
+        usize::try_from(count).ok()
+    }
+
+    /// Reads the items with the given partition key values, in requests of up to 100 keys
+    ///
+    /// Keys DynamoDB leaves unprocessed are requested again with backoff. Only tables
+    /// without a sort key can be read this way, as every key must be complete.
+    ///
+    /// # Parameters
+    /// * `table_name` - Name of the table to read
+    /// * `keys` - Partition key values; repeated values are read once
+    /// * `projection` - Attributes to return; every attribute is returned when empty
+    pub async fn batch_get(
+        &self,
+        table_name: &str,
+        keys: Vec<String>,
+        projection: &[String],
+    ) -> Result<BatchGetRecords, DynamoDBClientError> {
+        let KeySchema {
+            partition_key,
+            sort_key,
+        } = self.key_schema(table_name).await?;
+        if sort_key.is_some() {
+            return Err(DynamoDBClientError::BatchGetNeedsFullKey);
+        }
+
+        // Found items are matched to the requested keys, so the key is always returned
+        let mut projection = projection.to_vec();
+        if !projection.is_empty() && !projection.contains(&partition_key) {
+            projection.push(partition_key.clone());
+        }
+        let (projection_expr, attribute_names) = match build_projection(&projection) {
+            Some((expression, names)) => (Some(expression), Some(names)),
+            None => (None, None),
+        };
+
+        // BatchGetItem rejects a request that names the same key twice
+        let mut seen = HashSet::new();
+        let keys: Vec<String> = keys
+            .into_iter()
+            .filter(|key| seen.insert(key.clone()))
+            .collect();
+
+        let mut items = Vec::new();
+        let mut raw_items = Vec::new();
+        let mut found = HashSet::new();
+        for chunk in keys.chunks(BATCH_GET_LIMIT) {
+            let mut pending: Vec<HashMap<String, AttributeValue>> = chunk
+                .iter()
+                .map(|key| HashMap::from([(partition_key.clone(), AttributeValue::S(key.clone()))]))
+                .collect();
+            let mut retry = 0;
+            while !pending.is_empty() {
+                let request = KeysAndAttributes::builder()
+                    .set_keys(Some(pending))
+                    .set_projection_expression(projection_expr.clone())
+                    .set_expression_attribute_names(attribute_names.clone())
+                    .build()?;
+                let output = retry::with_retry(&self.retry_policy, || {
+                    self.client
+                        .batch_get_item()
+                        .request_items(table_name, request.clone())
+                        .send()
+                })
+                .await
+                .map_err(|err| DynamoDBClientError::from_request("dynamodb:BatchGetItem", err))?;
+
+                let returned = output
+                    .responses()
+                    .and_then(|responses| responses.get(table_name))
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                found.extend(returned.iter().filter_map(|item| {
+                    item.get(&partition_key)
+                        .and_then(|value| value.as_s().ok())
+                        .cloned()
+                }));
+                DynamoDBClient::collect_items(returned, &mut items, &mut raw_items);
+
+                // Keys left over when the table is throttled are sent again after a backoff
+                pending = output
+                    .unprocessed_keys()
+                    .and_then(|unprocessed| unprocessed.get(table_name))
+                    .map(|request| request.keys().to_vec())
+                    .unwrap_or_default();
+                if !pending.is_empty() {
+                    if retry >= self.retry_policy.max_retries {
+                        return Err(DynamoDBClientError::Throttled(
+                            retry,
+                            format!("{} keys were left unprocessed", pending.len()),
+                        ));
+                    }
+                    tokio::time::sleep(self.retry_policy.delay(retry)).await;
+                    retry += 1;
                 }
             }
         }
 
-        // Execute the query with key condition expression
-        let output = self
-            .client
-            .query()
-            .table_name(table_name)
-            .key_condition_expression(key_condition_expr)
-            .set_expression_attribute_values(Some(expression_attribute_values))
-            .send()
-            .await?;
+        let missing = keys.into_iter().filter(|key| !found.contains(key)).collect();
+        Ok(BatchGetRecords {
+            records: CappedRecords {
+                records: items,
+                truncated_at: None,
+                total: None,
+                raw: raw_items,
+            },
+            missing,
+        })
+    }
+
+    /// Appends items as simplified JSON records and, alongside, in DynamoDB JSON
+    fn collect_items(
+        output: &[HashMap<String, AttributeValue>],
+        items: &mut Vec<String>,
+        raw_items: &mut Vec<String>,
+    ) {
+        for item in output {
+            // Map each item's attributes to JSON
+            let json_value: Value = item
+                .iter()
+                .map(|(k, v)| (k.clone(), DynamoDBClient::attribute_to_json(v)))
+                .collect();
+            // Keep every attribute as well, since the JSON above drops unsupported types
+            let raw_value: Value = item
+                .iter()
+                .map(|(k, v)| (k.clone(), DynamoDBClient::attribute_to_dynamodb_json(v)))
+                .collect();
+
+            // Serialize to JSON string, skipping items that fail
+            if let (Ok(json), Ok(raw)) =
+                (serde_json::to_string(&json_value), serde_json::to_string(&raw_value))
+            {
+                items.push(json);
+                raw_items.push(raw);
+            }
+        }
+    }
+
+    /// Summarizes a table's size, key schema and capacity settings
+    ///
+    /// # Parameters
+    /// * `table_name` - Name of the table to describe
+    ///
+    /// # Returns
+    /// A JSON string with item count, size, key schema, billing mode and throughput.
+    /// Tables that are still being created or deleted only report their status.
+    pub async fn describe_table_summary(
+        &self,
+        table_name: &str,
+    ) -> Result<String, DynamoDBClientError> {
+        let result = retry::with_retry(&self.retry_policy, || {
+            self.client.describe_table().table_name(table_name).send()
+        })
+        .await
+        .map_err(|err| DynamoDBClientError::from_request("dynamodb:DescribeTable", err))?;
+
+        let table = result
+            .table()
+            .ok_or_else(|| DynamoDBClientError::TableNotDescribed(table_name.to_string()))?;
+        let status = table
+            .table_status()
+            .map(|status| status.as_str().to_string())
+            .unwrap_or_else(|| "UNKNOWN".to_string());
+
+        // Size and capacity figures are not meaningful while the table is in transition
+        if matches!(
+            table.table_status(),
+            Some(TableStatus::Creating) | Some(TableStatus::Deleting)
+        ) {
+            return Ok(json!({
+                "table_name": table_name,
+                "status": status,
+            })
+            .to_string());
+        }
 
-        // Convert DynamoDB items to JSON strings
-        let items = output
-            .items()
+        let key_schema: Vec<Value> = table
+            .key_schema()
             .iter()
-            .filter_map(|item| {
-                // Map each item's attributes to JSON
-                let json_value: Value = item
-                    .iter()
-                    .map(|(k, v)| (k.clone(), DynamoDBClient::attribute_to_json(v)))
-                    .collect();
-
-                // Serialize to JSON string, ignoring errors
-                serde_json::to_string(&json_value).ok()
+            .map(|key| {
+                json!({
+                    "attribute_name": key.attribute_name(),
+                    "key_type": key.key_type().as_str(),
+                })
             })
             .collect();
 
-        Ok(items)
+        // Tables created without an explicit billing mode are provisioned
+        let billing_mode = table
+            .billing_mode_summary()
+            .and_then(|summary| summary.billing_mode())
+            .map(|mode| mode.as_str().to_string())
+            .unwrap_or_else(|| "PROVISIONED".to_string());
+
+        let throughput = if billing_mode == "PAY_PER_REQUEST" {
+            let on_demand = table.on_demand_throughput();
+            json!({
+                "mode": "on-demand",
+                "max_read_request_units": on_demand.and_then(|t| t.max_read_request_units()),
+                "max_write_request_units": on_demand.and_then(|t| t.max_write_request_units()),
+            })
+        } else {
+            let provisioned = table.provisioned_throughput();
+            json!({
+                "mode": "provisioned",
+                "read_capacity_units": provisioned.and_then(|t| t.read_capacity_units()),
+                "write_capacity_units": provisioned.and_then(|t| t.write_capacity_units()),
+            })
+        };
+
+        Ok(json!({
+            "table_name": table_name,
+            "status": status,
+            "item_count": table.item_count(),
+            "table_size_bytes": table.table_size_bytes(),
+            "key_schema": key_schema,
+            "billing_mode": billing_mode,
+            "throughput": throughput,
+        })
+        .to_string())
     }
 
     /// Lists all DynamoDB tables in the account and region
@@ -212,7 +730,9 @@ impl DynamoDBClient {
     /// # Returns
     /// A vector of table names as Strings
     pub async fn list_tables(&self) -> Result<Vec<String>, DynamoDBClientError> {
-        let output = self.client.list_tables().send().await?;
+        let output = retry::with_retry(&self.retry_policy, || self.client.list_tables().send())
+            .await
+            .map_err(|err| DynamoDBClientError::from_request("dynamodb:ListTables", err))?;
         Ok(output.table_names().to_vec())
     }
 
@@ -241,4 +761,229 @@ impl DynamoDBClient {
             _ => Value::Null,
         }
     }
+
+    /// Converts a DynamoDB AttributeValue to DynamoDB JSON, e.g. `{"N": "42"}`
+    ///
+    /// Unlike `attribute_to_json` every type is kept, the way the AWS CLI prints items;
+    /// binary values are base64 encoded
+    fn attribute_to_dynamodb_json(attr: &AttributeValue) -> Value {
+        match attr {
+            AttributeValue::S(s) => json!({ "S": s }),
+            AttributeValue::N(n) => json!({ "N": n }),
+            AttributeValue::B(b) => json!({ "B": base64::encode(b) }),
+            AttributeValue::Ss(values) => json!({ "SS": values }),
+            AttributeValue::Ns(values) => json!({ "NS": values }),
+            AttributeValue::Bs(values) => {
+                json!({ "BS": values.iter().map(base64::encode).collect::<Vec<_>>() })
+            }
+            AttributeValue::L(values) => json!({
+                "L": values
+                    .iter()
+                    .map(DynamoDBClient::attribute_to_dynamodb_json)
+                    .collect::<Vec<_>>()
+            }),
+            AttributeValue::M(map) => json!({
+                "M": map
+                    .iter()
+                    .map(|(k, v)| (k.clone(), DynamoDBClient::attribute_to_dynamodb_json(v)))
+                    .collect::<serde_json::Map<_, _>>()
+            }),
+            AttributeValue::Null(is_null) => json!({ "NULL": is_null }),
+            AttributeValue::Bool(b) => json!({ "BOOL": b }),
+            // Types added to the SDK later are still shown rather than dropped
+            other => json!({ "UNKNOWN": format!("{:?}", other) }),
+        }
+    }
+}
+
+/// Splits a comma-separated list of attribute names, dropping blank entries
+pub fn parse_projection(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Partition key values of a batch get typed as a comma- or newline-separated list
+///
+/// `None` for a single value, which is queried as usual.
+pub fn parse_batch_keys(input: &str) -> Option<Vec<String>> {
+    if !input.contains([',', '\n']) {
+        return None;
+    }
+    let keys: Vec<String> = input
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(String::from)
+        .collect();
+    (keys.len() > 1).then_some(keys)
+}
+
+/// Value of the `key` attribute of an item shown as JSON, as it would be typed into the
+/// query input; `None` unless the record is a JSON object with a string or number there
+pub fn item_key_value(record: &str, key: &str) -> Option<String> {
+    let item: Value = serde_json::from_str(record).ok()?;
+    match item.as_object()?.get(key)? {
+        Value::String(value) => Some(value.clone()),
+        Value::Number(value) => value.as_f64().map(|value| value.to_string()),
+        _ => None,
+    }
+}
+
+/// Builds a projection expression and its attribute name placeholders
+///
+/// Every attribute goes through a `#pN` placeholder so reserved words such as
+/// `name` or `status` can be projected. Returns `None` when no attributes are given.
+pub fn build_projection(attributes: &[String]) -> Option<(String, HashMap<String, String>)> {
+    if attributes.is_empty() {
+        return None;
+    }
+
+    let names: HashMap<String, String> = attributes
+        .iter()
+        .enumerate()
+        .map(|(index, attribute)| (format!("#p{}", index), attribute.clone()))
+        .collect();
+    let expression = (0..attributes.len())
+        .map(|index| format!("#p{}", index))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Some((expression, names))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str, key_type: KeyType) -> KeySchemaElement {
+        KeySchemaElement::builder()
+            .attribute_name(name)
+            .key_type(key_type)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn key_schemas_need_a_partition_key() {
+        let elements = [key("created", KeyType::Range), key("customer", KeyType::Hash)];
+        let schema = KeySchema::from_elements(&elements).unwrap();
+        assert_eq!(schema.partition_key, "customer");
+        assert_eq!(schema.sort_key.as_deref(), Some("created"));
+
+        assert_eq!(KeySchema::from_elements(&[key("created", KeyType::Range)]), None);
+    }
+
+    /// A failed Query request carrying the given error code
+    fn query_error(code: &str) -> DynamoDBClientError {
+        let metadata = aws_smithy_types::error::ErrorMetadata::builder()
+            .code(code)
+            .message("details from AWS")
+            .build();
+        DynamoDBClientError::QueryError(SdkError::service_error(
+            QueryError::generic(metadata),
+            HttpResponse::new(400.try_into().unwrap(), aws_smithy_types::body::SdkBody::empty()),
+        ))
+    }
+
+    #[test]
+    fn query_failures_keep_the_aws_message_and_explain_common_causes() {
+        let failure = QueryFailure::from(query_error("ValidationException"));
+        assert_eq!(
+            failure.message,
+            "Query error: ValidationException: details from AWS"
+        );
+        assert!(failure.hint.unwrap().contains("GSI"));
+
+        let failure = QueryFailure::from(query_error("ResourceNotFoundException"));
+        assert!(failure.hint.unwrap().contains("another region"));
+        assert!(QueryFailure::from(query_error("ExpiredTokenException")).hint.is_some());
+        assert!(
+            QueryFailure::from(DynamoDBClientError::Throttled(3, String::new()))
+                .hint
+                .is_some()
+        );
+
+        // Anything else is shown as AWS reported it
+        let failure = QueryFailure::from(query_error("InternalServerError"));
+        assert_eq!(failure.hint, None);
+        assert_eq!(failure.message, "Query error: InternalServerError: details from AWS");
+    }
+
+    #[test]
+    fn dynamodb_json_keeps_every_attribute_type() {
+        let item = AttributeValue::M(HashMap::from([
+            (
+                "tags".to_string(),
+                AttributeValue::Ss(vec!["a".to_string(), "b".to_string()]),
+            ),
+            (
+                "parts".to_string(),
+                AttributeValue::L(vec![
+                    AttributeValue::N("1".to_string()),
+                    AttributeValue::Null(true),
+                ]),
+            ),
+            (
+                "blob".to_string(),
+                AttributeValue::B(aws_sdk_dynamodb::primitives::Blob::new("hi")),
+            ),
+        ]));
+        assert_eq!(DynamoDBClient::attribute_to_json(&item), Value::Null);
+        assert_eq!(
+            DynamoDBClient::attribute_to_dynamodb_json(&item),
+            json!({ "M": {
+                "tags": { "SS": ["a", "b"] },
+                "parts": { "L": [{ "N": "1" }, { "NULL": true }] },
+                "blob": { "B": "aGk=" },
+            }})
+        );
+    }
+
+    #[test]
+    fn parses_comma_separated_projection() {
+        assert_eq!(
+            parse_projection(" id, name ,,status "),
+            vec!["id".to_string(), "name".to_string(), "status".to_string()]
+        );
+        assert!(parse_projection(" , ").is_empty());
+    }
+
+    #[test]
+    fn key_lists_trigger_a_batch_get() {
+        assert_eq!(
+            parse_batch_keys("user#1, user#2\nuser#3,"),
+            Some(vec![
+                "user#1".to_string(),
+                "user#2".to_string(),
+                "user#3".to_string()
+            ])
+        );
+        // A single key, even with a stray separator, is a plain query
+        assert_eq!(parse_batch_keys("user#1"), None);
+        assert_eq!(parse_batch_keys("user#1,"), None);
+    }
+
+    #[test]
+    fn key_values_are_read_from_item_json() {
+        let item = r#"{"id":"user#1","age":42.0,"active":true}"#;
+        assert_eq!(item_key_value(item, "id"), Some("user#1".to_string()));
+        assert_eq!(item_key_value(item, "age"), Some("42".to_string()));
+        assert_eq!(item_key_value(item, "active"), None);
+        assert_eq!(item_key_value(item, "missing"), None);
+        assert_eq!(item_key_value("Fetching items...", "id"), None);
+    }
+
+    #[test]
+    fn projection_uses_placeholders_for_every_attribute() {
+        let (expression, names) =
+            build_projection(&["id".to_string(), "status".to_string()]).unwrap();
+        assert_eq!(expression, "#p0, #p1");
+        assert_eq!(names.get("#p0").map(String::as_str), Some("id"));
+        assert_eq!(names.get("#p1").map(String::as_str), Some("status"));
+        assert!(build_projection(&[]).is_none());
+    }
 }
@@ -0,0 +1,436 @@
+//! ECS client module
+//!
+//! Lists the clusters and services of Elastic Container Service and describes services.
+//! Requests use ECS's JSON protocol, signed and sent by the shared `json_protocol` client.
+
+use crate::services::aws::credentials::{self, CredentialsError};
+use crate::services::aws::json_protocol::{JsonProtocolClient, RequestError};
+use crate::services::aws::retry::{self, RetryError, RetryPolicy, Retryable};
+use crate::services::read_config;
+use crate::timestamps::timestamps;
+use aws_config::SdkConfig;
+use serde_json::{Value, json};
+use thiserror::Error;
+
+/// Most clusters or services a single list request returns
+const RESULTS_PER_PAGE: u32 = 100;
+
+/// Errors that can occur when interacting with ECS
+#[derive(Error, Debug)]
+pub enum EcsClientError {
+    /// Error returned by ECS with a code not mapped to a variant below
+    #[error("AWS ECS error: {0}")]
+    AwsError(String),
+
+    /// Request still throttled after retrying with backoff
+    #[error("ECS is throttling requests, gave up after {0} retries: {1}")]
+    Throttled(u32, String),
+
+    /// No cluster with the requested name exists in the region
+    #[error("Cluster not found: {0}")]
+    ClusterNotFound(String),
+
+    /// The cluster has no service with the requested name, e.g. it was just deleted
+    #[error("Service not found: {0}")]
+    ServiceNotFound(String),
+
+    /// The profile's IAM policy does not allow the named action
+    #[error("Access denied: the profile needs the `{0}` permission in its IAM policy")]
+    AccessDenied(&'static str),
+
+    /// The request could not be signed or sent, e.g. without credentials or network
+    #[error("Failed to connect with profile: {0}")]
+    ConnectionFailed(String),
+
+    /// The request ran past the `ecs` limit of the [timeouts] config table
+    #[error("ECS request timed out; raise `ecs` under [timeouts] in config.toml")]
+    TimedOut,
+
+    /// ECS answered with something other than the documented JSON
+    #[error("Unexpected ECS response: {0}")]
+    InvalidResponse(String),
+
+    /// Credentials of a role-assuming profile could not be obtained
+    #[error(transparent)]
+    Credentials(#[from] CredentialsError),
+}
+
+impl EcsClientError {
+    /// Maps the failure of a request for `action` on `cluster` and, when it concerns one,
+    /// `service`
+    fn from_request(
+        action: &'static str,
+        cluster: &str,
+        service: Option<&str>,
+        err: RetryError<RequestError>,
+    ) -> Self {
+        let err = match err {
+            RetryError::Exhausted(retries, err) if err.is_retryable() => {
+                return Self::Throttled(retries, err.to_string());
+            }
+            RetryError::TimedOut(_) => return Self::TimedOut,
+            // Requests that kept failing on the way report their last error
+            RetryError::Permanent(err) | RetryError::Exhausted(_, err) => err,
+        };
+        match err {
+            RequestError::TimedOut => Self::TimedOut,
+            RequestError::Transport(message) | RequestError::Connection(message) => {
+                Self::ConnectionFailed(message)
+            }
+            RequestError::InvalidResponse(message) => Self::InvalidResponse(message),
+            RequestError::Service { code, message, .. } => match (code.as_str(), service) {
+                ("ClusterNotFoundException", _) => Self::ClusterNotFound(cluster.to_string()),
+                ("ServiceNotFoundException", Some(service)) => {
+                    Self::ServiceNotFound(service.to_string())
+                }
+                ("AccessDeniedException", _) => Self::AccessDenied(action),
+                _ => Self::AwsError(format!("{}: {}", code, message)),
+            },
+        }
+    }
+}
+
+/// A deployment of a service, of which there is more than one while it rolls out
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deployment {
+    /// `PRIMARY` for the newest deployment, `ACTIVE` for ones being replaced
+    pub status: String,
+    /// `IN_PROGRESS`, `COMPLETED` or `FAILED`, if the service reports it
+    pub rollout_state: Option<String>,
+    pub task_definition: String,
+    pub desired_count: i64,
+    pub running_count: i64,
+}
+
+/// A service and its task counts
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceDetails {
+    pub name: String,
+    pub arn: Option<String>,
+    /// `ACTIVE`, or `DRAINING` and `INACTIVE` while and after the service is deleted
+    pub status: String,
+    pub desired_count: i64,
+    pub running_count: i64,
+    pub pending_count: i64,
+    /// Task definition of the primary deployment, e.g. `web:42`
+    pub task_definition: String,
+    pub launch_type: Option<String>,
+    pub created_at: Option<String>,
+    pub deployments: Vec<Deployment>,
+}
+
+impl ServiceDetails {
+    /// Reads a service object of a DescribeServices response
+    fn from_json(service: &Value) -> Option<Self> {
+        let count = |value: &Value, field: &str| value[field].as_i64().unwrap_or_default();
+        let deployments = service["deployments"]
+            .as_array()
+            .map(|deployments| {
+                deployments
+                    .iter()
+                    .map(|deployment| Deployment {
+                        status: deployment["status"].as_str().unwrap_or_default().to_string(),
+                        rollout_state: deployment["rolloutState"].as_str().map(String::from),
+                        task_definition: resource_name(
+                            deployment["taskDefinition"].as_str().unwrap_or_default(),
+                        )
+                        .to_string(),
+                        desired_count: count(deployment, "desiredCount"),
+                        running_count: count(deployment, "runningCount"),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(Self {
+            name: service["serviceName"].as_str()?.to_string(),
+            arn: service["serviceArn"].as_str().map(String::from),
+            status: service["status"].as_str().unwrap_or("UNKNOWN").to_string(),
+            desired_count: count(service, "desiredCount"),
+            running_count: count(service, "runningCount"),
+            pending_count: count(service, "pendingCount"),
+            task_definition: resource_name(service["taskDefinition"].as_str().unwrap_or_default())
+                .to_string(),
+            launch_type: service["launchType"].as_str().map(String::from),
+            // Seconds since the epoch, with a fraction
+            created_at: service["createdAt"]
+                .as_f64()
+                .and_then(|seconds| timestamps().format_millis((seconds * 1000.0) as i64)),
+            deployments,
+        })
+    }
+
+    /// Whether a deployment is still rolling out, so task counts are about to change
+    pub fn is_deploying(&self) -> bool {
+        self.deployments.len() > 1
+            || self
+                .deployments
+                .iter()
+                .any(|deployment| deployment.rollout_state.as_deref() == Some("IN_PROGRESS"))
+    }
+
+    /// Status with what the service is in the middle of, e.g. `ACTIVE (deploying)`
+    pub fn state(&self) -> String {
+        if self.status != "ACTIVE" {
+            return self.status.clone();
+        }
+        if self.is_deploying() {
+            "ACTIVE (deploying)".to_string()
+        } else if self.running_count != self.desired_count || self.pending_count > 0 {
+            "ACTIVE (scaling)".to_string()
+        } else {
+            "ACTIVE".to_string()
+        }
+    }
+
+    /// The service as JSON for the details popup
+    pub fn to_json(&self) -> String {
+        let deployments: Vec<Value> = self
+            .deployments
+            .iter()
+            .map(|deployment| {
+                json!({
+                    "status": deployment.status,
+                    "rollout_state": deployment.rollout_state,
+                    "task_definition": deployment.task_definition,
+                    "desired_count": deployment.desired_count,
+                    "running_count": deployment.running_count,
+                })
+            })
+            .collect();
+        json!({
+            "service_name": self.name,
+            "status": self.state(),
+            "desired_count": self.desired_count,
+            "running_count": self.running_count,
+            "pending_count": self.pending_count,
+            "task_definition": self.task_definition,
+            "launch_type": self.launch_type,
+            "created_at": self.created_at,
+            "deployments": deployments,
+            "service_arn": self.arn,
+        })
+        .to_string()
+    }
+}
+
+/// Name at the end of an ARN, e.g. `web` for `arn:aws:ecs:…:service/prod/web`; names are
+/// returned as they are
+fn resource_name(arn: &str) -> &str {
+    arn.rsplit('/').next().unwrap_or(arn)
+}
+
+/// Names of the resources whose ARNs are listed under `field`
+fn names_from_arns(response: &Value, field: &str) -> Vec<String> {
+    response[field]
+        .as_array()
+        .map(|arns| {
+            arns.iter()
+                .filter_map(|arn| arn.as_str().map(|arn| resource_name(arn).to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reads the described service from a DescribeServices response
+///
+/// A service ECS no longer knows comes back under `failures`, not as an error.
+fn service_from_response(
+    response: &Value,
+    service: &str,
+) -> Result<ServiceDetails, EcsClientError> {
+    if let Some(details) = response["services"]
+        .as_array()
+        .and_then(|services| services.first())
+        .and_then(ServiceDetails::from_json)
+    {
+        return Ok(details);
+    }
+    match response["failures"]
+        .as_array()
+        .and_then(|failures| failures.first())
+        .and_then(|failure| failure["reason"].as_str())
+    {
+        Some("MISSING") => Err(EcsClientError::ServiceNotFound(service.to_string())),
+        Some(reason) => Err(EcsClientError::AwsError(format!("{}: {}", service, reason))),
+        None => Err(EcsClientError::InvalidResponse(format!(
+            "no service in the answer for {}",
+            service
+        ))),
+    }
+}
+
+/// Client for Elastic Container Service
+pub struct EcsClient {
+    /// Sends the signed requests
+    client: JsonProtocolClient,
+    retry_policy: RetryPolicy,
+}
+
+impl EcsClient {
+    /// Creates a new ECS client from the shared SDK config of the given profile
+    pub async fn new(config: &SdkConfig, profile: &str) -> Result<Self, EcsClientError> {
+        credentials::verify_role_credentials(
+            config,
+            profile,
+            read_config::get_role_settings(profile).as_ref(),
+        )
+        .await?;
+
+        let client = JsonProtocolClient::new(
+            config,
+            "ecs",
+            "AmazonEC2ContainerServiceV20141113",
+        )
+        .ok_or_else(|| EcsClientError::ConnectionFailed("no HTTP client available".to_string()))?;
+
+        Ok(Self {
+            client,
+            retry_policy: RetryPolicy::from_config(),
+        })
+    }
+
+    /// Lists the names of all clusters in the region
+    pub async fn list_clusters(&self) -> Result<Vec<String>, EcsClientError> {
+        let mut names = Vec::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let mut body = json!({ "maxResults": RESULTS_PER_PAGE });
+            if let Some(token) = &next_token {
+                body["nextToken"] = json!(token);
+            }
+
+            let response =
+                retry::with_retry(&self.retry_policy, || self.client.send("ListClusters", &body))
+                    .await
+                    .map_err(|err| {
+                        EcsClientError::from_request("ecs:ListClusters", "", None, err)
+                    })?;
+
+            names.extend(names_from_arns(&response, "clusterArns"));
+            next_token = response["nextToken"].as_str().map(String::from);
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        names.sort();
+        Ok(names)
+    }
+
+    /// Lists the names of the services in a cluster
+    pub async fn list_services(&self, cluster: &str) -> Result<Vec<String>, EcsClientError> {
+        let mut names = Vec::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let mut body = json!({ "cluster": cluster, "maxResults": RESULTS_PER_PAGE });
+            if let Some(token) = &next_token {
+                body["nextToken"] = json!(token);
+            }
+
+            let response =
+                retry::with_retry(&self.retry_policy, || self.client.send("ListServices", &body))
+                    .await
+                    .map_err(|err| {
+                        EcsClientError::from_request("ecs:ListServices", cluster, None, err)
+                    })?;
+
+            names.extend(names_from_arns(&response, "serviceArns"));
+            next_token = response["nextToken"].as_str().map(String::from);
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        names.sort();
+        Ok(names)
+    }
+
+    /// Describes a service of a cluster, with its desired and running task counts and its
+    /// task definition
+    pub async fn describe_service(
+        &self,
+        cluster: &str,
+        service: &str,
+    ) -> Result<ServiceDetails, EcsClientError> {
+        let body = json!({ "cluster": cluster, "services": [service] });
+        let response =
+            retry::with_retry(&self.retry_policy, || self.client.send("DescribeServices", &body))
+                .await
+                .map_err(|err| {
+                    let action = "ecs:DescribeServices";
+                    EcsClientError::from_request(action, cluster, Some(service), err)
+                })?;
+        service_from_response(&response, service)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed(code: &str, service: Option<&str>) -> EcsClientError {
+        let err = RequestError::from_response(400, &json!({ "__type": code, "message": "" }));
+        let err = RetryError::Permanent(err);
+        EcsClientError::from_request("ecs:DescribeServices", "prod", service, err)
+    }
+
+    #[test]
+    fn request_failures_name_the_missing_cluster_or_service() {
+        assert!(matches!(
+            failed("ClusterNotFoundException", Some("web")),
+            EcsClientError::ClusterNotFound(cluster) if cluster == "prod"
+        ));
+        assert!(matches!(
+            failed("ServiceNotFoundException", Some("web")),
+            EcsClientError::ServiceNotFound(service) if service == "web"
+        ));
+        assert!(matches!(
+            failed("AccessDeniedException", None),
+            EcsClientError::AccessDenied("ecs:DescribeServices")
+        ));
+    }
+
+    #[test]
+    fn names_are_read_from_both_arn_formats() {
+        let listing = json!({ "serviceArns": [
+            "arn:aws:ecs:eu-west-1:123456789012:service/prod/worker",
+            "arn:aws:ecs:eu-west-1:123456789012:service/api",
+        ]});
+        assert_eq!(names_from_arns(&listing, "serviceArns"), ["worker", "api"]);
+        assert!(names_from_arns(&json!({ "serviceArns": [] }), "serviceArns").is_empty());
+    }
+
+    #[test]
+    fn services_in_transition_are_told_apart() {
+        let response = json!({ "services": [{
+            "serviceName": "web",
+            "status": "ACTIVE",
+            "desiredCount": 3,
+            "runningCount": 2,
+            "pendingCount": 1,
+            "taskDefinition": "arn:aws:ecs:eu-west-1:123456789012:task-definition/web:42",
+            "deployments": [
+                { "status": "PRIMARY", "rolloutState": "IN_PROGRESS", "desiredCount": 3,
+                  "runningCount": 1, "taskDefinition": "web:42" },
+                { "status": "ACTIVE", "desiredCount": 3, "runningCount": 1,
+                  "taskDefinition": "web:41" },
+            ],
+        }]});
+        let service = service_from_response(&response, "web").unwrap();
+        assert_eq!(service.task_definition, "web:42");
+        assert_eq!((service.desired_count, service.running_count), (3, 2));
+        assert_eq!(service.state(), "ACTIVE (deploying)");
+        assert!(service.to_json().contains("web:41"));
+
+        let draining = json!({ "services": [{ "serviceName": "old", "status": "DRAINING" }] });
+        assert_eq!(service_from_response(&draining, "old").unwrap().state(), "DRAINING");
+
+        let missing = json!({ "services": [], "failures": [{ "reason": "MISSING" }] });
+        assert!(matches!(
+            service_from_response(&missing, "gone"),
+            Err(EcsClientError::ServiceNotFound(service)) if service == "gone"
+        ));
+    }
+}
@@ -0,0 +1,324 @@
+//! AWS JSON protocol requests
+//!
+//! Services without an SDK crate in this build (SSM, ECS) speak AWS's JSON protocol:
+//! a POST naming the operation in `x-amz-target`, signed with SigV4. Requests are sent
+//! through the AWS SDK's HTTP connector, so they share the profile's credentials,
+//! region, endpoint and timeouts with the other clients.
+
+use crate::services::aws::retry::{Retryable, THROTTLING_CODES};
+use aws_config::SdkConfig;
+use aws_credential_types::provider::{ProvideCredentials, SharedCredentialsProvider};
+use aws_sigv4::http_request::{SignableBody, SignableRequest, SigningSettings, sign};
+use aws_sigv4::sign::v4;
+use aws_smithy_runtime_api::client::http::{
+    HttpConnector, HttpConnectorSettings, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::identity::Identity;
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::byte_stream::ByteStream;
+use serde_json::Value;
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+/// Content type of the JSON protocol
+const JSON_CONTENT_TYPE: &str = "application/x-amz-json-1.1";
+
+/// HTTP statuses that mean the request may succeed later (too many requests, unavailable)
+const RETRYABLE_STATUSES: [u16; 2] = [429, 503];
+
+/// Failure of a single request, before retries are taken into account
+#[derive(Debug)]
+pub(crate) enum RequestError {
+    /// The service answered with an error
+    Service {
+        status: u16,
+        code: String,
+        message: String,
+    },
+    /// The request could not be signed or sent, e.g. without credentials
+    Transport(String),
+    /// The connection failed or broke off before the whole answer arrived
+    Connection(String),
+    /// The request took longer than the operation timeout
+    TimedOut,
+    /// The service answered with a success but not with JSON
+    InvalidResponse(String),
+}
+
+impl RequestError {
+    /// Reads the error the service answered with from its status and JSON body
+    pub(crate) fn from_response(status: u16, body: &Value) -> Self {
+        // The type may be qualified, e.g. `com.amazonaws.ecs#ClusterNotFoundException`
+        let code = body["__type"]
+            .as_str()
+            .map(|code| code.rsplit('#').next().unwrap_or(code))
+            .unwrap_or("UnknownError")
+            .to_string();
+        let message = body["message"]
+            .as_str()
+            .or_else(|| body["Message"].as_str())
+            .unwrap_or_default()
+            .to_string();
+        Self::Service {
+            status,
+            code,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestError::Service { code, message, .. } => write!(f, "{}: {}", code, message),
+            RequestError::Transport(message) | RequestError::Connection(message) => {
+                write!(f, "{}", message)
+            }
+            RequestError::TimedOut => write!(f, "request timed out"),
+            RequestError::InvalidResponse(message) => write!(f, "invalid response: {}", message),
+        }
+    }
+}
+
+impl Retryable for RequestError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            RequestError::Service { status, code, .. } => {
+                THROTTLING_CODES.contains(&code.as_str()) || RETRYABLE_STATUSES.contains(status)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_transient(&self) -> bool {
+        match self {
+            RequestError::Connection(_) => true,
+            RequestError::Service { status, .. } => (500..600).contains(status),
+            _ => false,
+        }
+    }
+
+    fn is_timeout(&self) -> bool {
+        matches!(self, RequestError::TimedOut)
+    }
+
+    fn is_access_denied(&self) -> bool {
+        matches!(self, RequestError::Service { code, .. } if code == "AccessDeniedException")
+    }
+}
+
+/// Domain of the AWS endpoints in the partition of `region`, e.g. `amazonaws.com.cn` for
+/// the China regions
+///
+/// GovCloud regions (`us-gov-*`) share the commercial domain.
+fn dns_suffix(region: &str) -> &'static str {
+    if region.starts_with("cn-") {
+        "amazonaws.com.cn"
+    } else if region.starts_with("us-isob-") {
+        "sc2s.sgov.gov"
+    } else if region.starts_with("us-isof-") {
+        "csp.hci.ic.gov"
+    } else if region.starts_with("us-iso-") {
+        "c2s.ic.gov"
+    } else if region.starts_with("eu-isoe-") {
+        "cloud.adc-e.uk"
+    } else {
+        "amazonaws.com"
+    }
+}
+
+/// Signs and sends JSON protocol requests to one service
+pub(crate) struct JsonProtocolClient {
+    /// HTTP connector of the AWS SDK, with the profile's connect timeout
+    connector: SharedHttpConnector,
+    /// Credentials requests are signed with
+    credentials: Option<SharedCredentialsProvider>,
+    region: String,
+    /// Service endpoint, or the custom endpoint (e.g. LocalStack) of the profile
+    endpoint: String,
+    /// Name requests are signed for, e.g. `ecs`
+    signing_name: &'static str,
+    /// Prefix of the operation in `x-amz-target`, e.g. `AmazonEC2ContainerServiceV20141113`
+    target_prefix: &'static str,
+    /// Limit for each request, `None` for no limit
+    operation_timeout: Option<Duration>,
+}
+
+impl JsonProtocolClient {
+    /// Creates a client for the service signed as `signing_name`, whose endpoint is
+    /// `https://<signing_name>.<region>.<partition domain>` unless the profile has a custom one
+    ///
+    /// Returns `None` when no HTTP client is available.
+    pub(crate) fn new(
+        config: &SdkConfig,
+        signing_name: &'static str,
+        target_prefix: &'static str,
+    ) -> Option<Self> {
+        let timeouts = config.timeout_config();
+        let mut settings = HttpConnectorSettings::builder();
+        settings.set_connect_timeout(timeouts.and_then(|timeouts| timeouts.connect_timeout()));
+        let connector =
+            aws_smithy_http_client::default_connector(&settings.build(), config.sleep_impl())?;
+
+        let region = config
+            .region()
+            .map(|region| region.to_string())
+            .unwrap_or_default();
+        let endpoint = config
+            .endpoint_url()
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| {
+                format!("https://{}.{}.{}", signing_name, region, dns_suffix(&region))
+            });
+
+        Some(Self {
+            connector,
+            credentials: config.credentials_provider(),
+            region,
+            endpoint,
+            signing_name,
+            target_prefix,
+            operation_timeout: timeouts.and_then(|timeouts| timeouts.operation_timeout()),
+        })
+    }
+
+    /// Signs and sends one request, returning the answer's JSON
+    pub(crate) async fn send(&self, operation: &str, body: &Value) -> Result<Value, RequestError> {
+        let transport = |err: &dyn fmt::Display| RequestError::Transport(err.to_string());
+        let body = body.to_string();
+        let target = format!("{}.{}", self.target_prefix, operation);
+
+        let provider = self
+            .credentials
+            .as_ref()
+            .ok_or_else(|| RequestError::Transport("no credentials configured".to_string()))?;
+        let identity: Identity = provider
+            .provide_credentials()
+            .await
+            .map_err(|err| transport(&err))?
+            .into();
+        let params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region(&self.region)
+            .name(self.signing_name)
+            .time(SystemTime::now())
+            .settings(SigningSettings::default())
+            .build()
+            .map_err(|err| transport(&err))?
+            .into();
+        let headers = [
+            ("content-type", JSON_CONTENT_TYPE),
+            ("x-amz-target", &target),
+        ];
+        let signable = SignableRequest::new(
+            "POST",
+            self.endpoint.as_str(),
+            headers.into_iter(),
+            SignableBody::Bytes(body.as_bytes()),
+        )
+        .map_err(|err| transport(&err))?;
+        let (instructions, _signature) = sign(signable, &params)
+            .map_err(|err| transport(&err))?
+            .into_parts();
+
+        let mut request = http::Request::builder()
+            .method("POST")
+            .uri(&self.endpoint)
+            .header("content-type", JSON_CONTENT_TYPE)
+            .header("x-amz-target", &target)
+            .body(SdkBody::from(body))
+            .map_err(|err| transport(&err))?;
+        instructions.apply_to_request_http1x(&mut request);
+        let request = HttpRequest::try_from(request).map_err(|err| transport(&err))?;
+
+        let call = self.connector.call(request);
+        let response = match self.operation_timeout {
+            Some(limit) => tokio::time::timeout(limit, call)
+                .await
+                .map_err(|_| RequestError::TimedOut)?,
+            None => call.await,
+        }
+        .map_err(|err| RequestError::Connection(err.to_string()))?;
+
+        let status = response.status().as_u16();
+        let bytes = ByteStream::new(response.into_body())
+            .collect()
+            .await
+            .map_err(|err| RequestError::Connection(err.to_string()))?
+            .into_bytes();
+        read_answer(status, &bytes)
+    }
+}
+
+/// Reads the JSON a service answered with, or the error it stands for
+fn read_answer(status: u16, bytes: &[u8]) -> Result<Value, RequestError> {
+    let answer = if bytes.is_empty() {
+        Ok(Value::Object(Default::default()))
+    } else {
+        serde_json::from_slice::<Value>(bytes)
+    };
+    let success = (200..300).contains(&status);
+    match answer {
+        Ok(answer) if success => Ok(answer),
+        Ok(answer) => Err(RequestError::from_response(status, &answer)),
+        Err(err) if success => Err(RequestError::InvalidResponse(err.to_string())),
+        // Errors without a JSON body, e.g. from a proxy, are still told apart by status
+        Err(_) => Err(RequestError::from_response(status, &Value::Null)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn errors_are_read_from_qualified_types() {
+        let err = RequestError::from_response(
+            400,
+            &json!({ "__type": "com.amazonaws.ecs#ClusterNotFoundException", "Message": "gone" }),
+        );
+        assert_eq!(err.to_string(), "ClusterNotFoundException: gone");
+        assert!(!err.is_retryable());
+
+        assert!(
+            RequestError::from_response(400, &json!({ "__type": "ThrottlingException" }))
+                .is_retryable()
+        );
+        assert!(RequestError::from_response(503, &Value::Null).is_retryable());
+    }
+
+    #[test]
+    fn broken_connections_and_server_errors_are_transient() {
+        assert!(RequestError::Connection("connection reset by peer".to_string()).is_transient());
+        assert!(RequestError::from_response(500, &Value::Null).is_transient());
+        // Without credentials another attempt fails the same way
+        assert!(!RequestError::Transport("no credentials configured".to_string()).is_transient());
+        assert!(!RequestError::from_response(400, &Value::Null).is_transient());
+    }
+
+    #[test]
+    fn successes_without_json_are_invalid_responses() {
+        let listing = read_answer(200, br#"{"clusterArns":[]}"#).unwrap();
+        assert_eq!(listing, json!({ "clusterArns": [] }));
+        assert_eq!(read_answer(200, b"").unwrap(), json!({}));
+        assert!(matches!(
+            read_answer(200, b"<html>maintenance</html>"),
+            Err(RequestError::InvalidResponse(_))
+        ));
+        assert!(matches!(
+            read_answer(502, b"Bad Gateway"),
+            Err(RequestError::Service { status: 502, .. })
+        ));
+    }
+
+    #[test]
+    fn endpoints_use_the_domain_of_the_region_partition() {
+        assert_eq!(dns_suffix("eu-west-1"), "amazonaws.com");
+        assert_eq!(dns_suffix("us-gov-west-1"), "amazonaws.com");
+        assert_eq!(dns_suffix("cn-north-1"), "amazonaws.com.cn");
+        assert_eq!(dns_suffix("us-iso-east-1"), "c2s.ic.gov");
+        assert_eq!(dns_suffix("us-isob-east-1"), "sc2s.sgov.gov");
+    }
+}
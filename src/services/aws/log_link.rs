@@ -0,0 +1,168 @@
+//! Shareable CloudWatch log search links
+//!
+//! Describes a log search as a compact `cw://` link, e.g.
+//! `cw:///aws/lambda/api?filter=ERROR%20timeout&range=1h`, that teammates can paste into
+//! the query input or pass to `--open` to see the same search.
+
+use crate::services::aws::console_link::percent_encode;
+use thiserror::Error;
+
+/// Scheme every log search link starts with
+const SCHEME: &str = "cw://";
+
+/// Why a log search link could not be read
+#[derive(Error, Debug, PartialEq)]
+pub enum LogLinkError {
+    #[error("'{0}' is not a cw:// link")]
+    NotALink(String),
+
+    #[error("the link names no log group")]
+    MissingLogGroup,
+
+    #[error("unknown link parameter '{0}', expected filter or range")]
+    UnknownParameter(String),
+
+    #[error("'{0}' is not validly percent-encoded")]
+    InvalidEncoding(String),
+}
+
+/// A log group searched with a filter pattern over a time range
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogView {
+    pub log_group: String,
+    /// Filter pattern, empty for every event
+    pub filter_pattern: String,
+    /// Time range as typed into the time range input, the default when `None`
+    pub time_range: Option<String>,
+}
+
+impl LogView {
+    /// Formats the search as a `cw://` link
+    ///
+    /// Slashes in the log group name are kept readable; everything else that is not
+    /// URL-safe is percent-encoded.
+    pub fn to_link(&self) -> String {
+        let log_group = self
+            .log_group
+            .split('/')
+            .map(percent_encode)
+            .collect::<Vec<_>>()
+            .join("/");
+        let mut params = Vec::new();
+        if !self.filter_pattern.is_empty() {
+            params.push(format!("filter={}", percent_encode(&self.filter_pattern)));
+        }
+        if let Some(range) = &self.time_range {
+            params.push(format!("range={}", percent_encode(range)));
+        }
+        if params.is_empty() {
+            format!("{}{}", SCHEME, log_group)
+        } else {
+            format!("{}{}?{}", SCHEME, log_group, params.join("&"))
+        }
+    }
+
+    /// Reads a search back from its `cw://` link
+    pub fn parse(link: &str) -> Result<Self, LogLinkError> {
+        let link = link.trim();
+        let rest = link
+            .strip_prefix(SCHEME)
+            .ok_or_else(|| LogLinkError::NotALink(link.to_string()))?;
+        let (log_group, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let log_group = percent_decode(log_group)?;
+        if log_group.is_empty() {
+            return Err(LogLinkError::MissingLogGroup);
+        }
+
+        let mut view = LogView {
+            log_group,
+            filter_pattern: String::new(),
+            time_range: None,
+        };
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let (name, value) = param.split_once('=').unwrap_or((param, ""));
+            let value = percent_decode(value)?;
+            match name {
+                "filter" => view.filter_pattern = value,
+                "range" => view.time_range = Some(value).filter(|range| !range.is_empty()),
+                _ => return Err(LogLinkError::UnknownParameter(name.to_string())),
+            }
+        }
+        Ok(view)
+    }
+}
+
+/// Whether the text is meant as a log search link rather than a filter pattern
+pub fn is_log_link(text: &str) -> bool {
+    text.trim_start().starts_with(SCHEME)
+}
+
+/// Decodes `%XX` escapes, and `+` as a space like form-encoded links do
+fn percent_decode(value: &str) -> Result<String, LogLinkError> {
+    let invalid = || LogLinkError::InvalidEncoding(value.to_string());
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut input = value.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'%' => {
+                let hex = [
+                    input.next().ok_or_else(invalid)?,
+                    input.next().ok_or_else(invalid)?,
+                ];
+                let hex = std::str::from_utf8(&hex).map_err(|_| invalid())?;
+                bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            }
+            b'+' => bytes.push(b' '),
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_round_trip_with_special_characters() {
+        let view = LogView {
+            log_group: "/aws/lambda/api".to_string(),
+            filter_pattern: r#"{ $.level = "ERROR" } & 100%"#.to_string(),
+            time_range: Some("2024-01-01T00:00..2024-01-02T00:00".to_string()),
+        };
+        let link = view.to_link();
+        assert!(link.starts_with("cw:///aws/lambda/api?filter=%7B%20%24.level"));
+        assert!(!link.contains(' '));
+        assert_eq!(LogView::parse(&link), Ok(view));
+
+        assert_eq!(
+            LogView::parse("cw://app?filter=a+b&range=1h"),
+            Ok(LogView {
+                log_group: "app".to_string(),
+                filter_pattern: "a b".to_string(),
+                time_range: Some("1h".to_string()),
+            })
+        );
+        assert_eq!(LogView::parse("cw://app").unwrap().to_link(), "cw://app");
+    }
+
+    #[test]
+    fn rejects_malformed_links() {
+        assert_eq!(
+            LogView::parse("https://example.com"),
+            Err(LogLinkError::NotALink("https://example.com".to_string()))
+        );
+        assert_eq!(
+            LogView::parse("cw://?range=1h"),
+            Err(LogLinkError::MissingLogGroup)
+        );
+        assert_eq!(
+            LogView::parse("cw://app?profile=prod"),
+            Err(LogLinkError::UnknownParameter("profile".to_string()))
+        );
+        assert_eq!(
+            LogView::parse("cw://app?filter=%G1"),
+            Err(LogLinkError::InvalidEncoding("%G1".to_string()))
+        );
+    }
+}
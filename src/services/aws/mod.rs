@@ -4,9 +4,18 @@
 //! Provides unified error handling and client management.
 
 // Client implementations for specific AWS services
+pub mod cli_command;
 pub mod cloudwatch_client;
+pub mod console_link;
+pub mod credentials;
 pub mod dynamo_client;
+pub mod ecs_client;
+mod json_protocol;
+pub mod log_link;
+pub mod retry;
 pub mod s3_client;
+pub mod ssm_client;
+pub mod sts_client;
 mod tab_clients;
 
 // Re-export TabClients for profile and region management
@@ -15,9 +24,33 @@ pub use tab_clients::TabClients;
 // Import individual service error types for unified error handling
 use super::aws::cloudwatch_client::CloudWatchClientError;
 use super::aws::dynamo_client::DynamoDBClientError;
+use super::aws::ecs_client::EcsClientError;
 use super::aws::s3_client::S3ClientError;
+use super::aws::ssm_client::SsmClientError;
+use super::aws::sts_client::StsClientError;
 use thiserror::Error;
 
+/// Records of a fetch that stops following pages once `max_results` is reached
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CappedRecords {
+    pub records: Vec<String>,
+    /// The cap, when it was hit while more records were available
+    pub truncated_at: Option<usize>,
+    /// Records available in all when the service reports it for a truncated fetch
+    pub total: Option<usize>,
+    /// Complete form of each record when the records leave data out, else empty
+    pub raw: Vec<String>,
+}
+
+impl CappedRecords {
+    /// Title suffix telling that the records were cut off, empty when they were not
+    pub fn truncation_note(&self) -> String {
+        self.truncated_at
+            .map(|cap| format!(" (truncated at {}; raise max_results in config.toml)", cap))
+            .unwrap_or_default()
+    }
+}
+
 /// Unified error type for all AWS service operations
 ///
 /// Wraps service-specific errors into a single type for simpler error handling
@@ -35,4 +68,16 @@ pub enum ClientError {
     /// Errors from CloudWatch operations
     #[error("AWS CloudWatch error: {0}")]
     AWSCloudWatchError(#[from] CloudWatchClientError),
+
+    /// Errors from STS operations
+    #[error("AWS STS error: {0}")]
+    AWSStsError(#[from] StsClientError),
+
+    /// Errors from SSM Parameter Store operations
+    #[error("AWS SSM error: {0}")]
+    AWSSsmError(#[from] SsmClientError),
+
+    /// Errors from ECS operations
+    #[error("AWS ECS error: {0}")]
+    AWSEcsError(#[from] EcsClientError),
 }
@@ -0,0 +1,375 @@
+//! Retry module
+//!
+//! Retries AWS requests that were throttled or failed on the way, such as on a reset
+//! connection or a 5xx answer, waiting with exponential backoff and jitter between
+//! attempts. Any other error is returned straight away.
+
+use crate::diagnostics;
+use crate::services::read_config;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_types::error::metadata::ProvideErrorMetadata;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Error codes AWS uses to signal throttling
+pub(crate) const THROTTLING_CODES: [&str; 7] = [
+    "ProvisionedThroughputExceededException",
+    "ThrottlingException",
+    "Throttling",
+    "ThrottledException",
+    "RequestLimitExceeded",
+    "TooManyRequestsException",
+    "SlowDown",
+];
+
+/// Error codes of requests that did not reach the service in time and may be repeated
+const TRANSIENT_CODES: [&str; 3] = [
+    "RequestTimeout",
+    "RequestTimeoutException",
+    "PriorRequestNotComplete",
+];
+
+/// Error codes AWS uses when the caller lacks an IAM permission
+const ACCESS_DENIED_CODES: [&str; 3] = [
+    "AccessDenied",
+    "AccessDeniedException",
+    "UnauthorizedOperation",
+];
+
+/// HTTP statuses that are worth retrying (too many requests, service unavailable)
+const RETRYABLE_STATUSES: [u16; 2] = [429, 503];
+
+/// Errors that can tell whether repeating the request might succeed
+pub trait Retryable {
+    /// Whether AWS throttled the request or is unavailable for now
+    fn is_retryable(&self) -> bool;
+
+    /// Whether the request failed on the way or on the server, e.g. a reset connection,
+    /// a cut-off answer or a 5xx status, so the same request may succeed
+    fn is_transient(&self) -> bool {
+        false
+    }
+
+    /// Whether the request hit the configured operation timeout
+    fn is_timeout(&self) -> bool {
+        false
+    }
+
+    /// Whether AWS refused the request because a permission is missing
+    fn is_access_denied(&self) -> bool {
+        false
+    }
+}
+
+impl<E: ProvideErrorMetadata> Retryable for SdkError<E, HttpResponse> {
+    fn is_retryable(&self) -> bool {
+        let throttled = self
+            .code()
+            .is_some_and(|code| THROTTLING_CODES.contains(&code));
+        let unavailable = self
+            .raw_response()
+            .is_some_and(|response| RETRYABLE_STATUSES.contains(&response.status().as_u16()));
+        throttled || unavailable
+    }
+
+    fn is_transient(&self) -> bool {
+        match self {
+            // The connect timeout ends up here too, unlike the operation timeout
+            SdkError::DispatchFailure(failure) => failure.is_io() || failure.is_timeout(),
+            SdkError::ResponseError(_) => true,
+            _ => {
+                self.code().is_some_and(|code| TRANSIENT_CODES.contains(&code))
+                    || self
+                        .raw_response()
+                        .is_some_and(|response| response.status().is_server_error())
+            }
+        }
+    }
+
+    fn is_timeout(&self) -> bool {
+        matches!(self, SdkError::TimeoutError(_))
+    }
+
+    fn is_access_denied(&self) -> bool {
+        self.code()
+            .is_some_and(|code| ACCESS_DENIED_CODES.contains(&code))
+    }
+}
+
+/// Error from a request that was not retried, or still failed after every retry
+#[derive(Debug)]
+pub enum RetryError<E> {
+    /// The error is not retryable, so the request was attempted once
+    Permanent(E),
+    /// The request still failed with a retryable or transient error after the given
+    /// number of retries
+    Exhausted(u32, E),
+    /// The request took longer than the configured timeout and was not retried
+    TimedOut(E),
+}
+
+impl<E: Retryable> RetryError<E> {
+    /// Whether the request failed because a permission is missing; never retried
+    pub fn is_access_denied(&self) -> bool {
+        matches!(self, RetryError::Permanent(err) if err.is_access_denied())
+    }
+}
+
+impl<E: fmt::Display + Retryable> fmt::Display for RetryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetryError::Permanent(err) => write!(f, "{}", err),
+            RetryError::TimedOut(err) => write!(f, "Request timed out: {}", err),
+            RetryError::Exhausted(retries, err) if !err.is_retryable() => {
+                write!(f, "Request kept failing, gave up after {} retries: {}", retries, err)
+            }
+            RetryError::Exhausted(retries, err) => write!(
+                f,
+                "AWS is throttling requests, gave up after {} retries: {}",
+                retries, err
+            ),
+        }
+    }
+}
+
+/// How often and how long to wait before giving up on a throttled or failing request
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each one after
+    pub base_delay: Duration,
+    /// Upper bound for a single delay
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Default policy retrying until `max_attempts` from the application config were made
+    pub fn from_config() -> Self {
+        Self {
+            max_retries: read_config::get_max_attempts() - 1,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before the given retry (starting at 0): the exponential backoff, with
+    /// its upper half randomized so concurrent requests do not retry in lockstep
+    pub fn delay(&self, retry: u32) -> Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay);
+        let half = backoff / 2;
+        let jitter_millis = half.as_millis() as u64;
+        if jitter_millis == 0 {
+            return backoff;
+        }
+        let random = RandomState::new().build_hasher().finish();
+        half + Duration::from_millis(random % (jitter_millis + 1))
+    }
+}
+
+/// Runs the operation, retrying while it fails with a retryable error
+pub async fn with_retry<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    mut operation: F,
+) -> Result<T, RetryError<E>>
+where
+    E: Retryable,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut retry = 0;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(err) if err.is_timeout() => return Err(RetryError::TimedOut(err)),
+            Err(err) if !err.is_retryable() && !err.is_transient() => {
+                return Err(RetryError::Permanent(err));
+            }
+            Err(err) if retry >= policy.max_retries => {
+                diagnostics::error(format!("Request still failing after {} retries", retry));
+                return Err(RetryError::Exhausted(retry, err));
+            }
+            Err(err) => {
+                let delay = policy.delay(retry);
+                let reason = if err.is_retryable() {
+                    "throttled"
+                } else {
+                    "failed"
+                };
+                diagnostics::warn(format!(
+                    "Request {}, retry {} of {} in {} ms",
+                    reason,
+                    retry + 1,
+                    policy.max_retries,
+                    delay.as_millis()
+                ));
+                tokio::time::sleep(delay).await;
+                retry += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[derive(Debug, PartialEq)]
+    enum MockError {
+        Throttled,
+        ConnectionReset,
+        AccessDenied,
+    }
+
+    impl Retryable for MockError {
+        fn is_retryable(&self) -> bool {
+            *self == MockError::Throttled
+        }
+
+        fn is_transient(&self) -> bool {
+            *self == MockError::ConnectionReset
+        }
+
+        fn is_access_denied(&self) -> bool {
+            *self == MockError::AccessDenied
+        }
+    }
+
+    fn instant_policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_throttled_requests_until_they_succeed() {
+        let calls = Cell::new(0);
+        let result = with_retry(&instant_policy(3), || {
+            calls.set(calls.get() + 1);
+            let attempt = calls.get();
+            async move {
+                if attempt <= 2 {
+                    Err(MockError::Throttled)
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.ok(), Some(3));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors_until_they_succeed() {
+        let calls = Cell::new(0);
+        let result = with_retry(&instant_policy(3), || {
+            calls.set(calls.get() + 1);
+            let attempt = calls.get();
+            async move {
+                if attempt == 1 {
+                    Err(MockError::ConnectionReset)
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.ok(), Some(2));
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn dispatch_failures_cut_off_answers_and_server_errors_are_transient() {
+        use aws_smithy_runtime_api::client::result::ConnectorError;
+        use aws_smithy_types::body::SdkBody;
+        use aws_smithy_types::error::ErrorMetadata;
+
+        let reset = std::io::Error::from(std::io::ErrorKind::ConnectionReset);
+        let dispatch: SdkError<ErrorMetadata, HttpResponse> =
+            SdkError::dispatch_failure(ConnectorError::io(reset.into()));
+        assert!(dispatch.is_transient());
+
+        let response =
+            |status: u16| HttpResponse::new(status.try_into().unwrap(), SdkBody::empty());
+        let cut_off: SdkError<ErrorMetadata, HttpResponse> =
+            SdkError::response_error("body ended early", response(200));
+        assert!(cut_off.is_transient());
+
+        let service = |code: &str, status: u16| -> SdkError<ErrorMetadata, HttpResponse> {
+            SdkError::service_error(ErrorMetadata::builder().code(code).build(), response(status))
+        };
+        assert!(service("InternalFailure", 500).is_transient());
+        assert!(service("RequestTimeout", 400).is_transient());
+        assert!(!service("ValidationException", 400).is_transient());
+        assert!(!service("ValidationException", 400).is_retryable());
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_permanent_errors() {
+        let calls = Cell::new(0);
+        let result: Result<(), _> = with_retry(&instant_policy(3), || {
+            calls.set(calls.get() + 1);
+            async { Err(MockError::AccessDenied) }
+        })
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(RetryError::Permanent(MockError::AccessDenied))
+        ));
+        assert!(result.unwrap_err().is_access_denied());
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let calls = Cell::new(0);
+        let result: Result<(), _> = with_retry(&instant_policy(2), || {
+            calls.set(calls.get() + 1);
+            async { Err(MockError::Throttled) }
+        })
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(RetryError::Exhausted(2, MockError::Throttled))
+        ));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn backoff_grows_and_is_capped() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+        };
+        let first = policy.delay(0);
+        assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+        let third = policy.delay(2);
+        assert!(third >= Duration::from_millis(200) && third <= Duration::from_millis(400));
+        assert!(policy.delay(10) <= Duration::from_millis(500));
+    }
+}
@@ -2,13 +2,40 @@
 //!
 //! Provides functionality to interact with AWS S3 service,
 //! including listing buckets, browsing objects, and retrieving object metadata.
+//! Bucket operations are sent to the bucket's home region, which is looked up once
+//! per bucket and cached, so buckets outside the tab's region can be browsed too.
 
-use aws_config::{BehaviorVersion, Region};
+use crate::diagnostics;
+use crate::services::aws::credentials::{self, CredentialsError};
+use crate::services::aws::retry::Retryable;
+use crate::services::read_config;
+use crate::timestamps::timestamps;
+use aws_config::{Region, SdkConfig};
 use aws_sdk_s3::Client;
-use aws_sdk_s3::error::SdkError;
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::operation::delete_objects::DeleteObjectsOutput;
+use aws_sdk_s3::operation::get_object::GetObjectError;
+use aws_sdk_s3::presigning::PresigningConfig;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{
+    BucketLocationConstraint, BucketVersioningStatus, CreateBucketConfiguration, Delete,
+    ObjectIdentifier,
+};
 use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::Duration;
 use thiserror::Error;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// Region of buckets whose location constraint is empty
+const DEFAULT_BUCKET_REGION: &str = "us-east-1";
+/// Keys S3 accepts in one DeleteObjects request
+const DELETE_BATCH_LIMIT: usize = 1000;
+
+/// Longest a presigned URL stays valid, the limit of SigV4 signatures
+pub const MAX_PRESIGN_EXPIRY: Duration = Duration::from_secs(7 * 24 * 60 * 60);
 
 /// Errors that can occur when interacting with S3
 #[derive(Error, Debug)]
@@ -24,12 +51,66 @@ pub enum S3ClientError {
     /// Error converting data to JSON format
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    /// Error reading an object body or writing it to disk
+    #[error("Download error: {0}")]
+    DownloadError(String),
+
+    /// A file is already at the download path; downloads never overwrite files
+    #[error("{0} already exists and was kept")]
+    FileExists(PathBuf),
+
+    /// Request took longer than the configured timeout
+    #[error("S3 request timed out; raise `s3` under [timeouts] in config.toml")]
+    TimedOut,
+
+    /// The object's storage class keeps it offline until it is restored
+    #[error("Object is archived in {0}; restore it before downloading")]
+    Archived(String),
+
+    /// The bucket name is taken, by another account or by this one
+    #[error("Bucket {0} already exists; bucket names are unique across all accounts")]
+    BucketExists(String),
+
+    /// Only empty buckets can be deleted
+    #[error("Bucket {0} is not empty; delete its objects and versions first")]
+    BucketNotEmpty(String),
+
+    /// Presigned URLs cannot outlive their SigV4 signature
+    #[error("Presigned URLs expire after at most 7 days")]
+    ExpiryTooLong,
+
+    /// The profile's IAM policy does not allow the named action
+    #[error("Access denied: the profile needs the `{0}` permission in its IAM policy")]
+    AccessDenied(&'static str),
+
+    /// Credentials of a role-assuming profile could not be obtained
+    #[error(transparent)]
+    Credentials(#[from] CredentialsError),
 }
 
 // Implement From traits for SDK errors
 impl<T, E> From<SdkError<T, E>> for S3ClientError {
     fn from(err: SdkError<T, E>) -> Self {
-        S3ClientError::AwsError(err.to_string())
+        match err {
+            SdkError::TimeoutError(_) => S3ClientError::TimedOut,
+            err => S3ClientError::AwsError(err.to_string()),
+        }
+    }
+}
+
+impl S3ClientError {
+    /// Converts a failed request, naming the IAM action it needs when it was denied
+    fn from_request<E>(action: &'static str, err: E) -> Self
+    where
+        E: Retryable,
+        S3ClientError: From<E>,
+    {
+        if err.is_access_denied() {
+            S3ClientError::AccessDenied(action)
+        } else {
+            err.into()
+        }
     }
 }
 
@@ -37,39 +118,239 @@ impl<T, E> From<SdkError<T, E>> for S3ClientError {
 pub struct S3Client {
     /// AWS SDK S3 client
     client: Client,
+    /// Region the client was created for
+    region: String,
+    /// Whether a custom endpoint is used, which serves every bucket itself
+    custom_endpoint: bool,
+    /// Home region of each bucket looked up so far
+    bucket_regions: Mutex<HashMap<String, String>>,
+    /// Clients for regions other than the default one, created on demand
+    regional_clients: Mutex<HashMap<String, Client>>,
+}
+
+/// One page of an object listing
+#[derive(Debug, Clone, Default)]
+pub struct ObjectPage {
+    /// Object metadata as JSON strings
+    pub records: Vec<String>,
+    /// Continuation token of the next page, `None` once the listing is complete
+    pub next_token: Option<String>,
+}
+
+/// Result of a bulk delete
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeleteOutcome {
+    /// Keys S3 reported as deleted
+    pub deleted: Vec<String>,
+    /// Keys for which a delete marker was created, with the marker's version id
+    pub delete_markers: Vec<(String, Option<String>)>,
+    /// Keys that were not deleted, with the reason
+    pub failed: Vec<(String, String)>,
+}
+
+impl DeleteOutcome {
+    /// Adds the deleted and failed keys of one DeleteObjects response
+    fn add_response(&mut self, resp: &DeleteObjectsOutput) {
+        for deleted in resp.deleted() {
+            let key = deleted.key().unwrap_or_default().to_string();
+            if deleted.delete_marker().unwrap_or(false) {
+                self.delete_markers
+                    .push((key.clone(), deleted.delete_marker_version_id().map(String::from)));
+            }
+            self.deleted.push(key);
+        }
+        for err in resp.errors() {
+            self.failed.push((
+                err.key().unwrap_or_default().to_string(),
+                err.message().or(err.code()).unwrap_or("unknown error").to_string(),
+            ));
+        }
+    }
+
+    /// Marks keys whose request failed as a whole as not deleted
+    fn add_failed(&mut self, keys: &[String], reason: &str) {
+        self.failed
+            .extend(keys.iter().map(|key| (key.clone(), reason.to_string())));
+    }
+}
+
+/// Returns the error code S3 answered a failed request with
+fn error_code<E: ProvideErrorMetadata, R>(err: &SdkError<E, R>) -> Option<&str> {
+    err.as_service_error().and_then(ProvideErrorMetadata::code)
+}
+
+/// Storage classes whose objects must be restored before they can be read
+const ARCHIVED_STORAGE_CLASSES: [&str; 2] = ["GLACIER", "DEEP_ARCHIVE"];
+
+/// Returns whether objects of the storage class must be restored before they can be read
+pub fn is_archived_class(storage_class: &str) -> bool {
+    ARCHIVED_STORAGE_CLASSES.contains(&storage_class)
+}
+
+/// Maps a bucket location constraint to its region
+///
+/// Buckets in us-east-1 report no constraint, and old eu-west-1 buckets report `EU`.
+pub fn region_from_location(constraint: Option<&str>) -> String {
+    match constraint {
+        None | Some("") => DEFAULT_BUCKET_REGION.to_string(),
+        Some("EU") => "eu-west-1".to_string(),
+        Some(region) => region.to_string(),
+    }
+}
+
+/// Returns the location constraint to create a bucket in the region with
+///
+/// us-east-1 is the default location and S3 rejects it as an explicit constraint.
+pub fn location_constraint(region: &str) -> Option<&str> {
+    (region != DEFAULT_BUCKET_REGION && !region.is_empty()).then_some(region)
+}
+
+/// Parses how long a presigned URL stays valid, e.g. `90s`, `15m`, `12h` or `7d`
+pub fn parse_expiry(input: &str) -> Option<Duration> {
+    let input = input.trim();
+    let unit_start = input.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = input.split_at(unit_start);
+    let amount: u64 = amount.parse().ok().filter(|amount| *amount > 0)?;
+    let unit_seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return None,
+    };
+    Some(Duration::from_secs(amount.saturating_mul(unit_seconds)))
 }
 
 impl S3Client {
-    /// Creates a new S3 client with the specified AWS profile and region
+    /// Creates a new S3 client from the shared SDK config of the given profile
     ///
-    /// Attempts to connect to verify credentials are valid before returning
-    pub async fn new(profile: String, region: String) -> Result<Self, S3ClientError> {
-        // Configure AWS SDK with profile, region and timeouts
-        let config = aws_config::defaults(BehaviorVersion::latest())
-            .profile_name(&profile)
-            .region(Region::new(region))
-            .timeout_config(
-                aws_sdk_s3::config::timeout::TimeoutConfig::builder()
-                    .operation_timeout(Duration::from_secs(30))
-                    .build(),
-            )
-            .load()
-            .await;
+    /// Attempts to connect to verify credentials are valid before returning.
+    /// A custom endpoint (e.g. LocalStack) switches to path-style addressing.
+    pub async fn new(config: &SdkConfig, profile: &str) -> Result<Self, S3ClientError> {
+        let force_path_style = config.endpoint_url().is_some();
+        let default_region = config
+            .region()
+            .map(|region| region.to_string())
+            .unwrap_or_default();
+        credentials::verify_role_credentials(
+            config,
+            profile,
+            read_config::get_role_settings(profile).as_ref(),
+        )
+        .await?;
 
-        let client = Client::new(&config);
+        let s3_config = aws_sdk_s3::config::Builder::from(config)
+            .force_path_style(force_path_style)
+            .build();
+        let client = Client::from_conf(s3_config);
 
         // Validate connection by trying to list buckets
         match client.list_buckets().send().await {
-            Ok(_) => Ok(Self { client }),
+            Ok(_) => Ok(Self {
+                client,
+                region: default_region,
+                custom_endpoint: force_path_style,
+                bucket_regions: Mutex::new(HashMap::new()),
+                regional_clients: Mutex::new(HashMap::new()),
+            }),
+            // Credentials work, the profile is just not allowed to list buckets
+            Err(err) if err.is_access_denied() => {
+                Err(S3ClientError::AccessDenied("s3:ListAllMyBuckets"))
+            }
             Err(err) => Err(S3ClientError::ConnectionFailed(err.to_string())),
         }
     }
 
+    /// Returns the home region of a bucket, looking it up on first use
+    pub async fn bucket_region(&self, bucket_name: &str) -> Result<String, S3ClientError> {
+        if let Some(region) = self.cached_bucket_region(bucket_name) {
+            return Ok(region);
+        }
+
+        let resp = self
+            .client
+            .get_bucket_location()
+            .bucket(bucket_name)
+            .send()
+            .await?;
+        let region = region_from_location(
+            resp.location_constraint()
+                .map(|constraint| constraint.as_str()),
+        );
+
+        self.bucket_regions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(bucket_name.to_string(), region.clone());
+        Ok(region)
+    }
+
+    fn cached_bucket_region(&self, bucket_name: &str) -> Option<String> {
+        self.bucket_regions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(bucket_name)
+            .cloned()
+    }
+
+    /// Returns a client for the bucket's home region
+    ///
+    /// Falls back to the default client when the region cannot be looked up (e.g. missing
+    /// `s3:GetBucketLocation` permission) or a custom endpoint is configured.
+    async fn client_for(&self, bucket_name: &str) -> Client {
+        if self.custom_endpoint {
+            return self.client.clone();
+        }
+        let region = match self.bucket_region(bucket_name).await {
+            Ok(region) => region,
+            Err(err) => {
+                diagnostics::warn(format!(
+                    "Could not look up the region of bucket {}, using {}: {}",
+                    bucket_name, self.region, err
+                ));
+                return self.client.clone();
+            }
+        };
+        if region != self.region {
+            diagnostics::info(format!("Bucket {} is in {}", bucket_name, region));
+        }
+        self.client_in_region(&region)
+    }
+
+    /// Returns a client for the given region, reusing the default one where possible
+    fn client_in_region(&self, region: &str) -> Client {
+        if self.custom_endpoint || region == self.region {
+            return self.client.clone();
+        }
+
+        let mut clients = self
+            .regional_clients
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        clients
+            .entry(region.to_string())
+            .or_insert_with(|| {
+                let config = self
+                    .client
+                    .config()
+                    .to_builder()
+                    .region(Region::new(region.to_string()))
+                    .build();
+                Client::from_conf(config)
+            })
+            .clone()
+    }
+
     /// Lists all S3 buckets the user has access to
     ///
     /// Returns a vector of bucket names as strings
     pub async fn list_buckets(&self) -> Result<Vec<String>, S3ClientError> {
-        let resp = self.client.list_buckets().send().await?;
+        let resp = self
+            .client
+            .list_buckets()
+            .send()
+            .await
+            .map_err(|err| S3ClientError::from_request("s3:ListAllMyBuckets", err))?;
 
         // Extract bucket names from response
         let buckets = resp.buckets();
@@ -81,50 +362,111 @@ impl S3Client {
         Ok(bucket_names)
     }
 
-    /// Lists objects in a bucket with optional prefix (folder path)
+    /// Creates an empty bucket in the given region
+    pub async fn create_bucket(
+        &self,
+        bucket_name: &str,
+        region: &str,
+    ) -> Result<(), S3ClientError> {
+        let configuration = location_constraint(region).map(|constraint| {
+            CreateBucketConfiguration::builder()
+                .location_constraint(BucketLocationConstraint::from(constraint))
+                .build()
+        });
+
+        self.client_in_region(region)
+            .create_bucket()
+            .bucket(bucket_name)
+            .set_create_bucket_configuration(configuration)
+            .send()
+            .await
+            .map_err(|err| match error_code(&err) {
+                Some("BucketAlreadyExists" | "BucketAlreadyOwnedByYou") => {
+                    S3ClientError::BucketExists(bucket_name.to_string())
+                }
+                _ => S3ClientError::from_request("s3:CreateBucket", err),
+            })?;
+
+        self.bucket_regions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(bucket_name.to_string(), region.to_string());
+        Ok(())
+    }
+
+    /// Deletes a bucket, which S3 only allows once it holds no objects or versions
+    pub async fn delete_bucket(&self, bucket_name: &str) -> Result<(), S3ClientError> {
+        self.client_for(bucket_name)
+            .await
+            .delete_bucket()
+            .bucket(bucket_name)
+            .send()
+            .await
+            .map_err(|err| match error_code(&err) {
+                Some("BucketNotEmpty") => S3ClientError::BucketNotEmpty(bucket_name.to_string()),
+                _ => S3ClientError::from_request("s3:DeleteBucket", err),
+            })?;
+
+        self.bucket_regions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(bucket_name);
+        Ok(())
+    }
+
+    /// Lists one page of objects in a bucket with optional prefix (folder path)
     ///
-    /// Returns a vector of JSON strings containing object metadata
+    /// Pass the `next_token` of the previous page to continue the listing; `page_size`
+    /// limits the keys per page (S3 returns at most 1000).
     pub async fn list_objects(
         &self,
         bucket_name: &str,
         prefix: &str,
-    ) -> Result<Vec<String>, S3ClientError> {
+        continuation_token: Option<String>,
+        page_size: Option<i32>,
+    ) -> Result<ObjectPage, S3ClientError> {
         // Build the request with prefix if it's not empty
-        let mut request = self.client.list_objects_v2().bucket(bucket_name);
+        let mut request = self
+            .client_for(bucket_name)
+            .await
+            .list_objects_v2()
+            .bucket(bucket_name)
+            .set_continuation_token(continuation_token)
+            .set_max_keys(page_size);
 
         if !prefix.is_empty() {
             request = request.prefix(prefix);
         }
 
         // Execute the request
-        let resp = request.send().await?;
-
-        // Check if we have any objects
-        if resp.contents().is_empty() {
-            return Ok(vec!["No objects found".to_string()]);
-        }
+        let resp = request
+            .send()
+            .await
+            .map_err(|err| S3ClientError::from_request("s3:ListBucket", err))?;
 
         // Convert objects to JSON strings
-        let objects = resp
+        let records = resp
             .contents()
             .iter()
             .map(|obj| {
                 let last_modified = obj
                     .last_modified()
-                    .map(|dt| {
-                        dt.fmt(aws_smithy_types::date_time::Format::DateTime)
-                            .unwrap_or_default()
-                    })
+                    .and_then(|dt| timestamps().format_aws(dt))
                     .unwrap_or_default();
 
                 let size = obj.size().unwrap_or_default();
                 let key = obj.key().unwrap_or_default();
                 let etag = obj.e_tag().unwrap_or_default();
+                // S3 may leave out the class of STANDARD objects
+                let storage_class = obj
+                    .storage_class()
+                    .map_or("STANDARD", |class| class.as_str());
 
                 let json_obj = json!({
                     "key": key,
                     "size": format!("{} bytes", size),
                     "last_modified": last_modified,
+                    "storage_class": storage_class,
                     "etag": etag
                 });
 
@@ -133,7 +475,50 @@ impl S3Client {
             })
             .collect();
 
-        Ok(objects)
+        // S3 only returns a token while the listing is truncated
+        let next_token = resp
+            .is_truncated()
+            .unwrap_or(false)
+            .then(|| resp.next_continuation_token().map(String::from))
+            .flatten();
+
+        Ok(ObjectPage {
+            records,
+            next_token,
+        })
+    }
+
+    /// Lists the folders and objects directly below a prefix, as full keys with folders
+    /// ending in `/`, for completing a typed prefix
+    ///
+    /// Only the first page (up to 1000 entries) is listed.
+    pub async fn list_key_segments(
+        &self,
+        bucket_name: &str,
+        prefix: &str,
+    ) -> Result<Vec<String>, S3ClientError> {
+        let resp = self
+            .client_for(bucket_name)
+            .await
+            .list_objects_v2()
+            .bucket(bucket_name)
+            .prefix(prefix)
+            .delimiter("/")
+            .send()
+            .await
+            .map_err(|err| S3ClientError::from_request("s3:ListBucket", err))?;
+
+        let folders = resp
+            .common_prefixes()
+            .iter()
+            .filter_map(|folder| folder.prefix().map(String::from));
+        let keys = resp
+            .contents()
+            .iter()
+            .filter_map(|object| object.key().map(String::from));
+        let mut segments: Vec<String> = folders.chain(keys).collect();
+        segments.sort();
+        Ok(segments)
     }
 
     /// Gets detailed metadata about a specific S3 object
@@ -144,38 +529,423 @@ impl S3Client {
         bucket_name: &str,
         key: &str,
     ) -> Result<String, S3ClientError> {
+        self.head_object_details(bucket_name, key, None).await
+    }
+
+    /// Gets the metadata of one version of an object, as pretty-printed JSON
+    pub async fn get_object_version_details(
+        &self,
+        bucket_name: &str,
+        key: &str,
+        version_id: &str,
+    ) -> Result<String, S3ClientError> {
+        self.head_object_details(bucket_name, key, Some(version_id))
+            .await
+    }
+
+    /// Looks up an object's metadata, of a specific version if one is given
+    async fn head_object_details(
+        &self,
+        bucket_name: &str,
+        key: &str,
+        version_id: Option<&str>,
+    ) -> Result<String, S3ClientError> {
+        let action = if version_id.is_some() {
+            "s3:GetObjectVersion"
+        } else {
+            "s3:GetObject"
+        };
         let resp = self
-            .client
+            .client_for(bucket_name)
+            .await
             .head_object()
             .bucket(bucket_name)
             .key(key)
+            .set_version_id(version_id.map(String::from))
             .send()
-            .await?;
+            .await
+            .map_err(|err| S3ClientError::from_request(action, err))?;
 
         // Extract metadata from response
         let content_type = resp.content_type().unwrap_or_default();
         let content_length = resp.content_length().unwrap_or_default();
         let last_modified = resp
             .last_modified()
-            .map(|dt| {
-                dt.fmt(aws_smithy_types::date_time::Format::DateTime)
-                    .unwrap_or_default()
-            })
+            .and_then(|dt| timestamps().format_aws(dt))
             .unwrap_or_default();
         let etag = resp.e_tag().unwrap_or_default();
 
         // Build JSON response with object metadata
-        let metadata = json!({
+        let mut metadata = json!({
             "key": key,
             "bucket": bucket_name,
             "content_type": content_type,
             "size": format!("{} bytes", content_length),
             "last_modified": last_modified,
             "etag": etag,
+            "storage_class": resp.storage_class().map_or("STANDARD", |class| class.as_str()),
             "metadata": resp.metadata()
         });
+        if let Some(version_id) = resp.version_id() {
+            metadata["version_id"] = json!(version_id);
+        }
+        // Progress or expiry of a restore from an archive storage class
+        if let Some(restore) = resp.restore() {
+            metadata["restore"] = json!(restore);
+        }
+        if let Some(archive_status) = resp.archive_status() {
+            metadata["archive_status"] = json!(archive_status.as_str());
+        }
 
         serde_json::to_string_pretty(&metadata)
             .map_err(|e| S3ClientError::SerializationError(e.to_string()))
     }
+
+    /// Lists the versions and delete markers of the objects under a prefix, newest first
+    ///
+    /// Returns JSON records with the key, `version_id`, whether it is the `latest` version
+    /// and whether it is a `delete_marker`. Only the first page (up to 1000 versions) is
+    /// listed.
+    pub async fn list_object_versions(
+        &self,
+        bucket_name: &str,
+        prefix: &str,
+    ) -> Result<Vec<String>, S3ClientError> {
+        let resp = self
+            .client_for(bucket_name)
+            .await
+            .list_object_versions()
+            .bucket(bucket_name)
+            .prefix(prefix)
+            .send()
+            .await
+            .map_err(|err| S3ClientError::from_request("s3:ListBucketVersions", err))?;
+
+        let format_time = |time: Option<&aws_smithy_types::DateTime>| {
+            time.and_then(|dt| timestamps().format_aws(dt))
+                .unwrap_or_default()
+        };
+
+        let versions = resp.versions().iter().map(|version| {
+            (
+                version.last_modified(),
+                json!({
+                    "key": version.key().unwrap_or_default(),
+                    "version_id": version.version_id().unwrap_or_default(),
+                    "size": format!("{} bytes", version.size().unwrap_or_default()),
+                    "last_modified": format_time(version.last_modified()),
+                    "latest": version.is_latest().unwrap_or(false),
+                    "delete_marker": false,
+                }),
+            )
+        });
+        let markers = resp.delete_markers().iter().map(|marker| {
+            (
+                marker.last_modified(),
+                json!({
+                    "key": marker.key().unwrap_or_default(),
+                    "version_id": marker.version_id().unwrap_or_default(),
+                    "last_modified": format_time(marker.last_modified()),
+                    "latest": marker.is_latest().unwrap_or(false),
+                    "delete_marker": true,
+                }),
+            )
+        });
+
+        // S3 returns versions and delete markers separately; interleave them by time
+        let mut records: Vec<_> = versions.chain(markers).collect();
+        records.sort_by(|(a, _), (b, _)| b.cmp(a));
+        Ok(records
+            .into_iter()
+            .map(|(_, record)| record.to_string())
+            .collect())
+    }
+
+    /// Downloads an object into the given directory, streaming its body to disk
+    ///
+    /// The file keeps the key's path below the directory, creating its folders, so objects
+    /// of the same name under different prefixes do not collide. An existing file is never
+    /// overwritten; returns the written path
+    pub async fn download_object(
+        &self,
+        bucket_name: &str,
+        key: &str,
+        destination: &Path,
+    ) -> Result<PathBuf, S3ClientError> {
+        let path = download_path(destination, key)
+            .ok_or_else(|| S3ClientError::DownloadError(format!("{} has no file name", key)))?;
+        // Claimed before the request, so an existing file costs no download
+        let mut file = create_new_file(&path).await?;
+
+        let result = async {
+            let resp = self
+                .client_for(bucket_name)
+                .await
+                .get_object()
+                .bucket(bucket_name)
+                .key(key)
+                .send()
+                .await
+                .map_err(|err| match err.as_service_error() {
+                    // Archived objects that were not restored cannot be read
+                    Some(GetObjectError::InvalidObjectState(state)) => S3ClientError::Archived(
+                        state
+                            .storage_class()
+                            .map_or("an archive storage class", |class| class.as_str())
+                            .to_string(),
+                    ),
+                    _ => err.into(),
+                })?;
+            write_body(resp.body, &mut file).await
+        }
+        .await;
+
+        if let Err(err) = result {
+            // A missing or partly written object must not look downloaded
+            drop(file);
+            let _ = tokio::fs::remove_file(&path).await;
+            return Err(err);
+        }
+        Ok(path)
+    }
+
+    /// Signs a GET request for an object that anyone holding the URL can use until it
+    /// expires, without credentials of their own
+    ///
+    /// Signing happens locally; the URL works only while the profile's credentials
+    /// remain valid, so URLs of temporary credentials may expire sooner.
+    pub async fn generate_presigned_url(
+        &self,
+        bucket_name: &str,
+        key: &str,
+        expires: Duration,
+    ) -> Result<String, S3ClientError> {
+        if expires > MAX_PRESIGN_EXPIRY {
+            return Err(S3ClientError::ExpiryTooLong);
+        }
+        let config = PresigningConfig::expires_in(expires)
+            .map_err(|e| S3ClientError::AwsError(e.to_string()))?;
+        let request = self
+            .client_for(bucket_name)
+            .await
+            .get_object()
+            .bucket(bucket_name)
+            .key(key)
+            .presigned(config)
+            .await?;
+        Ok(request.uri().to_string())
+    }
+
+    /// Returns whether versioning is enabled on the bucket
+    ///
+    /// Suspended or never-enabled versioning both mean deletes are permanent.
+    pub async fn is_versioned(&self, bucket_name: &str) -> Result<bool, S3ClientError> {
+        let resp = self
+            .client_for(bucket_name)
+            .await
+            .get_bucket_versioning()
+            .bucket(bucket_name)
+            .send()
+            .await?;
+        Ok(resp.status() == Some(&BucketVersioningStatus::Enabled))
+    }
+
+    /// Deletes objects from a bucket, in requests of up to 1000 keys
+    ///
+    /// Returns the keys S3 deleted, which deletes only created a delete marker and the keys
+    /// that failed. A request that fails as a whole stops the delete, and its keys and
+    /// those of later requests are reported as failed.
+    pub async fn delete_objects(
+        &self,
+        bucket_name: &str,
+        keys: &[String],
+    ) -> Result<DeleteOutcome, S3ClientError> {
+        let client = self.client_for(bucket_name).await;
+        let mut outcome = DeleteOutcome::default();
+
+        for (index, batch) in keys.chunks(DELETE_BATCH_LIMIT).enumerate() {
+            let objects = batch
+                .iter()
+                .map(|key| ObjectIdentifier::builder().key(key).build())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| S3ClientError::AwsError(e.to_string()))?;
+            let delete = Delete::builder()
+                .set_objects(Some(objects))
+                .build()
+                .map_err(|e| S3ClientError::AwsError(e.to_string()))?;
+
+            match client
+                .delete_objects()
+                .bucket(bucket_name)
+                .delete(delete)
+                .send()
+                .await
+            {
+                Ok(resp) => outcome.add_response(&resp),
+                Err(err) => {
+                    let reason = S3ClientError::from_request("s3:DeleteObject", err).to_string();
+                    outcome.add_failed(&keys[index * DELETE_BATCH_LIMIT..], &reason);
+                    break;
+                }
+            }
+        }
+        Ok(outcome)
+    }
+}
+
+/// Where an object is downloaded to: its key as a path below `destination`, without
+/// segments that could leave it; `None` for keys without a file name, such as folders
+fn download_path(destination: &Path, key: &str) -> Option<PathBuf> {
+    if key.ends_with('/') {
+        return None;
+    }
+    let segments: Vec<&str> = key
+        .split('/')
+        .filter(|segment| !matches!(*segment, "" | "." | ".."))
+        .collect();
+    if segments.is_empty() {
+        return None;
+    }
+    Some(
+        segments
+            .into_iter()
+            .fold(destination.to_path_buf(), |path, segment| path.join(segment)),
+    )
+}
+
+/// Creates the file and its folders, failing with [`S3ClientError::FileExists`] instead of
+/// truncating a file that is already there
+async fn create_new_file(path: &Path) -> Result<tokio::fs::File, S3ClientError> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| S3ClientError::DownloadError(e.to_string()))?;
+    }
+    tokio::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => S3ClientError::FileExists(path.to_path_buf()),
+            _ => S3ClientError::DownloadError(e.to_string()),
+        })
+}
+
+/// Writes an object body chunk by chunk, so large objects are never held in memory
+async fn write_body(
+    mut body: ByteStream,
+    file: &mut (impl AsyncWrite + Unpin),
+) -> Result<(), S3ClientError> {
+    while let Some(chunk) = body
+        .try_next()
+        .await
+        .map_err(|e| S3ClientError::DownloadError(e.to_string()))?
+    {
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| S3ClientError::DownloadError(e.to_string()))?;
+    }
+    file.flush()
+        .await
+        .map_err(|e| S3ClientError::DownloadError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delete_outcomes_collect_deleted_and_failed_keys() {
+        use aws_sdk_s3::types::{DeletedObject, Error as DeleteError};
+
+        let resp = DeleteObjectsOutput::builder()
+            .deleted(DeletedObject::builder().key("a.log").build())
+            .deleted(
+                DeletedObject::builder()
+                    .key("b.log")
+                    .delete_marker(true)
+                    .delete_marker_version_id("v2")
+                    .build(),
+            )
+            .errors(DeleteError::builder().key("c.log").message("Access Denied").build())
+            .build();
+        let mut outcome = DeleteOutcome::default();
+        outcome.add_response(&resp);
+        outcome.add_failed(&["d.log".to_string()], "throttled");
+
+        assert_eq!(outcome.deleted, ["a.log", "b.log"]);
+        assert_eq!(
+            outcome.delete_markers,
+            [("b.log".to_string(), Some("v2".to_string()))]
+        );
+        assert_eq!(
+            outcome.failed,
+            [
+                ("c.log".to_string(), "Access Denied".to_string()),
+                ("d.log".to_string(), "throttled".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn presign_expiries_take_a_unit() {
+        assert_eq!(parse_expiry("15m"), Some(Duration::from_secs(900)));
+        assert_eq!(parse_expiry(" 7d "), Some(MAX_PRESIGN_EXPIRY));
+        assert_eq!(parse_expiry("0h"), None);
+        assert_eq!(parse_expiry("15"), None);
+        assert_eq!(parse_expiry("2w"), None);
+    }
+
+    #[test]
+    fn maps_location_constraints_to_regions() {
+        assert_eq!(region_from_location(None), "us-east-1");
+        assert_eq!(region_from_location(Some("")), "us-east-1");
+        assert_eq!(region_from_location(Some("EU")), "eu-west-1");
+        assert_eq!(
+            region_from_location(Some("ap-southeast-2")),
+            "ap-southeast-2"
+        );
+    }
+
+    #[test]
+    fn us_east_1_buckets_are_created_without_a_location_constraint() {
+        assert_eq!(location_constraint("us-east-1"), None);
+        assert_eq!(location_constraint(""), None);
+        assert_eq!(location_constraint("eu-central-1"), Some("eu-central-1"));
+    }
+
+    #[test]
+    fn downloads_keep_the_key_path_inside_the_destination() {
+        let destination = Path::new("downloads");
+        assert_eq!(
+            download_path(destination, "logs/2024/app.log"),
+            Some(destination.join("logs").join("2024").join("app.log"))
+        );
+        // Segments that would leave the destination are dropped
+        assert_eq!(
+            download_path(destination, "/../../etc/./passwd"),
+            Some(destination.join("etc").join("passwd"))
+        );
+        assert_eq!(download_path(destination, "logs/"), None);
+        assert_eq!(download_path(destination, ".."), None);
+    }
+
+    #[tokio::test]
+    async fn downloads_stream_into_new_files_and_never_overwrite() {
+        let dir = std::env::temp_dir().join(format!("aws-tui-download-{}", std::process::id()));
+        let path = dir.join("logs").join("app.log");
+
+        let mut file = create_new_file(&path).await.unwrap();
+        write_body(ByteStream::from_static(b"first"), &mut file)
+            .await
+            .unwrap();
+        drop(file);
+        assert_eq!(std::fs::read(&path).unwrap(), b"first");
+
+        let existing = create_new_file(&path).await.unwrap_err();
+        assert!(matches!(existing, S3ClientError::FileExists(ref kept) if *kept == path));
+        assert_eq!(std::fs::read(&path).unwrap(), b"first");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,279 @@
+//! SSM client module
+//!
+//! Reads parameters from AWS Systems Manager Parameter Store. Requests use SSM's JSON
+//! protocol, signed and sent by the shared `json_protocol` client.
+
+use crate::services::aws::credentials::{self, CredentialsError};
+use crate::services::aws::json_protocol::{JsonProtocolClient, RequestError};
+use crate::services::aws::retry::{self, RetryError, RetryPolicy, Retryable};
+use crate::services::read_config;
+use crate::timestamps::timestamps;
+use aws_config::SdkConfig;
+use serde_json::{Value, json};
+use thiserror::Error;
+
+/// Most parameters DescribeParameters returns per page
+const PARAMETERS_PER_PAGE: u32 = 50;
+
+/// Errors that can occur when interacting with Parameter Store
+#[derive(Error, Debug)]
+pub enum SsmClientError {
+    /// Error returned by SSM with a code not mapped to a variant below
+    #[error("AWS SSM error: {0}")]
+    AwsError(String),
+
+    /// Request still throttled after retrying with backoff
+    #[error("SSM is throttling requests, gave up after {0} retries: {1}")]
+    Throttled(u32, String),
+
+    /// No parameter with the requested name exists
+    #[error("Parameter not found: {0}")]
+    ParameterNotFound(String),
+
+    /// The profile's IAM policy does not allow the named action
+    #[error("Access denied: the profile needs the `{0}` permission in its IAM policy")]
+    AccessDenied(&'static str),
+
+    /// The SecureString parameter could be read but its KMS key refused to decrypt it
+    #[error(
+        "Access denied decrypting {0}: the profile needs `kms:Decrypt` on the parameter's KMS key"
+    )]
+    DecryptionDenied(String),
+
+    /// The request could not be signed or sent, e.g. without credentials or network
+    #[error("Failed to connect with profile: {0}")]
+    ConnectionFailed(String),
+
+    #[error("SSM request timed out; raise `ssm` under [timeouts] in config.toml")]
+    TimedOut,
+
+    /// SSM answered with something other than the documented JSON
+    #[error("Unexpected SSM response: {0}")]
+    InvalidResponse(String),
+
+    /// Credentials of a role-assuming profile could not be obtained
+    #[error(transparent)]
+    Credentials(#[from] CredentialsError),
+}
+
+impl SsmClientError {
+    /// Maps the failure of a request for `action`, naming `parameter` in errors about it
+    fn from_request(action: &'static str, parameter: &str, err: RetryError<RequestError>) -> Self {
+        let err = match err {
+            RetryError::Exhausted(retries, err) if err.is_retryable() => {
+                return Self::Throttled(retries, err.to_string());
+            }
+            RetryError::TimedOut(_) => return Self::TimedOut,
+            // Requests that kept failing on the way report their last error
+            RetryError::Permanent(err) | RetryError::Exhausted(_, err) => err,
+        };
+        match err {
+            RequestError::TimedOut => Self::TimedOut,
+            RequestError::Transport(message) | RequestError::Connection(message) => {
+                Self::ConnectionFailed(message)
+            }
+            RequestError::InvalidResponse(message) => Self::InvalidResponse(message),
+            RequestError::Service { code, message, .. } => match code.as_str() {
+                "ParameterNotFound" => Self::ParameterNotFound(parameter.to_string()),
+                // KMS refusals come back as access denied mentioning the KMS action
+                "InvalidKeyId" => Self::DecryptionDenied(parameter.to_string()),
+                "AccessDeniedException" if message.contains("kms:") => {
+                    Self::DecryptionDenied(parameter.to_string())
+                }
+                "AccessDeniedException" => Self::AccessDenied(action),
+                _ => Self::AwsError(format!("{}: {}", code, message)),
+            },
+        }
+    }
+}
+
+/// A parameter and its value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    /// `String`, `StringList` or `SecureString`
+    pub parameter_type: String,
+    /// The value, still encrypted for a SecureString read without decryption
+    pub value: String,
+    pub version: Option<i64>,
+    pub last_modified: Option<String>,
+    pub arn: Option<String>,
+    /// Whether the value was requested decrypted
+    pub decrypted: bool,
+}
+
+impl Parameter {
+    /// Reads the `Parameter` object of a GetParameter response
+    fn from_json(parameter: &Value, decrypted: bool) -> Option<Self> {
+        Some(Self {
+            name: parameter["Name"].as_str()?.to_string(),
+            parameter_type: parameter["Type"].as_str().unwrap_or("String").to_string(),
+            value: parameter["Value"].as_str().unwrap_or_default().to_string(),
+            version: parameter["Version"].as_i64(),
+            // Seconds since the epoch, with a fraction
+            last_modified: parameter["LastModifiedDate"]
+                .as_f64()
+                .and_then(|seconds| timestamps().format_millis((seconds * 1000.0) as i64)),
+            arn: parameter["ARN"].as_str().map(String::from),
+            decrypted,
+        })
+    }
+
+    /// Whether the value is stored encrypted with KMS
+    pub fn is_secure(&self) -> bool {
+        self.parameter_type == "SecureString"
+    }
+}
+
+/// Names of the parameters in a DescribeParameters response
+fn parameter_names(response: &Value) -> Vec<String> {
+    response["Parameters"]
+        .as_array()
+        .map(|parameters| {
+            parameters
+                .iter()
+                .filter_map(|parameter| parameter["Name"].as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Client for AWS Systems Manager Parameter Store
+pub struct SsmClient {
+    /// Sends the signed requests
+    client: JsonProtocolClient,
+    retry_policy: RetryPolicy,
+}
+
+impl SsmClient {
+    /// Creates a new SSM client from the shared SDK config of the given profile
+    pub async fn new(config: &SdkConfig, profile: &str) -> Result<Self, SsmClientError> {
+        credentials::verify_role_credentials(
+            config,
+            profile,
+            read_config::get_role_settings(profile).as_ref(),
+        )
+        .await?;
+
+        let client = JsonProtocolClient::new(config, "ssm", "AmazonSSM").ok_or_else(|| {
+            SsmClientError::ConnectionFailed("no HTTP client available".to_string())
+        })?;
+
+        Ok(Self {
+            client,
+            retry_policy: RetryPolicy::from_config(),
+        })
+    }
+
+    /// Lists the names of all parameters, or of those under a path like `/prod/api`
+    pub async fn list_parameters(&self, path: &str) -> Result<Vec<String>, SsmClientError> {
+        let mut names = Vec::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let mut body = json!({ "MaxResults": PARAMETERS_PER_PAGE });
+            let path = path.trim();
+            if !path.is_empty() {
+                body["ParameterFilters"] = json!([{
+                    "Key": "Path",
+                    "Option": "Recursive",
+                    "Values": [path],
+                }]);
+            }
+            if let Some(token) = &next_token {
+                body["NextToken"] = json!(token);
+            }
+
+            let response = retry::with_retry(&self.retry_policy, || {
+                self.client.send("DescribeParameters", &body)
+            })
+            .await
+            .map_err(|err| SsmClientError::from_request("ssm:DescribeParameters", path, err))?;
+
+            names.extend(parameter_names(&response));
+            next_token = response["NextToken"].as_str().map(String::from);
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        names.sort();
+        Ok(names)
+    }
+
+    /// Reads a parameter; SecureString values are only decrypted `with_decryption`,
+    /// which also needs `kms:Decrypt` on the parameter's key
+    pub async fn get_parameter(
+        &self,
+        name: &str,
+        with_decryption: bool,
+    ) -> Result<Parameter, SsmClientError> {
+        let body = json!({ "Name": name, "WithDecryption": with_decryption });
+        let response =
+            retry::with_retry(&self.retry_policy, || self.client.send("GetParameter", &body))
+                .await
+                .map_err(|err| SsmClientError::from_request("ssm:GetParameter", name, err))?;
+
+        Parameter::from_json(&response["Parameter"], with_decryption).ok_or_else(|| {
+            SsmClientError::InvalidResponse(format!("no parameter in the answer for {}", name))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn denied(body: Value) -> SsmClientError {
+        let err = RequestError::from_response(400, &body);
+        SsmClientError::from_request("ssm:GetParameter", "/prod/db", RetryError::Permanent(err))
+    }
+
+    #[test]
+    fn access_denied_is_told_apart_from_kms_refusals() {
+        assert!(matches!(
+            denied(json!({
+                "__type": "com.amazonaws.ssm#AccessDeniedException",
+                "message": "User is not authorized to perform: ssm:GetParameter",
+            })),
+            SsmClientError::AccessDenied("ssm:GetParameter")
+        ));
+        assert!(matches!(
+            denied(json!({
+                "__type": "AccessDeniedException",
+                "message": "User is not authorized to perform: kms:Decrypt on resource",
+            })),
+            SsmClientError::DecryptionDenied(name) if name == "/prod/db"
+        ));
+        assert!(matches!(
+            denied(json!({ "__type": "ParameterNotFound", "message": "" })),
+            SsmClientError::ParameterNotFound(name) if name == "/prod/db"
+        ));
+        assert!(
+            RequestError::from_response(400, &json!({ "__type": "ThrottlingException" }))
+                .is_retryable()
+        );
+    }
+
+    #[test]
+    fn reads_parameters_from_responses() {
+        let response = json!({
+            "Parameter": {
+                "Name": "/prod/db/password",
+                "Type": "SecureString",
+                "Value": "hunter2",
+                "Version": 3,
+                "LastModifiedDate": 1709296200.25,
+                "ARN": "arn:aws:ssm:eu-west-1:123456789012:parameter/prod/db/password",
+            }
+        });
+        let parameter = Parameter::from_json(&response["Parameter"], true).unwrap();
+        assert!(parameter.is_secure());
+        assert_eq!(parameter.value, "hunter2");
+        assert_eq!(parameter.version, Some(3));
+        assert!(parameter.last_modified.is_some());
+
+        let listing = json!({ "Parameters": [{ "Name": "/a" }, { "Name": "/b" }] });
+        assert_eq!(parameter_names(&listing), ["/a", "/b"]);
+    }
+}
@@ -0,0 +1,66 @@
+//! STS client module
+//!
+//! Provides functionality to interact with AWS Security Token Service,
+//! used to confirm which account and identity the selected profile resolves to.
+
+use crate::services::aws::credentials::{self, CredentialsError};
+use crate::services::read_config;
+use aws_config::SdkConfig;
+use aws_sdk_sts::Client;
+use aws_sdk_sts::error::SdkError;
+use thiserror::Error;
+
+/// Errors that can occur when interacting with STS
+#[derive(Error, Debug)]
+pub enum StsClientError {
+    /// Error returned from the AWS SDK
+    #[error("AWS SDK error: {0}")]
+    AwsError(String),
+
+    /// Credentials of a role-assuming profile could not be obtained
+    #[error(transparent)]
+    Credentials(#[from] CredentialsError),
+}
+
+// Implement From traits for SDK errors
+impl<T, E> From<SdkError<T, E>> for StsClientError {
+    fn from(err: SdkError<T, E>) -> Self {
+        StsClientError::AwsError(err.to_string())
+    }
+}
+
+/// Client for AWS STS API operations
+pub struct StsClient {
+    /// AWS SDK STS client
+    client: Client,
+}
+
+impl StsClient {
+    /// Creates a new STS client from the shared SDK config of the given profile
+    pub async fn new(config: &SdkConfig, profile: &str) -> Result<Self, StsClientError> {
+        credentials::verify_role_credentials(
+            config,
+            profile,
+            read_config::get_role_settings(profile).as_ref(),
+        )
+        .await?;
+
+        Ok(Self {
+            client: Client::new(config),
+        })
+    }
+
+    /// Returns the account, ARN and user id the current credentials resolve to
+    ///
+    /// Formatted as a single line suitable for a status bar
+    pub async fn get_caller_identity(&self) -> Result<String, StsClientError> {
+        let resp = self.client.get_caller_identity().send().await?;
+
+        Ok(format!(
+            "Account: {} | ARN: {} | UserId: {}",
+            resp.account().unwrap_or("unknown"),
+            resp.arn().unwrap_or("unknown"),
+            resp.user_id().unwrap_or("unknown"),
+        ))
+    }
+}
@@ -1,10 +1,18 @@
+use aws_config::retry::RetryConfig;
+use aws_config::timeout::TimeoutConfig;
+use aws_config::{BehaviorVersion, Region, SdkConfig};
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 use tokio::sync::Mutex;
 
+use super::super::read_config;
 use super::cloudwatch_client::{CloudWatchClient, CloudWatchClientError};
 use super::dynamo_client::{DynamoDBClient, DynamoDBClientError};
+use super::ecs_client::{EcsClient, EcsClientError};
 use super::s3_client::{S3Client, S3ClientError};
+use super::ssm_client::{SsmClient, SsmClientError};
+use super::sts_client::{StsClient, StsClientError};
 
 /// Error types specific to TabClients operations
 ///
@@ -23,6 +31,18 @@ pub enum TabClientsError {
     #[error("CloudWatch client error: {0}")]
     CloudWatchError(#[from] CloudWatchClientError),
 
+    /// Errors from STS client operations
+    #[error("STS client error: {0}")]
+    StsError(#[from] StsClientError),
+
+    /// Errors from SSM client operations
+    #[error("SSM client error: {0}")]
+    SsmError(#[from] SsmClientError),
+
+    /// Errors from ECS client operations
+    #[error("ECS client error: {0}")]
+    EcsError(#[from] EcsClientError),
+
     /// Direct AWS SDK errors for S3
     #[error("AWS S3 SDK error: {0}")]
     AWSS3Error(#[from] aws_sdk_s3::Error),
@@ -36,6 +56,63 @@ pub enum TabClientsError {
     AWSCloudWatchError(#[from] aws_sdk_cloudwatch::Error),
 }
 
+/// Settings applied to the shared AWS SDK config of every service client
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdkOptions {
+    /// Limit for each request, `None` for no limit
+    pub operation_timeout: Option<Duration>,
+    /// Limit for establishing a connection, `None` for no limit
+    pub connect_timeout: Option<Duration>,
+    /// Attempts per request in the standard retry mode, including the first one; `1`
+    /// turns the SDK's retries off
+    pub max_attempts: u32,
+    /// Custom endpoint (e.g. LocalStack) used instead of the real AWS endpoints
+    pub endpoint_url: Option<String>,
+}
+
+impl SdkOptions {
+    /// Builds options for a service with the given request timeout, taking the
+    /// connect timeout and max attempts from the application config
+    pub fn from_config(operation_timeout: Option<Duration>, endpoint_url: Option<String>) -> Self {
+        Self {
+            operation_timeout,
+            connect_timeout: read_config::get_timeouts().connect,
+            max_attempts: read_config::get_max_attempts(),
+            endpoint_url,
+        }
+    }
+
+    /// Turns the SDK's retries off, for clients whose requests are retried with
+    /// [`retry::with_retry`](super::retry::with_retry) so they are not retried twice
+    pub fn without_sdk_retries(self) -> Self {
+        Self {
+            max_attempts: 1,
+            ..self
+        }
+    }
+}
+
+/// Loads the AWS SDK config for a profile and region with the given timeouts,
+/// standard retry mode and optional custom endpoint
+pub async fn build_sdk_config(profile: &str, region: &str, opts: &SdkOptions) -> SdkConfig {
+    let mut timeout_config = TimeoutConfig::builder();
+    timeout_config.set_operation_timeout(opts.operation_timeout);
+    timeout_config.set_connect_timeout(opts.connect_timeout);
+    let mut loader = aws_config::defaults(BehaviorVersion::latest())
+        .profile_name(profile)
+        .region(Region::new(region.to_string()))
+        .timeout_config(timeout_config.build())
+        .retry_config(if opts.max_attempts > 1 {
+            RetryConfig::standard().with_max_attempts(opts.max_attempts)
+        } else {
+            RetryConfig::disabled()
+        });
+    if let Some(url) = &opts.endpoint_url {
+        loader = loader.endpoint_url(url);
+    }
+    loader.load().await
+}
+
 /// Manages AWS service clients for a specific tab
 ///
 /// Provides lazy initialization and caching of service clients
@@ -50,20 +127,38 @@ pub struct TabClients {
     /// Cached CloudWatch client instance
     cloudwatch_client: Option<Arc<Mutex<CloudWatchClient>>>,
 
+    /// Cached STS client instance
+    sts_client: Option<Arc<Mutex<StsClient>>>,
+
+    /// Cached SSM client instance
+    ssm_client: Option<Arc<Mutex<SsmClient>>>,
+
+    /// Cached ECS client instance
+    ecs_client: Option<Arc<Mutex<EcsClient>>>,
+
     /// AWS profile name used for authentication
     profile: String,
 
     /// AWS region for all service clients
     region: String,
+
+    /// Custom endpoint (e.g. LocalStack) used instead of the real AWS endpoints
+    endpoint_url: Option<String>,
 }
 
 impl TabClients {
     /// Creates a new TabClients instance with the specified profile and region
+    ///
+    /// The endpoint URL is resolved from `AWS_ENDPOINT_URL` or the profile's config
     pub fn new(profile: String, region: String) -> Self {
         Self {
+            endpoint_url: read_config::get_endpoint_url(&profile),
             s3_client: None,
             dynamodb_client: None,
             cloudwatch_client: None,
+            sts_client: None,
+            ssm_client: None,
+            ecs_client: None,
             profile,
             region,
         }
@@ -74,19 +169,66 @@ impl TabClients {
     /// This forces new clients to be created on next request with the new profile
     pub fn set_profile(&mut self, profile: String) {
         if self.profile != profile {
+            self.endpoint_url = read_config::get_endpoint_url(&profile);
             self.profile = profile;
             self.s3_client = None;
             self.dynamodb_client = None;
             self.cloudwatch_client = None;
+            self.sts_client = None;
+            self.ssm_client = None;
+            self.ecs_client = None;
         }
     }
 
+    /// Updates the region and invalidates all existing clients
+    pub fn set_region(&mut self, region: String) {
+        if self.region != region {
+            self.region = region;
+            self.s3_client = None;
+            self.dynamodb_client = None;
+            self.cloudwatch_client = None;
+            self.sts_client = None;
+            self.ssm_client = None;
+            self.ecs_client = None;
+        }
+    }
+
+    /// Returns the AWS profile name used by these clients
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    /// Returns the AWS region used by these clients
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    /// Returns the custom endpoint URL, if one is configured
+    pub fn endpoint_url(&self) -> Option<&str> {
+        self.endpoint_url.as_deref()
+    }
+
+    /// Loads the SDK config shared by the clients, with the service's request timeout
+    async fn sdk_config(&self, operation_timeout: Option<Duration>) -> SdkConfig {
+        let opts = SdkOptions::from_config(operation_timeout, self.endpoint_url.clone());
+        build_sdk_config(&self.profile, &self.region, &opts).await
+    }
+
+    /// Loads the SDK config for clients that retry throttled requests themselves, so
+    /// the SDK makes a single attempt per request
+    async fn sdk_config_without_retries(&self, operation_timeout: Option<Duration>) -> SdkConfig {
+        let opts = SdkOptions::from_config(operation_timeout, self.endpoint_url.clone())
+            .without_sdk_retries();
+        build_sdk_config(&self.profile, &self.region, &opts).await
+    }
+
     /// Gets or initializes an S3 client
     ///
     /// Creates a new client if none exists, otherwise returns the cached instance
     pub async fn get_s3_client(&mut self) -> Result<Arc<Mutex<S3Client>>, TabClientsError> {
         if self.s3_client.is_none() {
-            let client = S3Client::new(self.profile.clone(), self.region.clone()).await?;
+            let config = self.sdk_config(read_config::get_timeouts().s3).await;
+            let client = S3Client::new(&config, &self.profile).await?;
             self.s3_client = Some(Arc::new(Mutex::new(client)));
         }
         Ok(self.s3_client.as_ref().unwrap().clone())
@@ -99,7 +241,10 @@ impl TabClients {
         &mut self,
     ) -> Result<Arc<Mutex<DynamoDBClient>>, TabClientsError> {
         if self.dynamodb_client.is_none() {
-            let client = DynamoDBClient::new(self.profile.clone(), self.region.clone()).await?;
+            let config = self
+                .sdk_config_without_retries(read_config::get_timeouts().dynamodb)
+                .await;
+            let client = DynamoDBClient::new(&config, &self.profile).await?;
             self.dynamodb_client = Some(Arc::new(Mutex::new(client)));
         }
         Ok(self.dynamodb_client.as_ref().unwrap().clone())
@@ -112,9 +257,83 @@ impl TabClients {
         &mut self,
     ) -> Result<Arc<Mutex<CloudWatchClient>>, TabClientsError> {
         if self.cloudwatch_client.is_none() {
-            let client = CloudWatchClient::new(self.profile.clone(), self.region.clone()).await?;
+            let config = self
+                .sdk_config_without_retries(read_config::get_timeouts().cloudwatch)
+                .await;
+            let client = CloudWatchClient::new(&config, &self.profile).await?;
             self.cloudwatch_client = Some(Arc::new(Mutex::new(client)));
         }
         Ok(self.cloudwatch_client.as_ref().unwrap().clone())
     }
+
+    /// Gets or initializes an STS client
+    ///
+    /// Creates a new client if none exists, otherwise returns the cached instance
+    pub async fn get_sts_client(&mut self) -> Result<Arc<Mutex<StsClient>>, TabClientsError> {
+        if self.sts_client.is_none() {
+            let config = self.sdk_config(None).await;
+            let client = StsClient::new(&config, &self.profile).await?;
+            self.sts_client = Some(Arc::new(Mutex::new(client)));
+        }
+        Ok(self.sts_client.as_ref().unwrap().clone())
+    }
+
+    /// Gets or initializes an SSM Parameter Store client
+    ///
+    /// Creates a new client if none exists, otherwise returns the cached instance
+    pub async fn get_ssm_client(&mut self) -> Result<Arc<Mutex<SsmClient>>, TabClientsError> {
+        if self.ssm_client.is_none() {
+            let config = self
+                .sdk_config_without_retries(read_config::get_timeouts().ssm)
+                .await;
+            let client = SsmClient::new(&config, &self.profile).await?;
+            self.ssm_client = Some(Arc::new(Mutex::new(client)));
+        }
+        Ok(self.ssm_client.as_ref().unwrap().clone())
+    }
+
+    /// Gets or initializes an ECS client
+    ///
+    /// Creates a new client if none exists, otherwise returns the cached instance
+    pub async fn get_ecs_client(&mut self) -> Result<Arc<Mutex<EcsClient>>, TabClientsError> {
+        if self.ecs_client.is_none() {
+            let config = self
+                .sdk_config_without_retries(read_config::get_timeouts().ecs)
+                .await;
+            let client = EcsClient::new(&config, &self.profile).await?;
+            self.ecs_client = Some(Arc::new(Mutex::new(client)));
+        }
+        Ok(self.ecs_client.as_ref().unwrap().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn sdk_config_applies_timeouts_and_retries() {
+        let opts = SdkOptions {
+            operation_timeout: Some(Duration::from_secs(42)),
+            connect_timeout: Some(Duration::from_secs(7)),
+            max_attempts: 5,
+            endpoint_url: Some("http://localhost:4566".to_string()),
+        };
+        let config = build_sdk_config("default", "eu-west-1", &opts).await;
+
+        let timeouts = config.timeout_config().unwrap();
+        assert_eq!(timeouts.operation_timeout(), Some(Duration::from_secs(42)));
+        assert_eq!(timeouts.connect_timeout(), Some(Duration::from_secs(7)));
+        let retries = config.retry_config().unwrap();
+        assert_eq!(retries.max_attempts(), 5);
+        assert_eq!(
+            config.region().map(|region| region.as_ref()),
+            Some("eu-west-1")
+        );
+        assert_eq!(config.endpoint_url(), Some("http://localhost:4566"));
+
+        // Clients retrying throttled requests themselves get a single SDK attempt
+        let config = build_sdk_config("default", "eu-west-1", &opts.without_sdk_retries()).await;
+        assert_eq!(config.retry_config().unwrap().max_attempts(), 1);
+    }
 }
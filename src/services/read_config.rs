@@ -1,28 +1,803 @@
 use config::{Config, File, FileFormat};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use thiserror::Error;
 
-/// Reads the AWS configuration file and extracts profile names.
-pub fn get_aws_profiles() -> Result<Vec<String>, Box<dyn Error>> {
-    // Specify the path to the AWS config file
-    let config_path = dirs::home_dir()
-        .ok_or("Could not determine home directory")?
-        .join(".aws/config");
+/// Sections of an INI file mapped to their key/value pairs
+type IniSections = HashMap<String, HashMap<String, String>>;
 
-    // Load the INI file using the `config` crate
+/// Environment variable overriding the location of the AWS config file
+const AWS_CONFIG_FILE_ENV: &str = "AWS_CONFIG_FILE";
+/// Environment variable overriding the location of the AWS credentials file
+const AWS_CREDENTIALS_FILE_ENV: &str = "AWS_SHARED_CREDENTIALS_FILE";
+/// Environment variable naming the profile the AWS CLI uses
+const AWS_PROFILE_ENV: &str = "AWS_PROFILE";
+
+/// Environment variable that overrides the endpoint for every AWS service
+const ENDPOINT_URL_ENV: &str = "AWS_ENDPOINT_URL";
+
+/// Application config file, relative to the user's config directory
+const APP_CONFIG_PATH: &str = "aws-tui/config.toml";
+/// Layout state written by the application, kept apart from the hand-edited config
+const LAYOUT_STATE_PATH: &str = "aws-tui/layout.toml";
+/// Submitted queries and filters, saved per profile and input box
+const HISTORY_STATE_PATH: &str = "aws-tui/history.json";
+/// Most recently selected profiles, relative to the user's config directory
+const RECENT_PROFILES_PATH: &str = "aws-tui/recent_profiles.json";
+/// Profiles pinned at most at the top of the profile popup
+const MAX_RECENT_PROFILES: usize = 5;
+/// Records one CloudWatch search or DynamoDB query fetches unless `max_results` is set
+const DEFAULT_MAX_RESULTS: usize = 5000;
+/// Attempts the AWS SDK makes per request when `max_attempts` is not configured
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+/// Ticks per second while nothing animates, unless `tick_rate` is configured
+const DEFAULT_TICK_RATE: f64 = 4.0;
+/// Ticks per second while something animates, unless `active_tick_rate` is configured
+const DEFAULT_ACTIVE_TICK_RATE: f64 = 10.0;
+/// Fastest tick rate accepted from the config
+const MAX_TICK_RATE: f64 = 60.0;
+
+/// Reads an INI file into a map of sections to key/value pairs.
+fn read_ini(path: &Path) -> Result<IniSections, Box<dyn Error>> {
     let settings = Config::builder()
-        .add_source(File::new(config_path.to_str().unwrap(), FileFormat::Ini))
+        .add_source(File::from(path).format(FileFormat::Ini))
         .build()?;
+    Ok(settings.try_deserialize()?)
+}
+
+/// Path of an AWS file, honoring its environment override like the AWS CLI does
+fn aws_file_path(env_var: &str, default: &str) -> Option<PathBuf> {
+    match std::env::var(env_var) {
+        Ok(path) if !path.is_empty() => Some(PathBuf::from(path)),
+        _ => dirs::home_dir().map(|home| home.join(default)),
+    }
+}
+
+/// Reads the AWS configuration file into a map of sections to key/value pairs.
+fn read_config_map() -> Result<IniSections, Box<dyn Error>> {
+    let config_path = aws_file_path(AWS_CONFIG_FILE_ENV, ".aws/config")
+        .ok_or("Could not determine home directory")?;
+    read_ini(&config_path)
+}
 
-    // Deserialize the file into a HashMap
-    let config_map: HashMap<String, HashMap<String, String>> = settings.try_deserialize()?;
+/// Why no profile list could be read
+#[derive(Debug, Error)]
+pub enum ProfilesError {
+    /// Neither the config nor the credentials file exists
+    #[error("No AWS config or credentials file found (checked {0})")]
+    NoFiles(String),
 
-    // Collect profile names into a Vec
-    let mut profiles: Vec<String> = config_map
-        .keys()
-        .filter_map(|section| section.strip_prefix("profile ").map(String::from))
+    /// One of the files exists but is not valid INI
+    #[error("Could not parse {0}: {1}")]
+    Parse(String, String),
+}
+
+/// Reads the profile names from the given config and credentials files.
+///
+/// Missing files are skipped, but at least one of them must exist. Profiles defined
+/// in both files are listed once; `sso-session` and `services` sections are not profiles.
+pub fn read_profiles(
+    config_path: &Path,
+    credentials_path: &Path,
+) -> Result<Vec<String>, ProfilesError> {
+    if !config_path.exists() && !credentials_path.exists() {
+        return Err(ProfilesError::NoFiles(format!(
+            "{} and {}",
+            config_path.display(),
+            credentials_path.display()
+        )));
+    }
+    let read = |path: &Path| -> Result<IniSections, ProfilesError> {
+        if !path.exists() {
+            return Ok(IniSections::new());
+        }
+        read_ini(path)
+            .map_err(|err| ProfilesError::Parse(path.display().to_string(), err.to_string()))
+    };
+
+    // Config sections are `[default]` and `[profile name]`, credentials sections are plain names
+    let config_profiles = read(config_path)?.into_keys().filter_map(|section| {
+        match section.strip_prefix("profile ") {
+            Some(name) => Some(name.trim().to_string()),
+            None if section == "default" => Some(section),
+            None => None,
+        }
+    });
+    let profiles: BTreeSet<String> = config_profiles
+        .chain(read(credentials_path)?.into_keys())
+        .filter(|name| !name.is_empty())
         .collect();
-    profiles.sort();
+    Ok(profiles.into_iter().collect())
+}
+
+/// Lists the profiles from `~/.aws/config` and `~/.aws/credentials`, sorted by name.
+///
+/// `AWS_CONFIG_FILE` and `AWS_SHARED_CREDENTIALS_FILE` override the file locations.
+pub fn get_aws_profiles() -> Result<Vec<String>, ProfilesError> {
+    let missing = || ProfilesError::NoFiles(String::from("home directory not found"));
+    let config_path = aws_file_path(AWS_CONFIG_FILE_ENV, ".aws/config").ok_or_else(missing)?;
+    let credentials_path =
+        aws_file_path(AWS_CREDENTIALS_FILE_ENV, ".aws/credentials").ok_or_else(missing)?;
+    read_profiles(&config_path, &credentials_path)
+}
+
+/// Resolves a custom endpoint URL (e.g. LocalStack) for the given profile.
+///
+/// `AWS_ENDPOINT_URL` takes precedence over the profile's `endpoint_url` setting.
+pub fn get_endpoint_url(profile: &str) -> Option<String> {
+    if let Ok(url) = std::env::var(ENDPOINT_URL_ENV)
+        && !url.is_empty()
+    {
+        return Some(url);
+    }
+
+    read_config_map()
+        .ok()?
+        .remove(&profile_section(profile))?
+        .remove("endpoint_url")
+        .filter(|url| !url.is_empty())
+}
+
+/// Reads the profile's `region` from the given AWS config file, `None` when the file,
+/// the profile or the key is missing.
+pub fn read_profile_region(config_path: &Path, profile: &str) -> Option<String> {
+    read_ini(config_path)
+        .ok()?
+        .remove(&profile_section(profile))?
+        .remove("region")
+        .map(|region| region.trim().to_string())
+        .filter(|region| !region.is_empty())
+}
+
+/// Returns the region the profile configures in `~/.aws/config`, if any.
+///
+/// `AWS_CONFIG_FILE` overrides the file location.
+pub fn get_profile_region(profile: &str) -> Option<String> {
+    let config_path = aws_file_path(AWS_CONFIG_FILE_ENV, ".aws/config")?;
+    read_profile_region(&config_path, profile)
+}
+
+/// Name of the profile's section in the AWS config file
+fn profile_section(profile: &str) -> String {
+    if profile == "default" {
+        String::from("default")
+    } else {
+        format!("profile {}", profile)
+    }
+}
+
+/// Role a profile assumes through `role_arn`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoleSettings {
+    pub role_arn: String,
+    /// MFA device the role expects a code from, if any
+    pub mfa_serial: Option<String>,
+}
+
+/// Reads `role_arn` and `mfa_serial` from the profile's section of the AWS config file,
+/// `None` when the profile does not assume a role.
+pub fn get_role_settings(profile: &str) -> Option<RoleSettings> {
+    let mut section = read_config_map().ok()?.remove(&profile_section(profile))?;
+    let role_arn = section.remove("role_arn").filter(|arn| !arn.is_empty())?;
+    Some(RoleSettings {
+        role_arn,
+        mfa_serial: section
+            .remove("mfa_serial")
+            .filter(|serial| !serial.is_empty()),
+    })
+}
+
+/// Returns whether navigators should wrap from the last item to the first and back, from
+/// `wrap_around` in the application config
+///
+/// Off unless set to `true`.
+pub fn get_navigator_wrap_around() -> bool {
+    read_app_config()
+        .and_then(|settings| settings.get::<bool>("wrap_around").ok())
+        .unwrap_or(false)
+}
+
+/// Returns the fixed PageUp/PageDown jump size, from `page_size` in the application config
+///
+/// Without it navigators jump by their visible height.
+pub fn get_navigator_page_size() -> Option<usize> {
+    read_app_config()
+        .and_then(|settings| settings.get::<usize>("page_size").ok())
+        .filter(|size| *size > 0)
+}
+
+/// Returns whether console links should also be opened in the default browser, from
+/// `open_console` in the application config
+///
+/// Off unless set to `true`.
+pub fn get_open_console_links() -> bool {
+    read_app_config()
+        .and_then(|settings| settings.get::<bool>("open_console").ok())
+        .unwrap_or(false)
+}
+
+/// Returns whether copies may fall back to the terminal clipboard (OSC 52) when there is
+/// no system clipboard, from `osc52_clipboard` in the application config
+///
+/// Unless set to `true` or `false`, it is on in SSH sessions only.
+pub fn get_osc52_clipboard() -> bool {
+    read_app_config()
+        .and_then(|settings| settings.get::<bool>("osc52_clipboard").ok())
+        .unwrap_or_else(|| {
+            std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some()
+        })
+}
+
+/// Returns whether CloudWatch log lines are colored by severity, from `log_colors` in the
+/// application config
+///
+/// On unless set to `false`.
+pub fn get_log_colors() -> bool {
+    read_app_config()
+        .and_then(|settings| settings.get::<bool>("log_colors").ok())
+        .unwrap_or(true)
+}
+
+/// Returns the number of S3 objects listed per page, from `s3_page_size` in the application
+/// config
+///
+/// Values above S3's limit of 1000 are capped; without it S3 returns up to 1000 keys.
+pub fn get_s3_page_size() -> Option<i32> {
+    read_app_config()
+        .and_then(|settings| settings.get::<i32>("s3_page_size").ok())
+        .filter(|size| *size > 0)
+        .map(|size| size.min(1000))
+}
+
+/// Returns the projection DynamoDB queries of a table start with, from the `[dynamodb]`
+/// table of the application config
+///
+/// The table's entry in `[dynamodb.projections]` wins over `projection`, which applies to
+/// every other table. `None` when neither is set.
+pub fn get_dynamodb_projection(table: &str) -> Option<String> {
+    let settings = read_app_config()?;
+    settings
+        .get::<HashMap<String, String>>("dynamodb.projections")
+        .ok()
+        .and_then(|projections| projections.get(table).cloned())
+        .or_else(|| settings.get::<String>("dynamodb.projection").ok())
+        .filter(|projection| !projection.trim().is_empty())
+}
+
+/// Time limits from the `[timeouts]` table of the application config, `None` meaning no limit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timeouts {
+    /// Each S3 request
+    pub s3: Option<Duration>,
+    /// Each DynamoDB request; large scans and queries are not limited by default
+    pub dynamodb: Option<Duration>,
+    /// Each CloudWatch Logs request, and loading the log groups
+    pub cloudwatch: Option<Duration>,
+    /// A whole CloudWatch log search across all of its pages
+    pub cloudwatch_search: Option<Duration>,
+    /// Each SSM Parameter Store request
+    pub ssm: Option<Duration>,
+    /// Each ECS request
+    pub ecs: Option<Duration>,
+    /// Establishing a connection to any service endpoint
+    pub connect: Option<Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            s3: Some(Duration::from_secs(30)),
+            dynamodb: None,
+            cloudwatch: Some(Duration::from_secs(30)),
+            cloudwatch_search: Some(Duration::from_secs(330)),
+            ssm: Some(Duration::from_secs(30)),
+            ecs: Some(Duration::from_secs(30)),
+            connect: Some(Duration::from_secs(10)),
+        }
+    }
+}
+
+impl Timeouts {
+    /// Applies `<service> = <seconds>` settings over the defaults; `0` removes the limit
+    fn from_settings(settings: &HashMap<String, u64>) -> Self {
+        let defaults = Self::default();
+        let pick = |key: &str, default: Option<Duration>| match settings.get(key) {
+            Some(0) => None,
+            Some(seconds) => Some(Duration::from_secs(*seconds)),
+            None => default,
+        };
+        Self {
+            s3: pick("s3", defaults.s3),
+            dynamodb: pick("dynamodb", defaults.dynamodb),
+            cloudwatch: pick("cloudwatch", defaults.cloudwatch),
+            cloudwatch_search: pick("cloudwatch_search", defaults.cloudwatch_search),
+            ssm: pick("ssm", defaults.ssm),
+            ecs: pick("ecs", defaults.ecs),
+            connect: pick("connect", defaults.connect),
+        }
+    }
+}
+
+/// Reads the `[timeouts]` table (seconds per service) from the application config file.
+pub fn get_timeouts() -> Timeouts {
+    let settings = read_app_config()
+        .and_then(|settings| settings.get::<HashMap<String, u64>>("timeouts").ok())
+        .unwrap_or_default();
+    Timeouts::from_settings(&settings)
+}
+
+/// Returns how many records a CloudWatch search or DynamoDB query fetches before it
+/// stops following pages, from `max_results` in the application config.
+///
+/// Defaults to 5000; `0` removes the cap.
+pub fn get_max_results() -> Option<usize> {
+    let configured =
+        read_app_config().and_then(|settings| settings.get::<usize>("max_results").ok());
+    match configured {
+        Some(0) => None,
+        Some(max_results) => Some(max_results),
+        None => Some(DEFAULT_MAX_RESULTS),
+    }
+}
+
+/// Returns the CloudWatch log time range searched while none is typed in, from
+/// `log_time_range` in the application config.
+pub fn get_log_time_range() -> Option<String> {
+    read_app_config()
+        .and_then(|settings| settings.get::<String>("log_time_range").ok())
+        .map(|range| range.trim().to_string())
+        .filter(|range| !range.is_empty())
+}
+
+/// Returns whether a CloudWatch search moves focus to its results once it succeeds, from
+/// `focus_results_after_search` in the application config.
+///
+/// Enabled unless set to `false`.
+pub fn get_focus_results_after_search() -> bool {
+    read_app_config()
+        .and_then(|settings| settings.get::<bool>("focus_results_after_search").ok())
+        .unwrap_or(true)
+}
+
+/// When fetches that may return a lot of data ask for confirmation first, from the
+/// `[confirmations]` table of the application config
+#[derive(Debug, Clone, PartialEq)]
+pub struct LargeFetchConfirmations {
+    /// Whether a DynamoDB scan of a whole table asks first
+    pub scans: bool,
+    /// CloudWatch log searches over a longer time range ask first
+    pub log_range: Option<Duration>,
+}
+
+impl Default for LargeFetchConfirmations {
+    fn default() -> Self {
+        Self {
+            scans: true,
+            log_range: None,
+        }
+    }
+}
+
+/// Returns when to confirm large fetches: `scans = false` stops asking before scans, and
+/// `log_range_hours = <hours>` asks before log searches over more hours (`0` never asks).
+pub fn get_large_fetch_confirmations() -> LargeFetchConfirmations {
+    let settings = read_app_config();
+    let defaults = LargeFetchConfirmations::default();
+    let scans = settings
+        .as_ref()
+        .and_then(|settings| settings.get::<bool>("confirmations.scans").ok())
+        .unwrap_or(defaults.scans);
+    let log_range = settings
+        .as_ref()
+        .and_then(|settings| settings.get::<u64>("confirmations.log_range_hours").ok())
+        .map(|hours| (hours > 0).then(|| Duration::from_secs(hours * 3600)))
+        .unwrap_or(defaults.log_range);
+    LargeFetchConfirmations { scans, log_range }
+}
+
+/// Returns whether read-only mode is on, from `read_only` in the application config
+///
+/// Read-only mode disables everything that changes AWS resources, such as creating or
+/// deleting buckets and deleting objects. Off unless set to `true`.
+pub fn get_read_only() -> bool {
+    read_app_config()
+        .and_then(|settings| settings.get::<bool>("read_only").ok())
+        .unwrap_or(false)
+}
+
+/// Whether `--yes` turned power-user mode on for this run
+static POWER_USER_FLAG: AtomicBool = AtomicBool::new(false);
+
+/// Turns power-user mode on for this run whatever the config says, as `--yes` does
+pub fn enable_power_user() {
+    POWER_USER_FLAG.store(true, Ordering::Relaxed);
+}
+
+/// Returns whether power-user mode skips the confirmations before changes, from
+/// `power_user` in the application config or `--yes`
+///
+/// Read-only mode wins: while it is on, changes stay disabled and this is `false`. Off
+/// unless set to `true`.
+pub fn get_power_user() -> bool {
+    if get_read_only() {
+        return false;
+    }
+    POWER_USER_FLAG.load(Ordering::Relaxed)
+        || read_app_config()
+            .and_then(|settings| settings.get::<bool>("power_user").ok())
+            .unwrap_or(false)
+}
+
+/// Returns the profile new tabs open without the profile popup, when `auto_select_profile`
+/// is enabled in the application config.
+///
+/// That is `AWS_PROFILE` when it names one of `profiles`, else the `default` profile.
+pub fn get_auto_selected_profile(profiles: &[String]) -> Option<String> {
+    let enabled = read_app_config()
+        .and_then(|settings| settings.get::<bool>("auto_select_profile").ok())
+        .unwrap_or(false);
+    if !enabled {
+        return None;
+    }
+    pick_auto_profile(std::env::var(AWS_PROFILE_ENV).ok(), profiles)
+}
+
+/// Picks the environment's profile if it exists, falling back to `default`
+fn pick_auto_profile(env_profile: Option<String>, profiles: &[String]) -> Option<String> {
+    env_profile
+        .map(|profile| profile.trim().to_string())
+        .filter(|profile| profiles.contains(profile))
+        .or_else(|| {
+            profiles
+                .iter()
+                .find(|profile| *profile == "default")
+                .cloned()
+        })
+}
+
+/// Service and region to open right after a profile is selected
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProfileDefaults {
+    pub service: Option<String>,
+    pub region: Option<String>,
+}
+
+/// Returns how many times each AWS request is attempted, including the first one.
+///
+/// Read from `max_attempts` in the application config; defaults to 3 and is at least 1.
+/// Throttled requests are retried until this many attempts were made.
+pub fn get_max_attempts() -> u32 {
+    read_app_config()
+        .and_then(|settings| settings.get::<u32>("max_attempts").ok())
+        .unwrap_or(DEFAULT_MAX_ATTEMPTS)
+        .max(1)
+}
+
+/// How often the event loop ticks, in ticks per second
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TickRates {
+    /// Rate while the screen is static
+    pub idle: f64,
+    /// Rate while something animates, e.g. a live tail
+    pub active: f64,
+}
+
+impl TickRates {
+    /// Builds the rates from configured values, keeping each between 1 and 60 and falling
+    /// back to the defaults for missing or invalid ones; the active rate is never below the
+    /// idle one
+    fn from_settings(idle: Option<f64>, active: Option<f64>) -> Self {
+        let rate = |value: Option<f64>, default: f64| {
+            value
+                .filter(|rate| rate.is_finite() && *rate > 0.0)
+                .map_or(default, |rate| rate.clamp(1.0, MAX_TICK_RATE))
+        };
+        let idle = rate(idle, DEFAULT_TICK_RATE);
+        Self {
+            idle,
+            active: rate(active, DEFAULT_ACTIVE_TICK_RATE).max(idle),
+        }
+    }
+}
+
+/// Returns the event loop's tick rates
+///
+/// Read from `tick_rate` and `active_tick_rate` in the application config; default to 4
+/// and 10 ticks per second.
+pub fn get_tick_rates() -> TickRates {
+    let settings = read_app_config();
+    let get = |key: &str| {
+        settings
+            .as_ref()
+            .and_then(|settings| settings.get::<f64>(key).ok())
+    };
+    TickRates::from_settings(get("tick_rate"), get("active_tick_rate"))
+}
+
+/// Loads the application config file, `None` when it cannot be located or parsed.
+fn read_app_config() -> Option<Config> {
+    let config_path = dirs::config_dir()?.join(APP_CONFIG_PATH);
+
+    Config::builder()
+        .add_source(
+            File::from(config_path)
+                .format(FileFormat::Toml)
+                .required(false),
+        )
+        .build()
+        .ok()
+}
+
+/// Reads the `[theme]` table from the application config file.
+///
+/// Returns an empty map when the file or table is missing.
+pub fn get_theme_settings() -> HashMap<String, String> {
+    read_app_config()
+        .and_then(|settings| settings.get::<HashMap<String, String>>("theme").ok())
+        .unwrap_or_default()
+}
+
+/// Reads the `[timestamps]` table from the application config file.
+///
+/// Returns an empty map when the file or table is missing.
+pub fn get_timestamp_settings() -> HashMap<String, String> {
+    read_app_config()
+        .and_then(|settings| settings.get::<HashMap<String, String>>("timestamps").ok())
+        .unwrap_or_default()
+}
+
+/// Reads `default_service` and `default_region` from the profile's
+/// `[profiles.<name>]` table in the application config file.
+pub fn get_profile_defaults(profile: &str) -> ProfileDefaults {
+    let Some(mut profiles) = read_app_config().and_then(|settings| {
+        settings
+            .get::<HashMap<String, HashMap<String, String>>>("profiles")
+            .ok()
+    }) else {
+        return ProfileDefaults::default();
+    };
+
+    let mut settings = profiles.remove(profile).unwrap_or_default();
+    let mut take = |key: &str| {
+        settings
+            .remove(key)
+            .filter(|value| !value.trim().is_empty())
+    };
+    ProfileDefaults {
+        service: take("default_service"),
+        region: take("default_region"),
+    }
+}
+
+/// Reads the saved pane splits (left pane width in percent) keyed by layout name.
+///
+/// Returns an empty map when nothing has been saved yet.
+pub fn get_split_ratios() -> HashMap<String, u16> {
+    let Some(state_path) = dirs::config_dir().map(|dir| dir.join(LAYOUT_STATE_PATH)) else {
+        return HashMap::new();
+    };
+
+    Config::builder()
+        .add_source(
+            File::from(state_path)
+                .format(FileFormat::Toml)
+                .required(false),
+        )
+        .build()
+        .and_then(|settings| settings.get::<HashMap<String, u16>>("splits"))
+        .unwrap_or_default()
+}
+
+/// Saves the left pane width of one layout, keeping the others already saved.
+pub fn save_split_ratio(name: &str, ratio: u16) -> Result<(), Box<dyn Error>> {
+    let state_path = dirs::config_dir()
+        .ok_or("Could not determine config directory")?
+        .join(LAYOUT_STATE_PATH);
+
+    let mut splits: Vec<(String, u16)> = get_split_ratios().into_iter().collect();
+    splits.retain(|(key, _)| key != name);
+    splits.push((name.to_string(), ratio));
+    splits.sort();
+
+    let mut contents = String::from("[splits]\n");
+    for (key, ratio) in splits {
+        contents.push_str(&format!("{} = {}\n", key, ratio));
+    }
+
+    if let Some(parent) = state_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(state_path, contents)?;
+    Ok(())
+}
+
+/// Reads the saved input histories, keyed by profile and then by input name.
+fn read_input_histories() -> HashMap<String, HashMap<String, Vec<String>>> {
+    dirs::config_dir()
+        .and_then(|dir| std::fs::read_to_string(dir.join(HISTORY_STATE_PATH)).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Returns the saved entries of an input box for the profile, oldest first.
+pub fn get_input_history(profile: &str, name: &str) -> Vec<String> {
+    read_input_histories()
+        .remove(profile)
+        .and_then(|mut inputs| inputs.remove(name))
+        .unwrap_or_default()
+}
+
+/// Saves the entries of an input box for the profile; an empty history is removed.
+pub fn save_input_history(
+    profile: &str,
+    name: &str,
+    entries: &[String],
+) -> Result<(), Box<dyn Error>> {
+    let state_path = dirs::config_dir()
+        .ok_or("Could not determine config directory")?
+        .join(HISTORY_STATE_PATH);
+
+    let mut histories = read_input_histories();
+    let inputs = histories.entry(profile.to_string()).or_default();
+    if entries.is_empty() {
+        inputs.remove(name);
+    } else {
+        inputs.insert(name.to_string(), entries.to_vec());
+    }
+    histories.retain(|_, inputs| !inputs.is_empty());
+
+    if let Some(parent) = state_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(state_path, serde_json::to_string_pretty(&histories)?)?;
+    Ok(())
+}
+
+/// Returns the most recently selected profiles, newest first.
+pub fn get_recent_profiles() -> Vec<String> {
+    dirs::config_dir()
+        .and_then(|dir| std::fs::read_to_string(dir.join(RECENT_PROFILES_PATH)).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Moves the profile to the front of the recent profiles, keeping at most `cap` of them.
+fn remember_profile(recent: &mut Vec<String>, profile: &str, cap: usize) {
+    recent.retain(|entry| entry != profile);
+    recent.insert(0, profile.to_string());
+    recent.truncate(cap);
+}
+
+/// Records a profile selection so the profile popup of new tabs lists it first.
+pub fn save_recent_profile(profile: &str) -> Result<(), Box<dyn Error>> {
+    let state_path = dirs::config_dir()
+        .ok_or("Could not determine config directory")?
+        .join(RECENT_PROFILES_PATH);
+
+    let mut recent = get_recent_profiles();
+    remember_profile(&mut recent, profile, MAX_RECENT_PROFILES);
+
+    if let Some(parent) = state_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(state_path, serde_json::to_string_pretty(&recent)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_profile_prefers_an_existing_aws_profile_over_default() {
+        let profiles = vec!["default".to_string(), "prod".to_string()];
+        let pick = |env: Option<&str>| pick_auto_profile(env.map(String::from), &profiles);
+        assert_eq!(pick(Some("prod")), Some("prod".to_string()));
+        // A profile missing from the files falls back to the default one
+        assert_eq!(pick(Some("gone")), Some("default".to_string()));
+        assert_eq!(pick(None), Some("default".to_string()));
+        assert_eq!(pick_auto_profile(None, &["prod".to_string()]), None);
+    }
+
+    #[test]
+    fn timeouts_override_defaults_and_zero_disables() {
+        let settings =
+            HashMap::from([("dynamodb".to_string(), 120), ("cloudwatch".to_string(), 0)]);
+        let timeouts = Timeouts::from_settings(&settings);
+        assert_eq!(timeouts.dynamodb, Some(Duration::from_secs(120)));
+        assert_eq!(timeouts.cloudwatch, None);
+        assert_eq!(timeouts.s3, Timeouts::default().s3);
+        assert_eq!(timeouts.cloudwatch_search, Some(Duration::from_secs(330)));
+        assert_eq!(timeouts.connect, Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn recent_profiles_move_to_the_front_and_are_capped() {
+        let mut recent = vec!["dev".to_string(), "prod".to_string(), "ci".to_string()];
+        remember_profile(&mut recent, "ci", 3);
+        assert_eq!(recent, vec!["ci", "dev", "prod"]);
+        remember_profile(&mut recent, "sandbox", 3);
+        assert_eq!(recent, vec!["sandbox", "ci", "dev"]);
+    }
+
+    #[test]
+    fn tick_rates_are_clamped_and_fall_back_to_defaults() {
+        assert_eq!(
+            TickRates::from_settings(None, None),
+            TickRates {
+                idle: 4.0,
+                active: 10.0
+            }
+        );
+        assert_eq!(
+            TickRates::from_settings(Some(0.5), Some(240.0)),
+            TickRates {
+                idle: 1.0,
+                active: 60.0
+            }
+        );
+        // Zero, negative and active rates below the idle one are not honored
+        assert_eq!(
+            TickRates::from_settings(Some(0.0), Some(-3.0)),
+            TickRates {
+                idle: 4.0,
+                active: 10.0
+            }
+        );
+        assert_eq!(TickRates::from_settings(Some(20.0), None).active, 20.0);
+    }
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/aws")
+            .join(name)
+    }
+
+    #[test]
+    fn profiles_come_from_both_files_without_duplicates() {
+        let profiles = read_profiles(&fixture("config"), &fixture("credentials")).unwrap();
+        assert_eq!(
+            profiles,
+            vec!["ci", "default", "dev", "prod-admin", "sso-admin"]
+        );
+    }
+
+    #[test]
+    fn a_single_existing_file_is_enough() {
+        let profiles = read_profiles(&fixture("missing"), &fixture("credentials")).unwrap();
+        assert_eq!(profiles, vec!["ci", "default", "dev"]);
+    }
+
+    #[test]
+    fn profile_regions_come_from_their_config_section() {
+        let config = fixture("config");
+        assert_eq!(
+            read_profile_region(&config, "default").as_deref(),
+            Some("eu-west-1")
+        );
+        assert_eq!(
+            read_profile_region(&config, "sso-admin").as_deref(),
+            Some("us-east-1")
+        );
+        // A profile without a region, one only in the credentials file and a broken file
+        assert_eq!(read_profile_region(&config, "prod-admin"), None);
+        assert_eq!(read_profile_region(&config, "ci"), None);
+        assert_eq!(read_profile_region(&fixture("invalid"), "default"), None);
+        assert_eq!(read_profile_region(&fixture("missing"), "default"), None);
+    }
 
-    Ok(profiles)
+    #[test]
+    fn missing_and_invalid_files_are_reported_differently() {
+        assert!(matches!(
+            read_profiles(&fixture("missing"), &fixture("also-missing")),
+            Err(ProfilesError::NoFiles(_))
+        ));
+        assert!(matches!(
+            read_profiles(&fixture("invalid"), &fixture("credentials")),
+            Err(ProfilesError::Parse(..))
+        ));
+    }
 }
@@ -0,0 +1,188 @@
+//! Theme module
+//!
+//! Defines the colors and list markers used across the interface. The active theme is loaded once
+//! from the application config file and shared through a global.
+
+use crate::services::read_config;
+use ratatui::style::Color;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Theme used for the lifetime of the application
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Colors and list markers used when rendering components and widgets
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub active_border: Color,      // Border of the focused panel or widget
+    pub inactive_border: Color,    // Border of unfocused panels
+    pub selection_fg: Color,       // Selected item in lists and trees
+    pub help_fg: Color,            // Help toolbar and status labels
+    pub title_fg: Color,           // Tab titles
+    pub error_fg: Color,           // Error banners and failed checks
+    pub text_fg: Color,            // Regular text and widget borders
+    pub background: Color,         // Popup background
+    pub highlight_fg: Color,       // Text of search matches
+    pub highlight_bg: Color,       // Background of search matches
+    pub success_fg: Color,         // Successful checks such as the caller identity
+    pub info_fg: Color,            // Short-lived notifications
+    pub warning_fg: Color,         // Notices such as custom endpoints or empty lists
+    pub active_tab_fg: Color,      // Currently selected tab
+    pub selection_symbol: String,  // Marks the selected row of lists, e.g. "> "
+    pub truncation_symbol: String, // Ends list rows cut at the list width; empty to just clip
+    pub service_icons: bool,       // Emoji before service names; ASCII tags like "[S3]" if off
+}
+
+impl Default for Theme {
+    /// The original dark-terminal colors
+    fn default() -> Self {
+        Self {
+            active_border: Color::Red,
+            inactive_border: Color::DarkGray,
+            selection_fg: Color::Yellow,
+            help_fg: Color::DarkGray,
+            title_fg: Color::Yellow,
+            error_fg: Color::Red,
+            text_fg: Color::White,
+            background: Color::Black,
+            highlight_fg: Color::Black,
+            highlight_bg: Color::Yellow,
+            success_fg: Color::Green,
+            info_fg: Color::Cyan,
+            warning_fg: Color::Yellow,
+            active_tab_fg: Color::LightGreen,
+            selection_symbol: String::from("> "),
+            truncation_symbol: String::from("…"),
+            service_icons: true,
+        }
+    }
+}
+
+impl Theme {
+    /// Dark text on a light background for light terminals
+    pub fn light() -> Self {
+        Self {
+            active_border: Color::Red,
+            inactive_border: Color::Gray,
+            selection_fg: Color::Blue,
+            help_fg: Color::DarkGray,
+            title_fg: Color::Blue,
+            error_fg: Color::Red,
+            text_fg: Color::Black,
+            background: Color::White,
+            highlight_fg: Color::Black,
+            highlight_bg: Color::LightYellow,
+            success_fg: Color::Green,
+            info_fg: Color::Magenta,
+            warning_fg: Color::Magenta,
+            active_tab_fg: Color::Red,
+            ..Self::default()
+        }
+    }
+
+    /// Bright, saturated colors on black for maximum legibility
+    pub fn high_contrast() -> Self {
+        Self {
+            active_border: Color::LightYellow,
+            inactive_border: Color::White,
+            selection_fg: Color::LightCyan,
+            help_fg: Color::White,
+            title_fg: Color::White,
+            error_fg: Color::LightRed,
+            text_fg: Color::White,
+            background: Color::Black,
+            highlight_fg: Color::Black,
+            highlight_bg: Color::LightYellow,
+            success_fg: Color::LightGreen,
+            info_fg: Color::LightCyan,
+            warning_fg: Color::LightYellow,
+            active_tab_fg: Color::LightYellow,
+            ..Self::default()
+        }
+    }
+
+    /// Looks up a built-in theme by name
+    pub fn named(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "default" | "dark" => Some(Self::default()),
+            "light" => Some(Self::light()),
+            "high-contrast" | "high_contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Builds a theme from the `[theme]` config settings
+    ///
+    /// `name` selects a built-in theme (unknown names fall back to the default),
+    /// and any color field set individually overrides it. Invalid colors are ignored.
+    /// `selection_symbol` and `truncation_symbol` replace the list markers, and
+    /// `service_icons = false` tags services in ASCII for fonts without emoji.
+    pub fn from_settings(settings: &HashMap<String, String>) -> Self {
+        let mut theme = settings
+            .get("name")
+            .and_then(|name| Self::named(name))
+            .unwrap_or_default();
+
+        let fields: [(&str, &mut Color); 14] = [
+            ("active_border", &mut theme.active_border),
+            ("inactive_border", &mut theme.inactive_border),
+            ("selection_fg", &mut theme.selection_fg),
+            ("help_fg", &mut theme.help_fg),
+            ("title_fg", &mut theme.title_fg),
+            ("error_fg", &mut theme.error_fg),
+            ("text_fg", &mut theme.text_fg),
+            ("background", &mut theme.background),
+            ("highlight_fg", &mut theme.highlight_fg),
+            ("highlight_bg", &mut theme.highlight_bg),
+            ("success_fg", &mut theme.success_fg),
+            ("info_fg", &mut theme.info_fg),
+            ("warning_fg", &mut theme.warning_fg),
+            ("active_tab_fg", &mut theme.active_tab_fg),
+        ];
+        for (key, color) in fields {
+            if let Some(parsed) = settings
+                .get(key)
+                .and_then(|value| Color::from_str(value).ok())
+            {
+                *color = parsed;
+            }
+        }
+        if let Some(symbol) = settings.get("selection_symbol") {
+            theme.selection_symbol = symbol.clone();
+        }
+        if let Some(symbol) = settings.get("truncation_symbol") {
+            theme.truncation_symbol = symbol.clone();
+        }
+        if let Some(icons) = settings
+            .get("service_icons")
+            .and_then(|value| value.parse::<bool>().ok())
+        {
+            theme.service_icons = icons;
+        }
+
+        theme
+    }
+}
+
+/// Returns the active theme, loading it from the config file on first use
+pub fn theme() -> &'static Theme {
+    THEME.get_or_init(|| Theme::from_settings(&read_config::get_theme_settings()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn service_icons_can_be_turned_off_for_fonts_without_emoji() {
+        assert!(Theme::from_settings(&HashMap::new()).service_icons);
+
+        let settings = HashMap::from([("service_icons".to_string(), "false".to_string())]);
+        assert!(!Theme::from_settings(&settings).service_icons);
+
+        // An unreadable value keeps the icons
+        let settings = HashMap::from([("service_icons".to_string(), "maybe".to_string())]);
+        assert!(Theme::from_settings(&settings).service_icons);
+    }
+}
@@ -0,0 +1,202 @@
+//! Timestamp formatting module
+//!
+//! Formats the timestamps shown for S3 objects and CloudWatch log groups in the time zone and
+//! format chosen in the application config. Like the theme, the settings are loaded once and
+//! shared through a global.
+
+use crate::services::read_config;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, FixedOffset, Local, SecondsFormat, Utc};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Timestamp formatting used for the lifetime of the application
+static TIMESTAMPS: OnceLock<TimestampFormat> = OnceLock::new();
+
+/// Time zone timestamps are shown in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeZone {
+    Utc,
+    /// The zone of the machine running the application
+    Local,
+    /// A fixed offset from UTC such as `+05:30`
+    Fixed(FixedOffset),
+}
+
+impl TimeZone {
+    /// Reads `utc`, `local` or an offset like `+02:00`, `-0800` or `+05`
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        match value.to_lowercase().as_str() {
+            "utc" | "z" => return Some(Self::Utc),
+            "local" => return Some(Self::Local),
+            _ => {}
+        }
+
+        let (sign, digits) = match value.as_bytes().first()? {
+            b'+' => (1, &value[1..]),
+            b'-' => (-1, &value[1..]),
+            _ => return None,
+        };
+        let digits = digits.replace(':', "");
+        if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        let (hours, minutes) = match digits.len() {
+            2 => (digits.parse::<i32>().ok()?, 0),
+            4 => (
+                digits[..2].parse::<i32>().ok()?,
+                digits[2..].parse::<i32>().ok()?,
+            ),
+            _ => return None,
+        };
+        if minutes >= 60 {
+            return None;
+        }
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).map(Self::Fixed)
+    }
+}
+
+/// Time zone and format timestamps are shown with
+#[derive(Debug, Clone)]
+pub struct TimestampFormat {
+    zone: TimeZone,
+    /// Parsed strftime format, RFC 3339 when `None`
+    items: Option<Vec<Item<'static>>>,
+}
+
+impl Default for TimestampFormat {
+    /// RFC 3339 in UTC, matching what AWS itself reports
+    fn default() -> Self {
+        Self {
+            zone: TimeZone::Utc,
+            items: None,
+        }
+    }
+}
+
+impl TimestampFormat {
+    /// Builds the formatting from the `[timestamps]` config settings
+    ///
+    /// `timezone` takes `utc`, `local` or a fixed offset like `+02:00`, and `format` a
+    /// strftime-style format such as `%Y-%m-%d %H:%M:%S %Z`. Invalid values are ignored.
+    pub fn from_settings(settings: &HashMap<String, String>) -> Self {
+        let zone = settings
+            .get("timezone")
+            .and_then(|zone| TimeZone::parse(zone))
+            .unwrap_or(TimeZone::Utc);
+        let items = settings
+            .get("format")
+            .filter(|format| !format.trim().is_empty())
+            .and_then(|format| StrftimeItems::new(format).parse_to_owned().ok());
+        Self { zone, items }
+    }
+
+    /// Formats a point in time in the configured zone and format
+    pub fn format(&self, time: DateTime<Utc>) -> String {
+        self.format_in_zone(time, self.items.as_deref())
+    }
+
+    /// Formats a point in time in the configured zone but with a fixed strftime format,
+    /// for places with little room such as chart labels
+    pub fn format_with(&self, time: DateTime<Utc>, format: &str) -> String {
+        let items = StrftimeItems::new(format).parse().unwrap_or_default();
+        self.format_in_zone(time, Some(&items))
+    }
+
+    /// Formats milliseconds since the Unix epoch, as CloudWatch reports them
+    pub fn format_millis(&self, millis: i64) -> Option<String> {
+        DateTime::from_timestamp_millis(millis).map(|time| self.format(time))
+    }
+
+    /// Formats a timestamp from the AWS SDK, as S3 reports them
+    pub fn format_aws(&self, time: &aws_smithy_types::DateTime) -> Option<String> {
+        DateTime::from_timestamp(time.secs(), time.subsec_nanos()).map(|time| self.format(time))
+    }
+
+    fn format_in_zone(&self, time: DateTime<Utc>, items: Option<&[Item<'_>]>) -> String {
+        match self.zone {
+            TimeZone::Utc => format_in(time, items),
+            TimeZone::Local => format_in(time.with_timezone(&Local), items),
+            TimeZone::Fixed(offset) => format_in(time.with_timezone(&offset), items),
+        }
+    }
+}
+
+/// Formats with the parsed strftime items, or as RFC 3339 without any
+fn format_in<Tz: chrono::TimeZone>(time: DateTime<Tz>, items: Option<&[Item<'_>]>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    match items {
+        Some(items) => time.format_with_items(items.iter()).to_string(),
+        None => time.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+    }
+}
+
+/// Returns the active timestamp formatting, loading it from the config file on first use
+pub fn timestamps() -> &'static TimestampFormat {
+    TIMESTAMPS
+        .get_or_init(|| TimestampFormat::from_settings(&read_config::get_timestamp_settings()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-03-01 12:30:00.250 UTC
+    const EPOCH_MILLIS: i64 = 1_709_296_200_250;
+
+    fn format_with(zone: &str, format: &str) -> String {
+        let settings = HashMap::from([
+            ("timezone".to_string(), zone.to_string()),
+            ("format".to_string(), format.to_string()),
+        ]);
+        TimestampFormat::from_settings(&settings)
+            .format_millis(EPOCH_MILLIS)
+            .unwrap()
+    }
+
+    #[test]
+    fn defaults_to_rfc3339_in_utc() {
+        let default = TimestampFormat::default();
+        assert_eq!(
+            default.format_millis(EPOCH_MILLIS).unwrap(),
+            "2024-03-01T12:30:00.250Z"
+        );
+        assert_eq!(
+            default
+                .format_aws(&aws_smithy_types::DateTime::from_secs(1_709_296_200))
+                .unwrap(),
+            "2024-03-01T12:30:00Z"
+        );
+        // Unreadable settings fall back to the default
+        assert_eq!(
+            format_with("Mars/Olympus", "%Q"),
+            "2024-03-01T12:30:00.250Z"
+        );
+    }
+
+    #[test]
+    fn converts_to_fixed_offsets_and_custom_formats() {
+        assert_eq!(format_with("+05:30", ""), "2024-03-01T18:00:00.250+05:30");
+        assert_eq!(
+            format_with("-0800", "%Y-%m-%d %H:%M:%S %:z"),
+            "2024-03-01 04:30:00 -08:00"
+        );
+        assert_eq!(format_with("utc", "%d %b %Y %H:%M"), "01 Mar 2024 12:30");
+
+        let settings = HashMap::from([("timezone".to_string(), "+02:00".to_string())]);
+        let time = DateTime::from_timestamp_millis(EPOCH_MILLIS).unwrap();
+        assert_eq!(
+            TimestampFormat::from_settings(&settings).format_with(time, "%H:%M"),
+            "14:30"
+        );
+        assert_eq!(
+            TimeZone::parse("+14"),
+            FixedOffset::east_opt(14 * 3600).map(TimeZone::Fixed)
+        );
+        assert_eq!(TimeZone::parse("+02:75"), None);
+        assert_eq!(TimeZone::parse("Europe/Berlin"), None);
+    }
+}
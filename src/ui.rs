@@ -4,7 +4,15 @@
 //! enabling the application to be rendered to the terminal.
 
 use crate::app::App;
-use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use crate::components::tab::{MIN_HEIGHT, MIN_WIDTH};
+use crate::theme::theme;
+use crate::widgets::WidgetExt;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Style,
+    widgets::{Paragraph, Widget, Wrap},
+};
 
 /// Implements the Widget trait for App references
 ///
@@ -13,18 +21,74 @@ use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
 impl Widget for &App {
     /// Renders the entire application UI
     ///
-    /// Collects tab names and delegates rendering to the active tab
+    /// Collects tab titles and delegates rendering to the active tab
     fn render(self, area: Rect, buf: &mut Buffer) {
-        // Collect the names of all tabs for the tab bar
-        let all_tabs_names = self
-            .tabs
-            .iter()
-            .map(|t| t.name.to_string())
-            .collect::<Vec<String>>();
+        // The tab layout does not fit, so say so instead of drawing a broken one
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            render_too_small(area, buf);
+            return;
+        }
+
+        // Collect the titles of all tabs for the tab bar
+        let all_tabs_names = self.tabs.iter().map(|t| t.title()).collect::<Vec<String>>();
 
         // Render the currently active tab with the full area
         if let Some(active_tab) = self.tabs.get(self.active_tab) {
             active_tab.render(area, buf, all_tabs_names, self.active_tab);
         }
+
+        // Overlays float above whichever tab is active
+        if let Some(overlay) = &self.overlay {
+            overlay.render(area, buf);
+        }
+    }
+}
+
+/// Renders a centered message asking for a larger terminal
+fn render_too_small(area: Rect, buf: &mut Buffer) {
+    let message = format!(
+        "Terminal too small (need at least {}×{})",
+        MIN_WIDTH, MIN_HEIGHT
+    );
+    // Narrow terminals wrap the message, so it is centered on the rows it takes
+    let rows = (message.chars().count() as u16)
+        .div_ceil(area.width.max(1))
+        .min(area.height);
+    let message_area = Rect::new(
+        area.x,
+        area.y.saturating_add(area.height.saturating_sub(rows) / 2),
+        area.width,
+        rows,
+    );
+    Paragraph::new(message)
+        .style(Style::default().fg(theme().warning_fg))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .render(message_area, buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_small_message_is_centered() {
+        let area = Rect::new(0, 0, 60, 5);
+        let mut buf = Buffer::empty(area);
+        render_too_small(area, &mut buf);
+
+        let row: String = (0..area.width)
+            .map(|x| buf[(x, 2)].symbol().to_string())
+            .collect();
+        assert_eq!(
+            row.trim(),
+            format!("Terminal too small (need at least {MIN_WIDTH}×{MIN_HEIGHT})")
+        );
+
+        // Even a terminal with no room at all is handled
+        let area = Rect::new(0, 0, 0, 0);
+        render_too_small(area, &mut Buffer::empty(area));
+        let area = Rect::new(0, 0, 3, 1);
+        render_too_small(area, &mut Buffer::empty(area));
     }
 }
@@ -0,0 +1,99 @@
+//! Clipboard helper module
+//!
+//! Shares a single system clipboard context between widgets. When there is no system
+//! clipboard (e.g. Wayland without X11, SSH or headless sessions), copies fall back to
+//! the OSC 52 escape sequence where enabled, which asks the terminal itself to set its
+//! clipboard. Pasting needs the system clipboard. Failures are reported as
+//! [`ClipboardUnavailable`] so widgets can tell the user instead of doing nothing.
+
+use crate::services::read_config;
+use clipboard::{ClipboardContext, ClipboardProvider};
+use std::io::Write;
+use std::sync::{Mutex, OnceLock};
+use thiserror::Error;
+
+/// Lazily initialized clipboard, `None` if the system clipboard is unavailable
+static CLIPBOARD: OnceLock<Mutex<Option<ClipboardContext>>> = OnceLock::new();
+
+/// Characters of the standard base64 alphabet OSC 52 payloads are encoded with
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Neither the system clipboard nor a terminal fallback could be used
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+#[error("Clipboard unavailable in this environment")]
+pub struct ClipboardUnavailable;
+
+/// Runs the given operation against the shared clipboard if it is available
+fn with_clipboard<T>(operation: impl FnOnce(&mut ClipboardContext) -> Option<T>) -> Option<T> {
+    let clipboard = CLIPBOARD.get_or_init(|| Mutex::new(ClipboardProvider::new().ok()));
+    let mut guard = clipboard.lock().ok()?;
+    guard.as_mut().and_then(operation)
+}
+
+/// Copies text to the system clipboard, or to the terminal's clipboard with OSC 52
+/// when there is no system clipboard and the fallback is enabled
+pub fn copy_text(text: &str) -> Result<(), ClipboardUnavailable> {
+    if with_clipboard(|ctx| ctx.set_contents(text.to_string()).ok()).is_some() {
+        return Ok(());
+    }
+    if read_config::get_osc52_clipboard() {
+        let mut stdout = std::io::stdout();
+        return stdout
+            .write_all(osc52_sequence(text).as_bytes())
+            .and_then(|_| stdout.flush())
+            .map_err(|_| ClipboardUnavailable);
+    }
+    Err(ClipboardUnavailable)
+}
+
+/// Returns the current text contents of the system clipboard, empty when it holds no text
+pub fn paste_text() -> Result<String, ClipboardUnavailable> {
+    with_clipboard(|ctx| Some(ctx.get_contents().unwrap_or_default())).ok_or(ClipboardUnavailable)
+}
+
+/// Escape sequence asking the terminal to put the text on its clipboard
+fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+/// Encodes bytes as padded standard base64
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let block = chunk.iter().enumerate().fold(0u32, |block, (i, byte)| {
+            block | (*byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (block >> (18 - 6 * i)) & 0x3f;
+                encoded.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_base64_with_padding() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(
+            base64_encode(b"arn:aws:s3:::bucket"),
+            "YXJuOmF3czpzMzo6OmJ1Y2tldA=="
+        );
+    }
+
+    #[test]
+    fn osc52_sequence_targets_the_clipboard() {
+        assert_eq!(osc52_sequence("hi"), "\x1b]52;c;aGk=\x07");
+    }
+}
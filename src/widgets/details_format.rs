@@ -0,0 +1,323 @@
+//! Details format module
+//!
+//! Tells JSON, XML and YAML details apart from plain text for the details popup, and lays
+//! the text out in rows that fit the popup: XML is indented, keys and tags are colored and
+//! long lines are wrapped while their line breaks are kept.
+
+use crate::theme::theme;
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+use serde_json::Value;
+
+/// Columns a tab is expanded to
+const TAB_WIDTH: usize = 4;
+
+/// Kind of text shown in the details popup
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DetailsFormat {
+    /// A JSON object or array, optionally after a `[timestamp]`
+    Json,
+    /// An XML document or fragment
+    Xml,
+    /// YAML mappings or lists, as config values often are
+    Yaml,
+    /// Anything else, such as a log line
+    Text,
+}
+
+impl DetailsFormat {
+    /// Detects XML or YAML in details that are not JSON
+    pub fn detect_markup(content: &str) -> Self {
+        let trimmed = content.trim();
+        if trimmed.starts_with('<')
+            && trimmed.ends_with('>')
+            && (trimmed.contains("</") || trimmed.contains("/>"))
+        {
+            return Self::Xml;
+        }
+        if looks_like_yaml(trimmed) {
+            return Self::Yaml;
+        }
+        Self::Text
+    }
+}
+
+/// Parses details as a JSON object or array, skipping a leading `[timestamp]` if present
+///
+/// Only details that are JSON as a whole count, so a log line that merely contains braces,
+/// e.g. `ERROR [db] {id} not found`, stays text.
+pub fn parse_json(content: &str) -> Option<Value> {
+    let content = content.trim();
+    let structured = |text: &str| {
+        serde_json::from_str::<Value>(text)
+            .ok()
+            .filter(|json| json.is_object() || json.is_array())
+    };
+    if let Some(json) = structured(content) {
+        return Some(json);
+    }
+    // A log line like `[2024-03-01 12:30:00] {"level":"info"}`
+    let rest = content.strip_prefix('[')?;
+    let (_, body) = rest.split_once(']')?;
+    let body = body.trim_start();
+    if body.starts_with('{') || body.starts_with('[') {
+        structured(body)
+    } else {
+        None
+    }
+}
+
+/// Whether every line is a YAML key, list item, comment or continuation, with at least
+/// one key, across more than one line
+fn looks_like_yaml(content: &str) -> bool {
+    let lines: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    if content.starts_with("---") {
+        return true;
+    }
+    if lines.len() < 2 {
+        return false;
+    }
+    let mut keys = 0;
+    for line in &lines {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') {
+            continue;
+        }
+        let item = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+        if yaml_key(item).is_some() {
+            keys += 1;
+        } else if !(trimmed.starts_with("- ") || line.starts_with(' ')) {
+            return false;
+        }
+    }
+    keys > 0
+}
+
+/// Length of the key in a `key: value` or `key:` YAML line
+fn yaml_key(line: &str) -> Option<usize> {
+    let end = line
+        .find(": ")
+        .or_else(|| line.ends_with(':').then(|| line.len() - 1))?;
+    let key = &line[..end];
+    let quoted = key.len() >= 2
+        && (key.starts_with('"') && key.ends_with('"')
+            || key.starts_with('\'') && key.ends_with('\''));
+    let plain = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/'));
+    (quoted || plain).then_some(end)
+}
+
+/// Puts each XML tag on its own line, indented by depth; an element holding only text
+/// stays on one line
+pub fn indent_xml(content: &str) -> String {
+    let mut tokens = Vec::new();
+    let mut rest = content.trim();
+    while !rest.is_empty() {
+        let end = if rest.starts_with('<') {
+            rest.find('>').map_or(rest.len(), |end| end + 1)
+        } else {
+            rest.find('<').unwrap_or(rest.len())
+        };
+        let token = rest[..end].trim();
+        if !token.is_empty() {
+            tokens.push(token);
+        }
+        rest = &rest[end..];
+    }
+
+    let mut lines = Vec::new();
+    let mut depth = 0usize;
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = tokens[index];
+        let indent = "  ".repeat(depth);
+        if token.starts_with("</") {
+            depth = depth.saturating_sub(1);
+            lines.push(format!("{}{}", "  ".repeat(depth), token));
+        } else if !token.starts_with('<')
+            || token.ends_with("/>")
+            || token.starts_with("<?")
+            || token.starts_with("<!")
+        {
+            lines.push(format!("{}{}", indent, token));
+        } else if let (Some(text), Some(close)) = (tokens.get(index + 1), tokens.get(index + 2))
+            && !text.starts_with('<')
+            && close.starts_with("</")
+        {
+            lines.push(format!("{}{}{}{}", indent, token, text, close));
+            index += 2;
+        } else {
+            lines.push(format!("{}{}", indent, token));
+            depth += 1;
+        }
+        index += 1;
+    }
+    lines.join("\n")
+}
+
+/// Splits a line into rows of at most `width` characters, breaking after a space where
+/// there is one; a `width` of 0 leaves the line whole
+pub fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    if width == 0 || chars.len() <= width {
+        return vec![line.to_string()];
+    }
+    let mut rows = Vec::new();
+    let mut start = 0;
+    while chars.len() - start > width {
+        let chunk = &chars[start..start + width];
+        // Break after the last space, unless that leaves a very short row
+        let end = match chunk.iter().rposition(|c| *c == ' ') {
+            Some(space) if space >= width / 2 => start + space + 1,
+            _ => start + width,
+        };
+        rows.push(chars[start..end].iter().collect());
+        start = end;
+    }
+    rows.push(chars[start..].iter().collect());
+    rows
+}
+
+/// Lays out details text in rows fitting `width` columns, keeping its line breaks and
+/// coloring YAML keys, XML tags and comments
+pub fn render_rows(content: &str, format: DetailsFormat, width: usize) -> Vec<Line<'static>> {
+    let key_style = Style::default()
+        .fg(theme().title_fg)
+        .add_modifier(Modifier::BOLD);
+    let comment_style = Style::default().fg(theme().help_fg);
+
+    let mut rows = Vec::new();
+    for line in content.lines() {
+        let line = line.replace('\r', "").replace('\t', &" ".repeat(TAB_WIDTH));
+        let mut in_tag = false;
+        for (index, row) in wrap_line(&line, width).into_iter().enumerate() {
+            rows.push(match format {
+                DetailsFormat::Yaml if index == 0 => yaml_row(row, &line, key_style, comment_style),
+                DetailsFormat::Xml => xml_row(&row, &mut in_tag, key_style),
+                _ => Line::raw(row),
+            });
+        }
+    }
+    rows
+}
+
+/// First row of a YAML line, its key or the whole comment colored
+fn yaml_row(row: String, line: &str, key_style: Style, comment_style: Style) -> Line<'static> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('#') {
+        return Line::styled(row, comment_style);
+    }
+    let item = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+    let key_start = line.len() - item.len();
+    match yaml_key(item) {
+        Some(key_len) if key_start + key_len <= row.len() => {
+            let key_end = key_start + key_len;
+            Line::from(vec![
+                Span::raw(row[..key_start].to_string()),
+                Span::styled(row[key_start..key_end].to_string(), key_style),
+                Span::raw(row[key_end..].to_string()),
+            ])
+        }
+        _ => Line::raw(row),
+    }
+}
+
+/// Row of XML with its tags colored; `in_tag` carries a tag on into the next row
+fn xml_row(row: &str, in_tag: &mut bool, tag_style: Style) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    for c in row.chars() {
+        if c == '<' && !*in_tag {
+            if !current.is_empty() {
+                spans.push(Span::raw(std::mem::take(&mut current)));
+            }
+            *in_tag = true;
+        }
+        current.push(c);
+        if c == '>' && *in_tag {
+            spans.push(Span::styled(std::mem::take(&mut current), tag_style));
+            *in_tag = false;
+        }
+    }
+    if !current.is_empty() {
+        spans.push(if *in_tag {
+            Span::styled(current, tag_style)
+        } else {
+            Span::raw(current)
+        });
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_lines_with_braces_are_not_json() {
+        assert_eq!(parse_json("ERROR [db] {\"id\": 1} not found"), None);
+        assert_eq!(parse_json("WARN [worker] {id} retried"), None);
+        assert_eq!(parse_json("[2024-03-01] handler {user} done"), None);
+        assert_eq!(parse_json("[main] {\"id\": 1} trailing"), None);
+        assert_eq!(parse_json("42"), None);
+        assert_eq!(
+            DetailsFormat::detect_markup("ERROR [db] {\"id\": 1} not found"),
+            DetailsFormat::Text
+        );
+
+        assert_eq!(
+            parse_json("[2024-03-01 12:30:00] {\"level\": \"info\"}"),
+            Some(serde_json::json!({"level": "info"}))
+        );
+        assert_eq!(parse_json(" [1, 2] "), Some(serde_json::json!([1, 2])));
+    }
+
+    #[test]
+    fn xml_and_yaml_are_told_apart_from_text() {
+        assert_eq!(
+            DetailsFormat::detect_markup("<a><b>1</b></a>"),
+            DetailsFormat::Xml
+        );
+        assert_eq!(
+            DetailsFormat::detect_markup("name: api\nports:\n  - 80\n# public\n"),
+            DetailsFormat::Yaml
+        );
+        assert_eq!(
+            DetailsFormat::detect_markup("Started: 12:00\nsomething went wrong"),
+            DetailsFormat::Text
+        );
+        assert_eq!(
+            DetailsFormat::detect_markup("Status: ok"),
+            DetailsFormat::Text
+        );
+    }
+
+    #[test]
+    fn xml_is_indented_by_depth() {
+        assert_eq!(
+            indent_xml("<?xml version=\"1.0\"?><a><b>1</b><c/><d><e>x</e></d></a>"),
+            "<?xml version=\"1.0\"?>\n<a>\n  <b>1</b>\n  <c/>\n  <d>\n    <e>x</e>\n  </d>\n</a>"
+        );
+    }
+
+    #[test]
+    fn long_lines_wrap_and_line_breaks_are_kept() {
+        assert_eq!(
+            wrap_line("connection reset by peer", 12),
+            ["connection ", "reset by ", "peer"]
+        );
+        assert_eq!(wrap_line("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(wrap_line("short", 0), ["short"]);
+
+        let rows = render_rows("first\n\n\tindented", DetailsFormat::Text, 80);
+        let text: Vec<String> = rows.iter().map(|row| row.to_string()).collect();
+        assert_eq!(text, ["first", "", "    indented"]);
+    }
+}
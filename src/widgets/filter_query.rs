@@ -0,0 +1,156 @@
+//! Filter query module
+//!
+//! Parses navigator filters into a predicate. Space-separated terms must all match,
+//! a leading `-` negates a term and `|` separates alternatives, so
+//! `error -healthcheck | timeout` keeps errors that are not health checks, and timeouts.
+//! A backslash makes the next character literal, e.g. `\-1` or `a\|b`.
+//! Matching is a case-insensitive substring match per term.
+
+/// A single term of a filter group
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    text: String,
+    negated: bool,
+}
+
+impl Term {
+    fn matches(&self, text: &str) -> bool {
+        text.contains(&self.text) != self.negated
+    }
+}
+
+/// Parsed filter: records match if every term of at least one group matches
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterQuery {
+    groups: Vec<Vec<Term>>,
+}
+
+/// Accumulates the characters of the term being parsed
+#[derive(Default)]
+struct TermBuilder {
+    text: String,
+    negated: bool,
+}
+
+impl TermBuilder {
+    /// Completes the current term, ignoring a bare `-` or nothing at all
+    fn finish(&mut self, group: &mut Vec<Term>) {
+        let builder = std::mem::take(self);
+        if !builder.text.is_empty() {
+            group.push(Term {
+                text: builder.text.to_lowercase(),
+                negated: builder.negated,
+            });
+        }
+    }
+}
+
+impl FilterQuery {
+    /// Parses a filter string; plain text without operators behaves like a substring filter
+    pub fn parse(input: &str) -> Self {
+        let mut groups = Vec::new();
+        let mut group = Vec::new();
+        let mut term = TermBuilder::default();
+        let mut chars = input.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                // A trailing backslash has nothing to escape and is kept as is
+                '\\' => term.text.push(chars.next().unwrap_or('\\')),
+                '|' => {
+                    term.finish(&mut group);
+                    groups.push(std::mem::take(&mut group));
+                }
+                c if c.is_whitespace() => term.finish(&mut group),
+                '-' if term.text.is_empty() && !term.negated => term.negated = true,
+                c => term.text.push(c),
+            }
+        }
+        term.finish(&mut group);
+        groups.push(group);
+
+        // Alternatives still being typed (e.g. `error |`) must not match everything
+        groups.retain(|group| !group.is_empty());
+        Self { groups }
+    }
+
+    /// Returns whether the query has no terms and therefore matches everything
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Returns whether the text satisfies the query
+    pub fn matches(&self, text: &str) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let text = text.to_lowercase();
+        self.groups
+            .iter()
+            .any(|group| group.iter().all(|term| term.matches(&text)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(filter: &str, text: &str) -> bool {
+        FilterQuery::parse(filter).matches(text)
+    }
+
+    #[test]
+    fn plain_text_is_a_case_insensitive_substring_match() {
+        assert!(matches("Err", "an ERROR occurred"));
+        assert!(!matches("warn", "an ERROR occurred"));
+        assert!(matches("", "anything"));
+        assert!(matches("my-bucket", "prod-my-bucket"));
+    }
+
+    #[test]
+    fn space_separated_terms_are_anded() {
+        assert!(matches("error db", "db error: connection lost"));
+        assert!(!matches("error db", "cache error"));
+    }
+
+    #[test]
+    fn leading_dash_negates_a_term() {
+        assert!(matches("-healthcheck", "GET /orders"));
+        assert!(!matches("-healthcheck", "GET /healthcheck"));
+        assert!(matches("error -healthcheck", "error in /orders"));
+        assert!(!matches("error -healthcheck", "error in /healthcheck"));
+    }
+
+    #[test]
+    fn pipe_separates_alternatives() {
+        assert!(matches("error | timeout", "request timeout"));
+        assert!(matches("error|timeout", "fatal error"));
+        assert!(!matches("error | timeout", "all good"));
+    }
+
+    #[test]
+    fn operators_combine() {
+        let filter = "error -healthcheck | timeout";
+        assert!(matches(filter, "error in /orders"));
+        assert!(!matches(filter, "error in /healthcheck"));
+        assert!(matches(filter, "healthcheck timeout"));
+        assert!(!matches(filter, "ok"));
+    }
+
+    #[test]
+    fn incomplete_operators_are_ignored() {
+        assert!(matches("error -", "error"));
+        assert!(matches("error |", "error"));
+        assert!(!matches("error |", "other"));
+        assert!(matches("|", "anything"));
+    }
+
+    #[test]
+    fn backslash_escapes_operators() {
+        assert!(matches("\\-1", "balance -1"));
+        assert!(!matches("\\-1", "balance 1"));
+        assert!(matches("a\\|b", "a|b"));
+        assert!(!matches("a\\|b", "a"));
+        assert!(matches("\\\\", "C:\\path"));
+    }
+}
@@ -2,31 +2,111 @@
 //!
 //! Provides a text input box with clipboard support and cursor positioning.
 //! Used for search queries, filters, and other text input needs.
+//! Input boxes can keep a history of submitted text, recalled with Up/Down like a
+//! shell and saved per AWS profile.
 
+use crate::diagnostics;
 use crate::event_managment::event::{InputBoxEvent, InputBoxType, WidgetAction};
+use crate::services::read_config;
+use crate::theme::theme;
 use crate::widgets::WidgetExt;
-use clipboard::{ClipboardContext, ClipboardProvider};
+use crate::widgets::clipboard::{self, ClipboardUnavailable};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Alignment;
 use ratatui::widgets::{Borders, Wrap};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style},
+    style::Style,
     text::Line,
     widgets::{Block, BorderType, Paragraph, Widget},
 };
 use std::any::Any;
 
+/// Submitted inputs kept per input box
+const HISTORY_CAPACITY: usize = 20;
+
+/// Submitted inputs of an input box, oldest first
+struct InputHistory {
+    name: &'static str,      // Key of this history in the saved state
+    profile: Option<String>, // Profile the entries are saved for, nothing is saved until set
+    entries: Vec<String>,
+    position: Option<usize>, // Entry currently recalled, `None` while editing
+    draft: String,           // Text typed before recalling, restored past the newest entry
+}
+
+impl InputHistory {
+    /// Adds a submission as the newest entry, dropping an older copy and the oldest entries
+    fn record(&mut self, entry: &str) {
+        self.position = None;
+        if entry.trim().is_empty() {
+            return;
+        }
+        self.entries.retain(|existing| existing != entry);
+        self.entries.push(entry.to_string());
+        let excess = self.entries.len().saturating_sub(HISTORY_CAPACITY);
+        self.entries.drain(..excess);
+        self.save();
+    }
+
+    /// Writes the entries to the state file of the current profile
+    fn save(&self) {
+        if let Some(profile) = &self.profile
+            && let Err(err) = read_config::save_input_history(profile, self.name, &self.entries)
+        {
+            diagnostics::warn(format!("Saving {} history failed: {}", self.name, err));
+        }
+    }
+}
+
+/// Returns the byte offset of the char boundary before `pos`
+fn previous_char_boundary(text: &str, pos: usize) -> usize {
+    text[..pos].char_indices().next_back().map_or(0, |(i, _)| i)
+}
+
+/// Returns the byte offset of the char boundary after `pos`
+fn next_char_boundary(text: &str, pos: usize) -> usize {
+    text[pos..]
+        .chars()
+        .next()
+        .map_or(pos, |c| pos + c.len_utf8())
+}
+
+/// Returns the start of the word before `pos`, skipping any whitespace in between
+fn previous_word_boundary(text: &str, pos: usize) -> usize {
+    let mut chars = text[..pos].char_indices().rev().peekable();
+    // Skip whitespace directly left of the cursor
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    // Then skip the word itself
+    let mut start = chars.peek().map_or(0, |(i, _)| *i);
+    while let Some((i, _)) = chars.next_if(|(_, c)| !c.is_whitespace()) {
+        start = i;
+    }
+    start
+}
+
+/// Returns the start of the word after `pos`, or the end of the text
+fn next_word_boundary(text: &str, pos: usize) -> usize {
+    let mut chars = text[pos..].char_indices().peekable();
+    // Skip the rest of the current word
+    while chars.next_if(|(_, c)| !c.is_whitespace()).is_some() {}
+    // Then skip whitespace up to the next word
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    chars.peek().map_or(text.len(), |(i, _)| pos + i)
+}
+
 /// Widget for text input with cursor positioning and clipboard integration
 pub struct InputBoxWidget {
-    input_type: InputBoxType,            // Type of widget (e.g., InputBox)
-    content: String,                     // Current text content
-    cursor_position: usize,              // Position of cursor within the text
-    active: bool,                        // Whether this widget has input focus
-    visible: bool,                       // Whether this widget should be rendered
-    title: String,                       // Title displayed in the border
-    clipboard: Option<ClipboardContext>, // Clipboard access for copy/paste
+    input_type: InputBoxType,      // Type of widget (e.g., InputBox)
+    content: String,               // Current text content
+    cursor_position: usize,        // Byte offset of the cursor, always on a char boundary
+    active: bool,                  // Whether this widget has input focus
+    visible: bool,                 // Whether this widget should be rendered
+    title: String,                 // Title displayed in the border
+    history: Option<InputHistory>, // Submitted inputs for Up/Down recall, if enabled
+    disabled: bool,                // Greyed out and ignoring edits, e.g. a field that does not apply
+    completes_on_tab: bool,        // Whether Tab asks for completion instead of moving focus
+    paste_line_separator: Option<&'static str>, // Joins pasted lines; `None` keeps line breaks
 }
 
 impl InputBoxWidget {
@@ -39,24 +119,134 @@ impl InputBoxWidget {
             active,
             visible: true,
             title: title.to_string(),
-            clipboard: ClipboardProvider::new().ok(), // Initialize clipboard or None if unavailable
+            history: None,
+            disabled: false,
+            completes_on_tab: false,
+            paste_line_separator: Some(" "),
         }
     }
 
-    /// Pastes text from the system clipboard at the current cursor position
-    fn paste_from_clipboard(&mut self) {
-        if let Some(ref mut ctx) = self.clipboard {
-            if let Ok(contents) = ctx.get_contents() {
-                self.content.insert_str(self.cursor_position, &contents);
-                self.cursor_position += contents.len();
+    /// Sets what pasted lines are joined with, `None` to keep line breaks in an input that
+    /// may span lines; single-line inputs join them with a space by default
+    pub fn join_pasted_lines_with(&mut self, separator: Option<&'static str>) {
+        self.paste_line_separator = separator;
+    }
+
+    /// Makes Tab ask the component to complete the text, which then handles Tab itself
+    pub fn enable_tab_completion(&mut self) {
+        self.completes_on_tab = true;
+    }
+
+    /// Returns whether Tab asks for completion rather than moving focus
+    pub fn completes_on_tab(&self) -> bool {
+        self.completes_on_tab && !self.disabled
+    }
+
+    /// Keeps submitted text for Up/Down recall, saved under `name` once a profile is loaded
+    pub fn enable_history(&mut self, name: &'static str) {
+        self.history = Some(InputHistory {
+            name,
+            profile: None,
+            entries: Vec::new(),
+            position: None,
+            draft: String::new(),
+        });
+    }
+
+    /// Switches the history to the entries saved for the profile
+    pub fn load_history(&mut self, profile: &str) {
+        if let Some(history) = &mut self.history {
+            history.entries = read_config::get_input_history(profile, history.name);
+            history.profile = Some(profile.to_string());
+            history.position = None;
+        }
+    }
+
+    /// Whether Up recalls history: the input is empty or already showing an entry
+    fn can_recall(&self) -> bool {
+        self.history.as_ref().is_some_and(|history| {
+            !history.entries.is_empty() && (self.content.is_empty() || history.position.is_some())
+        })
+    }
+
+    /// Shows the entry before the recalled one, starting from the newest
+    fn recall_previous(&mut self) {
+        let Some(history) = &mut self.history else {
+            return;
+        };
+        let position = match history.position {
+            Some(position) => position.saturating_sub(1),
+            None if history.entries.is_empty() => return,
+            None => {
+                history.draft = self.content.clone();
+                history.entries.len() - 1
             }
+        };
+        history.position = Some(position);
+        self.content = history.entries[position].clone();
+        self.cursor_position = self.content.len();
+    }
+
+    /// Shows the entry after the recalled one, or the draft past the newest
+    fn recall_next(&mut self) {
+        let Some(history) = &mut self.history else {
+            return;
+        };
+        let Some(position) = history.position else {
+            return;
+        };
+        if position + 1 < history.entries.len() {
+            history.position = Some(position + 1);
+            self.content = history.entries[position + 1].clone();
+        } else {
+            history.position = None;
+            self.content = std::mem::take(&mut history.draft);
+        }
+        self.cursor_position = self.content.len();
+    }
+
+    /// Pastes text from the system clipboard at the current cursor position
+    fn paste_from_clipboard(&mut self) -> Result<(), ClipboardUnavailable> {
+        let contents = clipboard::paste_text()?;
+        self.insert_pasted(&contents);
+        Ok(())
+    }
+
+    /// Inserts pasted text at the cursor, joining its lines unless the input spans lines
+    fn insert_pasted(&mut self, text: &str) {
+        let text = match self.paste_line_separator {
+            Some(separator) => text
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .collect::<Vec<_>>()
+                .join(separator),
+            None => text.to_string(),
+        };
+        // Never split a character, even if the cursor was left inside one
+        let mut position = self.cursor_position.min(self.content.len());
+        while !self.content.is_char_boundary(position) {
+            position -= 1;
         }
+        self.content.insert_str(position, &text);
+        self.cursor_position = position + text.len();
+    }
+
+    /// Copies the current input text to the clipboard
+    fn copy_to_clipboard(&mut self) -> Result<(), ClipboardUnavailable> {
+        clipboard::copy_text(&self.content)
+    }
+
+    /// Replaces the text content, placing the cursor at the end
+    pub fn set_content(&mut self, content: &str) {
+        self.content = content.to_string();
+        self.cursor_position = self.content.len();
     }
 
-    /// Copies the current input text to the system clipboard
-    fn copy_to_clipboard(&mut self) {
-        if let Some(ref mut ctx) = self.clipboard {
-            let _ = ctx.set_contents(self.content.clone());
+    /// Greys the input out and ignores typing while disabled, clearing its text
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+        if disabled {
+            self.set_content("");
         }
     }
 
@@ -77,9 +267,11 @@ impl WidgetExt for InputBoxWidget {
             return;
         }
 
-        // Set border color based on focus state
-        let border_style = if self.active {
-            Style::default().fg(Color::Red)
+        // Set border color based on focus state, greyed out while disabled
+        let border_style = if self.disabled {
+            Style::default().fg(theme().inactive_border)
+        } else if self.active {
+            Style::default().fg(theme().active_border)
         } else {
             Style::default()
         };
@@ -93,7 +285,7 @@ impl WidgetExt for InputBoxWidget {
 
         // Create display text with cursor indicator
         let mut display_text = self.content.clone();
-        if self.active {
+        if self.active && !self.disabled {
             display_text.insert(self.cursor_position, '|');
         }
 
@@ -111,22 +303,45 @@ impl WidgetExt for InputBoxWidget {
         // if !self.active {
         //     return;
         // }
+        // A disabled input still submits, so Enter keeps working wherever focus is
+        if self.disabled && key_event.code != KeyCode::Enter {
+            return None;
+        }
         match key_event.code {
             // Clipboard operations with Ctrl modifiers
             KeyCode::Char('v') if key_event.modifiers == KeyModifiers::CONTROL => {
-                self.paste_from_clipboard();
+                if let Err(err) = self.paste_from_clipboard() {
+                    return Some(WidgetAction::Notify(err.to_string()));
+                }
                 Some(WidgetAction::InputBoxEvent(
                     InputBoxEvent::Written(self.content.clone()),
                     self.input_type.clone(),
                 ))
             }
             KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
-                self.copy_to_clipboard();
+                if let Err(err) = self.copy_to_clipboard() {
+                    return Some(WidgetAction::Notify(err.to_string()));
+                }
                 Some(WidgetAction::InputBoxEvent(
                     InputBoxEvent::Written(self.content.clone()),
                     self.input_type.clone(),
                 ))
             }
+            // Line editing: delete to start/end of the input
+            KeyCode::Char('u') if key_event.modifiers == KeyModifiers::CONTROL => Some(
+                WidgetAction::InputBoxEvent(InputBoxEvent::DeleteToStart, self.input_type.clone()),
+            ),
+            KeyCode::Char('k') if key_event.modifiers == KeyModifiers::CONTROL => Some(
+                WidgetAction::InputBoxEvent(InputBoxEvent::DeleteToEnd, self.input_type.clone()),
+            ),
+            KeyCode::Char('x')
+                if key_event.modifiers == KeyModifiers::CONTROL && self.history.is_some() =>
+            {
+                Some(WidgetAction::InputBoxEvent(
+                    InputBoxEvent::ClearHistory,
+                    self.input_type.clone(),
+                ))
+            }
             // Pass through regular character input
             KeyCode::Char(ref _c) => Some(WidgetAction::InputBoxEvent(
                 InputBoxEvent::KeyPress(key_event),
@@ -141,6 +356,21 @@ impl WidgetExt for InputBoxWidget {
                 InputBoxEvent::Delete,
                 self.input_type.clone(),
             )),
+            // Word-wise cursor movement
+            KeyCode::Left if key_event.modifiers == KeyModifiers::CONTROL => Some(
+                WidgetAction::InputBoxEvent(InputBoxEvent::WordLeft, self.input_type.clone()),
+            ),
+            KeyCode::Right if key_event.modifiers == KeyModifiers::CONTROL => Some(
+                WidgetAction::InputBoxEvent(InputBoxEvent::WordRight, self.input_type.clone()),
+            ),
+            KeyCode::Home => Some(WidgetAction::InputBoxEvent(
+                InputBoxEvent::Home,
+                self.input_type.clone(),
+            )),
+            KeyCode::End => Some(WidgetAction::InputBoxEvent(
+                InputBoxEvent::End,
+                self.input_type.clone(),
+            )),
             // Cursor movement
             KeyCode::Left => Some(WidgetAction::InputBoxEvent(
                 InputBoxEvent::Left,
@@ -150,6 +380,26 @@ impl WidgetExt for InputBoxWidget {
                 InputBoxEvent::Right,
                 self.input_type.clone(),
             )),
+            // History recall, only while the input is empty or showing a recalled entry
+            KeyCode::Up if self.can_recall() => Some(WidgetAction::InputBoxEvent(
+                InputBoxEvent::ArrowUp,
+                self.input_type.clone(),
+            )),
+            KeyCode::Down
+                if self
+                    .history
+                    .as_ref()
+                    .is_some_and(|history| history.position.is_some()) =>
+            {
+                Some(WidgetAction::InputBoxEvent(
+                    InputBoxEvent::ArrowDown,
+                    self.input_type.clone(),
+                ))
+            }
+            KeyCode::Tab if self.completes_on_tab => Some(WidgetAction::InputBoxEvent(
+                InputBoxEvent::Complete,
+                self.input_type.clone(),
+            )),
             // Submit content
             KeyCode::Enter => Some(WidgetAction::InputBoxEvent(
                 InputBoxEvent::Enter,
@@ -188,67 +438,44 @@ impl WidgetExt for InputBoxWidget {
     /// Processes input events and modifies content accordingly
     fn process_event(&mut self, event: WidgetAction) -> Option<WidgetAction> {
         match event {
-            WidgetAction::InputBoxEvent(input_event, _) => match input_event {
-                // Add character at cursor position
-                InputBoxEvent::KeyPress(key_event) => {
-                    if let KeyCode::Char(c) = key_event.code {
-                        self.content.insert(self.cursor_position, c);
-                        self.cursor_position += 1;
-                    }
-                    None
-                }
-                // Delete character to the left of cursor
-                InputBoxEvent::Backspace => {
-                    if self.cursor_position > 0 {
-                        self.cursor_position -= 1;
-                        self.content.remove(self.cursor_position);
-                    }
-                    None
+            WidgetAction::InputBoxEvent(input_event, _) => {
+                // Editing a recalled entry turns it into a new draft
+                if let Some(history) = &mut self.history
+                    && matches!(
+                        input_event,
+                        InputBoxEvent::KeyPress(_)
+                            | InputBoxEvent::Backspace
+                            | InputBoxEvent::Delete
+                            | InputBoxEvent::DeleteToStart
+                            | InputBoxEvent::DeleteToEnd
+                    )
+                {
+                    history.position = None;
                 }
-                // Delete character under cursor
-                InputBoxEvent::Delete => {
-                    if self.cursor_position < self.content.len() {
-                        self.content.remove(self.cursor_position);
-                    }
-                    None
-                }
-                // Move cursor left
-                InputBoxEvent::Left => {
-                    if self.cursor_position > 0 {
-                        self.cursor_position -= 1;
-                    }
-                    None
-                }
-                // Move cursor right
-                InputBoxEvent::Right => {
-                    if self.cursor_position < self.content.len() {
-                        self.cursor_position += 1;
-                    }
-                    None
-                }
-                // Submit current content
-                InputBoxEvent::Enter => {
-                    // Handle enter key event
-                    // For example, you can send the content to an event sender or process it
-                    Some(WidgetAction::InputBoxEvent(
-                        InputBoxEvent::Written(self.content.clone()),
-                        self.input_type.clone(),
-                    ))
-                }
-                _ => None,
-            },
+                self.apply_input_event(input_event)
+            }
             _ => None,
         }
     }
 
     /// Returns available keyboard shortcuts for the help toolbar
     fn get_help_items(&self) -> Vec<(String, String)> {
-        vec![
+        let mut items = vec![
             ("Ctrl+V".to_string(), "Paste".to_string()),
             ("Ctrl+C".to_string(), "Copy".to_string()),
-            ("Enter".to_string(), "Submit".to_string()),
-            ("Esc".to_string(), "Close".to_string()),
-        ]
+            ("Ctrl+←/→".to_string(), "Word jump".to_string()),
+            ("Ctrl+U/K".to_string(), "Delete to start/end".to_string()),
+        ];
+        if self.completes_on_tab {
+            items.push(("Tab".to_string(), "Complete".to_string()));
+        }
+        if self.history.is_some() {
+            items.push(("↑/↓".to_string(), "History".to_string()));
+            items.push(("Ctrl+X".to_string(), "Clear history".to_string()));
+        }
+        items.push(("Enter".to_string(), "Submit".to_string()));
+        items.push(("Esc".to_string(), "Close".to_string()));
+        items
     }
 
     /// Checks if the widget currently has focus
@@ -261,3 +488,302 @@ impl WidgetExt for InputBoxWidget {
         self.title = title;
     }
 }
+
+impl InputBoxWidget {
+    /// Applies an editing, cursor or history event to the content
+    fn apply_input_event(&mut self, input_event: InputBoxEvent) -> Option<WidgetAction> {
+        match input_event {
+            // Add character at cursor position
+            InputBoxEvent::KeyPress(key_event) => {
+                if let KeyCode::Char(c) = key_event.code {
+                    self.content.insert(self.cursor_position, c);
+                    self.cursor_position += c.len_utf8();
+                }
+                None
+            }
+            // Delete character to the left of cursor
+            InputBoxEvent::Backspace => {
+                if self.cursor_position > 0 {
+                    self.cursor_position =
+                        previous_char_boundary(&self.content, self.cursor_position);
+                    self.content.remove(self.cursor_position);
+                }
+                None
+            }
+            // Delete character under cursor
+            InputBoxEvent::Delete => {
+                if self.cursor_position < self.content.len() {
+                    self.content.remove(self.cursor_position);
+                }
+                None
+            }
+            // Move cursor left
+            InputBoxEvent::Left => {
+                self.cursor_position = previous_char_boundary(&self.content, self.cursor_position);
+                None
+            }
+            // Move cursor right
+            InputBoxEvent::Right => {
+                self.cursor_position = next_char_boundary(&self.content, self.cursor_position);
+                None
+            }
+            // Jump to the start of the previous word
+            InputBoxEvent::WordLeft => {
+                self.cursor_position = previous_word_boundary(&self.content, self.cursor_position);
+                None
+            }
+            // Jump to the start of the next word
+            InputBoxEvent::WordRight => {
+                self.cursor_position = next_word_boundary(&self.content, self.cursor_position);
+                None
+            }
+            // Jump to the start of the input
+            InputBoxEvent::Home => {
+                self.cursor_position = 0;
+                None
+            }
+            // Jump to the end of the input
+            InputBoxEvent::End => {
+                self.cursor_position = self.content.len();
+                None
+            }
+            // Delete everything left of the cursor
+            InputBoxEvent::DeleteToStart => {
+                self.content.drain(..self.cursor_position);
+                self.cursor_position = 0;
+                None
+            }
+            // Delete everything from the cursor to the end
+            InputBoxEvent::DeleteToEnd => {
+                self.content.truncate(self.cursor_position);
+                None
+            }
+            // Recall older and newer history entries
+            InputBoxEvent::ArrowUp => {
+                self.recall_previous();
+                None
+            }
+            InputBoxEvent::ArrowDown => {
+                self.recall_next();
+                None
+            }
+            InputBoxEvent::ClearHistory => {
+                if let Some(history) = &mut self.history {
+                    history.entries.clear();
+                    history.position = None;
+                    history.save();
+                }
+                None
+            }
+            // Ask for the current content to be completed
+            InputBoxEvent::Complete => Some(WidgetAction::InputBoxEvent(
+                InputBoxEvent::CompletionRequested(self.content.clone()),
+                self.input_type.clone(),
+            )),
+            // Submit current content
+            InputBoxEvent::Enter => {
+                if let Some(history) = &mut self.history {
+                    history.record(&self.content);
+                }
+                Some(WidgetAction::InputBoxEvent(
+                    InputBoxEvent::Written(self.content.clone()),
+                    self.input_type.clone(),
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_with(content: &str, cursor_position: usize) -> InputBoxWidget {
+        let mut input = InputBoxWidget::new(InputBoxType::Text, "Test", true);
+        input.content = content.to_string();
+        input.cursor_position = cursor_position;
+        input
+    }
+
+    fn apply(input: &mut InputBoxWidget, event: InputBoxEvent) {
+        input.process_event(WidgetAction::InputBoxEvent(event, InputBoxType::Text));
+    }
+
+    #[test]
+    fn pasted_lines_are_joined_in_single_line_inputs() {
+        let mut input = input_with("", 0);
+        input.insert_pasted("a\nb");
+        assert_eq!(input.content, "a b");
+        assert_eq!(input.cursor_position, 3);
+
+        // A trailing line break and Windows line endings leave nothing behind
+        let mut input = input_with("", 0);
+        input.join_pasted_lines_with(Some(", "));
+        input.insert_pasted("u1\r\nu2\n\nu3\n");
+        assert_eq!(input.content, "u1, u2, u3");
+
+        let mut input = input_with("", 0);
+        input.join_pasted_lines_with(None);
+        input.insert_pasted("a\nb");
+        assert_eq!(input.content, "a\nb");
+    }
+
+    #[test]
+    fn pasting_never_splits_a_character() {
+        let mut input = input_with("é", 1);
+        input.insert_pasted("x");
+        assert_eq!(input.content, "xé");
+        assert_eq!(input.cursor_position, 1);
+    }
+
+    #[test]
+    fn word_right_skips_multiple_spaces() {
+        let mut input = input_with("foo   bar  baz", 0);
+        apply(&mut input, InputBoxEvent::WordRight);
+        assert_eq!(input.cursor_position, 6);
+        apply(&mut input, InputBoxEvent::WordRight);
+        assert_eq!(input.cursor_position, 11);
+        apply(&mut input, InputBoxEvent::WordRight);
+        assert_eq!(input.cursor_position, 14);
+    }
+
+    #[test]
+    fn word_left_skips_multiple_spaces() {
+        let mut input = input_with("foo   bar  baz", 14);
+        apply(&mut input, InputBoxEvent::WordLeft);
+        assert_eq!(input.cursor_position, 11);
+        apply(&mut input, InputBoxEvent::WordLeft);
+        assert_eq!(input.cursor_position, 6);
+        apply(&mut input, InputBoxEvent::WordLeft);
+        assert_eq!(input.cursor_position, 0);
+    }
+
+    #[test]
+    fn word_jumps_stop_at_string_boundaries() {
+        let mut input = input_with("  foo  ", 0);
+        apply(&mut input, InputBoxEvent::WordLeft);
+        assert_eq!(input.cursor_position, 0);
+
+        input.cursor_position = 7;
+        apply(&mut input, InputBoxEvent::WordRight);
+        assert_eq!(input.cursor_position, 7);
+        apply(&mut input, InputBoxEvent::WordLeft);
+        assert_eq!(input.cursor_position, 2);
+
+        let mut empty = input_with("", 0);
+        apply(&mut empty, InputBoxEvent::WordLeft);
+        apply(&mut empty, InputBoxEvent::WordRight);
+        assert_eq!(empty.cursor_position, 0);
+    }
+
+    #[test]
+    fn word_jumps_respect_multibyte_chars() {
+        let mut input = input_with("héllo wörld", 0);
+        apply(&mut input, InputBoxEvent::WordRight);
+        assert_eq!(&input.content[input.cursor_position..], "wörld");
+        apply(&mut input, InputBoxEvent::Right);
+        apply(&mut input, InputBoxEvent::Right);
+        assert_eq!(&input.content[input.cursor_position..], "rld");
+        apply(&mut input, InputBoxEvent::Backspace);
+        assert_eq!(input.content, "héllo wrld");
+    }
+
+    #[test]
+    fn home_end_and_line_deletion() {
+        let mut input = input_with("table name", 5);
+        apply(&mut input, InputBoxEvent::End);
+        assert_eq!(input.cursor_position, 10);
+        apply(&mut input, InputBoxEvent::Home);
+        assert_eq!(input.cursor_position, 0);
+
+        input.cursor_position = 5;
+        apply(&mut input, InputBoxEvent::DeleteToEnd);
+        assert_eq!(input.content, "table");
+        assert_eq!(input.cursor_position, 5);
+
+        input.cursor_position = 2;
+        apply(&mut input, InputBoxEvent::DeleteToStart);
+        assert_eq!(input.content, "ble");
+        assert_eq!(input.cursor_position, 0);
+    }
+
+    fn key(input: &mut InputBoxWidget, code: KeyCode) {
+        if let Some(action) = input.handle_input(KeyEvent::new(code, KeyModifiers::NONE)) {
+            input.process_event(action);
+        }
+    }
+
+    fn submit(input: &mut InputBoxWidget, text: &str) {
+        input.set_content(text);
+        apply(input, InputBoxEvent::Enter);
+        input.set_content("");
+    }
+
+    #[test]
+    fn up_and_down_recall_submitted_inputs() {
+        let mut input = input_with("", 0);
+        input.enable_history("test");
+        submit(&mut input, "first");
+        submit(&mut input, "second");
+        submit(&mut input, "first");
+
+        key(&mut input, KeyCode::Up);
+        assert_eq!(input.content, "first");
+        key(&mut input, KeyCode::Up);
+        assert_eq!(input.content, "second");
+        key(&mut input, KeyCode::Up);
+        assert_eq!(input.content, "second");
+        key(&mut input, KeyCode::Down);
+        assert_eq!(input.content, "first");
+        key(&mut input, KeyCode::Down);
+        assert_eq!(input.content, "");
+    }
+
+    #[test]
+    fn typed_text_is_not_replaced_by_history() {
+        let mut input = input_with("", 0);
+        input.enable_history("test");
+        submit(&mut input, "saved");
+
+        input.set_content("typing");
+        key(&mut input, KeyCode::Up);
+        assert_eq!(input.content, "typing");
+
+        apply(&mut input, InputBoxEvent::ClearHistory);
+        input.set_content("");
+        key(&mut input, KeyCode::Up);
+        assert_eq!(input.content, "");
+    }
+
+    #[test]
+    fn disabled_inputs_ignore_typing_but_submit() {
+        let mut input = input_with("draft", 5);
+        input.set_disabled(true);
+        assert_eq!(input.get_content(), None);
+
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert!(input.handle_input(key(KeyCode::Char('x'))).is_none());
+        assert!(input.handle_input(key(KeyCode::Enter)).is_some());
+
+        input.set_disabled(false);
+        let typed = input.handle_input(key(KeyCode::Char('x'))).unwrap();
+        input.process_event(typed);
+        assert_eq!(input.get_content(), Some("x".to_string()));
+    }
+
+    #[test]
+    fn tab_requests_completion_only_when_enabled() {
+        let mut input = input_with("logs/20", 7);
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        assert!(input.handle_input(tab).is_none());
+
+        input.enable_tab_completion();
+        let complete = input.handle_input(tab).unwrap();
+        assert!(matches!(
+            input.process_event(complete),
+            Some(WidgetAction::InputBoxEvent(InputBoxEvent::CompletionRequested(text), _))
+                if text == "logs/20"
+        ));
+    }
+}
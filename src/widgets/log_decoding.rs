@@ -0,0 +1,86 @@
+//! Log decoding module
+//!
+//! Detects log messages that are base64 text, often gzip-compressed (e.g. records
+//! delivered through subscription filters such as VPC flow logs), and decodes them for
+//! the details popup. Only the opened message is decoded, never a whole result list.
+
+use flate2::read::MultiGzDecoder;
+use std::io::Read;
+
+/// Shortest message treated as possibly encoded; short words are often valid base64
+const MIN_ENCODED_LEN: usize = 16;
+
+/// Magic bytes at the start of gzip data
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Largest decompressed message shown, guarding against compression bombs
+const MAX_DECODED_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Returns whether the message looks like base64: long enough, padded to a multiple of
+/// four characters and made of base64 characters only
+pub fn looks_encoded(message: &str) -> bool {
+    let message = message.trim();
+    message.len() >= MIN_ENCODED_LEN
+        && message.len().is_multiple_of(4)
+        && message
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'='))
+}
+
+/// Decodes a base64 message, decompressing it when the decoded bytes are gzip data
+///
+/// Returns `None` unless the result is readable text, so callers keep the raw message.
+pub fn decode_message(message: &str) -> Option<String> {
+    if !looks_encoded(message) {
+        return None;
+    }
+    let bytes = aws_smithy_types::base64::decode(message.trim()).ok()?;
+    let bytes = if bytes.starts_with(&GZIP_MAGIC) {
+        let mut text = Vec::new();
+        MultiGzDecoder::new(bytes.as_slice())
+            .take(MAX_DECODED_BYTES)
+            .read_to_end(&mut text)
+            .ok()?;
+        text
+    } else {
+        bytes
+    };
+    let text = String::from_utf8(bytes).ok()?;
+    // Random bytes can be valid UTF-8 by chance, readable text has no control characters
+    text.chars()
+        .all(|c| !c.is_control() || c.is_whitespace())
+        .then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{Compression, write::GzEncoder};
+    use std::io::Write;
+
+    #[test]
+    fn decodes_gzipped_and_plain_base64_messages() {
+        let payload = r#"{"messageType":"DATA_MESSAGE","logEvents":[]}"#;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(payload.as_bytes()).unwrap();
+        let gzipped = aws_smithy_types::base64::encode(encoder.finish().unwrap());
+        assert_eq!(decode_message(&gzipped).as_deref(), Some(payload));
+
+        let plain = aws_smithy_types::base64::encode("user signed in from 10.0.0.1");
+        assert_eq!(
+            decode_message(&plain).as_deref(),
+            Some("user signed in from 10.0.0.1")
+        );
+    }
+
+    #[test]
+    fn plain_messages_and_binary_data_are_not_decoded() {
+        assert!(!looks_encoded("GET /health 200"));
+        assert!(!looks_encoded("short"));
+        assert_eq!(decode_message("GET /health 200 OK in 12ms"), None);
+        // Valid base64, but the bytes are not text
+        let binary = aws_smithy_types::base64::encode([0u8, 159, 146, 150, 1, 2, 3, 4, 5, 6, 7, 8]);
+        assert!(looks_encoded(&binary));
+        assert_eq!(decode_message(&binary), None);
+    }
+}
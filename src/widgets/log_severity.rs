@@ -0,0 +1,163 @@
+//! Log severity module
+//!
+//! Detects the severity of a log line so navigators can color ERROR/WARN lines.
+//! Detection is case-insensitive and looks at the level fields of JSON and logfmt
+//! lines, or otherwise at the first word after the line's timestamp.
+
+use crate::theme::theme;
+use ratatui::style::{Modifier, Style};
+
+/// Structured fields that carry the level in JSON and logfmt lines
+const LEVEL_FIELDS: [&str; 4] = ["level", "levelname", "severity", "log.level"];
+
+/// Severity of a single log line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Severity {
+    /// Maps a level keyword such as `ERROR` or `warning` to a severity
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword.to_ascii_lowercase().as_str() {
+            "error" | "fatal" | "critical" | "crit" | "panic" | "emerg" | "alert" => {
+                Some(Severity::Error)
+            }
+            "warn" | "warning" => Some(Severity::Warn),
+            "info" | "notice" => Some(Severity::Info),
+            "debug" | "trace" => Some(Severity::Debug),
+            _ => None,
+        }
+    }
+
+    /// Style applied to lines of this severity, `None` keeps the default text style
+    pub fn style(self) -> Option<Style> {
+        match self {
+            Severity::Error => Some(Style::default().fg(theme().error_fg)),
+            Severity::Warn => Some(Style::default().fg(theme().warning_fg)),
+            Severity::Info => None,
+            Severity::Debug => Some(
+                Style::default()
+                    .fg(theme().text_fg)
+                    .add_modifier(Modifier::DIM),
+            ),
+        }
+    }
+}
+
+/// Detects the severity of a log line, if it has a recognizable level
+pub fn detect_severity(line: &str) -> Option<Severity> {
+    structured_level(line).or_else(|| leading_keyword(line))
+}
+
+/// Reads the level from `"level":"error"` (JSON) or `level=error` (logfmt) fields
+fn structured_level(line: &str) -> Option<Severity> {
+    let lowered = line.to_ascii_lowercase();
+    LEVEL_FIELDS.iter().find_map(|field| {
+        [format!("\"{}\":", field), format!("{}=", field)]
+            .iter()
+            .find_map(|key| {
+                let start = lowered.find(key.as_str())? + key.len();
+                let value = lowered[start..]
+                    .trim_start()
+                    .trim_start_matches('"')
+                    .split(|c: char| !c.is_ascii_alphabetic())
+                    .next()?;
+                Severity::from_keyword(value)
+            })
+    })
+}
+
+/// Finds a level keyword as the first word after an optional prefix, e.g. `[ERROR] ...`,
+/// `2024-01-01 12:00:00 WARN ...` or `2024-01-01 12:00:00,123 - app - DEBUG - ...`
+///
+/// The prefix may hold timestamps and a request id, as in Lambda logs. Keywords further
+/// into the message, as in `no error reported`, are not levels.
+fn leading_keyword(line: &str) -> Option<Severity> {
+    let mut words = line
+        .split_whitespace()
+        .skip_while(|word| is_timestamp(word) || is_request_id(word))
+        .peekable();
+    // Python's default format puts the logger name between dashes before the level
+    if words.peek() == Some(&"-") {
+        words.next();
+        words.next();
+        if words.next() != Some("-") {
+            return None;
+        }
+    }
+    let keyword = words
+        .next()?
+        .trim_start_matches(|c: char| !c.is_ascii_alphabetic())
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()?;
+    Severity::from_keyword(keyword)
+}
+
+/// Whether a word is (part of) a timestamp, possibly bracketed, e.g. `2024-05-01T10:00:00Z`
+/// or `[10:00:00,123]`
+fn is_timestamp(word: &str) -> bool {
+    let word = word.trim_matches(|c| matches!(c, '[' | ']' | '(' | ')'));
+    let separator = |c: char| matches!(c, '-' | ':' | '.' | ',' | '/' | 'T' | 'Z' | '+');
+    word.starts_with(|c: char| c.is_ascii_digit())
+        && word.chars().all(|c| c.is_ascii_digit() || separator(c))
+}
+
+/// Whether a word is a request id (a UUID) such as Lambda writes after the timestamp
+fn is_request_id(word: &str) -> bool {
+    word.len() == 36 && word.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_leading_keywords_case_insensitively() {
+        assert_eq!(detect_severity("ERROR failed to connect"), Some(Severity::Error));
+        assert_eq!(detect_severity("[warn] disk almost full"), Some(Severity::Warn));
+        assert_eq!(
+            detect_severity("2024-05-01T10:00:00Z Fatal: out of memory"),
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            detect_severity("2024-05-01 10:00:00,123 - app - DEBUG - cache hit"),
+            Some(Severity::Debug)
+        );
+        assert_eq!(
+            detect_severity(
+                "2024-05-01T10:00:00.000Z\t6f0c4d2a-1b3e-4c5d-8e9f-0a1b2c3d4e5f\tERROR\tboom"
+            ),
+            Some(Severity::Error)
+        );
+        assert_eq!(detect_severity("ERROR:root:failed"), Some(Severity::Error));
+    }
+
+    #[test]
+    fn detects_structured_levels() {
+        assert_eq!(
+            detect_severity(r#"{"msg":"slow query","level":"warning"}"#),
+            Some(Severity::Warn)
+        );
+        assert_eq!(
+            detect_severity("ts=2024-05-01 level=error msg=\"boom\""),
+            Some(Severity::Error)
+        );
+    }
+
+    #[test]
+    fn ignores_keywords_deep_in_the_message() {
+        assert_eq!(
+            detect_severity("request completed in 12ms with 0 retries and no error reported"),
+            None
+        );
+        assert_eq!(detect_severity("START RequestId: 1234 Version: $LATEST"), None);
+        assert_eq!(detect_severity("no error"), None);
+        assert_eq!(detect_severity("2024-05-01 10:00:00 no error found"), None);
+        assert_eq!(detect_severity("[worker-3] retrying after error"), None);
+        assert_eq!(detect_severity("2024-05-01 10:00:00 - app - cache warn - miss"), None);
+    }
+}
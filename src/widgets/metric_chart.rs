@@ -0,0 +1,188 @@
+//! Metric chart module
+//!
+//! Draws the datapoints of a CloudWatch metric as a line chart, or a message in its
+//! place while they load, when none were found or when fetching them failed.
+
+use crate::theme::theme;
+use crate::timestamps::timestamps;
+use chrono::{DateTime, Utc};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Style,
+    symbols::Marker,
+    text::Span,
+    widgets::{Axis, Block, BorderType, Borders, Chart, Dataset, GraphType, Paragraph, Widget},
+};
+
+/// Format of the time labels under the chart
+const TIME_LABEL_FORMAT: &str = "%m-%d %H:%M";
+
+/// Line chart of one metric's datapoints
+pub struct MetricChart {
+    /// Title shown in the chart's border
+    title: String,
+    /// Datapoints, oldest first
+    points: Vec<(DateTime<Utc>, f64)>,
+    /// Shown instead of the chart, e.g. while loading or when the request failed
+    message: Option<String>,
+    /// Whether the chart has focus
+    active: bool,
+}
+
+impl MetricChart {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            points: Vec::new(),
+            message: Some("Select a metric".to_string()),
+            active: false,
+        }
+    }
+
+    /// Replaces the datapoints; an empty series says so instead of drawing empty axes
+    pub fn set_points(&mut self, title: String, points: Vec<(DateTime<Utc>, f64)>) {
+        self.title = title;
+        self.message = points
+            .is_empty()
+            .then(|| "No datapoints in the time range".to_string());
+        self.points = points;
+    }
+
+    /// Shows a message in place of the chart
+    pub fn show_message(&mut self, title: String, message: String) {
+        self.title = title;
+        self.points.clear();
+        self.message = Some(message);
+    }
+
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.active {
+            Style::default().fg(theme().active_border)
+        } else {
+            Style::default().fg(theme().text_fg)
+        };
+        let block = Block::default()
+            .title(self.title.clone())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(border_style);
+
+        if let Some(message) = &self.message {
+            Paragraph::new(message.as_str())
+                .style(Style::default().fg(theme().help_fg))
+                .alignment(Alignment::Center)
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        // Seconds since the first datapoint, so the x values stay small and precise
+        let origin = self.points[0].0;
+        let data: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .map(|(time, value)| ((*time - origin).num_seconds() as f64, *value))
+            .collect();
+        let last = self.points[self.points.len() - 1].0;
+        let x_max = (last - origin).num_seconds().max(1) as f64;
+        let y_bounds = y_bounds(&self.points);
+
+        // A single datapoint has nothing to connect, so it is drawn as a dot
+        let graph_type = if data.len() < 2 {
+            GraphType::Scatter
+        } else {
+            GraphType::Line
+        };
+        let dataset = Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(graph_type)
+            .style(Style::default().fg(theme().highlight_fg))
+            .data(&data);
+
+        let axis_style = Style::default().fg(theme().text_fg);
+        let x_axis = Axis::default()
+            .style(axis_style)
+            .bounds([0.0, x_max])
+            .labels([
+                Span::raw(timestamps().format_with(origin, TIME_LABEL_FORMAT)),
+                Span::raw(timestamps().format_with(last, TIME_LABEL_FORMAT)),
+            ]);
+        let y_axis = Axis::default().style(axis_style).bounds(y_bounds).labels([
+            Span::raw(format_value(y_bounds[0])),
+            Span::raw(format_value((y_bounds[0] + y_bounds[1]) / 2.0)),
+            Span::raw(format_value(y_bounds[1])),
+        ]);
+
+        Chart::new(vec![dataset])
+            .block(block)
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .render(area, buf);
+    }
+}
+
+/// Lowest and highest value, widened so a flat series is drawn mid-chart
+fn y_bounds(points: &[(DateTime<Utc>, f64)]) -> [f64; 2] {
+    let (min, max) = points
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), (_, value)| {
+            (min.min(*value), max.max(*value))
+        });
+    if min > max {
+        return [0.0, 1.0];
+    }
+    if (max - min).abs() < f64::EPSILON {
+        let margin = (max.abs() * 0.1).max(1.0);
+        return [min - margin, max + margin];
+    }
+    [min, max]
+}
+
+/// Axis label for a value, without decimals once they stop mattering
+fn format_value(value: f64) -> String {
+    if value.abs() >= 100.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(values: &[f64]) -> Vec<(DateTime<Utc>, f64)> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (DateTime::from_timestamp(i as i64 * 60, 0).unwrap(), *value))
+            .collect()
+    }
+
+    #[test]
+    fn bounds_span_the_values_and_widen_flat_series() {
+        assert_eq!(y_bounds(&series(&[3.0, 1.0, 2.0])), [1.0, 3.0]);
+        assert_eq!(y_bounds(&series(&[0.0, 0.0])), [-1.0, 1.0]);
+        assert_eq!(y_bounds(&series(&[50.0])), [45.0, 55.0]);
+    }
+
+    #[test]
+    fn empty_series_show_a_message_instead_of_a_chart() {
+        let mut chart = MetricChart::new("Metric");
+        chart.set_points("Errors".to_string(), Vec::new());
+        assert_eq!(
+            chart.message.as_deref(),
+            Some("No datapoints in the time range")
+        );
+
+        chart.set_points("Errors".to_string(), series(&[1.0]));
+        assert_eq!(chart.message, None);
+        let area = Rect::new(0, 0, 40, 10);
+        chart.render(area, &mut Buffer::empty(area));
+    }
+}
@@ -1,6 +1,14 @@
 // pub(crate) mod paragraph;
+pub(crate) mod clipboard;
+pub(crate) mod details_format;
+pub(crate) mod filter_query;
 pub(crate) mod input_box;
+pub(crate) mod log_decoding;
+pub(crate) mod log_severity;
+pub(crate) mod metric_chart;
 pub(crate) mod popup;
+pub(crate) mod record_sort;
+pub(crate) mod record_table;
 pub(crate) mod service_navigator;
 // pub(crate) mod input_box;
 use crate::event_managment::event::WidgetAction;
@@ -5,62 +5,150 @@
 
 use crate::{
     event_managment::event::{PopupAction, WidgetAction},
-    widgets::WidgetExt,
+    theme::theme,
+    widgets::{
+        WidgetExt,
+        details_format::{self, DetailsFormat},
+        log_decoding,
+    },
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::{
     buffer::Buffer,
     layout::Alignment,
     layout::Rect,
-    style::{Color, Style},
-    text::Line,
+    style::{Modifier, Style},
+    text::{Line, Text},
     widgets::{Block, Clear, Paragraph, Widget},
 };
-use serde_json;
+use serde_json::{self, Value};
 use std::any::Any;
+use std::cell::Cell;
+use std::collections::{HashSet, VecDeque};
 
 // Minimum dimensions for popup to ensure it's usable
-const MIN_POPUP_WIDTH: u16 = 20;
-const MIN_POPUP_HEIGHT: u16 = 10;
+pub(crate) const MIN_POPUP_WIDTH: u16 = 20;
+pub(crate) const MIN_POPUP_HEIGHT: u16 = 10;
+/// Details kept for going back with ←, the oldest are dropped first
+const DETAILS_HISTORY_LIMIT: usize = 20;
 
 /// Content types for the popup dialog
 ///
-/// Profiles displays a selectable list of AWS profiles
+/// Profiles displays a selectable, filterable list of AWS profiles
 /// Details displays formatted text content (often JSON)
+/// Confirm displays a yes/no prompt that must be confirmed by typing `y`
+/// Choices displays a selectable list of options (e.g. time range presets)
 #[derive(Clone, Debug)]
 pub enum PopupContent {
     Profiles(Vec<String>),
     Details(String),
+    Confirm(String),
+    Choices(Vec<String>),
 }
 
-impl PopupContent {
-    /// Returns the number of selectable items in the content
-    pub fn len(&self) -> usize {
-        match self {
-            PopupContent::Profiles(profiles) => profiles.len(),
-            PopupContent::Details(_) => 0, // Details are not selectable
-        }
+/// A visible row of the collapsible JSON tree
+struct TreeLine {
+    path: Vec<String>, // Keys/indices from the root to this node
+    depth: usize,      // Nesting level used for indentation
+    label: String,     // Key and value (or child count for containers)
+    expandable: bool,  // Whether the node is a non-empty object or array
+    expanded: bool,    // Whether the node's children are shown
+}
+
+/// Appends the visible rows for a JSON node, skipping the children of collapsed nodes
+fn flatten_json(
+    value: &Value,
+    key: Option<String>,
+    path: Vec<String>,
+    depth: usize,
+    collapsed: &HashSet<Vec<String>>,
+    lines: &mut Vec<TreeLine>,
+) {
+    let prefix = key.map(|key| format!("{}: ", key)).unwrap_or_default();
+    let children: Vec<(String, &Value)> = match value {
+        Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (format!("[{}]", i), v))
+            .collect(),
+        _ => vec![],
+    };
+
+    if children.is_empty() {
+        lines.push(TreeLine {
+            path,
+            depth,
+            label: format!("{}{}", prefix, value),
+            expandable: false,
+            expanded: false,
+        });
+        return;
     }
 
-    /// Gets an item at the specified index
-    pub fn get(&self, index: usize) -> Option<&String> {
-        match self {
-            PopupContent::Profiles(profiles) => profiles.get(index),
-            PopupContent::Details(_) => None, // Cannot select individual details
+    let summary = match value {
+        Value::Object(_) => format!("{{{} keys}}", children.len()),
+        _ => format!("[{} items]", children.len()),
+    };
+    let expanded = !collapsed.contains(&path);
+    lines.push(TreeLine {
+        path: path.clone(),
+        depth,
+        label: format!("{}{}", prefix, summary),
+        expandable: true,
+        expanded,
+    });
+
+    if expanded {
+        for (child_key, child) in children {
+            let mut child_path = path.clone();
+            child_path.push(child_key.clone());
+            flatten_json(
+                child,
+                Some(child_key),
+                child_path,
+                depth + 1,
+                collapsed,
+                lines,
+            );
         }
     }
 }
 
+/// Details shown earlier, with what is needed to show them again
+#[derive(Clone, Debug, PartialEq)]
+struct DetailsEntry {
+    title: String,
+    details: String,
+    complete: Option<String>, // Complete form of simplified details
+}
+
 /// Widget for displaying popup dialogs with different content types
 #[derive(Debug)]
 pub struct PopupWidget {
-    content: PopupContent,         // Content displayed in the popup
-    title: String,                 // Title displayed in the popup border
-    selected_item: Option<String>, // Currently selected item (if applicable)
-    selection_index: usize,        // Index of currently selected item (for lists)
-    active: bool,                  // Whether popup has input focus
-    visible: bool,                 // Whether popup is currently displayed
+    content: PopupContent,                   // Content displayed in the popup
+    title: String,                           // Title displayed in the popup border
+    selected_item: Option<String>,           // Currently selected item (if applicable)
+    selection_index: usize,                  // Index of currently selected item (for lists)
+    active: bool,                            // Whether popup has input focus
+    visible: bool,                           // Whether popup is currently displayed
+    details_json: Option<Value>,             // Parsed details content, None when not valid JSON
+    raw_view: bool,     // Whether JSON details show as plain text instead of a tree
+    tree_cursor: usize, // Selected row in the JSON tree
+    collapsed: HashSet<Vec<String>>, // Paths of collapsed tree nodes
+    recent_profiles: Vec<String>, // Recently selected profiles pinned above the full list
+    filter: String,     // Typed text narrowing the profile list down
+    encoded_details: Option<String>, // Details that look encoded, as they were given
+    decoded_details: Option<Option<String>>, // Decoded details, once decoding was asked for
+    show_decoded: bool, // Whether the decoded details replace the raw ones
+    scroll: usize,      // Lines the plain details are scrolled down
+    other_details: Option<String>, // Complete details for simplified ones, or the other way round
+    show_complete: bool, // Whether the complete details are shown instead of the simplified ones
+    details_format: DetailsFormat, // Kind of text the details are, for laying them out
+    content_width: Cell<usize>, // Columns the plain details were wrapped to at the last render
+    history: VecDeque<DetailsEntry>, // Details shown recently, oldest first
+    history_index: usize, // Entry of the history being shown
 }
 
 impl PopupWidget {
@@ -68,21 +156,324 @@ impl PopupWidget {
     pub fn new(content: PopupContent, title: &str, visible: bool, active: bool) -> Self {
         // Load AWS profiles by default
 
-        Self {
+        let mut popup = Self {
             title: title.to_string(),
             selected_item: None,
-            content: content,
+            content: PopupContent::Profiles(vec![]),
             selection_index: 0,
             active,
             visible,
-        }
+            details_json: None,
+            raw_view: false,
+            tree_cursor: 0,
+            collapsed: HashSet::new(),
+            recent_profiles: Vec::new(),
+            filter: String::new(),
+            encoded_details: None,
+            decoded_details: None,
+            show_decoded: false,
+            scroll: 0,
+            other_details: None,
+            show_complete: false,
+            details_format: DetailsFormat::Text,
+            content_width: Cell::new(0),
+            history: VecDeque::new(),
+            history_index: 0,
+        };
+        popup.set_content(content);
+        popup
     }
 
     /// Updates the content of the popup
     pub fn set_content(&mut self, content: PopupContent) {
+        self.selection_index = 0;
+        self.recent_profiles.clear();
+        self.filter.clear();
+        // Encoded details are only decoded once the user asks for it
+        self.encoded_details = match &content {
+            PopupContent::Details(details) if log_decoding::looks_encoded(details) => {
+                Some(details.clone())
+            }
+            _ => None,
+        };
+        self.decoded_details = None;
+        self.show_decoded = false;
+        self.other_details = None;
+        self.show_complete = false;
+        self.show_content(content);
+    }
+
+    /// Offers a complete form of the details, for details that leave data out
+    pub fn set_complete_details(&mut self, complete: String) {
+        self.other_details = Some(complete);
+        self.show_complete = false;
+    }
+
+    /// Switches between the simplified and the complete details
+    fn toggle_complete(&mut self) {
+        let Some(other) = self.other_details.take() else {
+            return;
+        };
+        if let PopupContent::Details(current) = &self.content {
+            self.other_details = Some(current.clone());
+            self.show_complete = !self.show_complete;
+            self.show_content(PopupContent::Details(other));
+        }
+    }
+
+    /// Shows the content, keeping the decoding state of the details
+    fn show_content(&mut self, content: PopupContent) {
+        // Details that parse as JSON start out as a fully expanded tree
+        self.details_json = match &content {
+            PopupContent::Details(details) => details_format::parse_json(details),
+            _ => None,
+        };
+        self.details_format = match &content {
+            _ if self.details_json.is_some() => DetailsFormat::Json,
+            PopupContent::Details(details) => DetailsFormat::detect_markup(details),
+            _ => DetailsFormat::Text,
+        };
+        self.raw_view = false;
+        self.tree_cursor = 0;
+        self.scroll = 0;
+        self.collapsed.clear();
         self.content = content;
     }
 
+    /// Hides the popup and drops its focus, scroll position and filter, so it opens
+    /// afresh next time
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.active = false;
+        self.scroll = 0;
+        self.tree_cursor = 0;
+        self.selection_index = 0;
+        self.filter.clear();
+    }
+
+    /// Adds the details being opened to the history, unless they are the ones shown last
+    fn remember_details(&mut self) {
+        let PopupContent::Details(details) = &self.content else {
+            return;
+        };
+        // Opened details always show as given: set_content resets decoding and completion
+        let entry = DetailsEntry {
+            title: self.title.clone(),
+            details: details.clone(),
+            complete: self.other_details.clone(),
+        };
+        if self.history.back() != Some(&entry) {
+            self.history.push_back(entry);
+            if self.history.len() > DETAILS_HISTORY_LIMIT {
+                self.history.pop_front();
+            }
+        }
+        self.history_index = self.history.len() - 1;
+    }
+
+    /// Shows the details viewed before (`back`) or after the current ones, if any
+    fn step_history(&mut self, back: bool) {
+        let index = if back {
+            self.history_index.checked_sub(1)
+        } else {
+            Some(self.history_index + 1).filter(|&index| index < self.history.len())
+        };
+        let Some(entry) = index.and_then(|index| self.history.get(index)).cloned() else {
+            return;
+        };
+        self.history_index = index.unwrap_or_default();
+        self.title = entry.title;
+        self.set_content(PopupContent::Details(entry.details));
+        if let Some(complete) = entry.complete {
+            self.set_complete_details(complete);
+        }
+    }
+
+    /// Whether ←/→ move through the details history
+    fn has_history(&self) -> bool {
+        matches!(self.content, PopupContent::Details(_)) && self.history.len() > 1
+    }
+
+    /// Forgets the details viewed so far, e.g. once the records they came from are replaced
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.history_index = 0;
+    }
+
+    /// Whether the content is details shown as plain text, which scroll rather than select
+    fn is_plain_details(&self) -> bool {
+        matches!(self.content, PopupContent::Details(_)) && !self.is_tree_view()
+    }
+
+    /// Switches between the raw and the decoded details, decoding them the first time
+    ///
+    /// Details that do not decode to readable text stay raw.
+    fn toggle_decoded(&mut self) {
+        let Some(raw) = self.encoded_details.clone() else {
+            return;
+        };
+        if self.show_decoded {
+            self.show_decoded = false;
+            self.show_content(PopupContent::Details(raw));
+            return;
+        }
+        let decoded = self
+            .decoded_details
+            .get_or_insert_with(|| log_decoding::decode_message(&raw))
+            .clone();
+        if let Some(decoded) = decoded {
+            self.show_decoded = true;
+            self.show_content(PopupContent::Details(decoded));
+        }
+    }
+
+    /// Pins recently selected profiles above the full list, skipping any that are no
+    /// longer configured
+    pub fn set_recent_profiles(&mut self, recent: Vec<String>) {
+        if let PopupContent::Profiles(profiles) = &self.content {
+            self.recent_profiles = recent
+                .into_iter()
+                .filter(|profile| profiles.contains(profile))
+                .collect();
+            self.selection_index = 0;
+        }
+    }
+
+    /// Returns the recent and the full profile lists narrowed down by the filter
+    fn filtered_profiles(&self) -> (Vec<&String>, Vec<&String>) {
+        let PopupContent::Profiles(profiles) = &self.content else {
+            return (vec![], vec![]);
+        };
+        let filter = self.filter.to_lowercase();
+        let matches = |profile: &&String| profile.to_lowercase().contains(&filter);
+        (
+            self.recent_profiles.iter().filter(matches).collect(),
+            profiles.iter().filter(matches).collect(),
+        )
+    }
+
+    /// Returns the selectable items in display order, recent profiles first
+    fn selectable_items(&self) -> Vec<&String> {
+        match &self.content {
+            PopupContent::Profiles(_) => {
+                let (recent, all) = self.filtered_profiles();
+                recent.into_iter().chain(all).collect()
+            }
+            PopupContent::Choices(items) => items.iter().collect(),
+            PopupContent::Details(_) | PopupContent::Confirm(_) => vec![], // Not selectable
+        }
+    }
+
+    /// Renders the filter, the recent profiles and the full list, scrolled so the
+    /// selection stays visible
+    fn render_profiles(&self, height: usize) -> Vec<Line<'static>> {
+        let header = Style::default()
+            .fg(theme().help_fg)
+            .add_modifier(Modifier::BOLD);
+        let (recent, all) = self.filtered_profiles();
+
+        let mut lines = Vec::new();
+        let mut selected_row = 0;
+        if !self.filter.is_empty() {
+            lines.push(Line::styled(format!("Filter: {}", self.filter), header));
+        }
+        let mut index = 0;
+        let mut push_items = |lines: &mut Vec<Line<'static>>, items: Vec<&String>| {
+            for item in items {
+                if index == self.selection_index {
+                    selected_row = lines.len();
+                    lines.push(Line::styled(
+                        format!("> {}", item),
+                        Style::default()
+                            .fg(theme().selection_fg)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                } else {
+                    lines.push(Line::raw(format!("  {}", item)));
+                }
+                index += 1;
+            }
+        };
+        if !recent.is_empty() {
+            lines.push(Line::styled("Recent", header));
+            push_items(&mut lines, recent);
+            lines.push(Line::raw(""));
+            lines.push(Line::styled("All profiles", header));
+        }
+        if all.is_empty() {
+            lines.push(Line::raw("  No matching profiles"));
+        }
+        push_items(&mut lines, all);
+
+        let height = height.max(1);
+        let offset = selected_row.saturating_sub(height - 1);
+        lines.into_iter().skip(offset).take(height).collect()
+    }
+
+    /// Returns whether details are currently shown as a collapsible JSON tree
+    fn is_tree_view(&self) -> bool {
+        self.details_json.is_some() && !self.raw_view
+    }
+
+    /// Returns the visible rows of the JSON tree
+    ///
+    /// The root object/array is not shown as a row of its own; its children start at depth 0
+    fn tree_lines(&self) -> Vec<TreeLine> {
+        let mut lines = Vec::new();
+        if let Some(json) = &self.details_json {
+            flatten_json(json, None, vec![], 0, &self.collapsed, &mut lines);
+            // Empty containers and scalars have no children, so keep their single row
+            if lines.first().is_some_and(|root| root.expandable) {
+                lines.remove(0);
+                for line in lines.iter_mut() {
+                    line.depth -= 1;
+                }
+            }
+        }
+        lines
+    }
+
+    /// Collapses or expands the node under the tree cursor
+    fn toggle_selected_node(&mut self) {
+        if let Some(line) = self.tree_lines().get(self.tree_cursor)
+            && line.expandable
+            && !self.collapsed.remove(&line.path)
+        {
+            self.collapsed.insert(line.path.clone());
+        }
+    }
+
+    /// Renders the JSON tree, scrolled so the cursor stays visible
+    fn render_tree(&self, height: usize) -> Vec<Line<'static>> {
+        let height = height.max(1);
+        let offset = self.tree_cursor.saturating_sub(height - 1);
+
+        self.tree_lines()
+            .into_iter()
+            .enumerate()
+            .skip(offset)
+            .take(height)
+            .map(|(i, line)| {
+                let marker = match (line.expandable, line.expanded) {
+                    (true, true) => "▼ ",
+                    (true, false) => "▶ ",
+                    _ => "  ",
+                };
+                let style = if i == self.tree_cursor {
+                    Style::default()
+                        .fg(theme().selection_fg)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme().text_fg)
+                };
+                Line::styled(
+                    format!("{}{}{}", "  ".repeat(line.depth), marker, line.label),
+                    style,
+                )
+            })
+            .collect()
+    }
+
     /// Calculates the area for the popup based on parent area and content type
     fn calculate_popup_area(&self, area: Rect) -> Option<Rect> {
         if area.width <= MIN_POPUP_WIDTH || area.height <= MIN_POPUP_HEIGHT {
@@ -92,6 +483,7 @@ impl PopupWidget {
         // Define percentage constraints based on popup type
         let (width_percent, height_percent) = match self.content {
             PopupContent::Details(_) => (80, 80), // Larger popup for details
+            PopupContent::Confirm(_) => (70, 50), // Room for the keys a prompt lists
             _ => (60, 60),                        // Smaller popup for profiles
         };
 
@@ -131,7 +523,7 @@ impl PopupWidget {
     /// Renders content as a list or formats details content with JSON pretty printing
     fn render_content(&self) -> String {
         match &self.content {
-            PopupContent::Profiles(items) => items
+            PopupContent::Profiles(items) | PopupContent::Choices(items) => items
                 .iter()
                 .enumerate()
                 .map(|(i, item)| {
@@ -143,39 +535,26 @@ impl PopupWidget {
                 })
                 .collect::<Vec<_>>()
                 .join("\n"),
-            PopupContent::Details(content) => {
-                // Check if the content starts with a timestamp pattern like [YYYY-MM-DD HH:MM:SS]
-                if let Some(json_start) = content.find("] {") {
-                    // Extract the potential JSON part (skipping timestamp)
-                    let json_str = &content[(json_start + 1).min(content.len())..].trim();
-
-                    // Parse the JSON string
-                    match serde_json::from_str::<serde_json::Value>(json_str) {
-                        Ok(json) => {
-                            // Pretty print with proper indentation
-                            serde_json::to_string_pretty(&json).unwrap_or_else(|_| content.clone())
-                        }
-                        Err(_) => {
-                            // Try the original content if JSON extraction failed
-                            match serde_json::from_str::<serde_json::Value>(content) {
-                                Ok(json) => serde_json::to_string_pretty(&json)
-                                    .unwrap_or_else(|_| content.clone()),
-                                Err(_) => content.clone(),
-                            }
-                        }
-                    }
-                } else {
-                    // If no timestamp pattern, try parsing the entire string as JSON
-                    match serde_json::from_str::<serde_json::Value>(content) {
-                        Ok(json) => {
-                            serde_json::to_string_pretty(&json).unwrap_or_else(|_| content.clone())
-                        }
-                        Err(_) => content.clone(),
-                    }
-                }
+            PopupContent::Confirm(message) => {
+                format!("{}\n\ny: confirm    n/Esc: cancel", message)
             }
+            PopupContent::Details(content) => match &self.details_json {
+                // Pretty print with proper indentation
+                Some(json) => {
+                    serde_json::to_string_pretty(json).unwrap_or_else(|_| content.clone())
+                }
+                None if self.details_format == DetailsFormat::Xml => {
+                    details_format::indent_xml(content)
+                }
+                None => content.clone(),
+            },
         }
     }
+
+    /// Rows of the plain details wrapped to `width` columns, line breaks kept
+    fn details_rows(&self, width: usize) -> Vec<Line<'static>> {
+        details_format::render_rows(&self.render_content(), self.details_format, width)
+    }
 }
 
 impl WidgetExt for PopupWidget {
@@ -193,26 +572,60 @@ impl WidgetExt for PopupWidget {
         let content_area = self.calculate_content_area(popup_area);
 
         // Render popup background and border
-        buf.set_style(popup_area, Style::default().bg(Color::Black));
+        buf.set_style(popup_area, Style::default().bg(theme().background));
         Clear.render(popup_area, buf); // Clear any content beneath popup
 
         // Set border style based on focus state
         let border_style = if self.active {
-            Style::default().fg(Color::Red)
+            Style::default().fg(theme().active_border)
         } else {
             Style::default()
         };
 
+        // The position in the history, while there is more than the current details
+        let title = if self.has_history() {
+            format!("{} [{}/{}]", self.title, self.history_index + 1, self.history.len())
+        } else {
+            self.title.clone()
+        };
+        let title = if self.show_decoded {
+            format!("{} (decoded)", title)
+        } else if self.show_complete {
+            format!("{} (all attribute types)", title)
+        } else if self.other_details.is_some() {
+            format!(
+                "{} (simplified: unsupported types show as null)",
+                title
+            )
+        } else {
+            title
+        };
         Block::bordered()
-            .title(Line::from(self.title.as_str()))
+            .title(Line::from(title))
             .border_style(border_style)
             .render(popup_area, buf);
 
-        // Render profiles list or details content
-        let content_text = self.render_content();
+        // Render the JSON tree, or the profiles list / details content as text
+        let content_text: Text = if self.is_tree_view() {
+            self.render_tree(content_area.height as usize).into()
+        } else if matches!(self.content, PopupContent::Profiles(_)) {
+            self.render_profiles(content_area.height as usize).into()
+        } else if self.is_plain_details() {
+            // Wrapped here rather than by the paragraph, so scrolling knows the row count
+            self.content_width.set(content_area.width as usize);
+            self.details_rows(content_area.width as usize).into()
+        } else {
+            self.render_content().into()
+        };
+        let scroll = if self.is_plain_details() {
+            self.scroll.min(u16::MAX as usize) as u16
+        } else {
+            0
+        };
         Paragraph::new(content_text)
+            .scroll((scroll, 0))
             .block(Block::default())
-            .style(Style::default().fg(Color::White).bg(Color::Black))
+            .style(Style::default().fg(theme().text_fg).bg(theme().background))
             .alignment(Alignment::Left)
             .render(content_area, buf);
     }
@@ -222,6 +635,47 @@ impl WidgetExt for PopupWidget {
         match key_event.code {
             KeyCode::Up => Some(WidgetAction::PopupAction(PopupAction::ArrowUp)),
             KeyCode::Down => Some(WidgetAction::PopupAction(PopupAction::ArrowDown)),
+            KeyCode::Enter | KeyCode::Char(' ') if self.is_tree_view() => {
+                Some(WidgetAction::PopupAction(PopupAction::ToggleNode))
+            }
+            KeyCode::Char('r') if self.details_json.is_some() => {
+                Some(WidgetAction::PopupAction(PopupAction::ToggleRaw))
+            }
+            KeyCode::Char('d') if self.encoded_details.is_some() => {
+                Some(WidgetAction::PopupAction(PopupAction::ToggleDecoded))
+            }
+            KeyCode::Char('t') if self.other_details.is_some() => {
+                Some(WidgetAction::PopupAction(PopupAction::ToggleComplete))
+            }
+            KeyCode::Left if self.has_history() => {
+                Some(WidgetAction::PopupAction(PopupAction::HistoryBack))
+            }
+            KeyCode::Right if self.has_history() => {
+                Some(WidgetAction::PopupAction(PopupAction::HistoryForward))
+            }
+            // Destructive prompts need a deliberate key, a stray Enter must not confirm them
+            KeyCode::Char('y') | KeyCode::Char('Y')
+                if matches!(self.content, PopupContent::Confirm(_)) =>
+            {
+                Some(WidgetAction::PopupAction(PopupAction::Confirmed))
+            }
+            KeyCode::Char('n') | KeyCode::Char('N')
+                if matches!(self.content, PopupContent::Confirm(_)) =>
+            {
+                Some(WidgetAction::PopupAction(PopupAction::Escape))
+            }
+            // Typing narrows the profile list down
+            KeyCode::Char(c)
+                if matches!(self.content, PopupContent::Profiles(_))
+                    && !key_event
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                Some(WidgetAction::PopupAction(PopupAction::FilterInput(c)))
+            }
+            KeyCode::Backspace if matches!(self.content, PopupContent::Profiles(_)) => {
+                Some(WidgetAction::PopupAction(PopupAction::FilterBackspace))
+            }
             KeyCode::Enter => Some(WidgetAction::PopupAction(PopupAction::Enter)),
             KeyCode::Esc => Some(WidgetAction::PopupAction(PopupAction::Escape)),
             _ => None,
@@ -243,9 +697,12 @@ impl WidgetExt for PopupWidget {
         self.active = false;
     }
 
-    /// Controls visibility of the popup
+    /// Controls visibility of the popup; details being shown are added to the history
     fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
+        if visible {
+            self.remember_details();
+        }
     }
 
     /// Returns self as Any for downcasting
@@ -257,6 +714,50 @@ impl WidgetExt for PopupWidget {
     fn process_event(&mut self, event: WidgetAction) -> Option<WidgetAction> {
         match event {
             WidgetAction::PopupAction(event) => match event {
+                PopupAction::ArrowUp if self.is_tree_view() => {
+                    self.tree_cursor = self.tree_cursor.saturating_sub(1);
+                    None
+                }
+                PopupAction::ArrowDown if self.is_tree_view() => {
+                    if self.tree_cursor + 1 < self.tree_lines().len() {
+                        self.tree_cursor += 1;
+                    }
+                    None
+                }
+                PopupAction::ToggleNode => {
+                    self.toggle_selected_node();
+                    None
+                }
+                PopupAction::ToggleRaw => {
+                    self.raw_view = !self.raw_view;
+                    None
+                }
+                PopupAction::ToggleDecoded => {
+                    self.toggle_decoded();
+                    None
+                }
+                PopupAction::ToggleComplete => {
+                    self.toggle_complete();
+                    None
+                }
+                PopupAction::HistoryBack => {
+                    self.step_history(true);
+                    None
+                }
+                PopupAction::HistoryForward => {
+                    self.step_history(false);
+                    None
+                }
+                PopupAction::ArrowUp if self.is_plain_details() => {
+                    self.scroll = self.scroll.saturating_sub(1);
+                    None
+                }
+                PopupAction::ArrowDown if self.is_plain_details() => {
+                    if self.scroll + 1 < self.details_rows(self.content_width.get()).len() {
+                        self.scroll += 1;
+                    }
+                    None
+                }
                 PopupAction::ArrowUp => {
                     if self.selection_index > 0 {
                         self.selection_index -= 1;
@@ -264,22 +765,45 @@ impl WidgetExt for PopupWidget {
                     None
                 }
                 PopupAction::ArrowDown => {
-                    if self.selection_index < self.content.len() - 1 {
+                    if self.selection_index + 1 < self.selectable_items().len() {
                         self.selection_index += 1;
                     }
                     None
                 }
+                PopupAction::Confirmed => {
+                    self.close();
+                    Some(WidgetAction::PopupAction(PopupAction::Confirmed))
+                }
                 PopupAction::Enter => {
-                    if let Some(item) = self.content.get(self.selection_index) {
-                        self.selected_item = Some(item.clone());
+                    match self.content {
+                        PopupContent::Confirm(_) => return None,
+                        // Plain details have nothing to select, so Enter dismisses them
+                        PopupContent::Details(_) => {
+                            self.close();
+                            return None;
+                        }
+                        PopupContent::Profiles(_) | PopupContent::Choices(_) => {}
+                    }
+                    if let Some(item) = self.selectable_items().get(self.selection_index) {
+                        self.selected_item = Some(item.to_string());
                         return Some(WidgetAction::PopupAction(PopupAction::ItemSelected(
                             self.selected_item.clone().unwrap(),
                         )));
                     }
                     None
                 }
+                PopupAction::FilterInput(c) => {
+                    self.filter.push(c);
+                    self.selection_index = 0;
+                    None
+                }
+                PopupAction::FilterBackspace => {
+                    self.filter.pop();
+                    self.selection_index = 0;
+                    None
+                }
                 PopupAction::Escape => {
-                    self.set_visible(false);
+                    self.close();
                     None
                 }
                 _ => None,
@@ -295,14 +819,49 @@ impl WidgetExt for PopupWidget {
         match self.content {
             PopupContent::Profiles(_) => {
                 items.push(("Enter".to_string(), "Select profile".to_string()));
+                items.push(("Type".to_string(), "Filter profiles".to_string()));
+            }
+            PopupContent::Choices(_) => {
+                items.push(("Enter".to_string(), "Select".to_string()));
+            }
+            PopupContent::Details(_) if self.is_tree_view() => {
+                items.push(("Enter/Space".to_string(), "Expand/collapse".to_string()));
+                items.push(("r".to_string(), "Raw JSON".to_string()));
             }
             PopupContent::Details(_) => {
-                items.push(("PgUp/PgDn".to_string(), "Scroll content".to_string()));
+                items.push(("↑/↓".to_string(), "Scroll content".to_string()));
+                if self.details_json.is_some() {
+                    items.push(("r".to_string(), "Tree view".to_string()));
+                }
+            }
+            PopupContent::Confirm(_) => {
+                items.push(("y".to_string(), "Confirm".to_string()));
             }
         }
 
+        if self.encoded_details.is_some() {
+            let decode = match (&self.decoded_details, self.show_decoded) {
+                (_, true) => "Raw message",
+                (Some(None), false) => "Not decodable, showing raw",
+                _ => "Decode message",
+            };
+            items.push(("d".to_string(), decode.to_string()));
+        }
+        if self.other_details.is_some() {
+            let view = if self.show_complete {
+                "Simplified view"
+            } else {
+                "All attribute types"
+            };
+            items.push(("t".to_string(), view.to_string()));
+        }
+        if self.has_history() {
+            items.push(("←/→".to_string(), "Earlier/later details".to_string()));
+        }
         items.push(("Esc".to_string(), "Close popup".to_string()));
-        items.push(("↑/↓".to_string(), "Navigate".to_string()));
+        if !self.is_plain_details() {
+            items.push(("↑/↓".to_string(), "Navigate".to_string()));
+        }
 
         items
     }
@@ -317,3 +876,168 @@ impl WidgetExt for PopupWidget {
         self.title = title;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn press(popup: &mut PopupWidget, code: KeyCode) -> Option<WidgetAction> {
+        popup
+            .handle_input(KeyEvent::new(code, KeyModifiers::NONE))
+            .and_then(|action| popup.process_event(action))
+    }
+
+    #[test]
+    fn recent_profiles_come_first_and_typing_filters() {
+        let profiles = ["ci", "dev", "prod", "prod-admin"]
+            .map(String::from)
+            .to_vec();
+        let mut popup = PopupWidget::new(PopupContent::Profiles(profiles), "Profiles", true, true);
+        popup.set_recent_profiles(vec!["prod".to_string(), "removed".to_string()]);
+        assert_eq!(
+            popup.selectable_items(),
+            ["prod", "ci", "dev", "prod", "prod-admin"]
+        );
+
+        press(&mut popup, KeyCode::Char('A'));
+        press(&mut popup, KeyCode::Char('d'));
+        assert_eq!(popup.selectable_items(), ["prod-admin"]);
+        assert!(matches!(
+            press(&mut popup, KeyCode::Enter),
+            Some(WidgetAction::PopupAction(PopupAction::ItemSelected(profile)))
+                if profile == "prod-admin"
+        ));
+
+        // Esc closes the popup and drops the filter
+        press(&mut popup, KeyCode::Esc);
+        assert!(!popup.is_visible());
+        assert_eq!(popup.selectable_items().len(), 5);
+    }
+
+    #[test]
+    fn esc_closes_details_and_resets_the_scroll() {
+        let details = (1..=30).map(|i| format!("line {}", i)).collect::<Vec<_>>();
+        let mut popup = PopupWidget::new(
+            PopupContent::Details(details.join("\n")),
+            "Details",
+            true,
+            true,
+        );
+
+        press(&mut popup, KeyCode::Down);
+        press(&mut popup, KeyCode::Down);
+        assert_eq!(popup.scroll, 2);
+
+        press(&mut popup, KeyCode::Esc);
+        assert!(!popup.is_visible());
+        assert!(!popup.is_active());
+        assert_eq!(popup.scroll, 0);
+    }
+
+    #[test]
+    fn encoded_details_toggle_between_decoded_and_raw() {
+        let encoded = aws_smithy_types::base64::encode(r#"{"action":"ACCEPT"}"#);
+        let mut popup = PopupWidget::new(
+            PopupContent::Details(encoded.clone()),
+            "Details",
+            true,
+            true,
+        );
+        // Nothing is decoded until asked for
+        assert!(popup.decoded_details.is_none());
+
+        press(&mut popup, KeyCode::Char('d'));
+        assert!(popup.show_decoded);
+        assert_eq!(
+            popup.details_json,
+            Some(serde_json::json!({"action": "ACCEPT"}))
+        );
+
+        press(&mut popup, KeyCode::Char('d'));
+        assert!(!popup.show_decoded);
+        assert!(matches!(&popup.content, PopupContent::Details(raw) if *raw == encoded));
+    }
+
+    #[test]
+    fn complete_details_replace_simplified_ones_on_t() {
+        let mut popup = PopupWidget::new(
+            PopupContent::Details(r#"{"blob":null}"#.into()),
+            "Item",
+            true,
+            true,
+        );
+        // Without a complete form there is nothing to switch to
+        assert!(press(&mut popup, KeyCode::Char('t')).is_none());
+
+        popup.set_complete_details(r#"{"blob":{"B":"aGk="}}"#.to_string());
+        press(&mut popup, KeyCode::Char('t'));
+        assert!(popup.show_complete);
+        assert_eq!(
+            popup.details_json,
+            Some(serde_json::json!({"blob": {"B": "aGk="}}))
+        );
+
+        press(&mut popup, KeyCode::Char('t'));
+        assert!(!popup.show_complete);
+        assert_eq!(popup.details_json, Some(serde_json::json!({"blob": null})));
+    }
+
+    #[test]
+    fn confirmations_need_y_rather_than_enter() {
+        let mut popup = PopupWidget::new(
+            PopupContent::Confirm("Delete?".to_string()),
+            "Confirm",
+            true,
+            true,
+        );
+        assert!(press(&mut popup, KeyCode::Enter).is_none());
+        assert!(matches!(
+            press(&mut popup, KeyCode::Char('y')),
+            Some(WidgetAction::PopupAction(PopupAction::Confirmed))
+        ));
+    }
+
+    fn open_details(popup: &mut PopupWidget, title: &str, details: &str) {
+        popup.set_title(title.to_string());
+        popup.set_content(PopupContent::Details(details.to_string()));
+        popup.set_visible(true);
+    }
+
+    fn shown_details(popup: &PopupWidget) -> String {
+        match &popup.content {
+            PopupContent::Details(details) => details.clone(),
+            _ => String::new(),
+        }
+    }
+
+    #[test]
+    fn arrows_go_back_and_forth_through_viewed_details() {
+        let mut popup =
+            PopupWidget::new(PopupContent::Details(String::new()), "Details", false, true);
+        open_details(&mut popup, "First", "one");
+        // A single entry leaves the arrows to the component
+        assert!(press(&mut popup, KeyCode::Left).is_none());
+
+        open_details(&mut popup, "Second", "two");
+        open_details(&mut popup, "Second", "two");
+        press(&mut popup, KeyCode::Esc);
+        open_details(&mut popup, "Third", "three");
+        assert_eq!(popup.history.len(), 3);
+
+        press(&mut popup, KeyCode::Left);
+        press(&mut popup, KeyCode::Left);
+        press(&mut popup, KeyCode::Left);
+        assert_eq!((popup.title.as_str(), shown_details(&popup).as_str()), ("First", "one"));
+        press(&mut popup, KeyCode::Right);
+        assert_eq!(shown_details(&popup), "two");
+
+        for i in 0..DETAILS_HISTORY_LIMIT {
+            open_details(&mut popup, "Log", &i.to_string());
+        }
+        assert_eq!(popup.history.len(), DETAILS_HISTORY_LIMIT);
+
+        popup.clear_history();
+        assert!(press(&mut popup, KeyCode::Left).is_none());
+    }
+}
@@ -0,0 +1,164 @@
+//! Record sort module
+//!
+//! Orders navigator records by a field, e.g. S3 objects by size or DynamoDB items by an
+//! attribute. Only the fetched records are reordered; nothing is queried again. Records
+//! that are not JSON objects, or lack the field, are ordered by their whole text.
+
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+
+/// Most sort fields derived from the record keys
+pub const MAX_SORT_FIELDS: usize = 8;
+
+/// Field records can be ordered by
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortKey {
+    /// Name shown in the title, e.g. `size`
+    pub label: String,
+    /// JSON attributes holding the field; the first one a record has is used
+    pub attributes: Vec<String>,
+}
+
+impl SortKey {
+    /// Sorts by the given attributes, shown under `label`
+    pub fn new(label: &str, attributes: &[&str]) -> Self {
+        Self {
+            label: label.to_string(),
+            attributes: attributes.iter().map(|attribute| attribute.to_string()).collect(),
+        }
+    }
+
+    /// Sorts by a single attribute of the same name
+    pub fn attribute(name: &str) -> Self {
+        Self::new(name, &[name])
+    }
+
+    /// Value a record is ordered by
+    fn value(&self, record: &str) -> SortValue {
+        let field = match serde_json::from_str::<Map<String, Value>>(record) {
+            Ok(object) => self
+                .attributes
+                .iter()
+                .find_map(|attribute| object.get(attribute).cloned()),
+            Err(_) => None,
+        };
+        match field {
+            Some(Value::Number(number)) => number
+                .as_f64()
+                .map_or_else(|| SortValue::Text(number.to_string()), SortValue::Number),
+            Some(Value::String(text)) => SortValue::from_text(text),
+            Some(other) => SortValue::Text(other.to_string()),
+            None => SortValue::Text(record.to_string()),
+        }
+    }
+}
+
+/// Numbers, including ones with a unit such as `120 bytes`, sort before and apart from text
+#[derive(Debug, PartialEq)]
+enum SortValue {
+    Number(f64),
+    Text(String),
+}
+
+impl SortValue {
+    fn from_text(text: String) -> Self {
+        match text.split_whitespace().next().map(str::parse::<f64>) {
+            Some(Ok(number)) if number.is_finite() => SortValue::Number(number),
+            _ => SortValue::Text(text),
+        }
+    }
+
+    fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortValue::Number(a), SortValue::Number(b)) => a.total_cmp(b),
+            (SortValue::Number(_), SortValue::Text(_)) => Ordering::Less,
+            (SortValue::Text(_), SortValue::Number(_)) => Ordering::Greater,
+            (SortValue::Text(a), SortValue::Text(b)) => a.cmp(b),
+        }
+    }
+}
+
+/// Orders the records by the key, keeping records with equal values in fetch order
+pub fn sort_records(records: &mut [String], key: &SortKey, descending: bool) {
+    let mut keyed: Vec<(SortValue, String)> = records
+        .iter()
+        .map(|record| (key.value(record), record.clone()))
+        .collect();
+    keyed.sort_by(|(a, _), (b, _)| {
+        let order = a.compare(b);
+        if descending { order.reverse() } else { order }
+    });
+    for (slot, (_, record)) in records.iter_mut().zip(keyed) {
+        *slot = record;
+    }
+}
+
+/// Sort keys for the attributes of JSON object records, in the order they are first seen
+pub fn attribute_keys(records: &[String]) -> Vec<SortKey> {
+    let mut names: Vec<String> = Vec::new();
+    for record in records {
+        let Ok(object) = serde_json::from_str::<Map<String, Value>>(record) else {
+            continue;
+        };
+        for name in object.keys() {
+            if names.len() == MAX_SORT_FIELDS {
+                break;
+            }
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+    }
+    names.iter().map(|name| SortKey::attribute(name)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn numbers_with_units_sort_by_value() {
+        let mut objects = records(&[
+            r#"{"key":"b","size":"120 bytes"}"#,
+            r#"{"key":"a","size":"9 bytes"}"#,
+            r#"{"key":"c","size":"1000 bytes"}"#,
+        ]);
+        sort_records(&mut objects, &SortKey::attribute("size"), false);
+        assert_eq!(
+            objects,
+            records(&[
+                r#"{"key":"a","size":"9 bytes"}"#,
+                r#"{"key":"b","size":"120 bytes"}"#,
+                r#"{"key":"c","size":"1000 bytes"}"#,
+            ])
+        );
+
+        sort_records(&mut objects, &SortKey::attribute("key"), true);
+        assert!(objects[0].contains(r#""key":"c""#));
+    }
+
+    #[test]
+    fn records_without_the_field_sort_by_their_text() {
+        let mut lines = records(&[
+            "2024-03-01T12:00:02Z done",
+            r#"{"timestamp":"2024-03-01T12:00:01Z","message":"start"}"#,
+            "2024-03-01T12:00:00Z boot",
+        ]);
+        let time = SortKey::new("time", &["timestamp", "@timestamp"]);
+        sort_records(&mut lines, &time, false);
+        assert_eq!(lines[0], "2024-03-01T12:00:00Z boot");
+        assert!(lines[1].contains("start"));
+        assert_eq!(lines[2], "2024-03-01T12:00:02Z done");
+    }
+
+    #[test]
+    fn sort_fields_come_from_the_record_keys() {
+        let items = records(&[r#"{"id":"1","age":3}"#, r#"{"id":"2","name":"x"}"#, "not json"]);
+        let labels: Vec<String> = attribute_keys(&items).into_iter().map(|key| key.label).collect();
+        assert_eq!(labels, ["age", "id", "name"]);
+    }
+}
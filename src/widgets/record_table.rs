@@ -0,0 +1,141 @@
+//! Record table module
+//!
+//! Lays out navigator records that are JSON objects as table rows, so DynamoDB items
+//! and S3 objects can be compared column by column. When any record is not a JSON
+//! object (e.g. a placeholder message) there is no table and the list view is kept.
+
+use serde_json::{Map, Value};
+
+/// Most columns derived from the record keys; the remaining keys stay in the details view
+pub const MAX_COLUMNS: usize = 8;
+/// Widest a column is laid out, longer cells are cut off
+const MAX_COLUMN_WIDTH: usize = 40;
+
+/// Records split into named columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordTable {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl RecordTable {
+    /// Builds the table from JSON object records.
+    ///
+    /// Uses the given columns, or else the union of the record keys in the order they
+    /// are first seen, capped at [`MAX_COLUMNS`]. Returns `None` for non-uniform records.
+    pub fn from_records(records: &[String], columns: Option<&[String]>) -> Option<Self> {
+        let objects = records
+            .iter()
+            .map(|record| match serde_json::from_str(record) {
+                Ok(Value::Object(object)) => Some(object),
+                _ => None,
+            })
+            .collect::<Option<Vec<Map<String, Value>>>>()?;
+        if objects.is_empty() {
+            return None;
+        }
+
+        let columns = match columns {
+            Some(columns) => columns.to_vec(),
+            None => {
+                let mut columns: Vec<String> = Vec::new();
+                for key in objects.iter().flat_map(|object| object.keys()) {
+                    if columns.len() == MAX_COLUMNS {
+                        break;
+                    }
+                    if !columns.contains(key) {
+                        columns.push(key.clone());
+                    }
+                }
+                columns
+            }
+        };
+
+        let rows = objects
+            .iter()
+            .map(|object| {
+                columns
+                    .iter()
+                    .map(|column| object.get(column).map(cell_text).unwrap_or_default())
+                    .collect()
+            })
+            .collect();
+        Some(Self { columns, rows })
+    }
+
+    /// Width of each column: its longest cell or header, capped
+    pub fn widths(&self) -> Vec<u16> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let longest = self
+                    .rows
+                    .iter()
+                    .map(|row| row[index].chars().count())
+                    .chain(std::iter::once(column.chars().count()))
+                    .max()
+                    .unwrap_or_default();
+                longest.min(MAX_COLUMN_WIDTH) as u16
+            })
+            .collect()
+    }
+}
+
+/// Text of a cell; strings are shown without their quotes
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn columns_are_the_union_of_record_keys() {
+        let table = RecordTable::from_records(
+            &records(&[r#"{"id":"1","name":"a"}"#, r#"{"id":"2","count":3}"#]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(table.columns, vec!["id", "name", "count"]);
+        assert_eq!(table.rows[1], vec!["2", "", "3"]);
+    }
+
+    #[test]
+    fn derived_columns_are_capped() {
+        let wide: Map<String, Value> = (0..20)
+            .map(|i| (format!("attr{:02}", i), Value::from(i)))
+            .collect();
+        let table = RecordTable::from_records(&[Value::Object(wide).to_string()], None).unwrap();
+        assert_eq!(table.columns.len(), MAX_COLUMNS);
+    }
+
+    #[test]
+    fn fixed_columns_pick_fields() {
+        let columns = vec!["key".to_string(), "size".to_string()];
+        let table = RecordTable::from_records(
+            &records(&[r#"{"key":"a.txt","size":"3 bytes","etag":"x"}"#]),
+            Some(&columns),
+        )
+        .unwrap();
+        assert_eq!(table.rows, vec![vec!["a.txt", "3 bytes"]]);
+    }
+
+    #[test]
+    fn non_object_records_have_no_table() {
+        assert_eq!(
+            RecordTable::from_records(&records(&[r#"{"id":"1"}"#, "No objects found"]), None),
+            None
+        );
+        assert_eq!(RecordTable::from_records(&[], None), None);
+    }
+}
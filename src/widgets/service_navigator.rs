@@ -1,14 +1,56 @@
 use crate::event_managment::event::{WidgetAction, WidgetEventType, WidgetType};
-use crate::{event_managment::event::ServiceNavigatorEvent, widgets::WidgetExt};
+use crate::{
+    event_managment::event::ServiceNavigatorEvent,
+    services::read_config,
+    theme::theme,
+    widgets::{
+        WidgetExt, clipboard,
+        filter_query::FilterQuery,
+        log_severity,
+        record_sort::{self, SortKey},
+        record_table::RecordTable,
+    },
+};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Rect},
-    style::{Color, Style},
+    layout::{Alignment, Constraint, Rect},
+    style::{Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, BorderType, Borders, Paragraph, Widget},
+    widgets::{
+        Block, BorderType, Borders, Cell as TableCell, Paragraph, Row, StatefulWidget, Table,
+        TableState, Widget, Wrap,
+    },
 };
 use std::any::Any;
+use std::cell::Cell;
+
+// Viewport height assumed until the navigator has been rendered once
+const DEFAULT_VIEWPORT_HEIGHT: usize = 10;
+// Columns moved by one Left/Right press in horizontal scroll mode
+const HORIZONTAL_SCROLL_STEP: usize = 8;
+
+/// How records longer than the navigator width are shown
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LineMode {
+    /// One row per record, cut off at the navigator width
+    Truncate,
+    /// Records continue on the following rows
+    Wrap,
+    /// One row per record, shifted by a column offset with Left/Right
+    Scroll,
+}
+
+impl LineMode {
+    /// Next mode in the order the toggle key cycles through
+    fn next(self) -> Self {
+        match self {
+            LineMode::Truncate => LineMode::Wrap,
+            LineMode::Wrap => LineMode::Scroll,
+            LineMode::Scroll => LineMode::Truncate,
+        }
+    }
+}
 
 /// Content types that can be displayed in the navigator
 /// Services are AWS service types, Records are string entries like log groups
@@ -26,11 +68,38 @@ pub struct ServiceNavigator {
     content: NavigatorContent,          // Original unfiltered content
     filtered_content: NavigatorContent, // Content after applying filters
     filter_text: String,                // Current filter string
+    filter_pinned: bool,                // Whether the filter survives resets and new content
     selected_index: usize,              // Currently selected item
     scroll_offset: usize,               // Scroll position for viewing large lists
     active: bool,                       // Whether this widget has focus
     visible: bool,                      // Whether this widget should be rendered
     filter_mode: bool,                  // Whether filter input mode is active
+    search_text: String,                // Current search query (highlights without hiding rows)
+    search_mode: bool,                  // Whether search input mode is active
+    multi_select: bool,                 // Whether records can be marked with Space
+    marked_records: Vec<String>,        // Records marked for bulk operations
+    viewport_height: Cell<usize>,       // Item rows shown by the last render
+    page_size: Option<usize>,           // Fixed PageUp/PageDown jump, viewport height if unset
+    wrap_around: bool,                  // Whether Up/Down wrap at the ends of the list
+    severity_colors: bool,              // Whether records are colored by detected log severity
+    record_highlight: Option<fn(&str) -> bool>, // Records shown in the warning color
+    record_colors: Option<fn(&str) -> Option<Style>>, // Style picked per record, e.g. by state
+    line_mode: LineMode,                // How records wider than the navigator are shown
+    column_offset: usize,               // Columns hidden on the left in horizontal scroll mode
+    table_toggle: bool,                 // Whether `t` switches JSON records to a table
+    table_columns: Option<Vec<String>>, // Fixed table columns, derived from the records if unset
+    table_view: bool,                   // Whether JSON records are shown as a table
+    table_offset: Cell<usize>,          // First table row shown by the last render
+    placeholder: Option<String>,        // Message shown instead of records, never selectable
+    pretty_toggle: bool,                // Whether `p` switches JSON records to pretty-printing
+    pretty_json: bool,                  // Whether JSON records span several indented rows
+    selection_symbol: String,           // Marks the selected row, from the theme
+    truncation_symbol: String,          // Ends rows cut at the navigator width, from the theme
+    total_available: Option<usize>,     // Records the service has in all, when more than fetched
+    sort_toggle: bool,                  // Whether `s` cycles the order of the records
+    sort_keys: Option<Vec<SortKey>>,    // Fixed sort fields, derived from the records if unset
+    sort: Option<SortKey>,              // Field the records are shown in order of, if any
+    sort_descending: bool,              // Whether the sort runs from the largest value down
 }
 
 impl ServiceNavigator {
@@ -42,11 +111,279 @@ impl ServiceNavigator {
             content: content.clone(),
             filtered_content: content,
             filter_text: String::new(),
+            filter_pinned: false,
             selected_index: 0,
             scroll_offset: 0,
             active,
             visible: true,
             filter_mode: false, // Start with filter mode disabled
+            search_text: String::new(),
+            search_mode: false,
+            multi_select: false, // Opt-in per navigator instance
+            marked_records: Vec::new(),
+            viewport_height: Cell::new(DEFAULT_VIEWPORT_HEIGHT),
+            page_size: read_config::get_navigator_page_size(),
+            wrap_around: read_config::get_navigator_wrap_around(),
+            severity_colors: false, // Opt-in for navigators showing log lines
+            record_highlight: None,
+            record_colors: None,
+            line_mode: LineMode::Truncate,
+            column_offset: 0,
+            table_toggle: false, // Opt-in for navigators showing JSON records
+            table_columns: None,
+            table_view: false,
+            table_offset: Cell::new(0),
+            placeholder: None,
+            pretty_toggle: false, // Opt-in for navigators showing JSON records
+            pretty_json: false,
+            selection_symbol: theme().selection_symbol.clone(),
+            truncation_symbol: theme().truncation_symbol.clone(),
+            total_available: None,
+            sort_toggle: false, // Opt-in for navigators showing records worth ordering
+            sort_keys: None,
+            sort: None,
+            sort_descending: false,
+        }
+    }
+
+    /// Returns the number of items PageUp/PageDown move the selection
+    fn page_jump(&self) -> usize {
+        self.page_size
+            .unwrap_or_else(|| self.viewport_height.get())
+            .max(1)
+    }
+
+    /// Enables or disables marking records with Space for bulk operations
+    pub fn set_multi_select(&mut self, enabled: bool) {
+        self.multi_select = enabled;
+        self.marked_records.clear();
+    }
+
+    /// Lets `t` switch between the list and a table of JSON records, with fixed
+    /// columns or, if `None`, columns derived from the record keys
+    pub fn enable_table_view(&mut self, columns: Option<Vec<String>>) {
+        self.table_toggle = true;
+        self.table_columns = columns;
+    }
+
+    /// Lets `p` switch JSON records between the compact single row and pretty-printed,
+    /// indented rows; compact stays the default since pretty-printing parses every
+    /// visible record on each render
+    pub fn enable_pretty_json(&mut self) {
+        self.pretty_toggle = true;
+    }
+
+    /// Lets `s` cycle the records through the given sort fields or, if `None`, fields
+    /// derived from the record keys, and back to fetch order; `S` reverses the order
+    pub fn enable_sorting(&mut self, keys: Option<Vec<SortKey>>) {
+        self.sort_toggle = true;
+        self.sort_keys = keys;
+    }
+
+    /// Orders the records by the next sort field, or by fetch order after the last one,
+    /// keeping the selected record selected
+    fn cycle_sort(&mut self) {
+        let keys = match (&self.sort_keys, &self.content) {
+            (Some(keys), _) => keys.clone(),
+            (None, NavigatorContent::Records(records)) => record_sort::attribute_keys(records),
+            (None, NavigatorContent::Services(_)) => return,
+        };
+        let next = match &self.sort {
+            Some(current) => keys.iter().position(|key| key == current).map_or(0, |i| i + 1),
+            None => 0,
+        };
+        self.sort = keys.get(next).cloned();
+        self.reorder();
+    }
+
+    /// Shows the records in their new order; the content itself stays in fetch order
+    fn reorder(&mut self) {
+        if self.placeholder.is_none() {
+            self.set_content_keeping_selection(self.content.clone());
+        }
+    }
+
+    /// Sorts the shown records by the active sort field, if any
+    fn sort_filtered(&mut self) {
+        if let (Some(key), NavigatorContent::Records(records)) =
+            (&self.sort, &mut self.filtered_content)
+        {
+            record_sort::sort_records(records, key, self.sort_descending);
+        }
+    }
+
+    /// Rows of text a record is shown as: its pretty-printed lines in pretty mode
+    /// when it is a JSON object or array, otherwise the record itself
+    fn record_lines(&self, record: &str) -> Vec<String> {
+        if self.pretty_json
+            && let Ok(value @ (serde_json::Value::Object(_) | serde_json::Value::Array(_))) =
+                serde_json::from_str::<serde_json::Value>(record)
+            && let Ok(pretty) = serde_json::to_string_pretty(&value)
+        {
+            return pretty.lines().map(String::from).collect();
+        }
+        vec![record.to_string()]
+    }
+
+    /// Screen rows a record takes at the given width, counting wrapped continuations
+    fn record_rows(&self, record: &str, width: usize) -> usize {
+        let prefix_width = self.record_prefix_width();
+        self.record_lines(record)
+            .iter()
+            .map(|line| match self.line_mode {
+                LineMode::Wrap => {
+                    wrapped_rows(prefix_width + line.chars().count(), width, prefix_width)
+                }
+                _ => 1,
+            })
+            .sum()
+    }
+
+    /// Table of the filtered records, if the table view is on and they are JSON objects
+    fn record_table(&self) -> Option<RecordTable> {
+        match &self.filtered_content {
+            NavigatorContent::Records(records) if self.table_view => {
+                RecordTable::from_records(records, self.table_columns.as_deref())
+            }
+            _ => None,
+        }
+    }
+
+    /// Renders the records as a table, keeping its own scroll position between renders
+    fn render_table(&self, table: &RecordTable, area: Rect, buf: &mut Buffer) {
+        let NavigatorContent::Records(records) = &self.filtered_content else {
+            return;
+        };
+        let mut area = area;
+        if self.filter_mode || self.search_mode {
+            let hint = if self.filter_mode {
+                "Type to filter, Esc to exit filter mode"
+            } else {
+                "Type to search, Enter to confirm, n/N to jump between matches"
+            };
+            Paragraph::new(hint)
+                .style(Style::default().fg(theme().text_fg))
+                .render(Rect { height: 1, ..area }, buf);
+            area.y += 1;
+            area.height = area.height.saturating_sub(1);
+        }
+
+        let mut widths: Vec<Constraint> =
+            table.widths().into_iter().map(Constraint::Length).collect();
+        let mut header: Vec<TableCell> = table
+            .columns
+            .iter()
+            .map(|column| TableCell::from(column.clone()))
+            .collect();
+        if self.multi_select {
+            widths.insert(0, Constraint::Length(3));
+            header.insert(0, TableCell::from(""));
+        }
+        let rows = table.rows.iter().zip(records).map(|(row, record)| {
+            let mut cells: Vec<TableCell> =
+                row.iter().map(|cell| TableCell::from(cell.clone())).collect();
+            if self.multi_select {
+                let mark = if self.marked_records.contains(record) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                cells.insert(0, TableCell::from(mark));
+            }
+            Row::new(cells).style(self.record_style(record))
+        });
+
+        let widget = Table::new(rows, widths)
+            .header(
+                Row::new(header).style(
+                    Style::default()
+                        .fg(theme().title_fg)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            )
+            .column_spacing(2)
+            .highlight_symbol(self.selection_symbol.as_str())
+            .row_highlight_style(
+                Style::default()
+                    .fg(theme().selection_fg)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let mut state = TableState::default()
+            .with_offset(self.table_offset.get())
+            .with_selected(Some(self.selected_index));
+        StatefulWidget::render(widget, area, buf, &mut state);
+        self.table_offset.set(state.offset());
+        // The header takes one row, the rest are records
+        self.viewport_height
+            .set((area.height as usize).saturating_sub(1).max(1));
+    }
+
+    /// Enables or disables coloring records by their detected log severity
+    pub fn set_severity_colors(&mut self, enabled: bool) {
+        self.severity_colors = enabled;
+    }
+
+    /// Returns whether records are colored by their detected log severity
+    pub fn severity_colors(&self) -> bool {
+        self.severity_colors
+    }
+
+    /// Shows the records the predicate accepts in the warning color, e.g. archived objects
+    pub fn set_record_highlight(&mut self, highlight: Option<fn(&str) -> bool>) {
+        self.record_highlight = highlight;
+    }
+
+    /// Styles records with the style the function picks for them, plain text if none
+    pub fn set_record_colors(&mut self, colors: Option<fn(&str) -> Option<Style>>) {
+        self.record_colors = colors;
+    }
+
+    /// Style of an unselected record: highlighted, colored per record or by severity, or
+    /// plain text
+    fn record_style(&self, record: &str) -> Style {
+        if self.record_highlight.is_some_and(|highlight| highlight(record)) {
+            return Style::default().fg(theme().warning_fg);
+        }
+        if let Some(style) = self.record_colors.and_then(|colors| colors(record)) {
+            return style;
+        }
+        self.severity_colors
+            .then(|| log_severity::detect_severity(record))
+            .flatten()
+            .and_then(|severity| severity.style())
+            .unwrap_or_else(|| Style::default().fg(theme().text_fg))
+    }
+
+    /// Returns the records currently marked for bulk operations
+    pub fn marked_records(&self) -> Vec<String> {
+        self.marked_records.clone()
+    }
+
+    /// Unmarks all records
+    pub fn clear_marked_records(&mut self) {
+        self.marked_records.clear();
+    }
+
+    /// Returns the label of the currently highlighted item
+    pub fn selected_label(&self) -> Option<String> {
+        self.item_label(self.selected_index)
+    }
+
+    /// Returns whether the navigator is capturing typed characters (filter or search)
+    pub fn is_capturing_input(&self) -> bool {
+        self.filter_mode || self.search_mode
+    }
+
+    /// Marks or unmarks the currently selected record
+    fn toggle_marked(&mut self) {
+        if let NavigatorContent::Records(records) = &self.filtered_content
+            && let Some(record) = records.get(self.selected_index)
+        {
+            if let Some(position) = self.marked_records.iter().position(|r| r == record) {
+                self.marked_records.remove(position);
+            } else {
+                self.marked_records.push(record.clone());
+            }
         }
     }
 
@@ -58,6 +395,22 @@ impl ServiceNavigator {
         }
     }
 
+    /// Describes how many records were fetched, are available in all and pass the filter,
+    /// e.g. "Records 500 fetched / 1234 total / 37 shown"
+    ///
+    /// The total is left out while it is unknown or no larger than what was fetched, and
+    /// the shown count while no filter is set.
+    fn record_counts(&self, fetched: usize, shown: usize) -> String {
+        let mut counts = format!("Records {} fetched", fetched);
+        if let Some(total) = self.total_available.filter(|total| *total > fetched) {
+            counts.push_str(&format!(" / {} total", total));
+        }
+        if !self.filter_text.is_empty() {
+            counts.push_str(&format!(" / {} shown", shown));
+        }
+        counts
+    }
+
     /// Returns a widget action for the currently selected item
     fn selected_item(&self) -> Option<WidgetAction> {
         match &self.filtered_content {
@@ -90,10 +443,104 @@ impl ServiceNavigator {
         }
     }
 
+    /// Returns the display label of the item at the given index in the filtered content
+    fn item_label(&self, index: usize) -> Option<String> {
+        match &self.filtered_content {
+            NavigatorContent::Services(services) => services.get(index).map(|s| s.to_string()),
+            NavigatorContent::Records(records) => records.get(index).cloned(),
+        }
+    }
+
+    /// Finds the next item containing the search text, wrapping around the list
+    ///
+    /// When `include_current` is set the currently selected item is checked first,
+    /// which keeps the selection in place while the query is being typed
+    fn find_match(&self, forward: bool, include_current: bool) -> Option<usize> {
+        let len = self.content_len();
+        if len == 0 || self.search_text.is_empty() {
+            return None;
+        }
+
+        let query = self.search_text.to_lowercase();
+        let start = if include_current { 0 } else { 1 };
+        (start..len + start)
+            .map(|step| {
+                if forward {
+                    (self.selected_index + step) % len
+                } else {
+                    (self.selected_index + len * 2 - step) % len
+                }
+            })
+            .find(|&index| {
+                self.item_label(index)
+                    .is_some_and(|label| label.to_lowercase().contains(&query))
+            })
+    }
+
+    /// Moves the selection to the next (or previous) item matching the search text
+    fn jump_to_match(&mut self, forward: bool, include_current: bool) {
+        if let Some(index) = self.find_match(forward, include_current) {
+            self.selected_index = index;
+            self.update_scroll_offset();
+        }
+    }
+
+    /// Splits a line into spans, highlighting case-insensitive occurrences of the search text
+    fn highlight_matches(&self, text: String, style: Style) -> Vec<Span<'static>> {
+        let lowered = text.to_lowercase();
+        // Byte offsets only line up when lowercasing preserves length
+        if self.search_text.is_empty() || lowered.len() != text.len() {
+            return vec![Span::styled(text, style)];
+        }
+
+        let query = self.search_text.to_lowercase();
+        let highlight = style.fg(theme().highlight_fg).bg(theme().highlight_bg);
+        let mut spans = Vec::new();
+        let mut last = 0;
+        for (start, matched) in lowered.match_indices(&query) {
+            if start > last {
+                spans.push(Span::styled(text[last..start].to_string(), style));
+            }
+            let end = start + matched.len();
+            spans.push(Span::styled(text[start..end].to_string(), highlight));
+            last = end;
+        }
+        if last < text.len() {
+            spans.push(Span::styled(text[last..].to_string(), style));
+        }
+        spans
+    }
+
+    /// Length in characters of the longest record, the limit for horizontal scrolling
+    fn longest_record(&self) -> usize {
+        match &self.filtered_content {
+            NavigatorContent::Records(records) => records
+                .iter()
+                .map(|record| record.chars().count())
+                .max()
+                .unwrap_or(0),
+            NavigatorContent::Services(_) => 0,
+        }
+    }
+
+    /// Width of the selection marker (and checkbox) shown before each record
+    fn record_prefix_width(&self) -> usize {
+        self.selection_prefix(false).chars().count() + if self.multi_select { 4 } else { 0 }
+    }
+
+    /// Lead of a row: the selection symbol, or as many spaces for the other rows
+    fn selection_prefix(&self, selected: bool) -> String {
+        if selected {
+            self.selection_symbol.clone()
+        } else {
+            " ".repeat(self.selection_symbol.chars().count())
+        }
+    }
+
     /// Adjusts scroll position to keep selected item visible
-    fn update_scroll_offset(&mut self, height: usize) {
+    fn update_scroll_offset(&mut self) {
         // Make sure height is at least 1 to avoid division by zero
-        let height = height.max(1);
+        let height = self.viewport_height.get().max(1);
 
         // If the selected index is above the current scroll position, scroll up
         if self.selected_index < self.scroll_offset {
@@ -109,7 +556,9 @@ impl ServiceNavigator {
         }
     }
 
-    /// Applies a filter to the content, showing only items containing the filter text
+    /// Applies a filter to the content, showing only items matching the filter query
+    ///
+    /// See [`FilterQuery`] for the AND (space), OR (`|`) and negation (`-`) syntax
     pub fn apply_filter(&mut self, filter: &str) {
         self.filter_text = filter.to_lowercase();
 
@@ -117,9 +566,11 @@ impl ServiceNavigator {
         self.selected_index = 0;
         self.scroll_offset = 0;
 
-        // If filter is empty, show all content
-        if self.filter_text.is_empty() {
+        // If filter has no terms, show all content
+        let query = FilterQuery::parse(&self.filter_text);
+        if query.is_empty() {
             self.filtered_content = self.content.clone();
+            self.sort_filtered();
             return;
         }
 
@@ -128,12 +579,7 @@ impl ServiceNavigator {
             NavigatorContent::Services(services) => {
                 let filtered = services
                     .iter()
-                    .filter(|service| {
-                        service
-                            .to_string()
-                            .to_lowercase()
-                            .contains(&self.filter_text)
-                    })
+                    .filter(|service| query.matches(service.display_label()))
                     .cloned()
                     .collect();
                 self.filtered_content = NavigatorContent::Services(filtered);
@@ -141,12 +587,13 @@ impl ServiceNavigator {
             NavigatorContent::Records(records) => {
                 let filtered = records
                     .iter()
-                    .filter(|record| record.to_lowercase().contains(&self.filter_text))
+                    .filter(|record| query.matches(record))
                     .cloned()
                     .collect();
                 self.filtered_content = NavigatorContent::Records(filtered);
             }
         }
+        self.sort_filtered();
     }
 
     /// Adds a character to the filter and applies it
@@ -168,13 +615,49 @@ impl ServiceNavigator {
     fn clear_filter(&mut self) {
         self.filter_text.clear();
         self.filtered_content = self.content.clone();
+        self.sort_filtered();
         self.filter_mode = false;
     }
 
+    /// Clears the filter, search, sort, marks and selection, keeping the content.
+    /// A pinned filter is kept and applied again.
+    pub fn reset_view(&mut self) {
+        self.sort = None;
+        self.sort_descending = false;
+        if self.filter_pinned {
+            let filter_text = self.filter_text.clone();
+            self.apply_filter(&filter_text);
+            self.filter_mode = false;
+        } else {
+            self.clear_filter();
+        }
+        self.search_text.clear();
+        self.search_mode = false;
+        self.marked_records.clear();
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+    }
+
+    /// Clears the records and shows a message in their place, e.g. that a listing
+    /// came back empty, so the message itself can never be selected
+    pub fn show_placeholder(&mut self, message: impl Into<String>) {
+        self.set_content(NavigatorContent::Records(Vec::new()));
+        self.placeholder = Some(message.into());
+    }
+
+    /// Sets how many records the service has in all, shown next to the fetched count
+    /// until the content is replaced
+    pub fn set_total_available(&mut self, total: Option<usize>) {
+        self.total_available = total;
+    }
+
     /// Sets new content for the navigator
     /// If a filter is active, it will be applied to the new content
     pub fn set_content(&mut self, content: NavigatorContent) {
+        self.placeholder = None;
+        self.total_available = None;
         self.content = content.clone();
+        self.marked_records.clear();
 
         // Apply existing filter to new content
         if !self.filter_text.is_empty() {
@@ -182,54 +665,112 @@ impl ServiceNavigator {
             self.apply_filter(&filter_text_clone);
         } else {
             self.filtered_content = content;
+            self.sort_filtered();
         }
 
         self.selected_index = 0;
         self.scroll_offset = 0;
     }
-    pub fn update_content(&mut self, content: NavigatorContent) {
-        self.content = match (&self.content, &content) {
-            (NavigatorContent::Services(existing), NavigatorContent::Services(new)) => {
-                NavigatorContent::Services([existing.clone(), new.clone()].concat())
-            }
-            (NavigatorContent::Records(existing), NavigatorContent::Records(new)) => {
-                NavigatorContent::Records([existing.clone(), new.clone()].concat())
-            }
-            _ => self.content.clone(), // Handle mismatched types gracefully
+    /// Replaces the content of a background refresh, keeping the filter, the marks that
+    /// are still listed and the highlighted record at the same row of the view
+    ///
+    /// Records are matched by identity, so the selection follows its record when records
+    /// are added or dropped above it; it starts over at the top once the record is gone.
+    pub fn set_content_keeping_selection(&mut self, content: NavigatorContent) {
+        let selected = self.selected_label();
+        let (index, row) = (
+            self.selected_index,
+            self.selected_index.saturating_sub(self.scroll_offset),
+        );
+
+        self.placeholder = None;
+        self.content = content;
+        if let NavigatorContent::Records(records) = &self.content {
+            self.marked_records.retain(|marked| records.contains(marked));
+        }
+        let filter_text = self.filter_text.clone();
+        self.apply_filter(&filter_text);
+
+        // The nearest copy of the record, as log lines may repeat
+        let Some(label) = selected else {
+            return;
         };
-        // Apply existing filter to new content
-        if !self.filter_text.is_empty() {
-            let filter_text_clone = self.filter_text.clone();
-            self.apply_filter(&filter_text_clone);
-        } else {
-            self.filtered_content = content;
+        if let Some(found) = (0..self.content_len())
+            .filter(|&i| self.item_label(i).as_deref() == Some(label.as_str()))
+            .min_by_key(|&i| i.abs_diff(index))
+        {
+            self.selected_index = found;
+            self.scroll_offset = found.saturating_sub(row);
+        }
+    }
+
+    /// Appends records to the end of the list, keeping the filter and the selection
+    pub fn append_records(&mut self, records: Vec<String>) {
+        let mut content = match &self.content {
+            NavigatorContent::Records(existing) => existing.clone(),
+            NavigatorContent::Services(_) => return,
+        };
+        content.extend(records);
+        self.set_content_keeping_selection(NavigatorContent::Records(content));
+    }
+
+    /// Appends records, selecting the last one so the view follows a growing list such as
+    /// tailed logs while the last record is selected; once another record was picked, e.g.
+    /// to read it, the selection stays on that record. The oldest records are dropped once
+    /// there are more than `cap`.
+    pub fn append_and_follow(&mut self, records: Vec<String>, cap: Option<usize>) {
+        let following = self.selected_index + 1 >= self.content_len();
+        let mut content = match &self.content {
+            NavigatorContent::Records(existing) => existing.clone(),
+            NavigatorContent::Services(_) => return,
+        };
+        content.extend(records);
+        if let Some(cap) = cap {
+            content.drain(..content.len().saturating_sub(cap));
+        }
+
+        self.set_content_keeping_selection(NavigatorContent::Records(content));
+        if following {
+            self.selected_index = self.content_len().saturating_sub(1);
+            self.update_scroll_offset();
         }
-        self.selected_index = 0;
-        self.scroll_offset = 0;
     }
 }
 
-impl WidgetExt for ServiceNavigator {
-    /// Renders the navigator widget to the buffer
-    fn render(&self, area: Rect, buf: &mut Buffer) {
+impl ServiceNavigator {
+    /// Renders the navigator, showing `prompt` in place of an empty list that has no
+    /// placeholder, e.g. to tell newcomers what to pick before anything is loaded
+    pub fn render_with_prompt(&self, area: Rect, buf: &mut Buffer, prompt: Option<&str>) {
         if !self.visible {
             return;
         }
 
         // Set border style based on active state
         let border_style = if self.active {
-            Style::default().fg(Color::Red)
+            Style::default().fg(theme().active_border)
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(theme().text_fg)
         };
 
         // Modify title to show filter status
         let mut title = self.title.clone();
+        if self.search_mode {
+            title = format!("[Search: {}] {} ", self.search_text, title);
+        } else if !self.search_text.is_empty() {
+            title = format!("[Searched: {}] {} ", self.search_text, title);
+        }
         if self.filter_mode {
             title = format!("[Filter: {}] {} ", self.filter_text, title);
         } else if !self.filter_text.is_empty() {
             title = format!("[Filtered: {}] {} ", self.filter_text, title);
         }
+        if let Some(sort) = &self.sort {
+            let direction = if self.sort_descending { "↓" } else { "↑" };
+            title = format!("[Sorted: {} {}] {}", sort.label, direction, title);
+        }
+        if !self.marked_records.is_empty() {
+            title = format!("[{} selected] {}", self.marked_records.len(), title);
+        }
 
         // Create outer block with title and active border
         let outer_block = Block::default()
@@ -238,6 +779,9 @@ impl WidgetExt for ServiceNavigator {
             .border_type(BorderType::Double)
             .border_style(border_style);
 
+        // Shown instead of the list when the table view is on and the records allow it
+        let table = self.record_table();
+
         // Get item count information
         let total_items = self.content_len();
         let original_total = match &self.content {
@@ -246,20 +790,27 @@ impl WidgetExt for ServiceNavigator {
         };
 
         // Create inner title with item count
+        let pinned = if self.filter_pinned { " [filter pinned]" } else { "" };
         let inner_title = match &self.content {
             NavigatorContent::Services(_) => {
                 if self.filter_text.is_empty() {
-                    format!("Available Services ({})", total_items)
+                    format!("Available Services ({}){}", total_items, pinned)
                 } else {
-                    format!("Available Services ({}/{})", total_items, original_total)
+                    format!("Available Services ({}/{}){}", total_items, original_total, pinned)
                 }
             }
             NavigatorContent::Records(_) => {
-                if self.filter_text.is_empty() {
-                    format!("Available Records ({})", total_items)
-                } else {
-                    format!("Available Records ({}/{})", total_items, original_total)
+                let mut mode = match self.line_mode {
+                    _ if table.is_some() => String::from(" [table]"),
+                    LineMode::Truncate => String::new(),
+                    LineMode::Wrap => String::from(" [wrap]"),
+                    LineMode::Scroll => format!(" [scroll +{}]", self.column_offset),
+                };
+                if self.pretty_json && table.is_none() {
+                    mode.push_str(" [pretty]");
                 }
+                mode.push_str(pinned);
+                format!("{}{}", self.record_counts(original_total, total_items), mode)
             }
         };
 
@@ -268,19 +819,24 @@ impl WidgetExt for ServiceNavigator {
             .title(inner_title)
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::White));
+            .border_style(Style::default().fg(theme().text_fg));
 
         outer_block.render(area, buf);
 
         // Calculate inner area for content
-        let inner_area = Rect::new(area.x + 2, area.y + 2, area.width - 4, area.height - 4);
+        let inner_area = Rect::new(
+            area.x.saturating_add(2),
+            area.y.saturating_add(2),
+            area.width.saturating_sub(4),
+            area.height.saturating_sub(4),
+        );
         inner_block.render(inner_area, buf);
         // Text content area with padding
         let text_area = Rect::new(
-            inner_area.x + 2,
-            inner_area.y + 1,
-            inner_area.width - 4,
-            inner_area.height - 2,
+            inner_area.x.saturating_add(2),
+            inner_area.y.saturating_add(1),
+            inner_area.width.saturating_sub(4),
+            inner_area.height.saturating_sub(2),
         );
 
         // Calculate visible height (accounting for borders and padding)
@@ -289,19 +845,29 @@ impl WidgetExt for ServiceNavigator {
         // If there's no content after filtering, show a message
         let total_items = self.content_len();
         if total_items == 0 {
-            let message = if !self.filter_text.is_empty() {
-                "No items match your filter"
+            let (message, color) = if !self.filter_text.is_empty() {
+                ("No items match your filter", theme().warning_fg)
+            } else if let Some(placeholder) = &self.placeholder {
+                (placeholder.as_str(), theme().warning_fg)
+            } else if let Some(prompt) = prompt {
+                (prompt, theme().help_fg)
             } else {
-                "No items available"
+                ("No items available", theme().warning_fg)
             };
 
             let paragraph = Paragraph::new(message)
                 .alignment(Alignment::Center)
-                .style(Style::default().fg(Color::Yellow));
+                .wrap(Wrap { trim: true })
+                .style(Style::default().fg(color));
             paragraph.render(text_area, buf);
             return;
         }
 
+        if let Some(table) = &table {
+            self.render_table(table, text_area, buf);
+            return;
+        }
+
         // Generate content with scroll indicators and filtered items
         let mut lines: Vec<Line> = Vec::new();
 
@@ -309,7 +875,12 @@ impl WidgetExt for ServiceNavigator {
         if self.filter_mode {
             lines.push(Line::from(Span::styled(
                 "Type to filter, Esc to exit filter mode",
-                Style::default().fg(Color::White),
+                Style::default().fg(theme().text_fg),
+            )));
+        } else if self.search_mode {
+            lines.push(Line::from(Span::styled(
+                "Type to search, Enter to confirm, n/N to jump between matches",
+                Style::default().fg(theme().text_fg),
             )));
         }
 
@@ -317,12 +888,12 @@ impl WidgetExt for ServiceNavigator {
         if self.scroll_offset > 0 {
             lines.push(Line::from(Span::styled(
                 "▲ Scroll up for more",
-                Style::default().fg(Color::White),
+                Style::default().fg(theme().text_fg),
             )));
         }
 
         // Calculate how many elements to show based on available height and scroll indicators
-        let filter_bar_height = if self.filter_mode { 1 } else { 0 };
+        let filter_bar_height = if self.filter_mode || self.search_mode { 1 } else { 0 };
         let scroll_indicators_height = if self.scroll_offset > 0 { 1 } else { 0 }
             + if self.scroll_offset + visible_height < total_items {
                 1
@@ -333,13 +904,36 @@ impl WidgetExt for ServiceNavigator {
         let available_height =
             visible_height.saturating_sub(scroll_indicators_height + filter_bar_height);
 
+        // Wrapped and pretty-printed records take several rows, so fewer of them fit
+        let shown_items = match &self.filtered_content {
+            NavigatorContent::Records(records)
+                if self.line_mode == LineMode::Wrap || self.pretty_json =>
+            {
+                let width = text_area.width as usize;
+                let mut rows = 0;
+                let mut count = 0;
+                for record in records.iter().skip(self.scroll_offset) {
+                    let record_rows = self.record_rows(record, width);
+                    if count > 0 && rows + record_rows > available_height {
+                        break;
+                    }
+                    rows += record_rows;
+                    count += 1;
+                }
+                count
+            }
+            _ => available_height,
+        };
+        // Remember the real viewport so scrolling and paging match what is on screen
+        self.viewport_height.set(shown_items);
+
         // Add visible items with proper scrolling
         match &self.filtered_content {
             NavigatorContent::Services(services) => {
                 if services.is_empty() && !self.filter_text.is_empty() {
                     lines.push(Line::from(Span::styled(
                         "No matching services found",
-                        Style::default().fg(Color::White),
+                        Style::default().fg(theme().text_fg),
                     )));
                 } else {
                     for (i, service) in services
@@ -351,20 +945,21 @@ impl WidgetExt for ServiceNavigator {
                         let actual_index = i + self.scroll_offset;
                         let style = if actual_index == self.selected_index {
                             Style::default()
-                                .fg(Color::Yellow)
+                                .fg(theme().selection_fg)
                                 .add_modifier(ratatui::style::Modifier::BOLD)
                         } else {
-                            Style::default().fg(Color::White)
+                            Style::default().fg(theme().text_fg)
                         };
 
-                        let prefix = if actual_index == self.selected_index {
-                            "> "
-                        } else {
-                            "  "
-                        };
-                        lines.push(Line::from(Span::styled(
-                            format!("{}{}", prefix, service),
-                            style,
+                        let prefix = self.selection_prefix(actual_index == self.selected_index);
+                        let mut spans = vec![Span::styled(prefix, style)];
+                        spans.extend(
+                            self.highlight_matches(service.display_label().to_string(), style),
+                        );
+                        lines.push(Line::from(truncate_spans(
+                            spans,
+                            text_area.width as usize,
+                            &self.truncation_symbol,
                         )));
                     }
                 }
@@ -373,43 +968,72 @@ impl WidgetExt for ServiceNavigator {
                 if records.is_empty() && !self.filter_text.is_empty() {
                     lines.push(Line::from(Span::styled(
                         "No matching records found",
-                        Style::default().fg(Color::White),
+                        Style::default().fg(theme().text_fg),
                     )));
                 } else {
                     for (i, record) in records
                         .iter()
                         .skip(self.scroll_offset)
-                        .take(available_height)
+                        .take(shown_items)
                         .enumerate()
                     {
                         let actual_index = i + self.scroll_offset;
+                        // The selection color wins; highlights and severity color the other rows
                         let style = if actual_index == self.selected_index {
                             Style::default()
-                                .fg(Color::Yellow)
+                                .fg(theme().selection_fg)
                                 .add_modifier(ratatui::style::Modifier::BOLD)
                         } else {
-                            Style::default().fg(Color::White)
+                            self.record_style(record)
                         };
 
-                        let prefix = if actual_index == self.selected_index {
-                            "> "
-                        } else {
-                            "  "
-                        };
-                        lines.push(Line::from(Span::styled(
-                            format!("{}{}", prefix, record),
-                            style,
-                        )));
+                        let mut prefix = self.selection_prefix(actual_index == self.selected_index);
+                        if self.multi_select {
+                            prefix.push_str(if self.marked_records.contains(record) {
+                                "[x] "
+                            } else {
+                                "[ ] "
+                            });
+                        }
+                        // Rows after the first of a pretty-printed record line up under it
+                        let indent = " ".repeat(prefix.chars().count());
+                        for (row, line) in self.record_lines(record).into_iter().enumerate() {
+                            let lead = if row == 0 { prefix.clone() } else { indent.clone() };
+                            let mut spans = vec![Span::styled(lead, style)];
+                            let text = self.highlight_matches(line, style);
+                            match self.line_mode {
+                                LineMode::Truncate => {
+                                    spans.extend(text);
+                                    lines.push(Line::from(truncate_spans(
+                                        spans,
+                                        text_area.width as usize,
+                                        &self.truncation_symbol,
+                                    )));
+                                }
+                                LineMode::Scroll => {
+                                    spans.extend(skip_columns(text, self.column_offset));
+                                    lines.push(Line::from(spans));
+                                }
+                                LineMode::Wrap => {
+                                    spans.extend(text);
+                                    lines.extend(wrap_spans(
+                                        spans,
+                                        text_area.width as usize,
+                                        self.record_prefix_width(),
+                                    ));
+                                }
+                            }
+                        }
                     }
                 }
             }
         }
 
         // Add scroll down indicator if needed
-        if self.scroll_offset + available_height < total_items {
+        if self.scroll_offset + shown_items < total_items {
             lines.push(Line::from(Span::styled(
                 "▼ Scroll down for more",
-                Style::default().fg(Color::White),
+                Style::default().fg(theme().text_fg),
             )));
         }
 
@@ -417,9 +1041,50 @@ impl WidgetExt for ServiceNavigator {
         let paragraph = Paragraph::new(Text::from(lines)).alignment(Alignment::Left);
         paragraph.render(text_area, buf);
     }
+}
+
+impl WidgetExt for ServiceNavigator {
+    /// Renders the navigator widget to the buffer
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.render_with_prompt(area, buf, None);
+    }
 
     /// Handles keyboard input and returns appropriate widget actions
     fn handle_input(&mut self, key_event: KeyEvent) -> Option<WidgetAction> {
+        // If we're in search mode, handle search query input
+        if self.search_mode {
+            return match key_event.code {
+                KeyCode::Char(c) => {
+                    if !key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                        self.search_text.push(c);
+                    }
+                    Some(WidgetAction::ServiceNavigatorEvent(
+                        ServiceNavigatorEvent::SearchTextChanged(self.search_text.clone()),
+                        self.widget_type,
+                    ))
+                }
+                KeyCode::Backspace => {
+                    self.search_text.pop();
+                    Some(WidgetAction::ServiceNavigatorEvent(
+                        ServiceNavigatorEvent::SearchTextChanged(self.search_text.clone()),
+                        self.widget_type,
+                    ))
+                }
+                KeyCode::Esc => {
+                    // Exit search mode and drop the query
+                    self.search_mode = false;
+                    self.search_text.clear();
+                    None
+                }
+                KeyCode::Enter => {
+                    // Exit search mode but keep the query for n/N
+                    self.search_mode = false;
+                    None
+                }
+                _ => None,
+            };
+        }
+
         // If we're in filter mode, handle text input
         if self.filter_mode {
             match key_event.code {
@@ -479,6 +1144,90 @@ impl WidgetExt for ServiceNavigator {
                     self.filter_mode = true;
                     None
                 }
+                KeyCode::Char('s') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    // Enter search mode with Ctrl+S, starting a fresh query
+                    self.search_mode = true;
+                    self.search_text.clear();
+                    None
+                }
+                KeyCode::Char('y') if key_event.modifiers.is_empty() => {
+                    // Copy the selected item's identifier, telling why when it cannot be
+                    self.item_label(self.selected_index)
+                        .map(|label| match clipboard::copy_text(&label) {
+                            Ok(()) => WidgetAction::ServiceNavigatorEvent(
+                                ServiceNavigatorEvent::Copied(label),
+                                self.widget_type,
+                            ),
+                            Err(err) => WidgetAction::Notify(err.to_string()),
+                        })
+                }
+                KeyCode::Char('w')
+                    if key_event.modifiers.is_empty()
+                        && matches!(self.content, NavigatorContent::Records(_)) =>
+                {
+                    // Cycle between truncated, wrapped and horizontally scrolled records
+                    self.line_mode = self.line_mode.next();
+                    self.column_offset = 0;
+                    None
+                }
+                KeyCode::Char('F') => {
+                    // Pin the filter so resets and refreshed content keep it
+                    self.filter_pinned = !self.filter_pinned;
+                    None
+                }
+                KeyCode::Char('p') if key_event.modifiers.is_empty() && self.pretty_toggle => {
+                    // Switch between compact and pretty-printed JSON records
+                    self.pretty_json = !self.pretty_json;
+                    None
+                }
+                KeyCode::Char('s') if key_event.modifiers.is_empty() && self.sort_toggle => {
+                    // Order the fetched records by the next field, without fetching again
+                    self.cycle_sort();
+                    None
+                }
+                KeyCode::Char('S') if self.sort_toggle && self.sort.is_some() => {
+                    // Reverse the order of the sorted records
+                    self.sort_descending = !self.sort_descending;
+                    self.reorder();
+                    None
+                }
+                KeyCode::Char('t') if key_event.modifiers.is_empty() && self.table_toggle => {
+                    // Switch between the list and the table, which has its own scroll position
+                    self.table_view = !self.table_view;
+                    self.table_offset.set(0);
+                    None
+                }
+                KeyCode::Left
+                    if key_event.modifiers.is_empty() && self.line_mode == LineMode::Scroll =>
+                {
+                    self.column_offset = self.column_offset.saturating_sub(HORIZONTAL_SCROLL_STEP);
+                    None
+                }
+                KeyCode::Right
+                    if key_event.modifiers.is_empty() && self.line_mode == LineMode::Scroll =>
+                {
+                    self.column_offset = (self.column_offset + HORIZONTAL_SCROLL_STEP)
+                        .min(self.longest_record().saturating_sub(1));
+                    None
+                }
+                KeyCode::Char(' ') if self.multi_select => {
+                    Some(WidgetAction::ServiceNavigatorEvent(
+                        ServiceNavigatorEvent::ToggleSelection,
+                        self.widget_type,
+                    ))
+                }
+                KeyCode::Char('n') if !self.search_text.is_empty() => {
+                    Some(WidgetAction::ServiceNavigatorEvent(
+                        ServiceNavigatorEvent::SearchNext,
+                        self.widget_type,
+                    ))
+                }
+                KeyCode::Char('N') if !self.search_text.is_empty() => {
+                    Some(WidgetAction::ServiceNavigatorEvent(
+                        ServiceNavigatorEvent::SearchPrevious,
+                        self.widget_type,
+                    ))
+                }
                 KeyCode::Esc => {
                     // Clear filter with escape when not in filter mode
                     if !self.filter_text.is_empty() {
@@ -491,9 +1240,13 @@ impl WidgetExt for ServiceNavigator {
                     }
                 }
                 KeyCode::Up => {
+                    let content_len = self.content_len();
                     if self.selected_index > 0 {
                         self.selected_index -= 1;
-                        self.update_scroll_offset(10); // Will be refined in render
+                        self.update_scroll_offset();
+                    } else if self.wrap_around && content_len > 0 {
+                        self.selected_index = content_len - 1;
+                        self.update_scroll_offset();
                     }
                     Some(WidgetAction::ServiceNavigatorEvent(
                         ServiceNavigatorEvent::ArrowUp,
@@ -504,7 +1257,10 @@ impl WidgetExt for ServiceNavigator {
                     let content_len = self.content_len();
                     if content_len > 0 && self.selected_index < content_len - 1 {
                         self.selected_index += 1;
-                        self.update_scroll_offset(10); // Will be refined in render
+                        self.update_scroll_offset();
+                    } else if self.wrap_around && content_len > 0 {
+                        self.selected_index = 0;
+                        self.scroll_offset = 0;
                     }
                     Some(WidgetAction::ServiceNavigatorEvent(
                         ServiceNavigatorEvent::ArrowDown,
@@ -512,11 +1268,11 @@ impl WidgetExt for ServiceNavigator {
                     ))
                 }
                 KeyCode::PageUp => {
-                    // Jump multiple lines up
-                    let jump_size = 5;
+                    // Jump a page up
+                    let jump_size = self.page_jump();
                     if self.selected_index > 0 {
                         self.selected_index = self.selected_index.saturating_sub(jump_size);
-                        self.update_scroll_offset(10); // Will be refined in render
+                        self.update_scroll_offset();
                     }
                     Some(WidgetAction::ServiceNavigatorEvent(
                         ServiceNavigatorEvent::PageUp,
@@ -524,13 +1280,13 @@ impl WidgetExt for ServiceNavigator {
                     ))
                 }
                 KeyCode::PageDown => {
-                    // Jump multiple lines down
-                    let jump_size = 5;
+                    // Jump a page down
+                    let jump_size = self.page_jump();
                     let content_len = self.content_len();
                     if content_len > 0 && self.selected_index < content_len - 1 {
                         self.selected_index =
                             (self.selected_index + jump_size).min(content_len - 1);
-                        self.update_scroll_offset(10); // Will be refined in render
+                        self.update_scroll_offset();
                     }
                     Some(WidgetAction::ServiceNavigatorEvent(
                         ServiceNavigatorEvent::PageDown,
@@ -557,7 +1313,7 @@ impl WidgetExt for ServiceNavigator {
                     let content_len = self.content_len();
                     if content_len > 0 && self.selected_index < content_len - 1 {
                         self.selected_index = content_len - 1;
-                        self.update_scroll_offset(10); // Will be refined in render
+                        self.update_scroll_offset();
                     }
                     Some(WidgetAction::ServiceNavigatorEvent(
                         ServiceNavigatorEvent::End,
@@ -611,14 +1367,36 @@ impl WidgetExt for ServiceNavigator {
                     }
                     None
                 }
-                ServiceNavigatorEvent::UpdateContent(content, reset_flag) => {
-                    // Update content and apply existing filter
-                    if reset_flag{
-                        self.set_content(NavigatorContent::Records(content));
-                    }else {
-                        self.update_content(NavigatorContent::Records(content));
+                ServiceNavigatorEvent::SearchTextChanged(text) => {
+                    // Incremental search keeps the current match while it still matches
+                    self.search_text = text;
+                    self.jump_to_match(true, true);
+                    None
+                }
+                ServiceNavigatorEvent::ToggleSelection => {
+                    self.toggle_marked();
+                    None
+                }
+                ServiceNavigatorEvent::SearchNext => {
+                    self.jump_to_match(true, false);
+                    None
+                }
+                ServiceNavigatorEvent::SearchPrevious => {
+                    self.jump_to_match(false, false);
+                    None
+                }
+                ServiceNavigatorEvent::UpdateContent(records, append, total) => {
+                    // Both keep the filter; only appending keeps the selection as well
+                    if append {
+                        self.append_records(records);
+                    } else {
+                        self.set_content(NavigatorContent::Records(records));
+                    }
+                    self.set_total_available(total);
+                    // A pinned filter is left alone, even while it is being typed
+                    if !self.filter_pinned {
+                        self.filter_mode = false; // Reset filter mode
                     }
-                    self.filter_mode = false; // Reset filter mode
                     // self.set_title(title);
                     None
                 }
@@ -626,6 +1404,11 @@ impl WidgetExt for ServiceNavigator {
                     self.set_title(title);
                     None
                 }
+                ServiceNavigatorEvent::ShowPlaceholder(message) => {
+                    self.show_placeholder(message);
+                    self.filter_mode = false;
+                    None
+                }
                 ServiceNavigatorEvent::Backspace => {
                     if self.filter_mode {
                         self.remove_from_filter();
@@ -642,7 +1425,12 @@ impl WidgetExt for ServiceNavigator {
     fn get_help_items(&self) -> Vec<(String, String)> {
         let mut items = vec![];
 
-        if self.filter_mode {
+        if self.search_mode {
+            // Search mode help
+            items.push(("Type".to_string(), "Search".to_string()));
+            items.push(("Esc".to_string(), "Cancel search".to_string()));
+            items.push(("Enter".to_string(), "Confirm search".to_string()));
+        } else if self.filter_mode {
             // Filter mode help
             items.push(("Type".to_string(), "Filter".to_string()));
             items.push(("Esc".to_string(), "Exit filter".to_string()));
@@ -652,6 +1440,40 @@ impl WidgetExt for ServiceNavigator {
             items.push(("Enter".to_string(), "Select".to_string()));
             items.push(("Ctrl+F".to_string(), "Filter".to_string()));
             items.push(("/".to_string(), "Filter".to_string()));
+            let pin = if self.filter_pinned { "Unpin filter" } else { "Pin filter" };
+            items.push(("F".to_string(), pin.to_string()));
+            items.push(("Ctrl+S".to_string(), "Search".to_string()));
+            items.push(("y".to_string(), "Copy".to_string()));
+
+            if !self.search_text.is_empty() {
+                items.push(("n/N".to_string(), "Next/previous match".to_string()));
+            }
+
+            if self.multi_select {
+                items.push(("Space".to_string(), "Toggle selection".to_string()));
+            }
+
+            if self.table_toggle {
+                items.push(("t".to_string(), "List/table view".to_string()));
+            }
+
+            if self.sort_toggle {
+                items.push(("s".to_string(), "Sort by next field".to_string()));
+                if self.sort.is_some() {
+                    items.push(("S".to_string(), "Reverse sort".to_string()));
+                }
+            }
+
+            if self.pretty_toggle {
+                items.push(("p".to_string(), "Compact/pretty JSON".to_string()));
+            }
+
+            if matches!(self.content, NavigatorContent::Records(_)) {
+                items.push(("w".to_string(), "Truncate/wrap/scroll lines".to_string()));
+                if self.line_mode == LineMode::Scroll {
+                    items.push(("←/→".to_string(), "Scroll sideways".to_string()));
+                }
+            }
 
             if !self.filter_text.is_empty() {
                 items.push(("Esc".to_string(), "Clear filter".to_string()));
@@ -700,3 +1522,429 @@ impl WidgetExt for ServiceNavigator {
         self.active
     }
 }
+
+/// Number of rows a line of `len` characters takes when wrapped at `width`,
+/// with continuation rows indented by `indent`
+fn wrapped_rows(len: usize, width: usize, indent: usize) -> usize {
+    if width == 0 || len <= width {
+        return 1;
+    }
+    let indent = if indent < width { indent } else { 0 };
+    1 + (len - width).div_ceil(width - indent)
+}
+
+/// Splits styled spans into rows of at most `width` characters, indenting continuation rows
+fn wrap_spans(spans: Vec<Span<'static>>, width: usize, indent: usize) -> Vec<Line<'static>> {
+    let width = width.max(1);
+    let indent = if indent < width { indent } else { 0 };
+    let mut lines = Vec::new();
+    let mut row: Vec<Span<'static>> = Vec::new();
+    let mut row_len = 0;
+
+    for span in spans {
+        let mut rest: &str = &span.content;
+        while !rest.is_empty() {
+            if row_len == width {
+                lines.push(Line::from(std::mem::take(&mut row)));
+                row.push(Span::raw(" ".repeat(indent)));
+                row_len = indent;
+            }
+            let split = rest
+                .char_indices()
+                .nth(width - row_len)
+                .map_or(rest.len(), |(index, _)| index);
+            row.push(Span::styled(rest[..split].to_string(), span.style));
+            row_len += rest[..split].chars().count();
+            rest = &rest[split..];
+        }
+    }
+    if !row.is_empty() {
+        lines.push(Line::from(row));
+    }
+    lines
+}
+
+/// Cuts styled spans to at most `width` characters, ending a cut row with `symbol` in the
+/// style of the text it replaces; cuts fall on character boundaries
+fn truncate_spans(spans: Vec<Span<'static>>, width: usize, symbol: &str) -> Vec<Span<'static>> {
+    let len: usize = spans.iter().map(|span| span.content.chars().count()).sum();
+    if len <= width {
+        return spans;
+    }
+    let symbol_len = symbol.chars().count();
+    // Too narrow for the symbol and any text, so just clip
+    let (mut remaining, symbol) = if symbol_len < width {
+        (width - symbol_len, symbol)
+    } else {
+        (width, "")
+    };
+
+    let mut cut = Vec::new();
+    let mut last_style = Style::default();
+    for span in spans {
+        last_style = span.style;
+        let span_len = span.content.chars().count();
+        if span_len <= remaining {
+            remaining -= span_len;
+            cut.push(span);
+            continue;
+        }
+        let end = span
+            .content
+            .char_indices()
+            .nth(remaining)
+            .map_or(span.content.len(), |(index, _)| index);
+        cut.push(Span::styled(span.content[..end].to_string(), span.style));
+        break;
+    }
+    if !symbol.is_empty() {
+        cut.push(Span::styled(symbol.to_string(), last_style));
+    }
+    cut
+}
+
+/// Drops the first `columns` characters of the styled spans
+fn skip_columns(spans: Vec<Span<'static>>, columns: usize) -> Vec<Span<'static>> {
+    let mut remaining = columns;
+    spans
+        .into_iter()
+        .filter_map(|span| {
+            let len = span.content.chars().count();
+            if remaining >= len {
+                remaining -= len;
+                return None;
+            }
+            let start = span
+                .content
+                .char_indices()
+                .nth(remaining)
+                .map_or(0, |(index, _)| index);
+            remaining = 0;
+            Some(Span::styled(span.content[start..].to_string(), span.style))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn truncates_long_lines_on_char_boundaries() {
+        let spans = vec![Span::raw("> "), Span::raw("héllo wörld")];
+        let texts =
+            |width, symbol| line_text(&Line::from(truncate_spans(spans.clone(), width, symbol)));
+        assert_eq!(texts(13, "…"), "> héllo wörld");
+        assert_eq!(texts(8, "…"), "> héllo…");
+        assert_eq!(texts(8, ""), "> héllo ");
+        assert_eq!(texts(1, "…"), ">");
+        assert_eq!(texts(0, "…"), "");
+    }
+
+    #[test]
+    fn wraps_long_lines_with_indented_continuations() {
+        let spans = vec![Span::raw("> "), Span::raw("abcdefghij")];
+        let lines = wrap_spans(spans, 6, 2);
+        let texts: Vec<String> = lines.iter().map(line_text).collect();
+        assert_eq!(texts, vec!["> abcd", "  efgh", "  ij"]);
+        assert_eq!(wrapped_rows(12, 6, 2), lines.len());
+        assert_eq!(wrapped_rows(4, 6, 2), 1);
+    }
+
+    #[test]
+    fn skips_columns_across_spans() {
+        let spans = vec![Span::raw("abc"), Span::raw("def")];
+        let skipped: String = skip_columns(spans, 4)
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(skipped, "ef");
+    }
+
+    #[test]
+    fn table_view_shows_json_records_in_columns() {
+        let mut navigator = ServiceNavigator::new(
+            WidgetType::QueryResultsNavigator,
+            true,
+            NavigatorContent::Records(vec![
+                r#"{"id":"1","status":"open"}"#.to_string(),
+                r#"{"id":"2","status":"closed"}"#.to_string(),
+            ]),
+        );
+        navigator.enable_table_view(None);
+        navigator.handle_input(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+        navigator.render(area, &mut buf);
+        let screen: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("[table]"));
+        assert!(screen.contains("id  status"));
+        assert!(screen.contains("> 1   open"));
+    }
+
+    #[test]
+    fn placeholders_are_shown_but_never_selected() {
+        let mut navigator = ServiceNavigator::new(
+            WidgetType::AWSServiceNavigator,
+            true,
+            NavigatorContent::Records(vec!["old-group".to_string()]),
+        );
+        navigator.show_placeholder("No log groups found");
+        assert!(navigator.selected_item().is_none());
+        assert_eq!(navigator.selected_label(), None);
+
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+        navigator.render(area, &mut buf);
+        let screen: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("No log groups found"));
+
+        navigator.set_content(NavigatorContent::Records(vec![]));
+        let mut buf = Buffer::empty(area);
+        navigator.render(area, &mut buf);
+        let screen: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("No items available"));
+    }
+
+    #[test]
+    fn prompts_give_way_to_placeholders_and_records() {
+        let mut navigator = ServiceNavigator::new(
+            WidgetType::QueryResultsNavigator,
+            true,
+            NavigatorContent::Records(vec![]),
+        );
+        let area = Rect::new(0, 0, 40, 12);
+        let screen = |navigator: &ServiceNavigator| {
+            let mut buf = Buffer::empty(area);
+            navigator.render_with_prompt(area, &mut buf, Some("Pick a bucket"));
+            buf.content().iter().map(|cell| cell.symbol()).collect::<String>()
+        };
+        assert!(screen(&navigator).contains("Pick a bucket"));
+
+        navigator.show_placeholder("No objects found");
+        assert!(!screen(&navigator).contains("Pick a bucket"));
+
+        navigator.set_content(NavigatorContent::Records(vec!["report.csv".to_string()]));
+        let loaded = screen(&navigator);
+        assert!(loaded.contains("report.csv"));
+        assert!(!loaded.contains("Pick a bucket"));
+    }
+
+    #[test]
+    fn pretty_json_records_span_rows_but_select_as_one() {
+        let mut navigator = ServiceNavigator::new(
+            WidgetType::QueryResultsNavigator,
+            true,
+            NavigatorContent::Records(vec![
+                r#"{"id":"1","status":"open"}"#.to_string(),
+                r#"{"id":"2","status":"closed"}"#.to_string(),
+            ]),
+        );
+        navigator.enable_pretty_json();
+        navigator.handle_input(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+        assert_eq!(navigator.record_rows(r#"{"id":"1","status":"open"}"#, 40), 4);
+        assert_eq!(navigator.record_rows("plain log line", 40), 1);
+
+        let area = Rect::new(0, 0, 40, 16);
+        let mut buf = Buffer::empty(area);
+        navigator.render(area, &mut buf);
+        let screen: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("[pretty]"));
+        assert!(screen.contains("> {"));
+        assert!(screen.contains(r#"    "status": "open""#));
+
+        // Down moves to the next record, not the next row
+        navigator.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        assert_eq!(
+            navigator.selected_label().as_deref(),
+            Some(r#"{"id":"2","status":"closed"}"#)
+        );
+    }
+
+    fn filtered_navigator(records: &[&str], filter: &str) -> ServiceNavigator {
+        let mut navigator = ServiceNavigator::new(
+            WidgetType::QueryResultsNavigator,
+            true,
+            NavigatorContent::Records(records.iter().map(|r| r.to_string()).collect()),
+        );
+        navigator.apply_filter(filter);
+        navigator
+    }
+
+    #[test]
+    fn new_content_is_filtered_whether_or_not_the_filter_is_pinned() {
+        for pinned in [false, true] {
+            let mut navigator = filtered_navigator(&["error: a", "info: b"], "error");
+            navigator.filter_pinned = pinned;
+            navigator.set_content(NavigatorContent::Records(vec![
+                "info: c".to_string(),
+                "error: d".to_string(),
+            ]));
+            assert_eq!(navigator.content_len(), 1);
+            assert_eq!(navigator.selected_label().as_deref(), Some("error: d"));
+        }
+    }
+
+    #[test]
+    fn only_a_pinned_filter_survives_a_reset() {
+        let mut navigator = filtered_navigator(&["error: a", "info: b"], "error");
+        navigator.reset_view();
+        assert_eq!(navigator.content_len(), 2);
+
+        let mut navigator = filtered_navigator(&["error: a", "info: b"], "error");
+        navigator.handle_input(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT));
+        navigator.reset_view();
+        navigator.set_content(NavigatorContent::Records(vec![
+            "error: c".to_string(),
+            "info: d".to_string(),
+        ]));
+        assert_eq!(navigator.content_len(), 1);
+        assert_eq!(navigator.filter_text, "error");
+
+        let area = Rect::new(0, 0, 60, 12);
+        let mut buf = Buffer::empty(area);
+        navigator.render(area, &mut buf);
+        let screen: String = buf.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("[filter pinned]"));
+    }
+
+    #[test]
+    fn update_content_appends_or_replaces_keeping_the_filter() {
+        let update = |navigator: &mut ServiceNavigator, records: &[&str], append| {
+            navigator.process_event(WidgetAction::ServiceNavigatorEvent(
+                ServiceNavigatorEvent::UpdateContent(
+                    records.iter().map(|r| r.to_string()).collect(),
+                    append,
+                    None,
+                ),
+                WidgetType::QueryResultsNavigator,
+            ));
+        };
+        let mut navigator = filtered_navigator(&["error: a", "info: b", "error: c"], "error");
+        navigator.selected_index = 1;
+
+        // Appending keeps the selection on the same record
+        update(&mut navigator, &["error: d", "info: e"], true);
+        assert_eq!(navigator.content_len(), 3);
+        assert_eq!(navigator.selected_label().as_deref(), Some("error: c"));
+
+        // Replacing starts over at the top
+        update(&mut navigator, &["info: f", "error: g"], false);
+        assert_eq!(navigator.content_len(), 1);
+        assert_eq!(navigator.selected_label().as_deref(), Some("error: g"));
+
+        // Appending to a placeholder starts the list
+        navigator.show_placeholder("No logs yet");
+        update(&mut navigator, &["error: h"], true);
+        assert_eq!(navigator.placeholder, None);
+        assert_eq!(navigator.selected_label().as_deref(), Some("error: h"));
+    }
+
+    #[test]
+    fn counts_leave_out_what_is_unknown() {
+        let mut navigator = filtered_navigator(&["error: a", "info: b", "error: c"], "");
+        assert_eq!(navigator.record_counts(3, 3), "Records 3 fetched");
+
+        navigator.process_event(WidgetAction::ServiceNavigatorEvent(
+            ServiceNavigatorEvent::UpdateContent(
+                vec!["error: a".to_string(), "info: b".to_string()],
+                false,
+                Some(1234),
+            ),
+            WidgetType::QueryResultsNavigator,
+        ));
+        navigator.apply_filter("error");
+        assert_eq!(
+            navigator.record_counts(2, navigator.content_len()),
+            "Records 2 fetched / 1234 total / 1 shown"
+        );
+
+        // New records without a total drop the stale one
+        navigator.set_content(NavigatorContent::Records(vec!["error: d".to_string()]));
+        assert_eq!(navigator.record_counts(1, 1), "Records 1 fetched / 1 shown");
+    }
+
+    #[test]
+    fn following_stops_once_an_earlier_record_is_selected() {
+        let lines = |lines: &[&str]| lines.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+        let mut navigator = filtered_navigator(&[], "");
+        navigator.append_and_follow(lines(&["a", "b", "c"]), None);
+        assert_eq!(navigator.selected_label().as_deref(), Some("c"));
+
+        // Reading an earlier line keeps it selected while the oldest lines are dropped
+        navigator.selected_index = 1;
+        navigator.scroll_offset = 1;
+        navigator.append_and_follow(lines(&["d", "e"]), Some(4));
+        assert_eq!(navigator.selected_label().as_deref(), Some("b"));
+        assert_eq!((navigator.selected_index, navigator.scroll_offset), (0, 0));
+
+        // Selecting the last line follows again
+        navigator.selected_index = navigator.content_len() - 1;
+        navigator.append_and_follow(lines(&["f"]), Some(4));
+        assert_eq!(navigator.selected_label().as_deref(), Some("f"));
+    }
+
+    #[test]
+    fn refreshed_content_keeps_the_selected_record() {
+        let mut navigator = filtered_navigator(&["a", "b", "c"], "");
+        navigator.selected_index = 2;
+        navigator.set_content_keeping_selection(NavigatorContent::Records(vec![
+            "new".to_string(),
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string(),
+        ]));
+        assert_eq!(navigator.selected_index, 3);
+        assert_eq!(navigator.selected_label().as_deref(), Some("c"));
+
+        // A record that is gone starts over at the top
+        navigator.set_content_keeping_selection(NavigatorContent::Records(vec!["x".to_string()]));
+        assert_eq!(navigator.selected_index, 0);
+    }
+
+    #[test]
+    fn sorting_cycles_fields_and_keeps_the_selection() {
+        let records = [
+            r#"{"key":"b","size":2}"#,
+            r#"{"key":"a","size":3}"#,
+            r#"{"key":"c","size":1}"#,
+        ];
+        let mut navigator = filtered_navigator(&records, "");
+        navigator.enable_sorting(None);
+        let press = |navigator: &mut ServiceNavigator, c| {
+            navigator.handle_input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        };
+        let shown_keys = |navigator: &ServiceNavigator| -> String {
+            (0..navigator.content_len())
+                .filter_map(|i| navigator.item_label(i))
+                .map(|record| record[8..9].to_string())
+                .collect()
+        };
+
+        press(&mut navigator, 's');
+        assert_eq!(shown_keys(&navigator), "abc");
+        assert_eq!(navigator.selected_label().as_deref(), Some(records[0]));
+        press(&mut navigator, 'S');
+        assert_eq!(shown_keys(&navigator), "cba");
+        // The next field keeps the direction: by size, largest first
+        press(&mut navigator, 's');
+        assert_eq!(shown_keys(&navigator), "abc");
+        press(&mut navigator, 'S');
+        assert_eq!(shown_keys(&navigator), "cba");
+
+        // New content is shown in the same order, and after the last field in fetch order
+        navigator.set_content(NavigatorContent::Records(vec![
+            records[1].to_string(),
+            records[2].to_string(),
+        ]));
+        assert_eq!(shown_keys(&navigator), "ca");
+        press(&mut navigator, 's');
+        assert_eq!(shown_keys(&navigator), "ac");
+        assert!(navigator.sort.is_none());
+    }
+}